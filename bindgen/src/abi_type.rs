@@ -0,0 +1,70 @@
+//! Solidity type to rust type mapping.
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::quote;
+use syn::Error;
+
+/// Parse solidity type name into rust type mapping.
+pub fn sol_type(name: &str, span: Span) -> syn::Result<TokenStream> {
+    let err = || Error::new(span, format!("Unsupported solidity type `{}`", name));
+
+    if let Some(element) = name.strip_suffix("[]") {
+        let element = sol_type(element, span)?;
+
+        return Ok(quote!(::std::vec::Vec<#element>));
+    }
+
+    if let Some(prefix) = name.strip_suffix(']') {
+        let (element, len) = prefix.rsplit_once('[').ok_or_else(err)?;
+
+        let len = Literal::usize_unsuffixed(len.parse().map_err(|_| err())?);
+
+        let element = sol_type(element, span)?;
+
+        return Ok(quote!([#element; #len]));
+    }
+
+    let bits = |digits: &str| -> syn::Result<Literal> {
+        if digits.is_empty() {
+            return Ok(Literal::usize_unsuffixed(256));
+        }
+
+        match digits.parse::<usize>() {
+            Ok(bits) if bits > 0 && bits <= 256 && bits % 8 == 0 => {
+                Ok(Literal::usize_unsuffixed(bits))
+            }
+            _ => Err(err()),
+        }
+    };
+
+    let mapping = match name {
+        "address" => quote!(ethers_rs::Address),
+        "bool" => quote!(bool),
+        "string" => quote!(::std::string::String),
+        "bytes" => quote!(ethers_rs::Bytes),
+        _ => {
+            if let Some(digits) = name.strip_prefix("uint") {
+                let bits = bits(digits)?;
+
+                quote!(ethers_rs::Uint<#bits>)
+            } else if let Some(digits) = name.strip_prefix("int") {
+                let bits = bits(digits)?;
+
+                quote!(ethers_rs::Int<#bits>)
+            } else if let Some(digits) = name.strip_prefix("bytes") {
+                match digits.parse::<usize>() {
+                    Ok(len) if (1..=32).contains(&len) => {
+                        let len = Literal::usize_unsuffixed(len);
+
+                        quote!(ethers_rs::BytesM<#len>)
+                    }
+                    _ => return Err(err()),
+                }
+            } else {
+                return Err(err());
+            }
+        }
+    };
+
+    Ok(mapping)
+}
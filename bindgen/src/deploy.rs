@@ -0,0 +1,162 @@
+//! Library linking deploy methods generated from hardhat artifact `linkReferences`.
+
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::quote;
+use serde_json::Value;
+use syn::Error;
+
+use crate::namespace::params;
+
+/// Generate `link_references()` of artifact `linkReferences`, and `link_libraries()`/`deploy_linked()`
+/// resolving library placeholders of the deployment bytecode.
+///
+/// Artifacts without deployment bytecode, e.g. interfaces, generate nothing.
+pub fn deployers(artifact: &Value, span: Span) -> syn::Result<TokenStream> {
+    let contract_name = artifact["contractName"]
+        .as_str()
+        .ok_or_else(|| Error::new(span, "Hardhat artifact without `contractName` field"))?;
+
+    let bytecode = match artifact["bytecode"].as_str() {
+        Some(bytecode) if bytecode.trim_start_matches("0x").len() > 0 => bytecode,
+        _ => return Ok(quote!()),
+    };
+
+    let contract_ident = Ident::new(contract_name, Span::call_site());
+
+    let mut references = vec![];
+
+    for (source_name, libraries) in artifact["linkReferences"].as_object().into_iter().flatten() {
+        for (library_name, offsets) in libraries.as_object().into_iter().flatten() {
+            let mut link_offsets = vec![];
+
+            for offset in offsets.as_array().into_iter().flatten() {
+                let (start, length) = match (offset["start"].as_u64(), offset["length"].as_u64()) {
+                    (Some(start), Some(length)) => (
+                        Literal::usize_unsuffixed(start as usize),
+                        Literal::usize_unsuffixed(length as usize),
+                    ),
+                    _ => {
+                        return Err(Error::new(
+                            span,
+                            format!(
+                                "Invalid linkReferences offset of {}:{}, {}",
+                                source_name, library_name, offset
+                            ),
+                        ))
+                    }
+                };
+
+                link_offsets.push(quote!(ethers_rs::LinkOffset { start: #start, length: #length }));
+            }
+
+            references.push(quote! {
+                references
+                    .entry(#source_name.to_owned())
+                    .or_default()
+                    .insert(#library_name.to_owned(), vec![#(#link_offsets),*]);
+            });
+        }
+    }
+
+    let constructor = artifact["abi"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|item| item["type"].as_str() == Some("constructor"));
+
+    let (args, arg_types) = match constructor {
+        Some(constructor) => params(&constructor["inputs"], span)?,
+        None => (vec![], vec![]),
+    };
+
+    let deploy_doc = format!(
+        "Deploy `{}` linking `libraries` into the deployment bytecode, see [`link_libraries`](Self::link_libraries).",
+        contract_name
+    );
+
+    Ok(quote! {
+        impl #contract_ident {
+            /// Artifact `linkReferences` of the deployment bytecode.
+            pub fn link_references() -> ethers_rs::LinkReferences {
+                #[allow(unused_mut)]
+                let mut references = ethers_rs::LinkReferences::new();
+
+                #(#references)*
+
+                references
+            }
+
+            /// Returns `client` linking `libraries` into the deployment bytecode, library names are
+            /// bare, e.g. `Math`, or fully qualified, e.g. `contracts/Math.sol:Math`.
+            pub fn link_libraries(
+                client: ethers_rs::Client,
+                libraries: &[(&str, ethers_rs::Address)],
+            ) -> ::std::result::Result<ethers_rs::Client, ethers_rs::Error> {
+                let mut client = client.with_link_references(Self::link_references());
+
+                for (name, address) in libraries {
+                    client = client.with_library(name, address.clone())?;
+                }
+
+                Ok(client)
+            }
+
+            #[doc = #deploy_doc]
+            ///
+            /// Returns `LinkError::Unlinked` if any referenced library is missing.
+            pub async fn deploy_linked(
+                client: ethers_rs::Client,
+                libraries: &[(&str, ethers_rs::Address)],
+                #(#args: #arg_types,)*
+                ops: ethers_rs::SendOptions,
+            ) -> ::std::result::Result<ethers_rs::Deployed<ethers_rs::Address>, ethers_rs::Error> {
+                let call_data = ethers_rs::to_abi(&(#(#args,)*))?;
+
+                Self::link_libraries(client, libraries)?
+                    .deploy_contract_with_receipt(#contract_name, call_data, #bytecode, ops)
+                    .await
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deployers() {
+        let placeholder = "__$30bbc0abd4d6364515865950d3e0d10953$__";
+
+        let artifact = json!({
+            "contractName": "Calculator",
+            "abi": [
+                { "type": "constructor", "stateMutability": "nonpayable", "inputs": [{ "name": "initial", "type": "uint256" }] }
+            ],
+            "bytecode": format!("0x6080{}6000", placeholder),
+            "linkReferences": {
+                "libraries/bigint.sol": {
+                    "BigInt": [{ "start": 2, "length": 20 }]
+                }
+            }
+        });
+
+        let tokens = deployers(&artifact, Span::call_site()).unwrap().to_string();
+
+        assert!(tokens.contains(
+            "references . entry (\"libraries/bigint.sol\" . to_owned ()) . or_default () . insert (\"BigInt\" . to_owned () , vec ! [ethers_rs :: LinkOffset { start : 2 , length : 20 }]) ;"
+        ));
+        assert!(tokens.contains("pub async fn deploy_linked (client : ethers_rs :: Client , libraries : & [(& str , ethers_rs :: Address)] , initial : ethers_rs :: Uint < 256 > , ops : ethers_rs :: SendOptions ,)"));
+        assert!(tokens.contains(&format!(
+            "deploy_contract_with_receipt (\"Calculator\" , call_data , \"0x6080{}6000\" , ops)",
+            placeholder
+        )));
+
+        // Interfaces have no deployment bytecode.
+        let artifact = json!({ "contractName": "ICalculator", "abi": [], "bytecode": "0x" });
+
+        assert!(deployers(&artifact, Span::call_site()).unwrap().is_empty());
+    }
+}
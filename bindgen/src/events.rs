@@ -0,0 +1,108 @@
+//! Events enum of contract bindings.
+
+use heck::ToUpperCamelCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use serde_json::Value;
+use syn::Error;
+
+/// Generate `{Contract}Events` enum of all contract events, which implements `ContractEvent`.
+///
+/// Events with topic0 are matched first, anonymous events are matched by decode success only.
+pub fn events_enum(artifact: &Value, span: Span) -> syn::Result<TokenStream> {
+    let contract_name = artifact["contractName"]
+        .as_str()
+        .ok_or_else(|| Error::new(span, "Hardhat artifact without `contractName` field"))?;
+
+    let mut events = artifact["abi"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|item| item["type"].as_str() == Some("event"))
+        .collect::<Vec<_>>();
+
+    if events.is_empty() {
+        return Ok(quote!());
+    }
+
+    events.sort_by_key(|item| item["anonymous"].as_bool() == Some(true));
+
+    let contract_ident = Ident::new(contract_name, Span::call_site());
+    let events_ident = format_ident!("{}Events", contract_ident);
+
+    let mut variants = vec![];
+    let mut decoders = vec![];
+
+    for item in events {
+        let name = item["name"]
+            .as_str()
+            .ok_or_else(|| Error::new(span, format!("Abi event without `name` field, {}", item)))?;
+
+        let variant = format_ident!("{}", name.to_upper_camel_case());
+        let event_ident = format_ident!("{}{}", contract_ident, name);
+
+        variants.push(quote!(#variant(#event_ident)));
+
+        decoders.push(quote! {
+            let event = ethers_rs::event_fragment_from_json(#event_ident::abi_json())?;
+
+            if let Some(event) = ethers_rs::decode_log_with(&event, log)? {
+                return Ok(Some(Self::#variant(event)));
+            }
+        });
+    }
+
+    let doc = format!(
+        "Events of `{}`, decode receipt logs with [`DecodedLogs`](ethers_rs::DecodedLogs).",
+        contract_name
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        pub enum #events_ident {
+            #(#variants,)*
+        }
+
+        impl ethers_rs::ContractEvent for #events_ident {
+            fn decode_log(log: &ethers_rs::Log) -> ::std::result::Result<::std::option::Option<Self>, ethers_rs::Error> {
+                #(#decoders)*
+
+                Ok(None)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_events_enum() {
+        let artifact = json!({
+            "contractName": "Example",
+            "abi": [
+                { "type": "event", "name": "Anon", "anonymous": true, "inputs": [{ "name": "id", "type": "uint256", "indexed": true }] },
+                { "type": "event", "name": "Withdrawal", "anonymous": false, "inputs": [{ "name": "amount", "type": "uint256", "indexed": false }] },
+                { "type": "function", "name": "withdraw", "inputs": [], "outputs": [] }
+            ]
+        });
+
+        let tokens = events_enum(&artifact, Span::call_site())
+            .unwrap()
+            .to_string();
+
+        assert!(tokens.contains(
+            "pub enum ExampleEvents { Withdrawal (ExampleWithdrawal) , Anon (ExampleAnon) , }"
+        ));
+        assert!(tokens.contains("impl ethers_rs :: ContractEvent for ExampleEvents"));
+
+        let artifact = json!({ "contractName": "Example", "abi": [] });
+
+        assert!(events_enum(&artifact, Span::call_site())
+            .unwrap()
+            .is_empty());
+    }
+}
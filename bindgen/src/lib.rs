@@ -0,0 +1,53 @@
+//! Contract binding generator of hardhat artifacts, shared by `ethers_macros` proc-macros
+//! and the `cargo ethers bindgen` command.
+
+use ethbind::rust::{BindingBuilder, JsonRuntimeBinder, RustGenerator, ToTokenStream};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use serde_json::Value;
+use syn::Error;
+
+mod abi_type;
+pub use abi_type::*;
+
+mod deploy;
+mod events;
+mod namespace;
+mod selector;
+
+/// Returns `contractName` of artifact, or `None` if `artifact` is not a contract artifact.
+pub fn contract_name(artifact: &Value) -> Option<&str> {
+    if !artifact["abi"].is_array() {
+        return None;
+    }
+
+    artifact["contractName"].as_str()
+}
+
+/// Generate contract binding of hardhat artifact json, errors are reported at `span`.
+pub fn bind_artifact(artifact: &Value, span: Span) -> syn::Result<TokenStream> {
+    let type_mapping: JsonRuntimeBinder = include_str!("./mapping.json")
+        .parse()
+        .expect("Parse mapping.json");
+
+    let selectors = selector::selector_consts(artifact, span)?;
+
+    let namespaces = namespace::namespaces(artifact, span)?;
+
+    let deployers = deploy::deployers(artifact, span)?;
+
+    let events = events::events_enum(artifact, span)?;
+
+    let generate_err = |err| Error::new(span, format!("Generate contract binding: {}", err));
+
+    let generator = BindingBuilder::new((RustGenerator::default(), type_mapping))
+        .bind_hardhat(artifact.to_string())
+        .finalize()
+        .map_err(|err| generate_err(err.to_string()))?;
+
+    let contracts = generator
+        .to_token_streams()
+        .map_err(|err| generate_err(err.to_string()))?;
+
+    Ok(quote!(#(#contracts)* #selectors #namespaces #deployers #events))
+}
@@ -0,0 +1,323 @@
+//! Read (`calls()`) and write (`sends()`) method namespaces of contract bindings.
+
+use std::collections::HashMap;
+
+use heck::ToSnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use serde_json::Value;
+use syn::Error;
+
+use crate::{abi_type::sol_type, selector::signature};
+
+/// Rust type of abi parameter, tuples are mapped to rust tuples.
+fn rust_type(param: &Value, span: Span) -> syn::Result<TokenStream> {
+    let ty = param["type"].as_str().ok_or_else(|| {
+        Error::new(
+            span,
+            format!("Abi parameter without `type` field, {}", param),
+        )
+    })?;
+
+    let suffix = match ty.strip_prefix("tuple") {
+        Some(suffix) => suffix,
+        None => return sol_type(ty, span),
+    };
+
+    let components = param["components"]
+        .as_array()
+        .map(|components| {
+            components
+                .iter()
+                .map(|component| rust_type(component, span))
+                .collect::<syn::Result<Vec<_>>>()
+        })
+        .unwrap_or_else(|| Ok(vec![]))?;
+
+    let mut rust_type = quote!((#(#components,)*));
+
+    // `tuple[2][]` is a dynamic array of `tuple[2]`.
+    for dimension in suffix.split_terminator(']') {
+        rust_type = match dimension.strip_prefix('[') {
+            Some("") => quote!(::std::vec::Vec<#rust_type>),
+            Some(len) => {
+                let len = len
+                    .parse::<usize>()
+                    .map_err(|_| Error::new(span, format!("Invalid abi type `{}`", ty)))?;
+
+                let len = proc_macro2::Literal::usize_unsuffixed(len);
+
+                quote!([#rust_type; #len])
+            }
+            None => return Err(Error::new(span, format!("Invalid abi type `{}`", ty))),
+        };
+    }
+
+    Ok(rust_type)
+}
+
+/// Returns identifiers and types of abi parameters, unnamed parameters are named `arg{index}`.
+pub(crate) fn params(params: &Value, span: Span) -> syn::Result<(Vec<Ident>, Vec<TokenStream>)> {
+    let mut idents = vec![];
+    let mut types = vec![];
+
+    for (index, param) in params.as_array().into_iter().flatten().enumerate() {
+        let name = param["name"].as_str().unwrap_or_default().to_snake_case();
+
+        let ident = if name.is_empty() {
+            format_ident!("arg{}", index)
+        } else {
+            syn::parse_str::<Ident>(&name).unwrap_or_else(|_| Ident::new_raw(&name, span))
+        };
+
+        idents.push(ident);
+        types.push(rust_type(param, span)?);
+    }
+
+    Ok((idents, types))
+}
+
+/// Returns true if abi function does not modify state, e.g. `view` or `pure` functions.
+fn is_read_only(item: &Value) -> bool {
+    match item["stateMutability"].as_str() {
+        Some(mutability) => mutability == "view" || mutability == "pure",
+        None => item["constant"].as_bool() == Some(true),
+    }
+}
+
+/// Generate `calls()` namespace of `view`/`pure` functions and `sends()` namespace of other functions.
+///
+/// Read functions return decoded outputs via `eth_call`, write functions send transaction and
+/// return [`DefaultTransactionReceipter`](ethers_rs::DefaultTransactionReceipter).
+pub fn namespaces(artifact: &Value, span: Span) -> syn::Result<TokenStream> {
+    let contract_name = artifact["contractName"]
+        .as_str()
+        .ok_or_else(|| Error::new(span, "Hardhat artifact without `contractName` field"))?;
+
+    let contract_ident = Ident::new(contract_name, Span::call_site());
+    let calls_ident = format_ident!("{}Calls", contract_ident);
+    let sends_ident = format_ident!("{}Sends", contract_ident);
+    let statics_ident = format_ident!("{}CallStatic", contract_ident);
+
+    let mut overloads = HashMap::new();
+
+    let mut calls = vec![];
+    let mut sends = vec![];
+    let mut statics = vec![];
+
+    for item in artifact["abi"].as_array().into_iter().flatten() {
+        if item["type"].as_str() != Some("function") {
+            continue;
+        }
+
+        let signature = signature(item).map_err(|err| Error::new(span, err))?;
+
+        let name = item["name"].as_str().unwrap_or_default().to_snake_case();
+
+        let index = overloads
+            .entry(name.clone())
+            .and_modify(|index| *index += 1)
+            .or_insert(0usize);
+
+        let name = if *index == 0 {
+            name
+        } else {
+            format!("{}_{}", name, index)
+        };
+
+        let method = syn::parse_str::<Ident>(&name).unwrap_or_else(|_| Ident::new_raw(&name, span));
+
+        let (args, arg_types) = params(&item["inputs"], span)?;
+
+        let (_, output_types) = params(&item["outputs"], span)?;
+
+        let (output, result) = match output_types.len() {
+            0 => (quote!(()), quote!(())),
+            1 => (quote!(#(#output_types)*), quote!(outputs.0)),
+            _ => (quote!((#(#output_types,)*)), quote!(outputs)),
+        };
+
+        if is_read_only(item) {
+            let doc = format!("Call `{}` via `eth_call`.", signature);
+
+            calls.push(quote! {
+                #[doc = #doc]
+                pub async fn #method(
+                    &self,
+                    #(#args: #arg_types,)*
+                ) -> ::std::result::Result<#output, ethers_rs::Error> {
+                    let call_data = ethers_rs::to_abi(&(#(#args,)*))?;
+
+                    let ops = ethers_rs::CallOptions {
+                        block: self.block.clone(),
+                        ..::std::default::Default::default()
+                    };
+
+                    let result = self
+                        .contract
+                        .client
+                        .eth_call_with(#signature, &self.contract.address, call_data, ops)
+                        .await?;
+
+                    let outputs: (#(#output_types,)*) = ethers_rs::from_abi(result)?;
+
+                    Ok(#result)
+                }
+            });
+        } else {
+            let doc = format!("Send `{}` transaction.", signature);
+
+            sends.push(quote! {
+                #[doc = #doc]
+                pub async fn #method(
+                    &self,
+                    #(#args: #arg_types,)*
+                    ops: ethers_rs::SendOptions,
+                ) -> ::std::result::Result<ethers_rs::DefaultTransactionReceipter, ethers_rs::Error> {
+                    let call_data = ethers_rs::to_abi(&(#(#args,)*))?;
+
+                    self.contract
+                        .client
+                        .send_raw_transaction(#signature, &self.contract.address, call_data, ops)
+                        .await
+                }
+            });
+
+            let doc = format!(
+                "Run `{}` via `eth_call` without sending transaction, returns the would-be outputs.",
+                signature
+            );
+
+            statics.push(quote! {
+                #[doc = #doc]
+                pub async fn #method(
+                    &self,
+                    #(#args: #arg_types,)*
+                    ops: ethers_rs::CallOptions,
+                ) -> ::std::result::Result<#output, ethers_rs::Error> {
+                    let call_data = ethers_rs::to_abi(&(#(#args,)*))?;
+
+                    let result = self
+                        .contract
+                        .client
+                        .call_static(#signature, &self.contract.address, call_data, ops)
+                        .await?;
+
+                    let outputs: (#(#output_types,)*) = ethers_rs::from_abi(result)?;
+
+                    Ok(#result)
+                }
+            });
+        }
+    }
+
+    let calls_doc = format!(
+        "Read only methods of [`{}`], invoked via `eth_call`.",
+        contract_name
+    );
+    let sends_doc = format!("Methods of [`{}`] sending transactions.", contract_name);
+    let statics_doc = format!(
+        "Static calls of [`{}`] state-changing methods, see `Client::call_static`.",
+        contract_name
+    );
+
+    Ok(quote! {
+        #[doc = #calls_doc]
+        pub struct #calls_ident<'a> {
+            contract: &'a #contract_ident,
+            block: ::std::option::Option<ethers_rs::BlockNumberOrTag>,
+        }
+
+        impl<'a> #calls_ident<'a> {
+            /// Pin calls to `block`, e.g. history block of archive node.
+            pub fn at_block<B: ::std::convert::Into<ethers_rs::BlockNumberOrTag>>(mut self, block: B) -> Self {
+                self.block = ::std::option::Option::Some(block.into());
+                self
+            }
+
+            #(#calls)*
+        }
+
+        #[doc = #sends_doc]
+        pub struct #sends_ident<'a> {
+            contract: &'a #contract_ident,
+        }
+
+        impl<'a> #sends_ident<'a> {
+            #(#sends)*
+        }
+
+        #[doc = #statics_doc]
+        pub struct #statics_ident<'a> {
+            contract: &'a #contract_ident,
+        }
+
+        impl<'a> #statics_ident<'a> {
+            #(#statics)*
+        }
+
+        impl #contract_ident {
+            /// Returns namespace of `view`/`pure` methods.
+            pub fn calls(&self) -> #calls_ident<'_> {
+                #calls_ident {
+                    contract: self,
+                    block: ::std::option::Option::None,
+                }
+            }
+
+            /// Returns namespace of methods sending transactions.
+            pub fn sends(&self) -> #sends_ident<'_> {
+                #sends_ident { contract: self }
+            }
+
+            /// Returns namespace running methods of [`sends`](Self::sends) via `eth_call`,
+            /// to get return values and catch reverts before spending gas.
+            pub fn call_static(&self) -> #statics_ident<'_> {
+                #statics_ident { contract: self }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_rust_type() {
+        let param = json!({
+            "type": "tuple[2][]",
+            "components": [{ "type": "address" }, { "type": "uint96[]" }]
+        });
+
+        let uint96 = quote!(ethers_rs::Uint<96>);
+
+        assert_eq!(
+            rust_type(&param, Span::call_site()).unwrap().to_string(),
+            quote!(::std::vec::Vec<[(ethers_rs::Address, ::std::vec::Vec<#uint96>,); 2]>)
+                .to_string()
+        );
+
+        let artifact = json!({
+            "contractName": "Example",
+            "abi": [
+                { "type": "function", "name": "withdraw", "stateMutability": "nonpayable", "inputs": [], "outputs": [] },
+                { "type": "function", "name": "balanceOf", "stateMutability": "view", "inputs": [{ "name": "owner", "type": "address" }], "outputs": [{ "name": "", "type": "uint256" }] }
+            ]
+        });
+
+        let tokens = namespaces(&artifact, Span::call_site())
+            .unwrap()
+            .to_string();
+
+        assert!(tokens.contains("impl < 'a > ExampleSends < 'a > { # [doc = \"Send `withdraw()` transaction.\"] pub async fn withdraw"));
+        assert!(
+            tokens.contains("pub async fn balance_of (& self , owner : ethers_rs :: Address ,)")
+        );
+        assert!(tokens.contains(
+            "impl < 'a > ExampleCallStatic < 'a > { # [doc = \"Run `withdraw()` via `eth_call`"
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+//! Function selector and event topic constants of contract bindings.
+
+use std::collections::HashMap;
+
+use heck::ToShoutySnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use syn::Error;
+
+/// Canonical type of abi parameter, tuples are expanded into `(T1,T2,..)`.
+fn canonical_type(param: &Value) -> Result<String, String> {
+    let ty = param["type"]
+        .as_str()
+        .ok_or_else(|| format!("Abi parameter without `type` field, {}", param))?;
+
+    match ty.strip_prefix("tuple") {
+        Some(suffix) => {
+            let components = param["components"]
+                .as_array()
+                .map(|components| components.iter().map(canonical_type).collect())
+                .unwrap_or_else(|| Ok(vec![]))?;
+
+            Ok(format!("({}){}", components.join(","), suffix))
+        }
+        None => Ok(ty.to_owned()),
+    }
+}
+
+/// Returns `name(T1,T2,..)` signature of abi item.
+pub fn signature(item: &Value) -> Result<String, String> {
+    let inputs = item["inputs"]
+        .as_array()
+        .map(|inputs| inputs.iter().map(canonical_type).collect())
+        .unwrap_or_else(|| Ok(vec![]))?;
+
+    Ok(format!(
+        "{}({})",
+        item["name"].as_str().unwrap_or_default(),
+        inputs.join(",")
+    ))
+}
+
+/// Generate `SELECTOR_{FUNCTION}: [u8; 4]` and `TOPIC_{EVENT}: H256` constants of hardhat artifact.
+///
+/// Overloaded functions/events are suffixed with declaration order, e.g. `SELECTOR_SAFE_TRANSFER_FROM_1`.
+pub fn selector_consts(artifact: &Value, span: Span) -> syn::Result<TokenStream> {
+    let contract_name = artifact["contractName"]
+        .as_str()
+        .ok_or_else(|| Error::new(span, "Hardhat artifact without `contractName` field"))?;
+
+    if !artifact["abi"].is_array() {
+        return Err(Error::new(span, "Hardhat artifact without `abi` array"));
+    }
+
+    let contract_ident = Ident::new(contract_name, Span::call_site());
+
+    let mut overloads = HashMap::new();
+
+    let mut consts = vec![];
+
+    for item in artifact["abi"].as_array().into_iter().flatten() {
+        let (prefix, len) = match item["type"].as_str() {
+            Some("function") => ("SELECTOR", 4),
+            Some("event") if item["anonymous"].as_bool() != Some(true) => ("TOPIC", 32),
+            _ => continue,
+        };
+
+        let signature = signature(item).map_err(|err| Error::new(span, err))?;
+
+        let hash = Keccak256::new()
+            .chain_update(signature.as_bytes())
+            .finalize();
+
+        let bytes = &hash[..len];
+
+        let name = item["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_shouty_snake_case();
+
+        let index = overloads
+            .entry(format!("{}_{}", prefix, name))
+            .and_modify(|index| *index += 1)
+            .or_insert(0usize);
+
+        let const_ident = if *index == 0 {
+            Ident::new(&format!("{}_{}", prefix, name), Span::call_site())
+        } else {
+            Ident::new(&format!("{}_{}_{}", prefix, name, index), Span::call_site())
+        };
+
+        let doc = format!("`{}`", signature);
+
+        if prefix == "SELECTOR" {
+            consts.push(quote! {
+                #[doc = #doc]
+                pub const #const_ident: [u8; 4] = [#(#bytes),*];
+            });
+        } else {
+            consts.push(quote! {
+                #[doc = #doc]
+                pub const #const_ident: ethers_rs::H256 = ethers_rs::FixedBytes::<32>([#(#bytes),*]);
+            });
+        }
+    }
+
+    Ok(quote! {
+        impl #contract_ident {
+            #(#consts)*
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_signature() {
+        let item = json!({
+            "type": "function",
+            "name": "fill",
+            "inputs": [
+                { "type": "tuple[]", "components": [{ "type": "address" }, { "type": "uint96" }] },
+                { "type": "bytes" }
+            ]
+        });
+
+        assert_eq!(signature(&item).unwrap(), "fill((address,uint96)[],bytes)");
+
+        let artifact = json!({
+            "contractName": "ERC20",
+            "abi": [
+                { "type": "function", "name": "transfer", "inputs": [{ "type": "address" }, { "type": "uint256" }] },
+                { "type": "event", "name": "Transfer", "anonymous": false, "inputs": [{ "type": "address" }, { "type": "address" }, { "type": "uint256" }] }
+            ]
+        });
+
+        let tokens = selector_consts(&artifact, Span::call_site())
+            .unwrap()
+            .to_string();
+
+        // transfer(address,uint256) => 0xa9059cbb
+        assert!(tokens.contains("SELECTOR_TRANSFER : [u8 ; 4] = [169u8 , 5u8 , 156u8 , 187u8]"));
+        // Transfer(address,address,uint256) => 0xddf252ad..
+        assert!(tokens.contains("TOPIC_TRANSFER"));
+        assert!(tokens.contains("[221u8 , 242u8 , 82u8 , 173u8"));
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    collections::HashMap,
+    fs::{create_dir_all, read_dir, read_to_string, write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use heck::ToSnakeCase;
+use proc_macro2::Span;
+use serde_json::Value;
+
+/// Collect hardhat contract artifact files, skipping `build-info` and `*.dbg.json` files.
+fn collect_artifacts(dir: &Path, artifacts: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    for entry in read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            if path.file_name().map(|n| n == "build-info").unwrap_or(false) {
+                continue;
+            }
+
+            collect_artifacts(&path, artifacts)?;
+
+            continue;
+        }
+
+        let file_name = path.file_name().unwrap().to_string_lossy();
+
+        if file_name.ends_with(".json") && !file_name.ends_with(".dbg.json") {
+            artifacts.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate binding source code of one hardhat artifact, returns `(contract_name, source)`.
+///
+/// Returns [`None`] if the artifact is not a contract artifact.
+fn generate(path: &Path) -> anyhow::Result<Option<(String, String)>> {
+    let artifact: Value = serde_json::from_str(&read_to_string(path)?)?;
+
+    let contract_name = match ethers_bindgen::contract_name(&artifact) {
+        Some(contract_name) => contract_name.to_owned(),
+        None => return Ok(None),
+    };
+
+    let source = ethers_bindgen::bind_artifact(&artifact, Span::call_site())
+        .map_err(|err| anyhow::format_err!("Generate binding of {:?}: {}", path, err))?;
+
+    Ok(Some((contract_name, source.to_string())))
+}
+
+/// Generate contract bindings of all artifacts in `artifacts` dir into `out` dir.
+pub fn bindgen(artifacts: PathBuf, out: PathBuf) -> anyhow::Result<()> {
+    let mut files = vec![];
+
+    collect_artifacts(&artifacts, &mut files)?;
+
+    files.sort();
+
+    create_dir_all(&out)?;
+
+    let mut modules = vec![];
+    let mut generated = HashMap::new();
+
+    for file in files {
+        if let Some((contract_name, source)) = generate(&file)? {
+            let module = contract_name.to_snake_case();
+
+            // Contracts of the same snake case name would overwrite each other's binding file.
+            if let Some(prev) = generated.insert(module.clone(), file.clone()) {
+                anyhow::bail!(
+                    "Duplicate contract {} in {:?} and {:?}, both generate {}.rs",
+                    contract_name,
+                    prev,
+                    file,
+                    module
+                );
+            }
+
+            let path = out.join(format!("{}.rs", module));
+
+            write(
+                &path,
+                format!(
+                    "// Generated by `cargo ethers bindgen`, do not edit.\n\n{}\n",
+                    source
+                ),
+            )?;
+
+            log::info!("generate {} => {:?}", contract_name, path);
+
+            modules.push(module);
+        }
+    }
+
+    let mod_source = modules
+        .iter()
+        .map(|m| format!("mod {};\npub use {}::*;\n", m, m))
+        .collect::<String>();
+
+    let mod_path = out.join("mod.rs");
+
+    write(
+        &mod_path,
+        format!(
+            "// Generated by `cargo ethers bindgen`, do not edit.\n\n{}",
+            mod_source
+        ),
+    )?;
+
+    // Format generated files, ignore rustfmt not found error.
+    let mut command = Command::new("rustfmt");
+
+    command.arg("--edition").arg("2021").arg(&mod_path);
+
+    for module in &modules {
+        command.arg(out.join(format!("{}.rs", module)));
+    }
+
+    match command.status() {
+        Ok(status) if !status.success() => log::warn!("rustfmt exit with {}", status),
+        Err(err) => log::warn!("rustfmt not found, {}", err),
+        _ => {}
+    }
+
+    println!(
+        "Generated {} contract bindings into {:?}",
+        modules.len(),
+        out
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_contracts() {
+        let artifacts = std::env::temp_dir().join(format!("ethers_bindgen_{}", std::process::id()));
+
+        let artifact = r#"{ "contractName": "Token", "abi": [], "bytecode": "0x" }"#;
+
+        for source in ["Token.sol", "legacy/Token.sol"] {
+            let dir = artifacts.join(source);
+
+            create_dir_all(&dir).unwrap();
+
+            write(dir.join("Token.json"), artifact).unwrap();
+        }
+
+        let err = bindgen(artifacts.clone(), artifacts.join("out")).unwrap_err();
+
+        std::fs::remove_dir_all(&artifacts).unwrap();
+
+        assert!(err.to_string().starts_with("Duplicate contract Token"));
+    }
+}
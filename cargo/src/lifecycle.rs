@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use ethers_hardhat::{
+    cmds::{HardhatBuildProject, HardhatCleanProject, HardhatNetwork},
+    error::HardhatError,
+    futures::executor::block_on,
+    utils::hardhat_default_path,
+};
+
+/// Returns hardhat project root, default is `$CARGO_MANIFEST_DIR/sol`.
+fn hardhat_root(root: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    if let Some(root) = root {
+        Ok(root)
+    } else {
+        hardhat_default_path()
+    }
+}
+
+/// Compile hardhat project contracts.
+pub fn compile(root: Option<PathBuf>) -> anyhow::Result<()> {
+    block_on(async {
+        let mut command = HardhatBuildProject::new_with(hardhat_root(root)?)?;
+
+        command.start().await?;
+
+        let status = command.status().await?;
+
+        if !status.success() {
+            return Err(
+                HardhatError::ChildProcess("npx hardhat compile".to_owned(), status).into(),
+            );
+        }
+
+        Ok(())
+    })
+}
+
+/// Clear hardhat project cache and artifacts.
+pub fn clean(root: Option<PathBuf>) -> anyhow::Result<()> {
+    block_on(async {
+        let mut command = HardhatCleanProject::new_with(hardhat_root(root)?)?;
+
+        command.start().await?;
+
+        let status = command.status().await?;
+
+        if !status.success() {
+            return Err(HardhatError::ChildProcess("npx hardhat clean".to_owned(), status).into());
+        }
+
+        Ok(())
+    })
+}
+
+/// Start hardhat network and keep it running until the process exits.
+pub fn node(root: Option<PathBuf>) -> anyhow::Result<()> {
+    block_on(async {
+        let mut network = HardhatNetwork::new_with(hardhat_root(root)?)?;
+
+        network.start().await?;
+
+        println!("Hardhat network started at http://localhost:8545, press Ctrl-C to stop");
+
+        let status = network.status().await?;
+
+        if !status.success() {
+            return Err(HardhatError::ChildProcess("npx hardhat node".to_owned(), status).into());
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+
+mod bindgen;
+mod lifecycle;
+
+#[derive(Parser)]
+#[command(name = "cargo", bin_name = "cargo")]
+enum Cargo {
+    /// ethers-rs contract workflow tools
+    Ethers(EthersArgs),
+}
+
+#[derive(Args)]
+#[command(version, about)]
+struct EthersArgs {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Generate contract bindings from hardhat artifacts into rust source files
+    Bindgen {
+        /// Hardhat artifacts directory
+        #[arg(long, default_value = "sol/artifacts")]
+        artifacts: PathBuf,
+        /// Output directory of generated binding files
+        #[arg(long, default_value = "src/contracts")]
+        out: PathBuf,
+    },
+    /// Compile hardhat project contracts
+    Compile {
+        /// Hardhat project root, default is `$CARGO_MANIFEST_DIR/sol`
+        #[arg(long)]
+        root: Option<PathBuf>,
+    },
+    /// Start a dev hardhat network and keep it running
+    Node {
+        /// Hardhat project root, default is `$CARGO_MANIFEST_DIR/sol`
+        #[arg(long)]
+        root: Option<PathBuf>,
+    },
+    /// Clear hardhat project cache and artifacts
+    Clean {
+        /// Hardhat project root, default is `$CARGO_MANIFEST_DIR/sol`
+        #[arg(long)]
+        root: Option<PathBuf>,
+    },
+}
+
+fn main() -> anyhow::Result<()> {
+    pretty_env_logger::init();
+
+    let Cargo::Ethers(args) = Cargo::parse();
+
+    match args.command {
+        Commands::Bindgen { artifacts, out } => bindgen::bindgen(artifacts, out),
+        Commands::Compile { root } => lifecycle::compile(root),
+        Commands::Node { root } => lifecycle::node(root),
+        Commands::Clean { root } => lifecycle::clean(root),
+    }
+}
@@ -0,0 +1,35 @@
+//! The blessed set of ethers-rs types.
+//!
+//! Depend on this crate instead of `ethers_primitives`, `ethers_eip2718` and `ethers_eip712`
+//! separately, so every crate of the dependency graph names the same types.
+//!
+//! ```
+//! use ethers_core::*;
+//!
+//! let tx = LegacyTransactionRequest::builder()
+//!     .to(Address::zero_address())
+//!     .value(1u64)
+//!     .build()
+//!     .unwrap();
+//!
+//! let _: U256 = tx.value.unwrap();
+//! ```
+
+pub use ethers_eip2718::*;
+pub use ethers_eip712::*;
+pub use ethers_primitives::*;
+
+/// Contract abi codec.
+pub mod abi {
+    pub use serde_ethabi::*;
+}
+
+/// Rlp codec.
+pub mod rlp {
+    pub use serde_ethrlp::*;
+}
+
+/// EIP-712 struct hashing codec.
+pub mod eip712 {
+    pub use serde_eip712::*;
+}
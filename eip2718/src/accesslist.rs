@@ -1,11 +1,85 @@
+use std::collections::HashSet;
+
 use ethers_primitives::Address;
 use serde::{Deserialize, Serialize};
 
-use crate::H256;
+use crate::{TxBuildError, H256};
+
+/// Gas cost of each address in access list, see EIP-2930.
+pub const ACCESS_LIST_ADDRESS_GAS: u64 = 2400;
+
+/// Gas cost of each storage key in access list, see EIP-2930.
+pub const ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1900;
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct AccessList(pub Vec<Access>);
 
+impl AccessList {
+    pub fn builder() -> AccessListBuilder {
+        AccessListBuilder::default()
+    }
+
+    /// Append items of `other` then [`dedup`](Self::dedup).
+    pub fn merge(&mut self, other: AccessList) {
+        self.0.extend(other.0);
+        self.dedup();
+    }
+
+    /// Merge items of the same address and remove duplicate storage keys,
+    /// the order of first occurrence is kept.
+    pub fn dedup(&mut self) {
+        let mut items: Vec<Access> = vec![];
+
+        for access in self.0.drain(..) {
+            match items.iter_mut().find(|item| item.address == access.address) {
+                Some(item) => item.storage_keys.extend(access.storage_keys),
+                None => items.push(access),
+            }
+        }
+
+        for item in items.iter_mut() {
+            let mut keys = HashSet::new();
+
+            item.storage_keys.retain(|key| keys.insert(key.clone()));
+        }
+
+        self.0 = items;
+    }
+
+    /// Returns the number of storage keys of all items.
+    pub fn storage_keys_len(&self) -> usize {
+        self.0.iter().map(|access| access.storage_keys.len()).sum()
+    }
+
+    /// Returns intrinsic gas added to the transaction by including this list.
+    ///
+    /// Duplicate addresses and storage keys are charged each time, call [`dedup`](Self::dedup) first.
+    pub fn intrinsic_gas(&self) -> u64 {
+        self.0.len() as u64 * ACCESS_LIST_ADDRESS_GAS
+            + self.storage_keys_len() as u64 * ACCESS_LIST_STORAGE_KEY_GAS
+    }
+}
+
+impl From<Vec<Access>> for AccessList {
+    fn from(value: Vec<Access>) -> Self {
+        Self(value)
+    }
+}
+
+impl FromIterator<Access> for AccessList {
+    fn from_iter<T: IntoIterator<Item = Access>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl FromIterator<(Address, Vec<H256>)> for AccessList {
+    fn from_iter<T: IntoIterator<Item = (Address, Vec<H256>)>>(iter: T) -> Self {
+        iter.into_iter()
+            .map(|(address, storage_keys)| Access::new(address, storage_keys))
+            .collect()
+    }
+}
+
 impl Serialize for AccessList {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -33,3 +107,121 @@ pub struct Access {
 
     pub storage_keys: Vec<H256>,
 }
+
+impl Access {
+    pub fn new<K>(address: Address, storage_keys: K) -> Self
+    where
+        K: IntoIterator,
+        K::Item: Into<H256>,
+    {
+        Self {
+            address,
+            storage_keys: storage_keys.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Builder of [`AccessList`], storage keys are added to the last [`address`](Self::address).
+///
+/// ```ignore
+/// let access_list = AccessList::builder()
+///     .address(token)
+///     .storage_keys([slot0, slot1])
+///     .address(pool)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AccessListBuilder {
+    items: Vec<Access>,
+    /// Set if storage keys are added before any address.
+    missing_address: bool,
+}
+
+impl AccessListBuilder {
+    /// Start new item of `address`.
+    pub fn address(mut self, address: Address) -> Self {
+        self.items.push(Access::new(address, Vec::<H256>::new()));
+        self
+    }
+
+    /// Add storage key to the last address.
+    pub fn storage_key<K: Into<H256>>(self, storage_key: K) -> Self {
+        self.storage_keys([storage_key])
+    }
+
+    /// Add storage keys to the last address.
+    pub fn storage_keys<K>(mut self, storage_keys: K) -> Self
+    where
+        K: IntoIterator,
+        K::Item: Into<H256>,
+    {
+        match self.items.last_mut() {
+            Some(access) => access
+                .storage_keys
+                .extend(storage_keys.into_iter().map(Into::into)),
+            None => self.missing_address = true,
+        }
+
+        self
+    }
+
+    /// Build access list, duplicate items are merged, see [`AccessList::dedup`].
+    pub fn build(self) -> Result<AccessList, TxBuildError> {
+        if self.missing_address {
+            return Err(TxBuildError::MissingField("address"));
+        }
+
+        let mut access_list = AccessList(self.items);
+
+        access_list.dedup();
+
+        Ok(access_list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let token: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+        let pool: Address = "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC"
+            .try_into()
+            .unwrap();
+
+        let access_list = AccessList::builder()
+            .address(token)
+            .storage_keys([[1u8; 32], [2u8; 32]])
+            .address(pool)
+            .address(token)
+            .storage_key([1u8; 32])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            access_list,
+            AccessList(vec![
+                Access::new(token, [[1u8; 32], [2u8; 32]]),
+                Access::new(pool, Vec::<H256>::new()),
+            ])
+        );
+
+        assert_eq!(access_list.intrinsic_gas(), 2 * 2400 + 2 * 1900);
+
+        let mut merged: AccessList = [(pool, vec![H256::from([3u8; 32])])].into_iter().collect();
+
+        merged.merge(access_list);
+
+        assert_eq!(merged.0[0].storage_keys.len(), 1);
+        assert_eq!(merged.0[1].address, token);
+        assert_eq!(merged.intrinsic_gas(), 2 * 2400 + 3 * 1900);
+
+        assert_eq!(
+            AccessList::builder().storage_key([1u8; 32]).build(),
+            Err(TxBuildError::MissingField("address"))
+        );
+    }
+}
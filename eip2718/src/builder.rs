@@ -0,0 +1,345 @@
+//! Fluent builders of transaction requests.
+
+use ethers_primitives::*;
+
+use crate::{
+    AccessList, Eip1559TransactionRequest, Eip2930TransactionRequest, LegacyTransactionRequest,
+};
+
+/// Transaction request builder errors
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TxBuildError {
+    /// Required field is not set
+    #[error("MissingField: {0} is required")]
+    MissingField(&'static str),
+    /// Priority fee is higher than max fee
+    #[error("InvalidFee: max priority fee per gas {max_priority_fee_per_gas} exceeds max fee per gas {max_fee_per_gas}")]
+    PriorityFeeTooHigh {
+        max_priority_fee_per_gas: U256,
+        max_fee_per_gas: U256,
+    },
+    /// Gas limit is lower than intrinsic gas
+    #[error("IntrinsicGasTooLow: gas limit {gas} is lower than intrinsic gas {intrinsic_gas}")]
+    IntrinsicGasTooLow { gas: U256, intrinsic_gas: u64 },
+}
+
+/// Setters of fields shared by all transaction types.
+macro_rules! tx_setters {
+    () => {
+        /// Set transaction nonce.
+        pub fn nonce<V: Into<U256>>(mut self, nonce: V) -> Self {
+            self.nonce = Some(nonce.into());
+            self
+        }
+
+        /// Set gas limit.
+        pub fn gas<V: Into<U256>>(mut self, gas: V) -> Self {
+            self.gas = Some(gas.into());
+            self
+        }
+
+        /// Set sender address, used by `eth_call` and `eth_estimateGas`.
+        pub fn from(mut self, from: Address) -> Self {
+            self.from = Some(from);
+            self
+        }
+
+        /// Set recipient, the transaction creates contract if not set.
+        pub fn to(mut self, to: Address) -> Self {
+            self.to = Some(to);
+            self
+        }
+
+        /// Set transferred value in wei, e.g. `"1.5".parse::<Ether>()?`.
+        pub fn value<V: Into<U256>>(mut self, value: V) -> Self {
+            self.value = Some(value.into());
+            self
+        }
+
+        /// Set contract call data or contract init code.
+        pub fn data<D: Into<Bytes>>(mut self, data: D) -> Self {
+            self.data = Some(data.into());
+            self
+        }
+    };
+}
+
+/// Builder of [`LegacyTransactionRequest`].
+#[derive(Debug, Clone, Default)]
+pub struct LegacyTransactionRequestBuilder {
+    from: Option<Address>,
+    nonce: Option<U256>,
+    gas_price: Option<U256>,
+    gas: Option<U256>,
+    to: Option<Address>,
+    value: Option<U256>,
+    data: Option<Bytes>,
+    chain_id: Option<U64>,
+}
+
+impl LegacyTransactionRequest {
+    pub fn builder() -> LegacyTransactionRequestBuilder {
+        LegacyTransactionRequestBuilder::default()
+    }
+}
+
+impl LegacyTransactionRequestBuilder {
+    tx_setters!();
+
+    /// Set gas price.
+    pub fn gas_price<V: Into<U256>>(mut self, gas_price: V) -> Self {
+        self.gas_price = Some(gas_price.into());
+        self
+    }
+
+    /// Set EIP-155 chain id.
+    pub fn chain_id<V: Into<U64>>(mut self, chain_id: V) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Convert into [`Eip2930TransactionRequest`] builder with `access_list`.
+    pub fn access_list(self, access_list: AccessList) -> Eip2930TransactionRequestBuilder {
+        Eip2930TransactionRequestBuilder {
+            tx: self,
+            access_list,
+        }
+    }
+
+    pub fn build(self) -> Result<LegacyTransactionRequest, TxBuildError> {
+        Ok(LegacyTransactionRequest {
+            from: self.from,
+            nonce: self.nonce,
+            gas_price: self.gas_price,
+            gas: self.gas,
+            to: self.to,
+            value: self.value,
+            data: self.data,
+            chain_id: self.chain_id,
+        })
+    }
+}
+
+/// Builder of [`Eip2930TransactionRequest`].
+#[derive(Debug, Clone, Default)]
+pub struct Eip2930TransactionRequestBuilder {
+    tx: LegacyTransactionRequestBuilder,
+    access_list: AccessList,
+}
+
+impl Eip2930TransactionRequest {
+    pub fn builder() -> Eip2930TransactionRequestBuilder {
+        Eip2930TransactionRequestBuilder::default()
+    }
+}
+
+impl Eip2930TransactionRequestBuilder {
+    /// Set transaction nonce.
+    pub fn nonce<V: Into<U256>>(mut self, nonce: V) -> Self {
+        self.tx = self.tx.nonce(nonce);
+        self
+    }
+
+    /// Set gas limit.
+    pub fn gas<V: Into<U256>>(mut self, gas: V) -> Self {
+        self.tx = self.tx.gas(gas);
+        self
+    }
+
+    /// Set sender address, used by `eth_call` and `eth_estimateGas`.
+    pub fn from(mut self, from: Address) -> Self {
+        self.tx = self.tx.from(from);
+        self
+    }
+
+    /// Set recipient, the transaction creates contract if not set.
+    pub fn to(mut self, to: Address) -> Self {
+        self.tx = self.tx.to(to);
+        self
+    }
+
+    /// Set transferred value in wei.
+    pub fn value<V: Into<U256>>(mut self, value: V) -> Self {
+        self.tx = self.tx.value(value);
+        self
+    }
+
+    /// Set contract call data or contract init code.
+    pub fn data<D: Into<Bytes>>(mut self, data: D) -> Self {
+        self.tx = self.tx.data(data);
+        self
+    }
+
+    /// Set gas price.
+    pub fn gas_price<V: Into<U256>>(mut self, gas_price: V) -> Self {
+        self.tx = self.tx.gas_price(gas_price);
+        self
+    }
+
+    /// Set chain id.
+    pub fn chain_id<V: Into<U64>>(mut self, chain_id: V) -> Self {
+        self.tx = self.tx.chain_id(chain_id);
+        self
+    }
+
+    /// Set EIP-2930 access list.
+    pub fn access_list(mut self, access_list: AccessList) -> Self {
+        self.access_list = access_list;
+        self
+    }
+
+    pub fn build(self) -> Result<Eip2930TransactionRequest, TxBuildError> {
+        Ok(Eip2930TransactionRequest {
+            tx: self.tx.build()?,
+            access_list: self.access_list,
+        })
+    }
+}
+
+/// Builder of [`Eip1559TransactionRequest`], unset nonce, gas limit and fees are zero.
+///
+/// ```
+/// # use ethers_eip2718::Eip1559TransactionRequest;
+/// # use ethers_primitives::{Address, Ether};
+/// let tx = Eip1559TransactionRequest::builder()
+///     .to(Address::ZERO)
+///     .value("1.5".parse::<Ether>().unwrap())
+///     .data(vec![0x12, 0x34])
+///     .chain_id(1u64)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Eip1559TransactionRequestBuilder {
+    from: Option<Address>,
+    chain_id: Option<U256>,
+    nonce: Option<U256>,
+    max_priority_fee_per_gas: Option<U256>,
+    max_fee_per_gas: Option<U256>,
+    gas: Option<U256>,
+    to: Option<Address>,
+    value: Option<U256>,
+    data: Option<Bytes>,
+    access_list: AccessList,
+}
+
+impl Eip1559TransactionRequest {
+    pub fn builder() -> Eip1559TransactionRequestBuilder {
+        Eip1559TransactionRequestBuilder::default()
+    }
+}
+
+impl Eip1559TransactionRequestBuilder {
+    tx_setters!();
+
+    /// Set chain id, required.
+    pub fn chain_id<V: Into<U256>>(mut self, chain_id: V) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Set max priority fee per gas paid to block producer.
+    pub fn max_priority_fee_per_gas<V: Into<U256>>(mut self, fee: V) -> Self {
+        self.max_priority_fee_per_gas = Some(fee.into());
+        self
+    }
+
+    /// Set max total fee per gas, including base fee and priority fee.
+    pub fn max_fee_per_gas<V: Into<U256>>(mut self, fee: V) -> Self {
+        self.max_fee_per_gas = Some(fee.into());
+        self
+    }
+
+    /// Set EIP-2930 access list.
+    pub fn access_list(mut self, access_list: AccessList) -> Self {
+        self.access_list = access_list;
+        self
+    }
+
+    /// Build request, checks `chain_id` is set and priority fee does not exceed max fee.
+    pub fn build(self) -> Result<Eip1559TransactionRequest, TxBuildError> {
+        let chain_id = self
+            .chain_id
+            .ok_or(TxBuildError::MissingField("chain_id"))?;
+
+        let zero = U256::from(0u8);
+
+        let max_priority_fee_per_gas = self.max_priority_fee_per_gas.unwrap_or(zero);
+        let max_fee_per_gas = self.max_fee_per_gas.unwrap_or(zero);
+
+        if max_priority_fee_per_gas > max_fee_per_gas {
+            return Err(TxBuildError::PriorityFeeTooHigh {
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+            });
+        }
+
+        Ok(Eip1559TransactionRequest {
+            from: self.from,
+            chain_id,
+            nonce: self.nonce.unwrap_or(zero),
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            gas: self.gas.unwrap_or(zero),
+            to: self.to,
+            value: self.value,
+            data: self.data,
+            access_list: self.access_list,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eip1559_builder() {
+        let to: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        let tx = Eip1559TransactionRequest::builder()
+            .to(to)
+            .value("1.5".parse::<Ether>().unwrap())
+            .data(vec![0x12, 0x34])
+            .chain_id(1u64)
+            .max_fee_per_gas(100u64)
+            .max_priority_fee_per_gas(2u64)
+            .build()
+            .unwrap();
+
+        assert_eq!(tx.to, Some(to));
+        assert_eq!(tx.value, Some(U256::from(1_500_000_000_000_000_000u128)));
+        assert_eq!(tx.data, Some(Bytes::from([0x12, 0x34])));
+        assert_eq!(tx.chain_id, U256::from(1u64));
+
+        assert_eq!(
+            Eip1559TransactionRequest::builder().build().unwrap_err(),
+            TxBuildError::MissingField("chain_id")
+        );
+
+        assert!(matches!(
+            Eip1559TransactionRequest::builder()
+                .chain_id(1u64)
+                .max_fee_per_gas(1u64)
+                .max_priority_fee_per_gas(2u64)
+                .build(),
+            Err(TxBuildError::PriorityFeeTooHigh { .. })
+        ));
+    }
+
+    #[test]
+    fn test_legacy_builder() {
+        let tx = LegacyTransactionRequest::builder()
+            .nonce(1u64)
+            .gas_price(10u64)
+            .chain_id(5u64)
+            .access_list(AccessList::default())
+            .build()
+            .unwrap();
+
+        assert_eq!(tx.tx.nonce, Some(U256::from(1u64)));
+        assert_eq!(tx.tx.chain_id, Some(U64::from(5u64)));
+    }
+}
@@ -0,0 +1,214 @@
+//! Unsigned transaction bundle of offline signing.
+//!
+//! The online machine creates [`UnsignedTransactionBundle`] and transports it to the offline
+//! signer as json or [`qr string`](UnsignedTransactionBundle::to_qr_string). The offline signer
+//! checks the bundle with [`verify`](UnsignedTransactionBundle::verify) and signs the returned hash,
+//! the signature is transported back and assembled by [`rlp_signed`](UnsignedTransactionBundle::rlp_signed).
+
+use ethers_primitives::*;
+use serde::{Deserialize, Serialize};
+
+use crate::TypedTransactionRequest;
+
+/// Prefix of [`UnsignedTransactionBundle::to_qr_string`].
+pub const BUNDLE_QR_PREFIX: &str = "ETHTX:";
+
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    #[error("Bundle chain id {bundle} mismatch transaction chain id {tx:?}")]
+    ChainId { bundle: u64, tx: Option<u64> },
+
+    #[error("Bundle sign hash {bundle} mismatch transaction sign hash {tx}")]
+    SignHash { bundle: H256, tx: H256 },
+
+    #[error(
+        "Invalid bundle qr string, expect prefix {BUNDLE_QR_PREFIX} followed by uppercase hex"
+    )]
+    QrString,
+}
+
+/// Unsigned transaction with chain id and sign hash, the payload of offline signing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedTransactionBundle {
+    pub chain_id: u64,
+    pub tx: TypedTransactionRequest,
+    /// Sign hash of `tx`, for display of offline signer, signers must sign the hash returned by
+    /// [`verify`](Self::verify).
+    pub sign_hash: H256,
+}
+
+tx_json_support!(UnsignedTransactionBundle);
+
+impl TypedTransactionRequest {
+    /// Returns chain id of signing, legacy transactions without chain id are signed with chain id `1`.
+    pub fn chain_id(&self) -> Option<u64> {
+        let default_chain_id = || U64::new(1u8).unwrap();
+
+        match self {
+            Self::Legacy(tx) => tx.chain_id.unwrap_or_else(default_chain_id).into(),
+            Self::Eip2930(tx) => tx.tx.chain_id.unwrap_or_else(default_chain_id).into(),
+            Self::Eip1559(tx) => tx.chain_id.into(),
+        }
+    }
+}
+
+impl UnsignedTransactionBundle {
+    /// Create bundle of `tx`.
+    pub fn new<T: Into<TypedTransactionRequest>>(tx: T) -> anyhow::Result<Self> {
+        let tx = tx.into();
+
+        let chain_id = tx.chain_id().ok_or(BundleError::ChainId {
+            bundle: 0,
+            tx: None,
+        })?;
+
+        let sign_hash = tx.sign_hash()?;
+
+        Ok(Self {
+            chain_id,
+            tx,
+            sign_hash,
+        })
+    }
+
+    /// Check chain id and sign hash match `tx`, returns sign hash recomputed from `tx`.
+    pub fn verify(&self) -> anyhow::Result<H256> {
+        let chain_id = self.tx.chain_id();
+
+        if chain_id != Some(self.chain_id) {
+            return Err(BundleError::ChainId {
+                bundle: self.chain_id,
+                tx: chain_id,
+            }
+            .into());
+        }
+
+        let sign_hash = self.tx.sign_hash()?;
+
+        if sign_hash != self.sign_hash {
+            return Err(BundleError::SignHash {
+                bundle: self.sign_hash.clone(),
+                tx: sign_hash,
+            }
+            .into());
+        }
+
+        Ok(sign_hash)
+    }
+
+    /// Returns signed transaction rlp of `signature` over [`sign_hash`](Self::sign_hash).
+    pub fn rlp_signed<S>(&self, signature: S) -> anyhow::Result<Bytes>
+    where
+        S: Into<Eip1559Signature>,
+    {
+        self.verify()?;
+
+        self.tx.rlp_signed(signature)
+    }
+
+    /// Encode bundle as [`BUNDLE_QR_PREFIX`] followed by uppercase hex of json,
+    /// which fits QR alphanumeric mode.
+    pub fn to_qr_string(&self) -> anyhow::Result<String> {
+        let json = serde_json::to_vec(self)?;
+
+        Ok(format!(
+            "{}{}",
+            BUNDLE_QR_PREFIX,
+            json.to_eth_hex()[2..].to_uppercase()
+        ))
+    }
+
+    /// Decode bundle from [`to_qr_string`](Self::to_qr_string) format.
+    pub fn from_qr_string(value: &str) -> anyhow::Result<Self> {
+        let hex = value
+            .strip_prefix(BUNDLE_QR_PREFIX)
+            .ok_or(BundleError::QrString)?;
+
+        let json = decode_eth_hex(hex, OddLength::Reject).map_err(|_| BundleError::QrString)?;
+
+        Ok(serde_json::from_slice(&json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Eip1559TransactionRequest;
+
+    fn bundle() -> UnsignedTransactionBundle {
+        let tx: Eip1559TransactionRequest = serde_json::from_value(serde_json::json!({
+            "chainId": "0x5",
+            "nonce": "0x1",
+            "maxPriorityFeePerGas": "0x1",
+            "maxFeePerGas": "0x2",
+            "gas": "0x5208",
+            "to": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "value": "0x1",
+            "accessList": []
+        }))
+        .unwrap();
+
+        UnsignedTransactionBundle::new(tx).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bundle = bundle();
+
+        assert_eq!(bundle.chain_id, 5);
+
+        let json = serde_json::to_string(&bundle).unwrap();
+
+        let decoded = UnsignedTransactionBundle::try_from(json).unwrap();
+
+        assert_eq!(decoded.verify().unwrap(), bundle.sign_hash);
+
+        let qr = bundle.to_qr_string().unwrap();
+
+        assert!(qr
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase() || c == ':'));
+
+        let decoded = UnsignedTransactionBundle::from_qr_string(&qr).unwrap();
+
+        let signature = Eip1559Signature {
+            v: 1,
+            r: U256::new(1u8).unwrap(),
+            s: U256::new(2u8).unwrap(),
+        };
+
+        assert_eq!(
+            decoded
+                .rlp_signed(Signature::try_from(signature).unwrap())
+                .unwrap(),
+            bundle
+                .tx
+                .rlp_signed(Eip1559Signature {
+                    v: 1,
+                    r: U256::new(1u8).unwrap(),
+                    s: U256::new(2u8).unwrap(),
+                })
+                .unwrap()
+        );
+
+        assert!(UnsignedTransactionBundle::from_qr_string("ETHTX:0").is_err());
+    }
+
+    #[test]
+    fn test_tampered() {
+        let mut bundle = bundle();
+
+        bundle.chain_id = 1;
+
+        assert!(bundle.verify().is_err());
+
+        let mut bundle = self::bundle();
+
+        if let TypedTransactionRequest::Eip1559(tx) = &mut bundle.tx {
+            tx.value = Some(U256::new(2u8).unwrap());
+        }
+
+        assert!(bundle.verify().is_err());
+    }
+}
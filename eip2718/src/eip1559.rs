@@ -1,5 +1,6 @@
 use ethers_primitives::*;
 
+use bytes::BytesMut;
 use serde::{Deserialize, Serialize};
 use serde_ethrlp::RlpEncoder;
 
@@ -8,6 +9,10 @@ use super::{keccak256, AccessList, H256};
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Eip1559TransactionRequest {
+    /// Sender address for `eth_call` and `eth_estimateGas`, not part of signed transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<Address>,
+
     pub chain_id: U256,
 
     /// Transaction nonce
@@ -25,7 +30,7 @@ pub struct Eip1559TransactionRequest {
     pub value: Option<U256>,
     /// The compiled code of a contract OR the first 4 bytes of the hash of the
     /// invoked method signature and encoded parameters. For details see Ethereum Contract ABI
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "input", skip_serializing_if = "Option::is_none")]
     pub data: Option<Bytes>,
 
     pub access_list: AccessList,
@@ -38,7 +43,7 @@ impl Eip1559TransactionRequest {
     }
 
     pub fn rlp(&self) -> anyhow::Result<Bytes> {
-        let mut s = RlpEncoder::default();
+        let mut s = RlpEncoder::with_buffer(BytesMut::from(&[0x02u8][..]));
         (
             &self.chain_id,
             &self.nonce,
@@ -52,18 +57,17 @@ impl Eip1559TransactionRequest {
         )
             .serialize(&mut s)?;
 
-        let mut buff = vec![0x02u8];
-
-        let mut append = s.finalize()?.into();
-
-        buff.append(&mut append);
-
-        Ok(buff.into())
+        Ok(s.finalize()?.into())
     }
 
     /// Returns signed tx rlp encoding stream.
-    pub fn rlp_signed(&self, signature: Eip1559Signature) -> anyhow::Result<Bytes> {
-        let mut s = RlpEncoder::default();
+    pub fn rlp_signed<S>(&self, signature: S) -> anyhow::Result<Bytes>
+    where
+        S: Into<Eip1559Signature>,
+    {
+        let signature: Eip1559Signature = signature.into();
+
+        let mut s = RlpEncoder::with_buffer(BytesMut::from(&[0x02u8][..]));
 
         (
             &self.chain_id,
@@ -81,22 +85,24 @@ impl Eip1559TransactionRequest {
         )
             .serialize(&mut s)?;
 
-        let mut buff = vec![0x02u8];
-
-        let mut append = s.finalize()?.into();
-
-        buff.append(&mut append);
+        Ok(s.finalize()?.into())
+    }
 
-        Ok(buff.into())
+    /// Returns hash of signed tx, known before broadcasting.
+    pub fn tx_hash<S>(&self, signature: S) -> anyhow::Result<H256>
+    where
+        S: Into<Eip1559Signature>,
+    {
+        Ok(keccak256(self.rlp_signed(signature)?).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use ethers_primitives::Eip1559Signature;
+    use ethers_primitives::{Bytes, Eip1559Signature};
     use serde_json::json;
 
-    use crate::Eip1559TransactionRequest;
+    use crate::{Eip1559TransactionRequest, InputField, TypedTransactionRequest};
 
     #[test]
     fn test_rlp() {
@@ -140,4 +146,36 @@ mod tests {
             "0x02f8bd0180808080808000f870f7940000000000000000000000000000000000000000e1a00000000000000000000000000000000000000000000000000000000000000000f7940000000000000000000000000000000000000000e1a0000000000000000000000000000000000000000000000000000000000000000080a07a53fb20b46d9cc2600d8dc3168a698d41c0dec029d46db4ba88ffe359bbe409a02536bd58c593edcda36c5f2e35ed4db158b0cab202b6b2648403117e483a9b30"
         );
     }
+
+    #[test]
+    fn test_input_field() {
+        let tx: Eip1559TransactionRequest = serde_json::from_value(json!({
+          "maxPriorityFeePerGas": "0x0",
+          "maxFeePerGas": "0x0",
+          "gas": "0x0",
+          "nonce": "0x0",
+          "chainId": "0x1",
+          "input": "0x5544",
+          "accessList": []
+        }))
+        .unwrap();
+
+        assert_eq!(tx.data, Some(Bytes::from([0x55, 0x44])));
+
+        let tx = TypedTransactionRequest::from(tx);
+
+        let value = tx.to_json_with(InputField::Data).unwrap();
+
+        assert_eq!(value["data"], "0x5544");
+        assert!(value.get("input").is_none());
+
+        let value = tx.to_json_with(InputField::Input).unwrap();
+
+        assert_eq!(value["input"], "0x5544");
+        assert!(value.get("data").is_none());
+
+        let value = tx.to_json_with(InputField::Both).unwrap();
+
+        assert_eq!(value["data"], value["input"]);
+    }
 }
@@ -6,6 +6,7 @@ use super::LegacyTransactionRequest;
 
 use ethers_primitives::*;
 
+use bytes::BytesMut;
 use serde_ethrlp::RlpEncoder;
 
 use super::{keccak256, H256};
@@ -26,7 +27,7 @@ impl Eip2930TransactionRequest {
     }
 
     pub fn rlp(&self) -> anyhow::Result<Bytes> {
-        let mut s = RlpEncoder::default();
+        let mut s = RlpEncoder::with_buffer(BytesMut::from(&[0x01u8][..]));
 
         let chain_id = self.tx.chain_id.unwrap_or(U64::new(1u8).unwrap());
 
@@ -42,18 +43,17 @@ impl Eip2930TransactionRequest {
         )
             .serialize(&mut s)?;
 
-        let mut buff = vec![0x01u8];
-
-        let mut append = s.finalize()?.into();
-
-        buff.append(&mut append);
-
-        Ok(buff.into())
+        Ok(s.finalize()?.into())
     }
 
     /// Returns signed tx rlp encoding stream.
-    pub fn rlp_signed(&self, signature: Eip1559Signature) -> anyhow::Result<Bytes> {
-        let mut s = RlpEncoder::default();
+    pub fn rlp_signed<S>(&self, signature: S) -> anyhow::Result<Bytes>
+    where
+        S: Into<Eip1559Signature>,
+    {
+        let signature: Eip1559Signature = signature.into();
+
+        let mut s = RlpEncoder::with_buffer(BytesMut::from(&[0x01u8][..]));
 
         let chain_id = self.tx.chain_id.unwrap_or(U64::new(1u8).unwrap());
 
@@ -72,13 +72,15 @@ impl Eip2930TransactionRequest {
         )
             .serialize(&mut s)?;
 
-        let mut buff = vec![0x01u8];
-
-        let mut append = s.finalize()?.into();
-
-        buff.append(&mut append);
+        Ok(s.finalize()?.into())
+    }
 
-        Ok(buff.into())
+    /// Returns hash of signed tx, known before broadcasting.
+    pub fn tx_hash<S>(&self, signature: S) -> anyhow::Result<H256>
+    where
+        S: Into<Eip1559Signature>,
+    {
+        Ok(keccak256(self.rlp_signed(signature)?).into())
     }
 }
 
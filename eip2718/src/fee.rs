@@ -0,0 +1,160 @@
+//! Size and fee summary of transaction requests.
+
+use ethers_primitives::*;
+
+use crate::{
+    Eip1559TransactionRequest, Eip2930TransactionRequest, LegacyTransactionRequest,
+    TypedTransactionRequest,
+};
+
+/// Signature with 32 bytes `r` and `s`, the longest rlp encoding of a signature.
+fn max_len_signature() -> Eip1559Signature {
+    Eip1559Signature {
+        v: 1,
+        r: U256::max_value(),
+        s: U256::max_value(),
+    }
+}
+
+/// Returns `gas * fee_per_gas + value`, [`None`] on overflow.
+fn max_cost(gas: Option<U256>, fee_per_gas: Option<U256>, value: Option<U256>) -> Option<U256> {
+    gas.unwrap_or_default()
+        .checked_mul(fee_per_gas.unwrap_or_default())?
+        .checked_add(value.unwrap_or_default())
+}
+
+impl LegacyTransactionRequest {
+    /// Returns upper bound of signed rlp length in bytes, signature `r` and `s` are counted as 32 bytes.
+    pub fn rlp_signed_len(&self) -> anyhow::Result<usize> {
+        Ok(self.rlp_signed(max_len_signature())?.len())
+    }
+
+    /// Returns `gas * gas_price + value`, the balance required by sender,
+    /// unset fields are zero and [`None`] is returned on overflow.
+    pub fn max_cost(&self) -> Option<U256> {
+        max_cost(self.gas, self.gas_price, self.value)
+    }
+
+    /// Returns tip per gas paid to block producer under `base_fee`,
+    /// [`None`] if gas price is lower than `base_fee`.
+    pub fn effective_tip(&self, base_fee: U256) -> Option<U256> {
+        self.gas_price.unwrap_or_default().checked_sub(base_fee)
+    }
+}
+
+impl Eip2930TransactionRequest {
+    /// Returns upper bound of signed rlp length in bytes, signature `r` and `s` are counted as 32 bytes.
+    pub fn rlp_signed_len(&self) -> anyhow::Result<usize> {
+        Ok(self.rlp_signed(max_len_signature())?.len())
+    }
+
+    /// Returns `gas * gas_price + value`, see [`LegacyTransactionRequest::max_cost`].
+    pub fn max_cost(&self) -> Option<U256> {
+        self.tx.max_cost()
+    }
+
+    /// Returns tip per gas under `base_fee`, see [`LegacyTransactionRequest::effective_tip`].
+    pub fn effective_tip(&self, base_fee: U256) -> Option<U256> {
+        self.tx.effective_tip(base_fee)
+    }
+}
+
+impl Eip1559TransactionRequest {
+    /// Returns upper bound of signed rlp length in bytes, signature `r` and `s` are counted as 32 bytes.
+    pub fn rlp_signed_len(&self) -> anyhow::Result<usize> {
+        Ok(self.rlp_signed(max_len_signature())?.len())
+    }
+
+    /// Returns `gas * max_fee_per_gas + value`, the balance required by sender,
+    /// [`None`] on overflow.
+    pub fn max_cost(&self) -> Option<U256> {
+        max_cost(Some(self.gas), Some(self.max_fee_per_gas), self.value)
+    }
+
+    /// Returns `min(max_priority_fee_per_gas, max_fee_per_gas - base_fee)`,
+    /// [`None`] if max fee per gas is lower than `base_fee`.
+    pub fn effective_tip(&self, base_fee: U256) -> Option<U256> {
+        let tip = self.max_fee_per_gas.checked_sub(base_fee)?;
+
+        if tip < self.max_priority_fee_per_gas {
+            Some(tip)
+        } else {
+            Some(self.max_priority_fee_per_gas)
+        }
+    }
+}
+
+impl TypedTransactionRequest {
+    /// Returns upper bound of signed rlp length in bytes.
+    pub fn rlp_signed_len(&self) -> anyhow::Result<usize> {
+        match self {
+            Self::Legacy(tx) => tx.rlp_signed_len(),
+            Self::Eip2930(tx) => tx.rlp_signed_len(),
+            Self::Eip1559(tx) => tx.rlp_signed_len(),
+        }
+    }
+
+    /// Returns the balance required by sender, [`None`] on overflow.
+    pub fn max_cost(&self) -> Option<U256> {
+        match self {
+            Self::Legacy(tx) => tx.max_cost(),
+            Self::Eip2930(tx) => tx.max_cost(),
+            Self::Eip1559(tx) => tx.max_cost(),
+        }
+    }
+
+    /// Returns tip per gas paid to block producer under `base_fee`, [`None`] if the
+    /// transaction can not be included.
+    pub fn effective_tip(&self, base_fee: U256) -> Option<U256> {
+        match self {
+            Self::Legacy(tx) => tx.effective_tip(base_fee),
+            Self::Eip2930(tx) => tx.effective_tip(base_fee),
+            Self::Eip1559(tx) => tx.effective_tip(base_fee),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_summary() {
+        let tx = Eip1559TransactionRequest::builder()
+            .chain_id(1u64)
+            .gas(21000u64)
+            .max_fee_per_gas(100u64)
+            .max_priority_fee_per_gas(2u64)
+            .value(1u64)
+            .build()
+            .unwrap();
+
+        assert_eq!(tx.max_cost(), Some(U256::from(2_100_001u64)));
+        assert_eq!(tx.effective_tip(U256::from(90u64)), Some(U256::from(2u64)));
+        assert_eq!(tx.effective_tip(U256::from(99u64)), Some(U256::from(1u64)));
+        assert_eq!(tx.effective_tip(U256::from(101u64)), None);
+
+        let signed = tx
+            .rlp_signed(Eip1559Signature {
+                v: 0,
+                r: U256::from(1u64),
+                s: U256::from(1u64),
+            })
+            .unwrap();
+
+        // Short `r` and `s` also shrink the list header.
+        assert_eq!(tx.rlp_signed_len().unwrap(), signed.len() + 2 * 32 + 1);
+
+        let tx = LegacyTransactionRequest::builder()
+            .gas(U256::max_value())
+            .gas_price(2u64)
+            .build()
+            .unwrap();
+
+        assert_eq!(tx.max_cost(), None);
+        assert_eq!(
+            TypedTransactionRequest::from(tx).effective_tip(U256::from(1u64)),
+            Some(U256::from(1u64))
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Intrinsic gas of transaction requests, the minimum gas limit accepted by nodes.
+
+use ethers_primitives::*;
+
+use crate::{
+    AccessList, Eip1559TransactionRequest, Eip2930TransactionRequest, LegacyTransactionRequest,
+    TxBuildError, TypedTransactionRequest,
+};
+
+/// Base gas of every transaction.
+pub const TX_GAS: u64 = 21000;
+
+/// Extra gas of contract creation transaction since Homestead.
+pub const TX_CREATE_GAS: u64 = 32000;
+
+/// Gas of each zero byte of call data.
+pub const TX_DATA_ZERO_GAS: u64 = 4;
+
+/// Gas of each non-zero byte of call data before Istanbul.
+pub const TX_DATA_NON_ZERO_GAS_FRONTIER: u64 = 68;
+
+/// Gas of each non-zero byte of call data since Istanbul, see EIP-2028.
+pub const TX_DATA_NON_ZERO_GAS: u64 = 16;
+
+/// Gas of each 32 bytes word of init code since Shanghai, see EIP-3860.
+pub const INIT_CODE_WORD_GAS: u64 = 2;
+
+/// Hardforks which change the intrinsic gas rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Hardfork {
+    Frontier,
+    /// Contract creation costs [`TX_CREATE_GAS`].
+    Homestead,
+    /// Non-zero call data byte costs [`TX_DATA_NON_ZERO_GAS`].
+    Istanbul,
+    /// Access list is charged, see EIP-2930.
+    Berlin,
+    /// Init code words are charged, see EIP-3860.
+    #[default]
+    Shanghai,
+}
+
+/// Returns intrinsic gas of transaction with call `data` and `access_list` under `fork` rules.
+pub fn intrinsic_gas(
+    fork: Hardfork,
+    create: bool,
+    data: &[u8],
+    access_list: Option<&AccessList>,
+) -> u64 {
+    let mut gas = TX_GAS;
+
+    if create && fork >= Hardfork::Homestead {
+        gas += TX_CREATE_GAS;
+    }
+
+    let zero_bytes = data.iter().filter(|b| **b == 0).count() as u64;
+    let non_zero_bytes = data.len() as u64 - zero_bytes;
+
+    let non_zero_gas = if fork >= Hardfork::Istanbul {
+        TX_DATA_NON_ZERO_GAS
+    } else {
+        TX_DATA_NON_ZERO_GAS_FRONTIER
+    };
+
+    gas += zero_bytes * TX_DATA_ZERO_GAS + non_zero_bytes * non_zero_gas;
+
+    if create && fork >= Hardfork::Shanghai {
+        gas += (data.len() as u64).div_ceil(32) * INIT_CODE_WORD_GAS;
+    }
+
+    if let Some(access_list) = access_list {
+        if fork >= Hardfork::Berlin {
+            gas += access_list.intrinsic_gas();
+        }
+    }
+
+    gas
+}
+
+fn data_of(data: &Option<Bytes>) -> &[u8] {
+    data.as_ref()
+        .map(|data| data.as_bytes())
+        .unwrap_or_default()
+}
+
+impl LegacyTransactionRequest {
+    /// Returns intrinsic gas under `fork` rules.
+    pub fn intrinsic_gas(&self, fork: Hardfork) -> u64 {
+        intrinsic_gas(fork, self.to.is_none(), data_of(&self.data), None)
+    }
+}
+
+impl Eip2930TransactionRequest {
+    /// Returns intrinsic gas under `fork` rules.
+    pub fn intrinsic_gas(&self, fork: Hardfork) -> u64 {
+        intrinsic_gas(
+            fork,
+            self.tx.to.is_none(),
+            data_of(&self.tx.data),
+            Some(&self.access_list),
+        )
+    }
+}
+
+impl Eip1559TransactionRequest {
+    /// Returns intrinsic gas under `fork` rules.
+    pub fn intrinsic_gas(&self, fork: Hardfork) -> u64 {
+        intrinsic_gas(
+            fork,
+            self.to.is_none(),
+            data_of(&self.data),
+            Some(&self.access_list),
+        )
+    }
+}
+
+impl TypedTransactionRequest {
+    /// Returns intrinsic gas under `fork` rules.
+    pub fn intrinsic_gas(&self, fork: Hardfork) -> u64 {
+        match self {
+            Self::Legacy(tx) => tx.intrinsic_gas(fork),
+            Self::Eip2930(tx) => tx.intrinsic_gas(fork),
+            Self::Eip1559(tx) => tx.intrinsic_gas(fork),
+        }
+    }
+
+    /// Returns error if gas limit is lower than [`intrinsic_gas`](Self::intrinsic_gas),
+    /// which is rejected by nodes with `intrinsic gas too low`. Unset gas limit is not checked.
+    pub fn check_intrinsic_gas(&self, fork: Hardfork) -> Result<(), TxBuildError> {
+        let gas = match self {
+            Self::Legacy(tx) => tx.gas,
+            Self::Eip2930(tx) => tx.tx.gas,
+            Self::Eip1559(tx) => Some(tx.gas),
+        };
+
+        let intrinsic_gas = self.intrinsic_gas(fork);
+
+        match gas {
+            Some(gas) if gas < U256::from(intrinsic_gas) => {
+                Err(TxBuildError::IntrinsicGasTooLow { gas, intrinsic_gas })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intrinsic_gas() {
+        let to: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        let tx = LegacyTransactionRequest::builder()
+            .to(to)
+            .data(vec![0u8, 1, 2])
+            .gas(21000u64)
+            .build()
+            .unwrap();
+
+        assert_eq!(tx.intrinsic_gas(Hardfork::Frontier), 21000 + 4 + 2 * 68);
+        assert_eq!(tx.intrinsic_gas(Hardfork::Shanghai), 21000 + 4 + 2 * 16);
+
+        let tx = TypedTransactionRequest::from(tx);
+
+        assert_eq!(
+            tx.check_intrinsic_gas(Hardfork::default()),
+            Err(TxBuildError::IntrinsicGasTooLow {
+                gas: 21000u64.into(),
+                intrinsic_gas: 21036
+            })
+        );
+
+        let tx = Eip2930TransactionRequest::builder()
+            .data(vec![1u8; 33])
+            .access_list(
+                AccessList::builder()
+                    .address(to)
+                    .storage_key([0u8; 32])
+                    .build()
+                    .unwrap(),
+            )
+            .gas(100000u64)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            tx.intrinsic_gas(Hardfork::Shanghai),
+            21000 + 32000 + 33 * 16 + 2 * 2 + 2400 + 1900
+        );
+        assert_eq!(
+            tx.intrinsic_gas(Hardfork::Istanbul),
+            21000 + 32000 + 33 * 16
+        );
+
+        assert!(TypedTransactionRequest::from(tx)
+            .check_intrinsic_gas(Hardfork::Shanghai)
+            .is_ok());
+    }
+}
@@ -0,0 +1,219 @@
+use ethers_primitives::*;
+
+use serde::{Deserialize, Serialize};
+use serde_ethrlp::RlpEncoder;
+
+use super::{keccak256, H256};
+
+/// Ethereum block header, which can be deserialized from `eth_getBlockBy*` responses.
+///
+/// Fork specific fields are optional, and must be set in fork order:
+/// London(`base_fee_per_gas`), Shanghai(`withdrawals_root`),
+/// Cancun(`blob_gas_used`/`excess_blob_gas`/`parent_beacon_block_root`) and Prague(`requests_hash`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Header {
+    pub parent_hash: H256,
+    #[serde(rename = "sha3Uncles")]
+    pub ommers_hash: H256,
+    #[serde(rename = "miner")]
+    pub beneficiary: Address,
+    pub state_root: H256,
+    pub transactions_root: H256,
+    pub receipts_root: H256,
+    pub logs_bloom: Bloom,
+    pub difficulty: U256,
+    pub number: U256,
+    pub gas_limit: U256,
+    pub gas_used: U256,
+    pub timestamp: U256,
+    pub extra_data: Bytes,
+    pub mix_hash: H256,
+    pub nonce: BytesM<8>,
+    /// EIP-1559
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+    /// EIP-4895
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<H256>,
+    /// EIP-4844
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_gas_used: Option<U64>,
+    /// EIP-4844
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excess_blob_gas: Option<U64>,
+    /// EIP-4788
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_beacon_block_root: Option<H256>,
+    /// EIP-7685
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_hash: Option<H256>,
+}
+
+impl Header {
+    /// Returns block hash, aka keccak256 hash of header rlp encoding.
+    pub fn hash(&self) -> anyhow::Result<H256> {
+        Ok(keccak256(self.rlp()?.0).into())
+    }
+
+    pub fn rlp(&self) -> anyhow::Result<Bytes> {
+        let mut s = RlpEncoder::default();
+
+        s.begin_list()?;
+
+        self.parent_hash.serialize(&mut s)?;
+        self.ommers_hash.serialize(&mut s)?;
+        self.beneficiary.serialize(&mut s)?;
+        self.state_root.serialize(&mut s)?;
+        self.transactions_root.serialize(&mut s)?;
+        self.receipts_root.serialize(&mut s)?;
+        self.logs_bloom.serialize(&mut s)?;
+        self.difficulty.serialize(&mut s)?;
+        self.number.serialize(&mut s)?;
+        self.gas_limit.serialize(&mut s)?;
+        self.gas_used.serialize(&mut s)?;
+        self.timestamp.serialize(&mut s)?;
+        self.extra_data.serialize(&mut s)?;
+        self.mix_hash.serialize(&mut s)?;
+        self.nonce.serialize(&mut s)?;
+
+        let mut missing = None;
+
+        fork_field(
+            &mut s,
+            &mut missing,
+            "baseFeePerGas",
+            &self.base_fee_per_gas,
+        )?;
+        fork_field(
+            &mut s,
+            &mut missing,
+            "withdrawalsRoot",
+            &self.withdrawals_root,
+        )?;
+        fork_field(&mut s, &mut missing, "blobGasUsed", &self.blob_gas_used)?;
+        fork_field(&mut s, &mut missing, "excessBlobGas", &self.excess_blob_gas)?;
+        fork_field(
+            &mut s,
+            &mut missing,
+            "parentBeaconBlockRoot",
+            &self.parent_beacon_block_root,
+        )?;
+        fork_field(&mut s, &mut missing, "requestsHash", &self.requests_hash)?;
+
+        s.end_list()?;
+
+        Ok(s.finalize()?.into())
+    }
+}
+
+/// Append optional fork field to header rlp list, fields introduced by later forks
+/// can't be set if any prior fork field is none.
+fn fork_field<T: Serialize>(
+    s: &mut RlpEncoder,
+    missing: &mut Option<&'static str>,
+    name: &'static str,
+    value: &Option<T>,
+) -> anyhow::Result<()> {
+    match value {
+        Some(value) => {
+            if let Some(missing) = missing {
+                return Err(anyhow::format_err!(
+                    "Header field {} is set, but prior fork field {} is none",
+                    name,
+                    missing
+                ));
+            }
+
+            value.serialize(s)?;
+        }
+        None => {
+            if missing.is_none() {
+                *missing = Some(name);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::Header;
+
+    #[test]
+    fn test_genesis_hash() {
+        let header = json!({
+            "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "miner": "0x0000000000000000000000000000000000000000",
+            "stateRoot": "0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+            "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "difficulty": "0x400000000",
+            "number": "0x0",
+            "gasLimit": "0x1388",
+            "gasUsed": "0x0",
+            "timestamp": "0x0",
+            "extraData": "0x11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+            "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "nonce": "0x0000000000000042",
+        });
+
+        let header: Header = serde_json::from_value(header).unwrap();
+
+        assert_eq!(
+            header.hash().unwrap().to_string(),
+            "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+        );
+    }
+
+    #[test]
+    fn test_holesky_genesis_hash() {
+        let header = json!({
+            "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "miner": "0x0000000000000000000000000000000000000000",
+            "stateRoot": "0x69d8c9d72f6fa4ad42d4702b433707212f90db395eb54dc20bc85de253788783",
+            "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "difficulty": "0x1",
+            "number": "0x0",
+            "gasLimit": "0x17d7840",
+            "gasUsed": "0x0",
+            "timestamp": "0x65156994",
+            "extraData": "0x",
+            "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "nonce": "0x0000000000001234",
+            "baseFeePerGas": "0x3b9aca00",
+        });
+
+        let header: Header = serde_json::from_value(header).unwrap();
+
+        assert_eq!(
+            header.hash().unwrap().to_string(),
+            "0xb5f7f912443c940f21fd611f12828d75b534364ed9e95ca4e307729a4661bde4"
+        );
+    }
+
+    #[test]
+    fn test_fork_fields_order() {
+        let mut header = Header {
+            blob_gas_used: Some(Default::default()),
+            ..Default::default()
+        };
+
+        header
+            .rlp()
+            .expect_err("missing base fee and withdrawals root");
+
+        header.base_fee_per_gas = Some(Default::default());
+        header.withdrawals_root = Some(Default::default());
+
+        header.rlp().unwrap();
+    }
+}
@@ -9,6 +9,9 @@ use super::{keccak256, H256};
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct LegacyTransactionRequest {
+    /// Sender address for `eth_call` and `eth_estimateGas`, not part of signed transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<Address>,
     /// Transaction nonce
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce: Option<U256>,
@@ -25,7 +28,7 @@ pub struct LegacyTransactionRequest {
     pub value: Option<U256>,
     /// The compiled code of a contract OR the first 4 bytes of the hash of the
     /// invoked method signature and encoded parameters. For details see Ethereum Contract ABI
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "input", skip_serializing_if = "Option::is_none")]
     pub data: Option<Bytes>,
     /// Chain id for EIP-155
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -60,7 +63,12 @@ impl LegacyTransactionRequest {
     }
 
     /// Returns signed tx rlp encoding stream.
-    pub fn rlp_signed(&self, signature: Eip1559Signature) -> anyhow::Result<Bytes> {
+    pub fn rlp_signed<S>(&self, signature: S) -> anyhow::Result<Bytes>
+    where
+        S: Into<Eip1559Signature>,
+    {
+        let signature: Eip1559Signature = signature.into();
+
         let mut rlp = RlpEncoder::default();
 
         // encode v,r,s
@@ -83,6 +91,14 @@ impl LegacyTransactionRequest {
 
         Ok(rlp.finalize()?.into())
     }
+
+    /// Returns hash of signed tx, known before broadcasting.
+    pub fn tx_hash<S>(&self, signature: S) -> anyhow::Result<H256>
+    where
+        S: Into<Eip1559Signature>,
+    {
+        Ok(keccak256(self.rlp_signed(signature)?).into())
+    }
 }
 
 #[cfg(test)]
@@ -27,29 +27,68 @@ macro_rules! tx_json_support {
     };
 }
 
-pub fn keccak256<S>(bytes: S) -> [u8; 32]
-where
-    S: AsRef<[u8]>,
-{
-    let mut hasher = Keccak256::new();
+pub use ethers_primitives::{keccak256, keccak256_concat, KeccakHasher};
 
-    hasher.update(bytes.as_ref());
-
-    hasher.finalize().into()
-}
-
-#[derive(Debug, Serialize, Deserialize)]
+/// ENS name or account address, names are resolved by provider.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum NameOrAddress {
-    Name(String),
     Address(crate::Address),
+    Name(String),
+}
+
+impl From<Address> for NameOrAddress {
+    fn from(value: Address) -> Self {
+        Self::Address(value)
+    }
+}
+
+impl From<&Address> for NameOrAddress {
+    fn from(value: &Address) -> Self {
+        Self::Address(*value)
+    }
+}
+
+impl std::str::FromStr for NameOrAddress {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("0x") {
+            Ok(Self::Address(s.try_into()?))
+        } else if s.is_empty() {
+            Err(anyhow::format_err!("ENS name is empty"))
+        } else {
+            Ok(Self::Name(s.to_owned()))
+        }
+    }
 }
 
-use sha3::{Digest, Keccak256};
+impl<'a> TryFrom<&'a str> for NameOrAddress {
+    type Error = anyhow::Error;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for NameOrAddress {
+    type Error = anyhow::Error;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl std::fmt::Display for NameOrAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Address(address) => write!(f, "{}", address),
+            Self::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
 
 tx_json_support!(LegacyTransactionRequest);
 tx_json_support!(Eip2930TransactionRequest);
 tx_json_support!(Eip1559TransactionRequest);
+tx_json_support!(UserOperation);
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type")]
@@ -65,6 +104,30 @@ pub enum TypedTransactionRequest {
     Eip1559(Eip1559TransactionRequest),
 }
 
+impl TryFrom<serde_json::Value> for TypedTransactionRequest {
+    type Error = serde_json::Error;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        Self::from_value(value)
+    }
+}
+
+impl TryFrom<&str> for TypedTransactionRequest {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::from_value(serde_json::from_str(value)?)
+    }
+}
+
+impl TryFrom<String> for TypedTransactionRequest {
+    type Error = serde_json::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_ref())
+    }
+}
+
 impl From<LegacyTransactionRequest> for TypedTransactionRequest {
     fn from(tx: LegacyTransactionRequest) -> Self {
         TypedTransactionRequest::Legacy(tx)
@@ -83,7 +146,92 @@ impl From<Eip1559TransactionRequest> for TypedTransactionRequest {
     }
 }
 
+/// Json field name of transaction call data, nodes disagree on `data` and `input`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputField {
+    /// `data`, accepted by hardhat and most nodes.
+    #[default]
+    Data,
+    /// `input`, the field name of geth transaction objects.
+    Input,
+    /// Both `data` and `input` with the same value.
+    Both,
+}
+
+impl InputField {
+    /// Rename call data field of serialized transaction `object`.
+    pub fn apply(&self, object: &mut serde_json::Map<String, serde_json::Value>) {
+        match self {
+            Self::Data => {}
+            Self::Input => {
+                if let Some(data) = object.remove("data") {
+                    object.insert("input".to_owned(), data);
+                }
+            }
+            Self::Both => {
+                if let Some(data) = object.get("data").cloned() {
+                    object.insert("input".to_owned(), data);
+                }
+            }
+        }
+    }
+}
+
 impl TypedTransactionRequest {
+    /// Parse json transaction request, the variant is inferred if `type` tag is missing:
+    ///
+    /// - `maxFeePerGas` is present, [`Eip1559`](Self::Eip1559).
+    /// - `accessList` is present without `maxFeePerGas`, [`Eip2930`](Self::Eip2930).
+    /// - otherwise [`Legacy`](Self::Legacy).
+    pub fn from_value(value: serde_json::Value) -> serde_json::Result<Self> {
+        let has_field = |name: &str| value.get(name).is_some_and(|v| !v.is_null());
+
+        let tx_type = match value.get("type") {
+            None | Some(serde_json::Value::Null) => {
+                if has_field("maxFeePerGas") {
+                    2
+                } else if has_field("accessList") {
+                    1
+                } else {
+                    0
+                }
+            }
+            // Also accepts non-canonical tags, e.g. `0x2`.
+            Some(serde_json::Value::String(tag)) => {
+                u8::from_str_radix(tag.trim_start_matches("0x"), 16).map_err(|_| {
+                    serde::de::Error::custom(format!("Invalid transaction type {}", tag))
+                })?
+            }
+            Some(tag) => {
+                return Err(serde::de::Error::custom(format!(
+                    "Invalid transaction type {}",
+                    tag
+                )))
+            }
+        };
+
+        match tx_type {
+            0 => Ok(Self::Legacy(serde_json::from_value(value)?)),
+            1 => Ok(Self::Eip2930(serde_json::from_value(value)?)),
+            2 => Ok(Self::Eip1559(serde_json::from_value(value)?)),
+            _ => Err(serde::de::Error::custom(format!(
+                "Unsupported transaction type 0x{:02x}",
+                tx_type
+            ))),
+        }
+    }
+
+    /// Serialize to json value, naming call data field by `field`.
+    pub fn to_json_with(&self, field: InputField) -> serde_json::Result<serde_json::Value> {
+        let mut value = serde_json::to_value(self)?;
+
+        if let Some(object) = value.as_object_mut() {
+            field.apply(object);
+        }
+
+        Ok(value)
+    }
+
     pub fn sign_hash(&self) -> anyhow::Result<H256> {
         match self {
             Self::Legacy(tx) => tx.sign_hash(),
@@ -92,13 +240,49 @@ impl TypedTransactionRequest {
         }
     }
 
-    pub fn rlp_signed(&self, signature: Eip1559Signature) -> anyhow::Result<Bytes> {
+    pub fn rlp_signed<S>(&self, signature: S) -> anyhow::Result<Bytes>
+    where
+        S: Into<Eip1559Signature>,
+    {
+        let signature: Eip1559Signature = signature.into();
+
         match self {
             Self::Legacy(tx) => tx.rlp_signed(signature),
             Self::Eip2930(tx) => tx.rlp_signed(signature),
             Self::Eip1559(tx) => tx.rlp_signed(signature),
         }
     }
+
+    /// Returns hash of signed tx, see [`rlp_signed`](Self::rlp_signed).
+    pub fn tx_hash<S>(&self, signature: S) -> anyhow::Result<H256>
+    where
+        S: Into<Eip1559Signature>,
+    {
+        match self {
+            Self::Legacy(tx) => tx.tx_hash(signature),
+            Self::Eip2930(tx) => tx.tx_hash(signature),
+            Self::Eip1559(tx) => tx.tx_hash(signature),
+        }
+    }
+
+    /// Returns signed rlp of `txs`, `signatures` are in the order of `txs`.
+    pub fn rlp_signed_batch<S>(txs: &[Self], signatures: Vec<S>) -> anyhow::Result<Vec<Bytes>>
+    where
+        S: Into<Eip1559Signature>,
+    {
+        if txs.len() != signatures.len() {
+            return Err(anyhow::format_err!(
+                "signatures length {} != transactions length {}",
+                signatures.len(),
+                txs.len()
+            ));
+        }
+
+        txs.iter()
+            .zip(signatures)
+            .map(|(tx, signature)| tx.rlp_signed(signature))
+            .collect()
+    }
 }
 
 mod accesslist;
@@ -112,3 +296,138 @@ pub use eip2930::*;
 
 mod eip1559;
 pub use eip1559::*;
+
+mod builder;
+pub use builder::*;
+
+mod header;
+pub use header::*;
+
+mod trie;
+pub use trie::*;
+
+mod receipt;
+pub use receipt::*;
+
+mod user_operation;
+pub use user_operation::*;
+
+mod bundle;
+pub use bundle::*;
+
+mod gas;
+pub use gas::*;
+
+mod fee;
+
+mod signed;
+pub use signed::*;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_infer_type() {
+        let tx = TypedTransactionRequest::from_value(json!({
+            "chainId": "0x1",
+            "nonce": "0x1",
+            "maxPriorityFeePerGas": "0x1",
+            "maxFeePerGas": "0x2",
+            "gas": "0x5208",
+            "to": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "value": "0x1",
+            "accessList": []
+        }))
+        .unwrap();
+
+        assert!(matches!(tx, TypedTransactionRequest::Eip1559(_)));
+
+        let tx = TypedTransactionRequest::from_value(json!({
+            "chainId": "0x1",
+            "nonce": "0x1",
+            "gasPrice": "0x1",
+            "gas": "0x5208",
+            "value": "0x1",
+            "accessList": []
+        }))
+        .unwrap();
+
+        assert!(matches!(tx, TypedTransactionRequest::Eip2930(_)));
+
+        let tx = TypedTransactionRequest::try_from(
+            r#"{"nonce":"0x1","gasPrice":"0x1","gas":"0x5208","value":"0x1","maxFeePerGas":null}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(tx, TypedTransactionRequest::Legacy(_)));
+    }
+
+    #[test]
+    fn test_type_tag() {
+        let tx = TypedTransactionRequest::from_value(json!({
+            "type": "0x2",
+            "chainId": "0x1",
+            "nonce": "0x1",
+            "maxPriorityFeePerGas": "0x1",
+            "maxFeePerGas": "0x2",
+            "gas": "0x5208",
+            "value": "0x1",
+            "accessList": []
+        }))
+        .unwrap();
+
+        assert!(matches!(tx, TypedTransactionRequest::Eip1559(_)));
+
+        assert!(TypedTransactionRequest::from_value(json!({ "type": "0x05" })).is_err());
+    }
+
+    #[test]
+    fn test_name_or_address() {
+        let value: NameOrAddress = "vitalik.eth".try_into().unwrap();
+
+        assert_eq!(value, NameOrAddress::Name("vitalik.eth".to_owned()));
+
+        let value: NameOrAddress = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        assert!(matches!(value, NameOrAddress::Address(_)));
+
+        assert!(NameOrAddress::try_from("0x1234").is_err());
+        assert!(NameOrAddress::try_from("").is_err());
+    }
+
+    #[test]
+    fn test_rlp_signed_batch() {
+        let txs = (1..=3u8)
+            .map(|nonce| {
+                TypedTransactionRequest::from_value(json!({
+                    "nonce": format!("0x{}", nonce),
+                    "gasPrice": "0x1",
+                    "gas": "0x5208",
+                    "value": "0x1",
+                }))
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let signature = |v: u8| Eip1559Signature {
+            v,
+            r: U256::new(1u8).unwrap(),
+            s: U256::new(2u8).unwrap(),
+        };
+
+        let signed = TypedTransactionRequest::rlp_signed_batch(
+            &txs,
+            vec![signature(0), signature(1), signature(0)],
+        )
+        .unwrap();
+
+        assert_eq!(signed[1], txs[1].rlp_signed(signature(1)).unwrap());
+
+        assert!(TypedTransactionRequest::rlp_signed_batch(&txs, vec![signature(0)]).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use ethers_primitives::*;
+
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+use serde_ethrlp::RlpEncoder;
+
+use super::{ordered_trie_root, H256};
+
+/// Log entry of [`TypedReceipt`]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReceiptLog {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Bytes,
+}
+
+impl BloomLog for ReceiptLog {
+    fn address(&self) -> &Address {
+        &self.address
+    }
+
+    fn topics(&self) -> &[H256] {
+        &self.topics
+    }
+}
+
+/// Post-Byzantium transaction receipt in consensus encoding.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedReceipt {
+    /// EIP-2718 transaction type, `0` for legacy transaction.
+    pub tx_type: u8,
+    /// Transaction execution status.
+    pub success: bool,
+    pub cumulative_gas_used: U256,
+    pub logs_bloom: Bloom,
+    pub logs: Vec<ReceiptLog>,
+}
+
+impl TypedReceipt {
+    /// Returns receipt encoding, `tx_type || rlp([status, cumulativeGasUsed, logsBloom, logs])`
+    /// for typed transactions, or the bare rlp list for legacy transactions.
+    pub fn rlp(&self) -> anyhow::Result<Bytes> {
+        // Typed receipt is prefixed by tx type.
+        let buff = if self.tx_type == 0 {
+            BytesMut::new()
+        } else {
+            BytesMut::from(&[self.tx_type][..])
+        };
+
+        let mut s = RlpEncoder::with_buffer(buff);
+
+        (
+            self.success as u8,
+            &self.cumulative_gas_used,
+            &self.logs_bloom,
+            &self.logs,
+        )
+            .serialize(&mut s)?;
+
+        Ok(s.finalize()?.into())
+    }
+}
+
+/// Calculate block `receiptsRoot` from receipts in block order.
+pub fn receipts_root<'a, I>(receipts: I) -> anyhow::Result<H256>
+where
+    I: IntoIterator<Item = &'a TypedReceipt>,
+{
+    let receipts = receipts
+        .into_iter()
+        .map(|receipt| receipt.rlp())
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(ordered_trie_root(receipts.iter().map(|receipt| &receipt.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{receipts_root, TypedReceipt};
+
+    #[test]
+    fn test_rlp() {
+        let receipt = json!({
+            "txType": 2,
+            "success": true,
+            "cumulativeGasUsed": "0x5208",
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "logs": [
+                {
+                    "address": "0x1111111111111111111111111111111111111111",
+                    "topics": ["0x2222222222222222222222222222222222222222222222222222222222222222"],
+                    "data": "0x33"
+                }
+            ]
+        });
+
+        let receipt: TypedReceipt = serde_json::from_value(receipt).unwrap();
+
+        assert_eq!(
+            receipt.rlp().unwrap().to_string(),
+            format!(
+                "0x02f9014301825208b90100{}f83af838941111111111111111111111111111111111111111e1a0222222222222222222222222222222222222222222222222222222222222222233",
+                "00".repeat(256)
+            )
+        );
+    }
+
+    #[test]
+    fn test_receipts_root() {
+        // `receiptsRoot` of blocks including only one successful legacy ether transfer.
+        let receipt: TypedReceipt = serde_json::from_value(json!({
+            "txType": 0,
+            "success": true,
+            "cumulativeGasUsed": "0x5208",
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "logs": []
+        }))
+        .unwrap();
+
+        assert_eq!(
+            receipts_root([&receipt]).unwrap().to_string(),
+            "0x056b23fbba480696b65fe5a59b8f2148a1299103c4f57df839233af2cf4ca2d2"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Signed transaction envelope.
+
+use ethers_primitives::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{keccak256, TypedTransactionRequest};
+
+/// Signed transaction, retains the request, signature, raw rlp encoding and tx hash.
+///
+/// Only `request` and `signature` are read by deserialization, `raw` and `hash` are recomputed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", try_from = "SignedTransactionParts")]
+pub struct SignedTransaction {
+    request: TypedTransactionRequest,
+    signature: Eip1559Signature,
+    raw: Bytes,
+    hash: H256,
+}
+
+#[derive(Deserialize)]
+struct SignedTransactionParts {
+    request: TypedTransactionRequest,
+    signature: Eip1559Signature,
+}
+
+impl TryFrom<SignedTransactionParts> for SignedTransaction {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SignedTransactionParts) -> Result<Self, Self::Error> {
+        Self::new(value.request, value.signature)
+    }
+}
+
+impl SignedTransaction {
+    /// Create signed transaction of `request` with `signature` of its [`sign_hash`](TypedTransactionRequest::sign_hash).
+    pub fn new<T, S>(request: T, signature: S) -> anyhow::Result<Self>
+    where
+        T: Into<TypedTransactionRequest>,
+        S: Into<Eip1559Signature>,
+    {
+        let request = request.into();
+        let signature = signature.into();
+
+        let raw = request.rlp_signed(signature.clone())?;
+
+        let hash = keccak256(&raw).into();
+
+        Ok(Self {
+            request,
+            signature,
+            raw,
+            hash,
+        })
+    }
+
+    /// Returns the signed request.
+    pub fn request(&self) -> &TypedTransactionRequest {
+        &self.request
+    }
+
+    /// Returns the signature.
+    pub fn signature(&self) -> &Eip1559Signature {
+        &self.signature
+    }
+
+    /// Returns signed rlp encoding, the payload of `eth_sendRawTransaction`.
+    pub fn raw(&self) -> &Bytes {
+        &self.raw
+    }
+
+    /// Returns transaction hash.
+    pub fn hash(&self) -> &H256 {
+        &self.hash
+    }
+
+    /// Consume self and returns signed rlp encoding.
+    pub fn into_raw(self) -> Bytes {
+        self.raw
+    }
+}
+
+impl From<SignedTransaction> for Bytes {
+    fn from(value: SignedTransaction) -> Self {
+        value.raw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_signed_transaction() {
+        let tx = TypedTransactionRequest::from_value(json!({
+            "nonce": "0x1",
+            "to": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "value": "0x1",
+            "data": "0x",
+            "gas": "0x60000",
+            "gasPrice": "0x60000111"
+        }))
+        .unwrap();
+
+        let signature: Eip1559Signature = "0x016c7e1e13070e6f10e51d7d20e986c59fd080fc6afc5508f44e8b0a84a58b7d1a13c20fa2b6d77ae6814a41b674946387dde6401c73eb0cab2246a2981c48e344".parse().unwrap();
+
+        let signed = SignedTransaction::new(tx, signature).unwrap();
+
+        assert_eq!(
+            signed.raw().to_string(),
+            "0xf864018460000111830600009470997970c51812dc3a010c7d01b50e0d17dc79c8018026a06c7e1e13070e6f10e51d7d20e986c59fd080fc6afc5508f44e8b0a84a58b7d1aa013c20fa2b6d77ae6814a41b674946387dde6401c73eb0cab2246a2981c48e344"
+        );
+
+        assert_eq!(signed.hash(), &H256::from(keccak256(signed.raw())));
+        assert_eq!(
+            signed.hash(),
+            &signed
+                .request()
+                .tx_hash(signed.signature().clone())
+                .unwrap()
+        );
+
+        let mut value = serde_json::to_value(&signed).unwrap();
+
+        assert_eq!(value["hash"], signed.hash().to_string());
+
+        // Tampered hash is recomputed.
+        value["hash"] = json!(H256::default());
+
+        let decoded: SignedTransaction = serde_json::from_value(value).unwrap();
+
+        assert_eq!(decoded.hash(), signed.hash());
+        assert_eq!(decoded.signature(), signed.signature());
+    }
+}
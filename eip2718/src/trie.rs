@@ -0,0 +1,245 @@
+use serde_ethrlp::rlp_encode;
+
+use super::{keccak256, H256};
+
+/// Root hash of an empty trie, aka keccak256(rlp("")).
+pub fn empty_trie_root() -> H256 {
+    keccak256([0x80u8]).into()
+}
+
+/// Calculate Merkle-Patricia trie root of `(key, value)` pairs.
+///
+/// Duplicated keys are not allowed, the later one wins.
+pub fn trie_root<I, K, V>(pairs: I) -> H256
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let mut items = pairs
+        .into_iter()
+        .map(|(key, value)| (to_nibbles(key.as_ref()), value.as_ref().to_vec()))
+        .collect::<Vec<_>>();
+
+    if items.is_empty() {
+        return empty_trie_root();
+    }
+
+    items.sort_by(|a, b| a.0.cmp(&b.0));
+    items.dedup_by(|later, prior| {
+        if later.0 == prior.0 {
+            std::mem::swap(later, prior);
+            true
+        } else {
+            false
+        }
+    });
+
+    keccak256(build_node(&items, 0)).into()
+}
+
+/// Calculate trie root of `values` keyed by rlp encoded index,
+/// which is used by block `transactionsRoot`/`receiptsRoot`/`withdrawalsRoot`.
+pub fn ordered_trie_root<I, V>(values: I) -> H256
+where
+    I: IntoIterator<Item = V>,
+    V: AsRef<[u8]>,
+{
+    trie_root(
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| (rlp_encode(&index).expect("Rlp encode usize"), value)),
+    )
+}
+
+/// Calculate block `transactionsRoot` from signed raw transactions,
+/// aka [`rlp_signed`](crate::TypedTransactionRequest::rlp_signed) outputs in block order.
+pub fn transactions_root<I, B>(signed_txs: I) -> H256
+where
+    I: IntoIterator<Item = B>,
+    B: AsRef<[u8]>,
+{
+    ordered_trie_root(signed_txs)
+}
+
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+/// Hex prefix encoding of node path.
+fn hex_prefix(nibbles: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = if leaf { 0x20u8 } else { 0x00u8 };
+
+    let mut buff = vec![];
+
+    if nibbles.len() % 2 == 1 {
+        buff.push(flag | 0x10 | nibbles[0]);
+
+        for pair in nibbles[1..].chunks(2) {
+            buff.push(pair[0] << 4 | pair[1]);
+        }
+    } else {
+        buff.push(flag);
+
+        for pair in nibbles.chunks(2) {
+            buff.push(pair[0] << 4 | pair[1]);
+        }
+    }
+
+    buff
+}
+
+fn rlp_length_prefix(len: usize, offset: u8) -> Vec<u8> {
+    if len < 56 {
+        vec![offset + len as u8]
+    } else {
+        let len_bytes = len.to_be_bytes();
+        let lead_zeros = len_bytes.iter().take_while(|c| **c == 0).count();
+
+        let mut buff = vec![offset + 55 + (len_bytes.len() - lead_zeros) as u8];
+
+        buff.extend_from_slice(&len_bytes[lead_zeros..]);
+
+        buff
+    }
+}
+
+fn rlp_string(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+
+    let mut buff = rlp_length_prefix(bytes.len(), 0x80);
+
+    buff.extend_from_slice(bytes);
+
+    buff
+}
+
+/// Make rlp list from rlp encoded items.
+fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+
+    let mut buff = rlp_length_prefix(payload.len(), 0xc0);
+
+    buff.extend_from_slice(&payload);
+
+    buff
+}
+
+/// Child node reference, nodes shorter than 32 bytes are embedded.
+fn node_ref(node: Vec<u8>) -> Vec<u8> {
+    if node.len() < 32 {
+        node
+    } else {
+        rlp_string(&keccak256(node))
+    }
+}
+
+/// Build rlp encoded node of sorted `items`, whose keys share the first `depth` nibbles.
+fn build_node(items: &[(Vec<u8>, Vec<u8>)], depth: usize) -> Vec<u8> {
+    if items.len() == 1 {
+        let (key, value) = &items[0];
+
+        return rlp_list(&[
+            rlp_string(&hex_prefix(&key[depth..], true)),
+            rlp_string(value),
+        ]);
+    }
+
+    let first = &items[0].0;
+    let last = &items[items.len() - 1].0;
+
+    // items are sorted, so the common prefix of all keys is the common prefix of first and last.
+    let prefix = first[depth..]
+        .iter()
+        .zip(&last[depth..])
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    if prefix > 0 {
+        let child = build_node(items, depth + prefix);
+
+        return rlp_list(&[
+            rlp_string(&hex_prefix(&first[depth..depth + prefix], false)),
+            node_ref(child),
+        ]);
+    }
+
+    let mut branch = vec![];
+    let mut value = rlp_string(&[]);
+    let mut items = items;
+
+    // key ends at this branch
+    if items[0].0.len() == depth {
+        value = rlp_string(&items[0].1);
+        items = &items[1..];
+    }
+
+    for nibble in 0..16u8 {
+        let len = items
+            .iter()
+            .take_while(|(key, _)| key[depth] == nibble)
+            .count();
+
+        if len == 0 {
+            branch.push(rlp_string(&[]));
+        } else {
+            branch.push(node_ref(build_node(&items[..len], depth + 1)));
+            items = &items[len..];
+        }
+    }
+
+    branch.push(value);
+
+    rlp_list(&branch)
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers_primitives::FromEtherHex;
+
+    use super::*;
+
+    #[test]
+    fn test_trie_root() {
+        assert_eq!(
+            empty_trie_root().to_string(),
+            "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+        );
+
+        assert_eq!(
+            trie_root([
+                ("doe", "reindeer"),
+                ("dog", "puppy"),
+                ("dogglesworth", "cat")
+            ])
+            .to_string(),
+            "0x8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3"
+        );
+
+        assert_eq!(
+            trie_root([
+                ("do", "verb"),
+                ("horse", "stallion"),
+                ("doge", "coin"),
+                ("dog", "puppy")
+            ])
+            .to_string(),
+            "0x5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84"
+        );
+    }
+
+    #[test]
+    fn test_transactions_root() {
+        // The only transaction of mainnet block 46147,
+        // 0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060
+        let raw = Vec::<u8>::from_eth_hex("0xf86780862d79883d2000825208945df9b87991262f6ba471f09758cde1c0fc1de734827a69801ca088ff6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0a045e0aff800961cfce805daef7016b9b675c137a6a41a548f7b60a3484c06a33a").unwrap();
+
+        assert_eq!(
+            transactions_root([&raw]).to_string(),
+            "0x4513310fcb9f6f616972a3b948dc5d547f280849a87ebb5af0191f98b87be598"
+        );
+    }
+}
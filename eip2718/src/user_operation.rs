@@ -0,0 +1,322 @@
+//! [`ERC-4337`](https://eips.ethereum.org/EIPS/eip-4337) user operation of entry point v0.7.
+
+use ethers_primitives::*;
+use serde::{Deserialize, Serialize};
+
+/// Entry point v0.7 contract address.
+pub const ENTRY_POINT_V07: &str = "0x0000000071727De22E5E9d8BAf0edAc6f37da032";
+
+#[derive(Debug, thiserror::Error)]
+pub enum UserOperationError {
+    #[error("Invalid init code length {0}, expect 0 or at least 20")]
+    InitCode(usize),
+
+    #[error("Invalid paymaster and data length {0}, expect 0 or at least 52")]
+    PaymasterAndData(usize),
+}
+
+/// Unpacked user operation, the json format of bundler rpc.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    /// Account factory, only for new accounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory_data: Option<Bytes>,
+    pub call_data: Bytes,
+    pub call_gas_limit: U128,
+    pub verification_gas_limit: U128,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U128,
+    pub max_priority_fee_per_gas: U128,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    pub signature: Bytes,
+}
+
+/// Packed user operation, the `PackedUserOperation` struct of entry point contract.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackedUserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    /// `factory ++ factory_data`, empty if factory is not set.
+    pub init_code: Bytes,
+    pub call_data: Bytes,
+    /// `verification_gas_limit ++ call_gas_limit`, 16 bytes each.
+    pub account_gas_limits: H256,
+    pub pre_verification_gas: U256,
+    /// `max_priority_fee_per_gas ++ max_fee_per_gas`, 16 bytes each.
+    pub gas_fees: H256,
+    /// `paymaster ++ paymaster_verification_gas_limit ++ paymaster_post_op_gas_limit ++ paymaster_data`,
+    /// empty if paymaster is not set.
+    pub paymaster_and_data: Bytes,
+    pub signature: Bytes,
+}
+
+/// Concat lower 16 bytes of `high` and `low`.
+fn pack_u128(high: &U128, low: &U128) -> H256 {
+    let mut buff = [0u8; 32];
+
+    buff[..16].copy_from_slice(&high.0[16..]);
+    buff[16..].copy_from_slice(&low.0[16..]);
+
+    buff.into()
+}
+
+/// Split packed word into `(high, low)`.
+fn unpack_u128(word: &[u8]) -> (U128, U128) {
+    let mut high = [0u8; 32];
+    let mut low = [0u8; 32];
+
+    high[16..].copy_from_slice(&word[..16]);
+    low[16..].copy_from_slice(&word[16..32]);
+
+    (Uint(high), Uint(low))
+}
+
+/// Left pad `address` into a 32 bytes abi word.
+fn address_word(address: &Address) -> [u8; 32] {
+    let mut buff = [0u8; 32];
+
+    buff[12..].copy_from_slice(&address.0);
+
+    buff
+}
+
+impl UserOperation {
+    /// Convert into [`PackedUserOperation`].
+    pub fn pack(&self) -> PackedUserOperation {
+        let mut init_code = vec![];
+
+        if let Some(factory) = &self.factory {
+            init_code.extend_from_slice(&factory.0);
+
+            if let Some(data) = &self.factory_data {
+                init_code.extend_from_slice(data);
+            }
+        }
+
+        let mut paymaster_and_data = vec![];
+
+        if let Some(paymaster) = &self.paymaster {
+            paymaster_and_data.extend_from_slice(&paymaster.0);
+
+            let limits = pack_u128(
+                &self.paymaster_verification_gas_limit.unwrap_or_default(),
+                &self.paymaster_post_op_gas_limit.unwrap_or_default(),
+            );
+
+            paymaster_and_data.extend_from_slice(&limits.0);
+
+            if let Some(data) = &self.paymaster_data {
+                paymaster_and_data.extend_from_slice(data);
+            }
+        }
+
+        PackedUserOperation {
+            sender: self.sender,
+            nonce: self.nonce,
+            init_code: init_code.into(),
+            call_data: self.call_data.clone(),
+            account_gas_limits: pack_u128(&self.verification_gas_limit, &self.call_gas_limit),
+            pre_verification_gas: self.pre_verification_gas,
+            gas_fees: pack_u128(&self.max_priority_fee_per_gas, &self.max_fee_per_gas),
+            paymaster_and_data: paymaster_and_data.into(),
+            signature: self.signature.clone(),
+        }
+    }
+
+    /// Returns `userOpHash` signed by account owner, for `entry_point` deployed on chain `chain_id`.
+    pub fn hash(&self, entry_point: &Address, chain_id: U256) -> H256 {
+        self.pack().hash(entry_point, chain_id)
+    }
+}
+
+impl PackedUserOperation {
+    /// Convert into [`UserOperation`].
+    pub fn unpack(&self) -> Result<UserOperation, UserOperationError> {
+        let (factory, factory_data) = match self.init_code.len() {
+            0 => (None, None),
+            len if len < 20 => return Err(UserOperationError::InitCode(len)),
+            _ => (
+                Some(Address(self.init_code[..20].try_into().unwrap())),
+                Some(self.init_code.slice(20..)),
+            ),
+        };
+
+        let (
+            paymaster,
+            paymaster_verification_gas_limit,
+            paymaster_post_op_gas_limit,
+            paymaster_data,
+        ) = match self.paymaster_and_data.len() {
+            0 => (None, None, None, None),
+            len if len < 52 => return Err(UserOperationError::PaymasterAndData(len)),
+            _ => {
+                let (verification, post_op) = unpack_u128(&self.paymaster_and_data[20..52]);
+
+                (
+                    Some(Address(self.paymaster_and_data[..20].try_into().unwrap())),
+                    Some(verification),
+                    Some(post_op),
+                    Some(self.paymaster_and_data.slice(52..)),
+                )
+            }
+        };
+
+        let (verification_gas_limit, call_gas_limit) = unpack_u128(&self.account_gas_limits.0);
+        let (max_priority_fee_per_gas, max_fee_per_gas) = unpack_u128(&self.gas_fees.0);
+
+        Ok(UserOperation {
+            sender: self.sender,
+            nonce: self.nonce,
+            factory,
+            factory_data,
+            call_data: self.call_data.clone(),
+            call_gas_limit,
+            verification_gas_limit,
+            pre_verification_gas: self.pre_verification_gas,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            paymaster,
+            paymaster_verification_gas_limit,
+            paymaster_post_op_gas_limit,
+            paymaster_data,
+            signature: self.signature.clone(),
+        })
+    }
+
+    /// Returns `userOpHash` signed by account owner, for `entry_point` deployed on chain `chain_id`.
+    ///
+    /// `keccak256(abi.encode(keccak256(encoded_op), entry_point, chain_id))`, signature is not hashed.
+    pub fn hash(&self, entry_point: &Address, chain_id: U256) -> H256 {
+        let encoded_op = keccak256_concat(&[
+            &address_word(&self.sender),
+            &self.nonce.0,
+            &keccak256(&self.init_code),
+            &keccak256(&self.call_data),
+            &self.account_gas_limits.0,
+            &self.pre_verification_gas.0,
+            &self.gas_fees.0,
+            &keccak256(&self.paymaster_and_data),
+        ]);
+
+        keccak256_concat(&[&encoded_op, &address_word(entry_point), &chain_id.0]).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn user_op() -> UserOperation {
+        serde_json::from_value(json!({
+            "sender": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "nonce": "0x1",
+            "factory": "0x9406Cc6185a346906296840746125a0E44976454",
+            "factoryData": "0x1234",
+            "callData": "0xb61d27f6",
+            "callGasLimit": "0x5208",
+            "verificationGasLimit": "0x10000",
+            "preVerificationGas": "0xc350",
+            "maxFeePerGas": "0x3b9aca00",
+            "maxPriorityFeePerGas": "0x5f5e100",
+            "paymaster": "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+            "paymasterVerificationGasLimit": "0x7530",
+            "paymasterPostOpGasLimit": "0x2710",
+            "paymasterData": "0xabcd",
+            "signature": "0x"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pack() {
+        let op = user_op();
+
+        let packed = op.pack();
+
+        assert_eq!(
+            packed.init_code.to_string(),
+            "0x9406cc6185a346906296840746125a0e449764541234"
+        );
+
+        assert_eq!(
+            packed.account_gas_limits.to_string(),
+            "0x0000000000000000000000000001000000000000000000000000000000005208"
+        );
+
+        assert_eq!(
+            packed.gas_fees.to_string(),
+            "0x00000000000000000000000005f5e1000000000000000000000000003b9aca00"
+        );
+
+        assert_eq!(
+            packed.paymaster_and_data.to_string(),
+            "0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc0000000000000000000000000000753000000000000000000000000000002710abcd"
+        );
+
+        assert_eq!(packed.unpack().unwrap(), op);
+
+        let mut op = op;
+
+        op.factory = None;
+        op.factory_data = None;
+        op.paymaster = None;
+        op.paymaster_verification_gas_limit = None;
+        op.paymaster_post_op_gas_limit = None;
+        op.paymaster_data = None;
+
+        assert_eq!(op.pack().unpack().unwrap(), op);
+
+        let mut packed = op.pack();
+
+        packed.init_code = vec![1u8; 10].into();
+
+        assert!(packed.unpack().is_err());
+    }
+
+    #[test]
+    fn test_hash() {
+        let op = user_op();
+
+        let entry_point: Address = ENTRY_POINT_V07.try_into().unwrap();
+
+        // `EntryPoint.getUserOpHash` of v0.7, aka
+        // `keccak256(abi.encode(keccak256(abi.encode(sender, nonce, keccak256(initCode), ..)), entryPoint, chainid))`,
+        // computed by a standalone js abi encoder.
+        let hash = op.hash(&entry_point, U256::new(1u8).unwrap());
+
+        assert_eq!(
+            hash.to_eth_hex(),
+            "0xc99ad0002c9e24bdd0be9db5aa33445cf4ebb61eb96035883ad0d637945c9edc"
+        );
+
+        assert_eq!(
+            op.hash(&entry_point, U256::new(11155111u32).unwrap())
+                .to_eth_hex(),
+            "0x8e4b082358c7bc65919c9df6536866c7af092743fb23de92e59d5df226406333"
+        );
+
+        // Signature is not hashed.
+        let mut signed = op.clone();
+
+        signed.signature = vec![1u8; 65].into();
+
+        assert_eq!(signed.hash(&entry_point, U256::new(1u8).unwrap()), hash);
+
+        assert_ne!(op.hash(&entry_point, U256::new(2u8).unwrap()), hash);
+    }
+}
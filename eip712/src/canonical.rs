@@ -0,0 +1,253 @@
+//! Canonical json form of [`TypedData`], used to compare `eth_signTypedData_v4` payloads
+//! byte by byte with other clients, e.g. MetaMask.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+use crate::{eip712_type_definitions, TypeDefinition, TypedData};
+
+impl<M> TypedData<M>
+where
+    M: Serialize,
+{
+    /// Returns canonical json value of this payload:
+    ///
+    /// - object keys are sorted, field order of type definitions is kept;
+    /// - `address`/`bytes`/`bytesN` values are lowercase with `0x` prefix;
+    /// - integers which fit in 64 bits are json numbers, otherwise lowercase hex strings;
+    /// - `null` domain fields are removed.
+    pub fn to_canonical_value(&self) -> anyhow::Result<Value> {
+        let mut domain_types = match self.types.get("EIP712Domain") {
+            Some(_) => HashMap::new(),
+            None => eip712_type_definitions(&self.domain)?,
+        };
+
+        domain_types.extend(self.types.clone());
+
+        let mut domain = serde_json::to_value(&self.domain)?;
+
+        if let Value::Object(object) = &mut domain {
+            object.retain(|_, value| !value.is_null());
+        }
+
+        let canonicalizer = Canonicalizer {
+            types: &domain_types,
+        };
+
+        let mut payload = Map::new();
+
+        payload.insert(
+            "domain".to_owned(),
+            canonicalizer.canonicalize("EIP712Domain", domain),
+        );
+        payload.insert(
+            "message".to_owned(),
+            canonicalizer.canonicalize(&self.primary_type, serde_json::to_value(&self.message)?),
+        );
+        payload.insert(
+            "primaryType".to_owned(),
+            Value::String(self.primary_type.clone()),
+        );
+        payload.insert("types".to_owned(), serde_json::to_value(&self.types)?);
+
+        Ok(sort_keys(Value::Object(payload)))
+    }
+
+    /// Returns compact json string of [`to_canonical_value`](Self::to_canonical_value).
+    pub fn to_canonical_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string(&self.to_canonical_value()?)?)
+    }
+
+    /// Check if canonical forms of two payloads are equal, e.g. typed request and untyped fixture.
+    pub fn canonical_eq<N: Serialize>(&self, other: &TypedData<N>) -> anyhow::Result<bool> {
+        Ok(self.to_canonical_value()? == other.to_canonical_value()?)
+    }
+
+    /// Check if canonical form of this payload equals to the one of `json` payload.
+    pub fn canonical_eq_json(&self, json: &str) -> anyhow::Result<bool> {
+        let other: TypedData<Value> = serde_json::from_str(json)?;
+
+        self.canonical_eq(&other)
+    }
+}
+
+struct Canonicalizer<'a> {
+    types: &'a HashMap<String, TypeDefinition>,
+}
+
+impl<'a> Canonicalizer<'a> {
+    /// Canonicalize `value` of `type_name`, values which do not match their types are kept as is.
+    fn canonicalize(&self, type_name: &str, value: Value) -> Value {
+        if let Some(element_type) = type_name
+            .strip_suffix(']')
+            .and_then(|type_name| type_name.rsplit_once('['))
+            .map(|(element_type, _)| element_type)
+        {
+            return match value {
+                Value::Array(elements) => Value::Array(
+                    elements
+                        .into_iter()
+                        .map(|element| self.canonicalize(element_type, element))
+                        .collect(),
+                ),
+                value => value,
+            };
+        }
+
+        if let Some(definition) = self.types.get(type_name) {
+            return match value {
+                Value::Object(mut object) => {
+                    for field in definition {
+                        if let Some(field_value) = object.remove(&field.name) {
+                            object.insert(
+                                field.name.clone(),
+                                self.canonicalize(&field.r#type, field_value),
+                            );
+                        }
+                    }
+
+                    Value::Object(object)
+                }
+                value => value,
+            };
+        }
+
+        match value {
+            Value::String(value) if type_name == "address" || type_name.starts_with("bytes") => {
+                Value::String(canonical_hex(&value).unwrap_or(value))
+            }
+            Value::String(value)
+                if type_name.starts_with("int") || type_name.starts_with("uint") =>
+            {
+                canonical_int(value)
+            }
+            value => value,
+        }
+    }
+}
+
+/// Returns lowercase `0x` prefixed form of hex string `value`, or [`None`] if it is not hex.
+fn canonical_hex(value: &str) -> Option<String> {
+    let hex = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Some(format!("0x{}", hex.to_lowercase()))
+}
+
+fn canonical_int(value: String) -> Value {
+    let number = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok().map(Number::from),
+        None => value
+            .parse::<u64>()
+            .map(Number::from)
+            .or_else(|_| value.parse::<i64>().map(Number::from))
+            .ok(),
+    };
+
+    match number {
+        Some(number) => Value::Number(number),
+        None => Value::String(canonical_hex(&value).unwrap_or(value)),
+    }
+}
+
+/// Rebuild objects with sorted keys, which is independent of `preserve_order` feature of `serde_json`.
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Array(elements) => Value::Array(elements.into_iter().map(sort_keys).collect()),
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| (key, sort_keys(value)))
+                .collect::<BTreeMap<_, _>>()
+                .into_iter()
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::eip712_into_request;
+
+    use super::*;
+
+    #[test]
+    fn test_canonical_json() {
+        let fixture = include_str!("./eip712_arrays.json");
+
+        let request: TypedData<Value> = serde_json::from_str(fixture).unwrap();
+
+        let mut upper = request.clone();
+
+        upper.message["from"]["wallets"][0] = json!("0XCD2A3D9F938E13CD947EC05ABC7FE734DF8DD826");
+
+        assert!(upper.canonical_eq(&request).unwrap());
+        assert!(upper.canonical_eq_json(fixture).unwrap());
+
+        let canonical = request.to_canonical_json().unwrap();
+
+        assert!(canonical.starts_with(
+            r#"{"domain":{"chainId":1,"name":"Ether Mail","verifyingContract":"0xcccccccccccccccccccccccccccccccccccccccc","version":"1"}"#
+        ));
+        assert!(canonical.contains(r#""wallets":["0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826","#));
+        assert!(canonical.contains(
+            r#""types":{"EIP712Domain":[{"name":"name","type":"string"},{"name":"version","type":"string"}"#
+        ));
+
+        upper.message["contents"] = json!("Hello, Alice!");
+
+        assert!(!upper.canonical_eq(&request).unwrap());
+
+        #[derive(Serialize)]
+        struct Person {
+            name: String,
+            wallets: Vec<crate::Address>,
+        }
+
+        #[derive(Serialize)]
+        struct Mail {
+            from: Person,
+            to: Vec<Person>,
+            contents: String,
+        }
+
+        let message = Mail {
+            from: Person {
+                name: "Cow".to_owned(),
+                wallets: serde_json::from_value(request.message["from"]["wallets"].clone())
+                    .unwrap(),
+            },
+            to: vec![Person {
+                name: "Bob".to_owned(),
+                wallets: serde_json::from_value(request.message["to"][0]["wallets"].clone())
+                    .unwrap(),
+            }],
+            contents: "Hello, Bob!".to_owned(),
+        };
+
+        let typed = eip712_into_request(request.domain.clone(), message).unwrap();
+
+        assert!(!typed.canonical_eq(&request).unwrap());
+
+        // Fixture declares unused type `Group`.
+        let mut request = request;
+
+        request.types.remove("Group");
+
+        assert!(typed.canonical_eq(&request).unwrap());
+    }
+}
@@ -5,18 +5,11 @@ use serde::{Deserialize, Serialize};
 pub use serde_eip712::*;
 
 use ethers_primitives::{Address, Bytes32, H256, U256};
-use sha3::{Digest, Keccak256};
 
-pub fn keccak256<S>(bytes: S) -> [u8; 32]
-where
-    S: AsRef<[u8]>,
-{
-    let mut hasher = Keccak256::new();
+pub use ethers_primitives::{keccak256, keccak256_concat, KeccakHasher};
 
-    hasher.update(bytes.as_ref());
+mod canonical;
 
-    hasher.finalize().into()
-}
 ///
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -59,19 +52,147 @@ where
         Ok(keccak256(self.encode()?).into())
     }
 
+    /// Check domain against `EIP712Domain` and message against [`types`](Self::types),
+    /// see [`eip712_validate`].
+    pub fn validate(&self) -> Result<(), ValidateError> {
+        let mut domain = serde_json::to_value(&self.domain)?;
+
+        // Unset optional fields, e.g. `salt`, are not declared by `EIP712Domain`.
+        if let serde_json::Value::Object(object) = &mut domain {
+            object.retain(|_, value| !value.is_null());
+        }
+
+        eip712_validate("EIP712Domain", &self.types, &domain)?;
+
+        eip712_validate(&self.primary_type, &self.types, &self.message)
+    }
+
+    /// Encode `"\x19\x01" ‖ domainSeparator ‖ hashStruct(message)`, message is validated first,
+    /// a malformed untyped message returns error instead of a wrong hash.
     pub fn encode(&self) -> anyhow::Result<[u8; 66]> {
+        self.validate()?;
+
         let domain = eip712_hash_struct("EIP712Domain", &self.types, &self.domain)?;
 
         let message = eip712_hash_struct(&self.primary_type, &self.types, &self.message)?;
 
-        // "\x19\x01" ‖ domainSeparator ‖ hashStruct(message)
-        let mut buff = [0u8; 66];
+        Ok(encode_hashes(&domain, &message))
+    }
+}
 
-        buff[0..2].copy_from_slice(&[0x19, 0x01]);
-        buff[2..34].copy_from_slice(&domain);
-        buff[34..66].copy_from_slice(&message);
+/// "\x19\x01" ‖ domainSeparator ‖ hashStruct(message)
+fn encode_hashes(domain: &[u8; 32], message: &[u8; 32]) -> [u8; 66] {
+    let mut buff = [0u8; 66];
 
-        Ok(buff)
+    buff[0..2].copy_from_slice(&[0x19, 0x01]);
+    buff[2..34].copy_from_slice(domain);
+    buff[34..66].copy_from_slice(message);
+
+    buff
+}
+
+/// Primary type and type definitions of message, computed once and reused for messages of the same type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageType {
+    pub primary_type: String,
+    pub types: HashMap<String, TypeDefinition>,
+}
+
+impl MessageType {
+    /// Generate type information of `message`.
+    pub fn of<M: Serialize>(message: &M) -> anyhow::Result<Self> {
+        let encode_type = eip712_encode_type(message)?;
+
+        let pos = encode_type.find('(').unwrap();
+
+        Ok(Self {
+            primary_type: encode_type[..pos].to_string(),
+            types: eip712_type_definitions(message)?,
+        })
+    }
+
+    /// Calculate `hashStruct(message)`, `message` must have the same type structure as this one.
+    pub fn hash_struct<M: Serialize>(&self, message: &M) -> anyhow::Result<[u8; 32]> {
+        Ok(eip712_hash_struct(
+            &self.primary_type,
+            &self.types,
+            message,
+        )?)
+    }
+}
+
+/// Precomputed `domainSeparator`, only `hashStruct(message)` is calculated per signing message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DomainSeparator {
+    domain: EIP712Domain,
+    types: HashMap<String, TypeDefinition>,
+    hash: [u8; 32],
+}
+
+impl DomainSeparator {
+    /// Calculate `domainSeparator = hashStruct(eip712Domain)`.
+    pub fn new(domain: EIP712Domain) -> anyhow::Result<Self> {
+        let types = eip712_type_definitions(&domain)?;
+
+        let hash = eip712_hash_struct("EIP712Domain", &types, &domain)?;
+
+        Ok(Self {
+            domain,
+            types,
+            hash,
+        })
+    }
+
+    pub fn domain(&self) -> &EIP712Domain {
+        &self.domain
+    }
+
+    /// Returns `domainSeparator` value.
+    pub fn hash(&self) -> H256 {
+        self.hash.into()
+    }
+
+    /// Encode `"\x19\x01" ‖ domainSeparator ‖ hashStruct(message)` of `message` with type `message_type`.
+    pub fn encode<M: Serialize>(
+        &self,
+        message_type: &MessageType,
+        message: &M,
+    ) -> anyhow::Result<[u8; 66]> {
+        let message = message_type.hash_struct(message)?;
+
+        Ok(encode_hashes(&self.hash, &message))
+    }
+
+    /// Returns hash of [`encode`](Self::encode) to be signed.
+    pub fn sign_hash<M: Serialize>(
+        &self,
+        message_type: &MessageType,
+        message: &M,
+    ) -> anyhow::Result<H256> {
+        Ok(keccak256(self.encode(message_type, message)?).into())
+    }
+
+    /// Create eth_signTypedData payload of `message`.
+    pub fn into_request<M: Serialize>(
+        &self,
+        message_type: &MessageType,
+        message: M,
+    ) -> TypedData<M> {
+        let mut types = self.types.clone();
+
+        types.extend(
+            message_type
+                .types
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+
+        TypedData {
+            types,
+            primary_type: message_type.primary_type.clone(),
+            domain: self.domain.clone(),
+            message,
+        }
     }
 }
 // /// encode(domainSeparator : 𝔹²⁵⁶, message : 𝕊)
@@ -103,25 +224,11 @@ pub fn eip712_into_request<S: Serialize>(
     domain: EIP712Domain,
     value: S,
 ) -> anyhow::Result<TypedData<S>> {
-    // Get primary type.
-
-    let encode_type = eip712_encode_type(&value)?;
-
-    let pos = encode_type.find("(").unwrap();
-
-    let primary_type = encode_type[..pos].to_string();
+    let message_type = MessageType::of(&value)?;
 
-    let mut type_definitions = eip712_type_definitions(&domain)?;
-
-    type_definitions.extend(eip712_type_definitions(&value)?.into_iter());
-
-    Ok(TypedData {
-        types: type_definitions,
-        primary_type,
-        domain,
-        message: value,
-    })
+    Ok(DomainSeparator::new(domain)?.into_request(&message_type, value))
 }
+
 #[cfg(test)]
 mod tests {
     use serde::{Deserialize, Serialize};
@@ -204,5 +311,204 @@ mod tests {
         );
 
         expect_request.sign_hash().unwrap();
+
+        let domain = DomainSeparator::new(expect_request.domain).unwrap();
+
+        assert_eq!(
+            domain.hash().to_eth_hex(),
+            "0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+
+        let mail: Mail = serde_json::from_value(expect_request.message).unwrap();
+
+        let message_type = MessageType::of(&mail).unwrap();
+
+        assert_eq!(message_type.primary_type, "Mail");
+
+        assert_eq!(
+            domain.sign_hash(&message_type, &mail).unwrap().to_eth_hex(),
+            "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    #[test]
+    fn test_validate_untyped_message() {
+        let mut request: TypedData<serde_json::Value> =
+            serde_json::from_str(include_str!("./eip712.json")).unwrap();
+
+        request.validate().unwrap();
+
+        request.message["from"]["wallet"] = json!("0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD8");
+
+        let err = request.sign_hash().unwrap_err();
+
+        assert!(err.to_string().contains("Mail.from.wallet"));
+
+        request.message["from"]
+            .as_object_mut()
+            .unwrap()
+            .remove("wallet");
+
+        assert!(matches!(
+            request.validate(),
+            Err(ValidateError::MissingField { .. })
+        ));
+
+        let mut request: TypedData<serde_json::Value> =
+            serde_json::from_str(include_str!("./eip712.json")).unwrap();
+
+        request
+            .types
+            .get_mut("EIP712Domain")
+            .unwrap()
+            .retain(|field| field.name != "verifyingContract");
+
+        assert!(matches!(
+            request.validate(),
+            Err(ValidateError::UnknownField { path, .. }) if path == "EIP712Domain.verifyingContract"
+        ));
+
+        request.types.remove("EIP712Domain");
+
+        assert!(matches!(
+            request.validate(),
+            Err(ValidateError::TypeNotFound(name)) if name == "EIP712Domain"
+        ));
+    }
+
+    /// Test vector of MetaMask `eth-sig-util` signTypedData_v4 with arrays of structs.
+    #[test]
+    fn test_arrays_of_structs() {
+        let request: TypedData<serde_json::Value> =
+            serde_json::from_str(include_str!("./eip712_arrays.json")).unwrap();
+
+        assert_eq!(
+            eip712_hash_struct(&request.primary_type, &request.types, &request.message)
+                .unwrap()
+                .to_eth_hex(),
+            "0xeb4221181ff3f1a83ea7313993ca9218496e424604ba9492bb4052c03d5c3df8"
+        );
+
+        assert_eq!(
+            request.sign_hash().unwrap().to_eth_hex(),
+            "0xa85c2e2b118698e88db68a8105b794a8cc7cec074e89ef991cb4f5f533819cc2"
+        );
+
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Person {
+            pub name: String,
+            pub wallets: Vec<Address>,
+        }
+
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Mail {
+            pub from: Person,
+            pub to: Vec<Person>,
+            pub contents: String,
+        }
+
+        let mail: Mail = serde_json::from_value(request.message.clone()).unwrap();
+
+        assert_eq!(
+            eip712_hash_struct("Mail", &request.types, &mail)
+                .unwrap()
+                .to_eth_hex(),
+            "0xeb4221181ff3f1a83ea7313993ca9218496e424604ba9492bb4052c03d5c3df8"
+        );
+    }
+
+    #[test]
+    fn test_recursive_types() {
+        let types: HashMap<String, TypeDefinition> = serde_json::from_value(json!({
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "friends", "type": "Person[]" }
+            ]
+        }))
+        .unwrap();
+
+        let message = json!({
+            "name": "Cow",
+            "friends": [{ "name": "Bob", "friends": [] }]
+        });
+
+        let type_hash = keccak256("Person(string name,Person[] friends)");
+
+        let bob = keccak256_concat(&[&type_hash, &keccak256("Bob"), &keccak256([])]);
+
+        let cow = keccak256_concat(&[&type_hash, &keccak256("Cow"), &keccak256(bob)]);
+
+        assert_eq!(eip712_hash_struct("Person", &types, &message).unwrap(), cow);
+
+        // Mutually recursive types are listed once in encodeType.
+        let types: HashMap<String, TypeDefinition> = serde_json::from_value(json!({
+            "Node": [
+                { "name": "value", "type": "uint256" },
+                { "name": "edges", "type": "Edge[]" }
+            ],
+            "Edge": [
+                { "name": "to", "type": "Node" },
+                { "name": "weight", "type": "uint8" }
+            ]
+        }))
+        .unwrap();
+
+        let message = json!({ "value": 1, "edges": [] });
+
+        let type_hash = keccak256("Node(uint256 value,Edge[] edges)Edge(Node to,uint8 weight)");
+
+        let mut value = [0u8; 32];
+        value[31] = 1;
+
+        assert_eq!(
+            eip712_hash_struct("Node", &types, &message).unwrap(),
+            keccak256_concat(&[&type_hash, &value, &keccak256([])])
+        );
+    }
+
+    #[test]
+    fn test_array_and_bool_types() {
+        #[derive(Debug, Serialize)]
+        struct Person {
+            pub name: String,
+            pub wallets: Vec<Address>,
+        }
+
+        #[derive(Debug, Serialize)]
+        struct Group {
+            pub members: Vec<Person>,
+            pub owners: [Person; 2],
+            pub scores: Vec<[U256; 3]>,
+            pub active: bool,
+        }
+
+        let person = || Person {
+            name: "Cow".to_owned(),
+            wallets: vec![Address::ZERO],
+        };
+
+        let group = Group {
+            members: vec![person(), person()],
+            owners: [person(), person()],
+            scores: vec![[U256::from(1usize), U256::from(2usize), U256::from(3usize)]],
+            active: true,
+        };
+
+        assert_eq!(
+            eip712_encode_type(&group).unwrap(),
+            "Group(Person[] members,Person[2] owners,uint256[3][] scores,bool active)Person(string name,address[] wallets)"
+        );
+
+        let types = eip712_type_definitions(&group).unwrap();
+
+        assert_eq!(types["Group"][3].r#type, "bool");
+        assert_eq!(types["Person"][1].r#type, "address[]");
+
+        let empty = Person {
+            name: "Bob".to_owned(),
+            wallets: vec![],
+        };
+
+        assert!(eip712_encode_type(&empty).is_err());
     }
 }
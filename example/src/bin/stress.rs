@@ -0,0 +1,201 @@
+//! Contract deployment and method-call stress test harness.
+//!
+//! Usage: `cargo run --bin stress -- [call|send] [concurrency] [iterations]`
+//!
+//! Starts a hardhat network, deploys one `Example` contract per worker, then fires `concurrency`
+//! workers, each invoking the contract `iterations` times, and reports throughput/latency
+//! percentiles. Deployment is excluded from the timed window.
+
+use std::time::{Duration, Instant};
+
+use ethers_rs::{
+    hardhat::{futures::future::join_all, hardhat_network, utils::*},
+    *,
+};
+
+use example::Example;
+
+/// Hardhat network builtin accounts count.
+const MAX_CONCURRENCY: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Workload {
+    /// Invoke pure method `get_from` via `eth_call`
+    Call,
+    /// Send `withdraw` transaction and wait for receipt
+    Send,
+}
+
+impl std::str::FromStr for Workload {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "call" => Ok(Self::Call),
+            "send" => Ok(Self::Send),
+            _ => Err(anyhow::format_err!(
+                "Unknown workload {}, expect call/send",
+                s
+            )),
+        }
+    }
+}
+
+struct Report {
+    requests: usize,
+    errors: usize,
+    elapsed: Duration,
+    latencies: Vec<Duration>,
+}
+
+impl Report {
+    fn percentile(&self, p: usize) -> Duration {
+        if self.latencies.is_empty() {
+            return Duration::ZERO;
+        }
+
+        let index = (self.latencies.len() - 1) * p / 100;
+
+        self.latencies[index]
+    }
+
+    fn print(&self, workload: Workload, concurrency: usize) {
+        println!("workload:    {:?}", workload);
+        println!("concurrency: {}", concurrency);
+        println!("requests:    {}", self.requests);
+        println!("errors:      {}", self.errors);
+        println!("elapsed:     {:?}", self.elapsed);
+        println!(
+            "throughput:  {:.2} req/s",
+            self.latencies.len() as f64 / self.elapsed.as_secs_f64()
+        );
+        println!("p50:         {:?}", self.percentile(50));
+        println!("p90:         {:?}", self.percentile(90));
+        println!("p99:         {:?}", self.percentile(99));
+        println!("max:         {:?}", self.percentile(100));
+    }
+}
+
+/// Deploy `Example` contract of one worker, returns the worker client and contract.
+async fn deploy_worker(worker: usize, provider: Provider) -> anyhow::Result<(Client, Example)> {
+    // Each worker using a dedicated account to avoid nonce conflicts.
+    let client: Client = (provider, get_hardhat_network_account(worker)).into();
+
+    let contract = Example::deploy_with(client.clone(), Default::default()).await?;
+
+    Ok((client, contract))
+}
+
+/// Run one worker, returns latencies of success requests and errors count.
+async fn run_worker(
+    workload: Workload,
+    worker: usize,
+    iterations: usize,
+    client: Client,
+    contract: Example,
+) -> anyhow::Result<(Vec<Duration>, usize)> {
+    let mut latencies = vec![];
+    let mut errors = 0;
+
+    for i in 0..iterations {
+        let start = Instant::now();
+
+        let result = match workload {
+            Workload::Call => {
+                let call_data = to_abi(&(U256::from(i), U256::from(worker)))?;
+
+                client
+                    .eth_call("get_from((uint256,uint256))", &contract.address, call_data)
+                    .await
+                    .map(|_| ())
+            }
+            Workload::Send => {
+                let result = client
+                    .send_raw_transaction(
+                        "withdraw()",
+                        &contract.address,
+                        vec![],
+                        Default::default(),
+                    )
+                    .await;
+
+                match result {
+                    Ok(mut receipter) => receipter.wait().await.map(|_| ()),
+                    Err(err) => Err(err),
+                }
+            }
+        };
+
+        match result {
+            Ok(_) => latencies.push(start.elapsed()),
+            Err(err) => {
+                log::error!("worker({}) request({}) failed, {}", worker, i, err);
+                errors += 1;
+            }
+        }
+    }
+
+    Ok((latencies, errors))
+}
+
+#[async_std::main]
+async fn main() -> anyhow::Result<()> {
+    _ = pretty_env_logger::try_init();
+
+    let mut args = std::env::args().skip(1);
+
+    let workload: Workload = args.next().unwrap_or("call".to_owned()).parse()?;
+
+    let concurrency: usize = args.next().unwrap_or("10".to_owned()).parse()?;
+
+    let iterations: usize = args.next().unwrap_or("100".to_owned()).parse()?;
+
+    if concurrency == 0 || concurrency > MAX_CONCURRENCY {
+        return Err(anyhow::format_err!(
+            "concurrency out of range, 0 < concurrency <= {}",
+            MAX_CONCURRENCY
+        ));
+    }
+
+    let mut network = hardhat_network!();
+
+    network.start().await?;
+
+    let provider = get_hardhat_network_provider();
+
+    let workers = join_all((0..concurrency).map(|worker| deploy_worker(worker, provider.clone())))
+        .await
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let start = Instant::now();
+
+    let results = join_all(
+        workers
+            .into_iter()
+            .enumerate()
+            .map(|(worker, (client, contract))| {
+                run_worker(workload, worker, iterations, client, contract)
+            }),
+    )
+    .await;
+
+    let mut report = Report {
+        requests: concurrency * iterations,
+        errors: 0,
+        elapsed: start.elapsed(),
+        latencies: vec![],
+    };
+
+    for result in results {
+        let (mut latencies, errors) = result?;
+
+        report.latencies.append(&mut latencies);
+        report.errors += errors;
+    }
+
+    report.latencies.sort();
+
+    report.print(workload, concurrency);
+
+    Ok(())
+}
@@ -2,6 +2,8 @@ use ethers_rs::hardhat;
 
 hardhat!(Example);
 
+ethers_rs::contract_event!(ExampleWithdrawal);
+
 #[cfg(test)]
 mod tests {
 
@@ -29,7 +31,7 @@ mod tests {
         let client: Client = (provider.clone(), s0).into();
 
         // Deploy contract and send ether to deployed
-        let lock = Example::deploy_with(client.clone(), value.clone().to_tx_options())
+        let lock = Example::deploy_with(client.clone(), SendOptions::new().value(value.clone()))
             .await
             .expect("Deploy lock contract");
 
@@ -41,6 +43,14 @@ mod tests {
 
         let receipt = tx.wait().await.expect("Wait withdraw tx mint");
 
+        let events: Vec<ExampleWithdrawal> = receipt
+            .decoded_logs()
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .expect("Decode withdraw tx events");
+
+        assert_eq!(events.len(), 1);
+
         let balance_after = client.balance().await.expect("Get after deploy balance");
 
         let used = receipt.gas_used * receipt.effective_gas_price;
@@ -0,0 +1,276 @@
+use std::{ffi::OsStr, io::ErrorKind};
+
+use async_process::{Child, Command, ExitStatus, Stdio};
+use ethers_core::Address;
+use ethers_provider::{providers::http, Provider};
+use ethers_signer::{signer::Signer, wallet::WalletSigner};
+use ethers_wallet::wallet::Wallet;
+use futures::{io::BufReader, AsyncBufReadExt, TryStreamExt};
+
+use crate::{
+    error::HardhatError,
+    utils::{kill_process_recursive, kill_process_recursive_blocking},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    None,
+    Accounts,
+    PrivateKeys,
+}
+
+/// Anvil node startup output.
+#[derive(Debug, Clone)]
+struct AnvilOutput {
+    section: Section,
+    accounts: Vec<Address>,
+    private_keys: Vec<String>,
+    listening: Option<String>,
+}
+
+impl Default for AnvilOutput {
+    fn default() -> Self {
+        Self {
+            section: Section::None,
+            accounts: vec![],
+            private_keys: vec![],
+            listening: None,
+        }
+    }
+}
+
+impl AnvilOutput {
+    /// Parse one stdout line, returns true if anvil node is listening.
+    fn parse_line(&mut self, line: &str) -> anyhow::Result<bool> {
+        let line = line.trim();
+
+        if line.starts_with("Available Accounts") {
+            self.section = Section::Accounts;
+        } else if line.starts_with("Private Keys") {
+            self.section = Section::PrivateKeys;
+        } else if let Some(listening) = line.strip_prefix("Listening on ") {
+            self.listening = Some(listening.trim().to_owned());
+            return Ok(true);
+        } else if line.starts_with('(') {
+            // (0) 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266 (10000.000000000000000000 ETH)
+            if let Some(value) = line.split_whitespace().nth(1) {
+                match self.section {
+                    Section::Accounts => self.accounts.push(value.try_into()?),
+                    Section::PrivateKeys => self.private_keys.push(value.to_owned()),
+                    Section::None => {}
+                }
+            }
+        } else if !line.is_empty() && !line.starts_with('=') {
+            self.section = Section::None;
+        }
+
+        Ok(false)
+    }
+}
+
+/// Anvil (foundry) dev node helper, an alternative of [`HardhatNetwork`](crate::cmds::HardhatNetwork)
+/// which doesn't require nodejs.
+#[derive(Debug)]
+pub struct AnvilNetwork {
+    command: Command,
+    child_process: Option<Child>,
+    output: AnvilOutput,
+}
+
+impl AnvilNetwork {
+    /// Create anvil network with default options.
+    pub fn new() -> Self {
+        let mut command = Command::new("anvil");
+
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+        Self {
+            command,
+            child_process: None,
+            output: Default::default(),
+        }
+    }
+
+    /// Append anvil command line argument, e.g. `--port`, `--fork-url`.
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Self {
+        self.command.arg(arg);
+        self
+    }
+
+    /// Set anvil listening port.
+    pub fn port(&mut self, port: u16) -> &mut Self {
+        self.arg("--port").arg(port.to_string())
+    }
+
+    /// Returns if network started.
+    pub fn is_started(&self) -> bool {
+        self.child_process.is_some()
+    }
+
+    /// Start anvil child process, and wait until listening.
+    ///
+    /// If already started, returns false.
+    pub async fn start(&mut self) -> anyhow::Result<bool> {
+        if self.is_started() {
+            return Ok(false);
+        }
+
+        let mut child = match self.command.spawn() {
+            Ok(child) => child,
+            Err(err) => {
+                if err.kind() == ErrorKind::NotFound {
+                    return Err(HardhatError::AnvilRequired.into());
+                } else {
+                    return Err(err.into());
+                }
+            }
+        };
+
+        let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+
+        let mut output = AnvilOutput::default();
+
+        let mut listening = false;
+
+        while let Some(line) = lines.try_next().await? {
+            log::trace!(target:"anvil" ,"{}", line);
+
+            if output.parse_line(&line)? {
+                listening = true;
+                break;
+            }
+        }
+
+        if !listening {
+            let status = child.status().await?;
+
+            return Err(HardhatError::ChildProcess("anvil".to_string(), status).into());
+        }
+
+        log::info!("anvil started, listening on {:?}", output.listening);
+
+        ethers_runtime::spawn(async move {
+            while let Ok(Some(line)) = lines.try_next().await {
+                log::trace!(target:"anvil" ,"{}", line);
+            }
+        });
+
+        self.output = output;
+        self.child_process = Some(child);
+
+        Ok(true)
+    }
+
+    /// Stop anvil network.
+    ///
+    /// If anvil network already stopped, returns [`HardhatError::HardhatNetworkStopped`]
+    pub async fn stop(&mut self) -> anyhow::Result<ExitStatus> {
+        if let Some(child_process) = self.child_process.take() {
+            kill_process_recursive(child_process.id()).await
+        } else {
+            Err(HardhatError::HardhatNetworkStopped.into())
+        }
+    }
+
+    /// Returns http endpoint of started anvil node.
+    pub fn endpoint(&self) -> String {
+        format!(
+            "http://{}",
+            self.output.listening.as_deref().unwrap_or("127.0.0.1:8545")
+        )
+    }
+
+    /// Create provider connecting to started anvil node.
+    pub fn provider(&self) -> Provider {
+        http::connect_to(self.endpoint())
+    }
+
+    /// Returns funded accounts of started anvil node.
+    pub fn accounts(&self) -> &[Address] {
+        &self.output.accounts
+    }
+
+    /// Get funded account signer of started anvil node.
+    pub fn get_account(&self, i: usize) -> anyhow::Result<Signer> {
+        let private_key = self
+            .output
+            .private_keys
+            .get(i)
+            .ok_or(HardhatError::AccountNotFound(i))?;
+
+        Ok(Wallet::new(private_key.as_str())?.try_into_signer()?)
+    }
+}
+
+impl Default for AnvilNetwork {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for AnvilNetwork {
+    fn drop(&mut self) {
+        if let Some(child_process) = self.child_process.take() {
+            _ = kill_process_recursive_blocking(child_process.id());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_anvil_output() {
+        let stdout = r#"
+                             _   _
+                            (_) | |
+      __ _   _ __   __   __  _  | |
+     / _` | | '_ \  \ \ / / | | | |
+    | (_| | | | | |  \ V /  | | | |
+     \__,_| |_| |_|   \_/   |_| |_|
+
+Available Accounts
+==================
+
+(0) 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266 (10000.000000000000000000 ETH)
+(1) 0x70997970C51812dc3A010C7d01b50e0d17dc79C8 (10000.000000000000000000 ETH)
+
+Private Keys
+==================
+
+(0) 0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80
+(1) 0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d
+
+Wallet
+==================
+Mnemonic:          test test test test test test test test test test test junk
+Derivation path:   m/44'/60'/0'/0/
+
+Listening on 127.0.0.1:8545
+"#;
+
+        let mut output = AnvilOutput::default();
+
+        let mut listening = false;
+
+        for line in stdout.lines() {
+            if output.parse_line(line).unwrap() {
+                listening = true;
+                break;
+            }
+        }
+
+        assert!(listening);
+        assert_eq!(output.listening.as_deref(), Some("127.0.0.1:8545"));
+        assert_eq!(output.accounts.len(), 2);
+        assert_eq!(
+            output.accounts[1],
+            Address::try_from("0x70997970C51812dc3A010C7d01b50e0d17dc79C8").unwrap()
+        );
+        assert_eq!(
+            output.private_keys[0],
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        );
+    }
+}
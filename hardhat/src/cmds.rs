@@ -1,16 +1,23 @@
 use std::{
     fs::{create_dir_all, remove_dir_all},
     path::PathBuf,
+    time::Duration,
 };
 
 use async_process::{Child, Command, ExitStatus, Stdio};
-use futures::{executor::block_on, io::BufReader, task::SpawnExt, AsyncBufReadExt, TryStreamExt};
+use futures::{io::BufReader, AsyncBufReadExt, TryStreamExt};
 
 use crate::{
     error::HardhatError,
-    utils::{thread_pool, HardhatCommand, HardhatCommandContext},
+    utils::{wait_for_port, HardhatCommand, HardhatCommandContext},
 };
 
+/// Hardhat network json-rpc server port.
+pub const HARDHAT_NETWORK_PORT: u16 = 8545;
+
+/// Max duration waiting for hardhat network json-rpc server ready.
+const HARDHAT_NETWORK_START_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// Hardhat network helper structure.
 ///
 #[derive(Debug)]
@@ -27,28 +34,22 @@ impl HardhatCommandContext for NetworkContext {
     async fn start_command(child_process: &mut Child) -> anyhow::Result<()> {
         let mut lines = BufReader::new(child_process.stdout.take().unwrap()).lines();
 
-        let mut waiting_started = false;
-
-        while let Some(line) = lines.try_next().await.expect("") {
-            if "Any funds sent to them on Mainnet or any other live network WILL BE LOST." == line {
-                log::info!("hardhat node started");
-                waiting_started = true;
-                break;
+        // Drain stdout, otherwise the node blocks on writing full pipe.
+        ethers_runtime::spawn(async move {
+            while let Ok(Some(line)) = lines.try_next().await {
+                log::trace!(target:"hardhat node" ,"{}", line);
             }
-        }
-
-        // Start hardhat network failed.
-        if !waiting_started {
-            let status = child_process.status().await?;
+        });
 
-            return Err(HardhatError::ChildProcess("npx hardhat node".to_string(), status).into());
-        }
+        wait_for_port(
+            child_process,
+            "npx hardhat node",
+            HARDHAT_NETWORK_PORT,
+            HARDHAT_NETWORK_START_TIMEOUT,
+        )
+        .await?;
 
-        thread_pool().spawn(async move {
-            while let Some(line) = lines.try_next().await.expect("") {
-                log::trace!(target:"hardhat node" ,"{}", line);
-            }
-        })?;
+        log::info!("hardhat node started");
 
         Ok(())
     }
@@ -123,9 +124,29 @@ impl HardhatCommandContext for BuildProjectContext {
 /// Command for creating new hardhat project .
 pub type HardhatBuildProject = HardhatCommand<BuildProjectContext>;
 
+#[derive(Debug)]
+pub struct CleanProjectContext;
+
+#[async_trait::async_trait]
+impl HardhatCommandContext for CleanProjectContext {
+    fn init_command(hardhat_root: PathBuf, c: &mut Command) -> anyhow::Result<()> {
+        log::debug!(
+            "try clean hardhat project {}",
+            hardhat_root.to_string_lossy()
+        );
+
+        c.arg("clean");
+
+        Ok(())
+    }
+}
+
+/// Command for clearing hardhat project cache and artifacts.
+pub type HardhatCleanProject = HardhatCommand<CleanProjectContext>;
+
 /// Helper fn to block run [`HardhatBuildProject`] command
 pub fn block_run_build() -> anyhow::Result<ExitStatus> {
-    block_on(async {
+    ethers_runtime::block_on(async {
         let mut command = HardhatBuildProject::new()?;
 
         command.start().await?;
@@ -0,0 +1,284 @@
+use std::{
+    fs::write,
+    path::PathBuf,
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    cmds::HardhatNetwork,
+    utils::{hardhat_default_path, HardhatCommand},
+};
+
+/// Generated hardhat config file name prefix, which extends the project config.
+const GENERATED_CONFIG_NAME: &str = "ethers-rs.hardhat.config";
+
+/// Sequence of generated config files in this process.
+static GENERATED_CONFIG_SEQ: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns unique generated config file name, networks launched concurrently in the same
+/// project, e.g. by parallel tests or processes, must not overwrite or remove each other's config.
+fn generated_config_name() -> String {
+    format!(
+        "{}.{}-{}",
+        GENERATED_CONFIG_NAME,
+        process::id(),
+        GENERATED_CONFIG_SEQ.fetch_add(1, Ordering::SeqCst)
+    )
+}
+
+/// Hardhat network mining mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiningMode {
+    /// Mine a block for each transaction, hardhat default mode.
+    Auto,
+    /// Mine a block every `interval` milliseconds.
+    Interval(u64),
+}
+
+/// Builder to configure [`HardhatNetwork`] before launch.
+///
+/// ```ignore
+/// let mut network = HardhatNetworkConfig::default()
+///     .fork("https://eth-mainnet.alchemyapi.io/v2/<key>", Some(14390000))
+///     .chain_id(1337)
+///     .mining(MiningMode::Interval(1000))
+///     .build()?;
+///
+/// network.start().await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HardhatNetworkConfig {
+    fork_url: Option<String>,
+    fork_block_number: Option<u64>,
+    chain_id: Option<u64>,
+    block_gas_limit: Option<u64>,
+    mining: Option<MiningMode>,
+    accounts_count: Option<usize>,
+    accounts_balance: Option<String>,
+}
+
+impl HardhatNetworkConfig {
+    /// Fork network from `url`, optionally pinned at `block_number`.
+    pub fn fork<U: Into<String>>(mut self, url: U, block_number: Option<u64>) -> Self {
+        self.fork_url = Some(url.into());
+        self.fork_block_number = block_number;
+        self
+    }
+
+    /// Set network chain id, hardhat default is `31337`.
+    pub fn chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Set block gas limit.
+    pub fn block_gas_limit(mut self, block_gas_limit: u64) -> Self {
+        self.block_gas_limit = Some(block_gas_limit);
+        self
+    }
+
+    /// Set mining mode.
+    pub fn mining(mut self, mining: MiningMode) -> Self {
+        self.mining = Some(mining);
+        self
+    }
+
+    /// Set builtin accounts count, accounts are still derived from the default test mnemonic.
+    pub fn accounts_count(mut self, count: usize) -> Self {
+        self.accounts_count = Some(count);
+        self
+    }
+
+    /// Set initial balance in wei of builtin accounts.
+    pub fn accounts_balance<B: ToString>(mut self, balance: B) -> Self {
+        self.accounts_balance = Some(balance.to_string());
+        self
+    }
+
+    /// Returns `networks.hardhat` config overrides.
+    fn network_overrides(&self) -> Value {
+        let mut overrides = Map::new();
+
+        if let Some(chain_id) = self.chain_id {
+            overrides.insert("chainId".to_owned(), json!(chain_id));
+        }
+
+        if let Some(block_gas_limit) = self.block_gas_limit {
+            overrides.insert("blockGasLimit".to_owned(), json!(block_gas_limit));
+        }
+
+        match self.mining {
+            Some(MiningMode::Auto) => {
+                overrides.insert("mining".to_owned(), json!({ "auto": true }));
+            }
+            Some(MiningMode::Interval(interval)) => {
+                overrides.insert(
+                    "mining".to_owned(),
+                    json!({ "auto": false, "interval": interval }),
+                );
+            }
+            None => {}
+        }
+
+        if self.accounts_count.is_some() || self.accounts_balance.is_some() {
+            let mut accounts = Map::new();
+
+            if let Some(count) = self.accounts_count {
+                accounts.insert("count".to_owned(), json!(count));
+            }
+
+            if let Some(balance) = &self.accounts_balance {
+                accounts.insert("accountsBalance".to_owned(), json!(balance));
+            }
+
+            overrides.insert("accounts".to_owned(), Value::Object(accounts));
+        }
+
+        Value::Object(overrides)
+    }
+
+    /// Generate hardhat config source which extends project config `base`.
+    fn config_source(&self, typescript: bool, base: &str) -> String {
+        let overrides = self.network_overrides();
+
+        if typescript {
+            format!(
+                r#"import baseConfig from "./{base}";
+
+const overrides: any = {overrides};
+const base: any = baseConfig;
+
+export default {{
+  ...base,
+  networks: {{
+    ...base.networks,
+    hardhat: {{ ...(base.networks?.hardhat ?? {{}}), ...overrides }},
+  }},
+}};
+"#
+            )
+        } else {
+            format!(
+                r#"const base = require("./{base}");
+
+const overrides = {overrides};
+
+module.exports = {{
+  ...base,
+  networks: {{
+    ...base.networks,
+    hardhat: {{ ...((base.networks && base.networks.hardhat) || {{}}), ...overrides }},
+  }},
+}};
+"#
+            )
+        }
+    }
+
+    /// Create [`HardhatNetwork`] in default hardhat project path.
+    pub fn build(self) -> anyhow::Result<HardhatNetwork> {
+        self.build_with(hardhat_default_path()?)
+    }
+
+    /// Create [`HardhatNetwork`] with hardhat project root path.
+    ///
+    /// The generated config file is written into the project root, hardhat resolves the project
+    /// paths relative to the config file. It is removed when the returned network is dropped.
+    pub fn build_with<P>(self, hardhat_root: P) -> anyhow::Result<HardhatNetwork>
+    where
+        P: Into<PathBuf>,
+    {
+        let hardhat_root: PathBuf = hardhat_root.into();
+
+        let mut network = HardhatCommand::new_with(hardhat_root.clone())?;
+
+        let command = network.command();
+
+        if let Some(fork_url) = &self.fork_url {
+            command.arg("--fork").arg(fork_url);
+
+            if let Some(block_number) = self.fork_block_number {
+                command
+                    .arg("--fork-block-number")
+                    .arg(block_number.to_string());
+            }
+        }
+
+        if self.network_overrides().as_object().unwrap().is_empty() {
+            return Ok(network);
+        }
+
+        let typescript = hardhat_root.join("hardhat.config.ts").exists();
+
+        let config_name = generated_config_name();
+
+        let (base, config_path) = if typescript {
+            (
+                "hardhat.config",
+                hardhat_root.join(format!("{}.ts", config_name)),
+            )
+        } else {
+            (
+                "hardhat.config.js",
+                hardhat_root.join(format!("{}.js", config_name)),
+            )
+        };
+
+        write(&config_path, self.config_source(typescript, base))?;
+
+        log::debug!("write hardhat network config {:?}", config_path);
+
+        // Hardhat reads global param `--config` from env `HARDHAT_CONFIG`
+        command.env("HARDHAT_CONFIG", &config_path);
+
+        network.remove_on_drop(config_path);
+
+        Ok(network)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_overrides() {
+        let config = HardhatNetworkConfig::default()
+            .chain_id(1337)
+            .block_gas_limit(30000000)
+            .mining(MiningMode::Interval(1000))
+            .accounts_count(5)
+            .accounts_balance("1000000000000000000");
+
+        assert_eq!(
+            config.network_overrides(),
+            json!({
+                "chainId": 1337,
+                "blockGasLimit": 30000000,
+                "mining": { "auto": false, "interval": 1000 },
+                "accounts": { "count": 5, "accountsBalance": "1000000000000000000" },
+            })
+        );
+
+        let source = config.config_source(true, "hardhat.config");
+
+        assert!(source.contains(r#"import baseConfig from "./hardhat.config";"#));
+        assert!(source.contains(r#""chainId":1337"#));
+
+        assert_eq!(
+            HardhatNetworkConfig::default().network_overrides(),
+            json!({})
+        );
+    }
+
+    #[test]
+    fn test_generated_config_name() {
+        let name = generated_config_name();
+
+        assert!(name.starts_with(&format!("{}.{}-", GENERATED_CONFIG_NAME, process::id())));
+        assert_ne!(name, generated_config_name());
+    }
+}
@@ -15,11 +15,20 @@ pub enum HardhatError {
     #[error("Child process '{0}' {1}")]
     ChildProcess(String, ExitStatus),
 
+    #[error("Child process '{0}' not listening on port {1} before timeout")]
+    StartTimeout(String, u16),
+
     #[error("Hardhat project directory exists, {0}")]
     ProjectExists(String),
     #[error("Nodejs env not found")]
     NodejsRequired,
 
+    #[error("Anvil command not found, install foundry first")]
+    AnvilRequired,
+
+    #[error("Dev node account {0} not found")]
+    AccountNotFound(usize),
+
     #[error("Cargo crate directory not found")]
     CargoManifestDirNotFound,
 }
@@ -0,0 +1,257 @@
+//! Opt-in gas usage reporter for hardhat test runs, like `hardhat-gas-reporter`.
+//!
+//! Enable it by calling [`enable_gas_reporter`] or setting env `REPORT_GAS=true`, pass the
+//! reporter to `Client::with_gas_recorder`, and print the summary table with
+//! [`print_gas_report`] at the end of test run.
+
+use std::{collections::HashMap, env, fmt::Write, sync::Mutex};
+
+use ethers_core::{Address, U256};
+use once_cell::sync::OnceCell;
+
+/// Gas usage statistics of one contract method or deployment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasStats {
+    pub calls: u64,
+    pub min: u64,
+    pub max: u64,
+    pub total: u128,
+}
+
+impl GasStats {
+    fn record(&mut self, gas_used: u64) {
+        if self.calls == 0 || gas_used < self.min {
+            self.min = gas_used;
+        }
+
+        if gas_used > self.max {
+            self.max = gas_used;
+        }
+
+        self.calls += 1;
+        self.total += gas_used as u128;
+    }
+
+    /// Average gas used.
+    pub fn avg(&self) -> u64 {
+        if self.calls == 0 {
+            0
+        } else {
+            (self.total / self.calls as u128) as u64
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Records {
+    /// Deployed contract address => contract name
+    contracts: HashMap<Address, String>,
+    /// (contract, method) => stats
+    methods: HashMap<(String, String), GasStats>,
+    /// contract => stats
+    deployments: HashMap<String, GasStats>,
+}
+
+/// Gas usage recorder for generated contract methods and deployments.
+#[derive(Debug, Default)]
+pub struct GasReporter {
+    records: Mutex<Records>,
+}
+
+fn to_u64(gas_used: U256) -> u64 {
+    let gas_used: Option<u64> = gas_used.into();
+
+    gas_used.unwrap_or(u64::MAX)
+}
+
+impl GasReporter {
+    /// Record contract `name` deployment gas used.
+    pub fn record_deployment(&self, name: &str, address: Address, gas_used: U256) {
+        let mut records = self.records.lock().unwrap();
+
+        records.contracts.insert(address, name.to_owned());
+
+        records
+            .deployments
+            .entry(name.to_owned())
+            .or_default()
+            .record(to_u64(gas_used));
+    }
+
+    /// Record gas used of calling `method` of contract deployed at `to`.
+    pub fn record_method(&self, to: &Address, method: &str, gas_used: U256) {
+        let mut records = self.records.lock().unwrap();
+
+        let contract = records
+            .contracts
+            .get(to)
+            .cloned()
+            .unwrap_or_else(|| to.to_string());
+
+        records
+            .methods
+            .entry((contract, method.to_owned()))
+            .or_default()
+            .record(to_u64(gas_used));
+    }
+
+    /// Returns method stats, sorted by contract and method name.
+    pub fn methods(&self) -> Vec<(String, String, GasStats)> {
+        let records = self.records.lock().unwrap();
+
+        let mut methods = records
+            .methods
+            .iter()
+            .map(|((contract, method), stats)| (contract.clone(), method.clone(), stats.clone()))
+            .collect::<Vec<_>>();
+
+        methods.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+        methods
+    }
+
+    /// Returns deployment stats, sorted by contract name.
+    pub fn deployments(&self) -> Vec<(String, GasStats)> {
+        let records = self.records.lock().unwrap();
+
+        let mut deployments = records
+            .deployments
+            .iter()
+            .map(|(contract, stats)| (contract.clone(), stats.clone()))
+            .collect::<Vec<_>>();
+
+        deployments.sort_by(|a, b| a.0.cmp(&b.0));
+
+        deployments
+    }
+
+    /// Format summary table.
+    pub fn summary(&self) -> String {
+        let mut rows = vec![];
+
+        for (contract, method, stats) in self.methods() {
+            rows.push([
+                contract,
+                method,
+                stats.min.to_string(),
+                stats.max.to_string(),
+                stats.avg().to_string(),
+                stats.calls.to_string(),
+            ]);
+        }
+
+        for (contract, stats) in self.deployments() {
+            rows.push([
+                contract,
+                "(deployment)".to_owned(),
+                stats.min.to_string(),
+                stats.max.to_string(),
+                stats.avg().to_string(),
+                stats.calls.to_string(),
+            ]);
+        }
+
+        let header = ["Contract", "Method", "Min", "Max", "Avg", "# calls"].map(|c| c.to_owned());
+
+        let mut widths = header.clone().map(|c| c.len());
+
+        for row in &rows {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(cell.len());
+            }
+        }
+
+        let mut summary = String::new();
+
+        let separator = widths
+            .iter()
+            .map(|w| "-".repeat(w + 2))
+            .collect::<Vec<_>>()
+            .join("+");
+
+        _ = writeln!(summary, "+{}+", separator);
+
+        for (index, row) in std::iter::once(&header).chain(rows.iter()).enumerate() {
+            let line = row
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| format!(" {:width$} ", cell, width = widths[i]))
+                .collect::<Vec<_>>()
+                .join("|");
+
+            _ = writeln!(summary, "|{}|", line);
+
+            if index == 0 {
+                _ = writeln!(summary, "+{}+", separator);
+            }
+        }
+
+        _ = writeln!(summary, "+{}+", separator);
+
+        summary
+    }
+}
+
+static GAS_REPORTER: OnceCell<GasReporter> = OnceCell::new();
+
+/// Enable global gas reporter.
+pub fn enable_gas_reporter() -> &'static GasReporter {
+    GAS_REPORTER.get_or_init(GasReporter::default)
+}
+
+/// Print summary table of global gas reporter if enabled.
+pub fn print_gas_report() {
+    if let Some(reporter) = GAS_REPORTER.get() {
+        println!("{}", reporter.summary());
+    }
+}
+
+/// Returns global gas reporter if enabled.
+///
+/// Setting env `REPORT_GAS=true` enables the reporter on first calling.
+pub fn gas_reporter() -> Option<&'static GasReporter> {
+    if let Some(reporter) = GAS_REPORTER.get() {
+        return Some(reporter);
+    }
+
+    match env::var("REPORT_GAS") {
+        Ok(value) if value == "true" || value == "1" => Some(enable_gas_reporter()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_reporter() {
+        let reporter = GasReporter::default();
+
+        let address = Address([1u8; 20]);
+
+        reporter.record_deployment("Example", address, 100000usize.into());
+        reporter.record_method(&address, "withdraw()", 300usize.into());
+        reporter.record_method(&address, "withdraw()", 100usize.into());
+        reporter.record_method(&address, "withdraw()", 200usize.into());
+
+        let methods = reporter.methods();
+
+        assert_eq!(methods.len(), 1);
+        assert_eq!(methods[0].0, "Example");
+        assert_eq!(
+            methods[0].2,
+            GasStats {
+                calls: 3,
+                min: 100,
+                max: 300,
+                total: 600
+            }
+        );
+        assert_eq!(methods[0].2.avg(), 200);
+
+        assert_eq!(reporter.deployments()[0].1.calls, 1);
+
+        log::debug!("\n{}", reporter.summary());
+    }
+}
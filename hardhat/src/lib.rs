@@ -1,5 +1,8 @@
+pub mod anvil;
 pub mod cmds;
+pub mod config;
 pub mod error;
+pub mod gas_reporter;
 pub mod utils;
 
 pub use colorable;
@@ -1,8 +1,9 @@
 use std::env;
-use std::fs::canonicalize;
+use std::fs::{canonicalize, remove_file};
 use std::io::ErrorKind;
 use std::marker::PhantomData;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use async_process::Child;
 use async_process::Command;
@@ -14,21 +15,111 @@ use ethers_signer::signer::Signer;
 use ethers_signer::wallet::WalletSigner;
 use ethers_wallet::{hd_wallet::bip32::DriveKey, wallet::Wallet};
 use futures::executor::block_on;
-use futures::executor::ThreadPool;
-use once_cell::sync::OnceCell;
 
 use crate::error::HardhatError;
 
-/// Kill process and all children processess by process id
+/// Returns `process_id` and all of its descendants, parents first.
+#[cfg(target_family = "unix")]
+fn process_tree(process_id: u32) -> Vec<u32> {
+    let mut tree = vec![process_id];
+    let mut i = 0;
+
+    while i < tree.len() {
+        if let Ok(output) = std::process::Command::new("pgrep")
+            .arg("-P")
+            .arg(tree[i].to_string())
+            .output()
+        {
+            tree.extend(
+                String::from_utf8_lossy(&output.stdout)
+                    .split_whitespace()
+                    .filter_map(|id| id.parse::<u32>().ok()),
+            );
+        }
+
+        i += 1;
+    }
+
+    tree
+}
+
+/// Create command killing process and all children processes, children first.
+///
+/// `npx` doesn't forward signals to the spawned node process, so killing `npx` only leaves orphaned nodes.
 #[cfg(target_family = "unix")]
+fn kill_tree_command(process_id: u32) -> std::process::Command {
+    let mut command = std::process::Command::new("kill");
+
+    for id in process_tree(process_id).iter().rev() {
+        command.arg(id.to_string());
+    }
+
+    command
+}
+
+#[cfg(target_family = "windows")]
+fn kill_tree_command(process_id: u32) -> std::process::Command {
+    let mut command = std::process::Command::new("taskkill");
+
+    command.args(["/T", "/F", "/PID", &process_id.to_string()]);
+
+    command
+}
+
+/// Kill process and all children processess by process id
 pub async fn kill_process_recursive(process_id: u32) -> anyhow::Result<ExitStatus> {
-    let mut child = Command::new("kill")
-        .arg(format!("{}", process_id))
-        .spawn()?;
+    let mut child = Command::from(kill_tree_command(process_id)).spawn()?;
 
     Ok(child.status().await?)
 }
 
+/// Blocking version of [`kill_process_recursive`], which is safe to call in [`Drop`].
+pub fn kill_process_recursive_blocking(
+    process_id: u32,
+) -> anyhow::Result<std::process::ExitStatus> {
+    Ok(kill_tree_command(process_id).status()?)
+}
+
+/// Max duration of one `eth_chainId` readiness probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Wait until json-rpc server of localhost `port` responds `eth_chainId`.
+///
+/// Probing with a json-rpc request instead of a tcp connect keeps other services listening on
+/// `port` from being reported as ready, and never blocks the executor thread.
+///
+/// Returns [`HardhatError::ChildProcess`] if `child` exited before, or [`HardhatError::StartTimeout`] after `timeout`.
+pub async fn wait_for_port(
+    child: &mut Child,
+    name: &str,
+    port: u16,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let mut provider = http::connect_to(format!("http://127.0.0.1:{}", port));
+
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        if let Some(status) = child.try_status()? {
+            return Err(HardhatError::ChildProcess(name.to_owned(), status).into());
+        }
+
+        if let Ok(Ok(chain_id)) =
+            ethers_runtime::timeout(PROBE_TIMEOUT, provider.eth_chain_id()).await
+        {
+            log::debug!("{} ready on port {}, chain_id {}", name, port, chain_id);
+
+            return Ok(());
+        }
+
+        if Instant::now() >= deadline {
+            return Err(HardhatError::StartTimeout(name.to_owned(), port).into());
+        }
+
+        ethers_runtime::sleep(Duration::from_millis(100)).await;
+    }
+}
+
 /// Returns hardhat base command instance
 pub fn hardhat_command<P>(hardhat_root: P) -> anyhow::Result<Command>
 where
@@ -43,12 +134,6 @@ where
     Ok(command)
 }
 
-/// Get global thread pool for async task to executing.
-pub fn thread_pool() -> &'static ThreadPool {
-    static POOLS: OnceCell<ThreadPool> = OnceCell::new();
-
-    POOLS.get_or_init(|| ThreadPool::new().unwrap())
-}
 /// Find the nearest cargo manifest dir.
 pub fn find_manifest_dir() -> anyhow::Result<PathBuf> {
     let start_dir = env::current_dir()?;
@@ -114,6 +199,8 @@ pub struct HardhatCommand<C: HardhatCommandContext> {
     command: Command,
     /// Started hardhat network process
     child_process: Option<Child>,
+    /// Generated files removed on drop
+    generated_files: Vec<PathBuf>,
 
     _marked: PhantomData<C>,
 }
@@ -138,14 +225,28 @@ where
 
         C::init_command(hardhat_root.clone(), &mut command)?;
 
+        // Kill `npx` at least, even if killing the process tree failed.
+        command.kill_on_drop(true);
+
         Ok(Self {
             hardhat_root,
             child_process: None,
+            generated_files: vec![],
             command,
             _marked: Default::default(),
         })
     }
 
+    /// Returns mutable ref of the command to launch.
+    pub(crate) fn command(&mut self) -> &mut Command {
+        &mut self.command
+    }
+
+    /// Register generated file `path`, which is removed when this instance is dropped.
+    pub(crate) fn remove_on_drop(&mut self, path: PathBuf) {
+        self.generated_files.push(path);
+    }
+
     /// Returns if network started.
     pub fn is_started(&self) -> bool {
         self.child_process.is_some()
@@ -177,13 +278,14 @@ where
         return Ok(true);
     }
 
-    /// Stop hardhat network.
+    /// Stop hardhat network, kill the child process tree and wait for it exiting.
     ///
     /// If hardhat network already stopped, returns [`HardhatError::HardhatNetworkStopped`]
-    pub async fn stop(&mut self) -> anyhow::Result<()> {
-        if let Some(child_process) = self.child_process.take() {
+    pub async fn stop(&mut self) -> anyhow::Result<ExitStatus> {
+        if let Some(mut child_process) = self.child_process.take() {
             kill_process_recursive(child_process.id()).await?;
-            Ok(())
+
+            Ok(child_process.status().await?)
         } else {
             Err(HardhatError::HardhatNetworkStopped.into())
         }
@@ -203,18 +305,22 @@ impl<C> Drop for HardhatCommand<C>
 where
     C: HardhatCommandContext,
 {
+    /// Kill the child process tree and remove generated files, also called during panic unwinding.
     fn drop(&mut self) {
-        if self.is_started() {
-            let child_process = self.child_process.take().unwrap();
-            let hardhat_root = self.hardhat_root.clone();
+        if let Some(child_process) = self.child_process.take() {
+            let drop_result = block_on(C::drop_command(self.hardhat_root.clone()));
 
-            block_on(async move {
-                let drop_result = C::drop_command(hardhat_root).await;
+            log::debug!("drop command result, {:?}", drop_result);
 
-                log::debug!("drop command result, {:?}", drop_result);
+            if let Err(err) = kill_process_recursive_blocking(child_process.id()) {
+                log::error!("kill process {} error, {}", child_process.id(), err);
+            }
+        }
 
-                _ = kill_process_recursive(child_process.id()).await;
-            });
+        for path in self.generated_files.drain(..) {
+            if let Err(err) = remove_file(&path) {
+                log::error!("remove generated file {:?} error, {}", path, err);
+            }
         }
     }
 }
@@ -251,4 +357,40 @@ mod tests {
 
         log::debug!("{:?}", find_manifest_dir().expect("find manifest dir"));
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_kill_process_recursive() {
+        use std::{process::Command, thread::sleep, time::Duration};
+
+        use super::{kill_process_recursive_blocking, process_tree};
+
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg("sleep 30 & wait")
+            .spawn()
+            .expect("spawn sh");
+
+        // Wait for `sh` forking `sleep`.
+        sleep(Duration::from_millis(200));
+
+        let tree = process_tree(child.id());
+
+        assert_eq!(tree.len(), 2);
+
+        assert!(kill_process_recursive_blocking(child.id())
+            .expect("kill")
+            .success());
+
+        child.wait().expect("wait sh");
+
+        sleep(Duration::from_millis(100));
+
+        // Orphaned `sleep` is killed, it is either reaped or a zombie.
+        let alive = std::fs::read_to_string(format!("/proc/{}/stat", tree[1]))
+            .map(|stat| !stat.contains(") Z "))
+            .unwrap_or(false);
+
+        assert!(!alive);
+    }
 }
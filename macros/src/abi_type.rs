@@ -0,0 +1,137 @@
+//! `#[derive(EthAbiType)]` implementation.
+
+use ethers_bindgen::sol_type;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Fields, LitStr, Token, Type};
+
+/// Returns solidity type of `#[ethabi(type = "..")]` attribute, if any.
+fn field_sol_type(field: &syn::Field) -> syn::Result<Option<TokenStream>> {
+    let mut sol = None;
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("ethabi"))
+    {
+        let name = attr.parse_args_with(|input: syn::parse::ParseStream| {
+            input.parse::<Token![type]>()?;
+            input.parse::<Token![=]>()?;
+            input.parse::<LitStr>()
+        })?;
+
+        if sol.is_some() {
+            return Err(Error::new(attr.span(), "Duplicate `ethabi` attribute"));
+        }
+
+        sol = Some(sol_type(&name.value(), name.span())?);
+    }
+
+    Ok(sol)
+}
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "EthAbiType does not support generic structs",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    ident.span(),
+                    "EthAbiType only supports structs with named fields",
+                ))
+            }
+        },
+        _ => return Err(Error::new(ident.span(), "EthAbiType only supports structs")),
+    };
+
+    let name = ident.to_string();
+    let ser_ident = format_ident!("__EthAbiSer{}", ident);
+    let de_ident = format_ident!("__EthAbiDe{}", ident);
+
+    let mut ser_fields = vec![];
+    let mut de_fields = vec![];
+    let mut to_values = vec![];
+    let mut from_values = vec![];
+
+    for field in fields {
+        let field_ident = field.ident.as_ref().unwrap();
+        let ty: &Type = &field.ty;
+
+        match field_sol_type(field)? {
+            Some(sol) => {
+                ser_fields.push(quote!(#field_ident: #sol));
+                de_fields.push(quote!(#field_ident: #sol));
+
+                to_values.push(quote! {
+                    #field_ident: ethers_rs::ToAbiType::<#sol>::to_abi_type(&self.#field_ident)
+                        .map_err(<S::Error as ::serde::ser::Error>::custom)?
+                });
+
+                from_values.push(quote! {
+                    #field_ident: <#ty as ethers_rs::FromAbiType<#sol>>::from_abi_type(value.#field_ident)
+                        .map_err(<D::Error as ::serde::de::Error>::custom)?
+                });
+            }
+            None => {
+                ser_fields.push(quote!(#field_ident: &'a #ty));
+                de_fields.push(quote!(#field_ident: #ty));
+                to_values.push(quote!(#field_ident: &self.#field_ident));
+                from_values.push(quote!(#field_ident: value.#field_ident));
+            }
+        }
+    }
+
+    Ok(quote! {
+        const _: () = {
+            #[derive(::serde::Serialize)]
+            #[serde(rename = #name)]
+            struct #ser_ident<'a> {
+                #(#ser_fields,)*
+                #[serde(skip)]
+                __marker: ::std::marker::PhantomData<&'a ()>,
+            }
+
+            #[derive(::serde::Deserialize)]
+            #[serde(rename = #name)]
+            struct #de_ident {
+                #(#de_fields,)*
+            }
+
+            impl ::serde::Serialize for #ident {
+                fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+                where
+                    S: ::serde::Serializer,
+                {
+                    let value = #ser_ident {
+                        #(#to_values,)*
+                        __marker: ::std::marker::PhantomData,
+                    };
+
+                    ::serde::Serialize::serialize(&value, serializer)
+                }
+            }
+
+            impl<'de> ::serde::Deserialize<'de> for #ident {
+                fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    let value = <#de_ident as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                    Ok(Self {
+                        #(#from_values,)*
+                    })
+                }
+            }
+        };
+    })
+}
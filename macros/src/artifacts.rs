@@ -0,0 +1,85 @@
+//! Hardhat artifacts lookup.
+
+use std::{
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::Span;
+use serde_json::Value;
+use syn::Error;
+
+/// Returns true if `path` is contract artifact json file, debug files `*.dbg.json` are skipped.
+fn is_artifact_file(path: &Path) -> bool {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+
+    file_name.ends_with(".json") && !file_name.ends_with(".dbg.json")
+}
+
+/// Collect artifact files under `dir` recursively, hardhat `build-info` directory is skipped.
+pub fn artifact_files(dir: &Path, span: Span) -> syn::Result<Vec<PathBuf>> {
+    let read_dir_err = |err: std::io::Error| {
+        let dir = dir.strip_prefix(crate::manifest_dir()).unwrap_or(dir);
+
+        Error::new(span, format!("Read dir `{}`: {}", dir.display(), err))
+    };
+
+    let mut files = vec![];
+
+    for entry in read_dir(dir).map_err(read_dir_err)? {
+        let path = entry.map_err(read_dir_err)?.path();
+
+        if path.is_dir() {
+            if path.file_name().and_then(|name| name.to_str()) != Some("build-info") {
+                files.append(&mut artifact_files(&path, span)?);
+            }
+        } else if is_artifact_file(&path) {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+/// Search `{contract_name}.json` under `dir` recursively, for nested sources and
+/// source files whose name differs from the contract name.
+pub fn find_artifact(dir: &Path, contract_name: &str, span: Span) -> syn::Result<Option<PathBuf>> {
+    let file_name = format!("{}.json", contract_name);
+
+    Ok(artifact_files(dir, span)?
+        .into_iter()
+        .find(|path| path.file_name().and_then(|name| name.to_str()) == Some(file_name.as_str())))
+}
+
+/// Replace `contractName` of artifact with `contract_name`, which names the generated binding.
+pub fn rename_contract(artifact: &mut Value, contract_name: &str) {
+    artifact["contractName"] = Value::String(contract_name.to_owned());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn test_is_artifact_file() {
+        assert!(is_artifact_file(Path::new(
+            "sol/artifacts/contracts/token/Token.sol/Token.json"
+        )));
+        assert!(!is_artifact_file(Path::new(
+            "sol/artifacts/contracts/token/Token.sol/Token.dbg.json"
+        )));
+
+        let mut artifact = serde_json::json!({ "contractName": "IToken", "abi": [] });
+
+        rename_contract(&mut artifact, "Token");
+
+        assert_eq!(ethers_bindgen::contract_name(&artifact), Some("Token"));
+    }
+}
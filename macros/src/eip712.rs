@@ -0,0 +1,171 @@
+//! `#[derive(Eip712)]` implementation.
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::quote;
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, DeriveInput, Error, Lit, MetaNameValue, Token,
+};
+
+/// Signing domain parsed from `#[eip712(..)]` attribute.
+///
+/// Every field except `salt` is required by `EIP712Domain`, missing ones are reported by [`derive`].
+#[derive(Default)]
+struct Domain {
+    name: Option<String>,
+    version: Option<String>,
+    chain_id: Option<u64>,
+    verifying_contract: Option<Vec<u8>>,
+    salt: Option<Vec<u8>>,
+}
+
+/// Decode `0x` prefixed hex string of `len` bytes.
+fn parse_hex(lit: &Lit, len: usize) -> syn::Result<Vec<u8>> {
+    let err = || {
+        Error::new(
+            lit.span(),
+            format!("Expect 0x prefixed hex string of {} bytes", len),
+        )
+    };
+
+    let value = match lit {
+        Lit::Str(value) => value.value(),
+        _ => return Err(err()),
+    };
+
+    let hex = value.strip_prefix("0x").ok_or_else(err)?;
+
+    if hex.len() != len * 2 || !hex.is_ascii() {
+        return Err(err());
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| err()))
+        .collect()
+}
+
+fn parse_str(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(value) => Ok(value.value()),
+        _ => Err(Error::new(lit.span(), "Expect string literal")),
+    }
+}
+
+impl Domain {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut domain = Domain::default();
+
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("eip712"))
+        {
+            let items =
+                attr.parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)?;
+
+            for item in items {
+                let key = item
+                    .path
+                    .get_ident()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or_default();
+
+                let duplicate = match key.as_str() {
+                    "name" => domain.name.replace(parse_str(&item.lit)?).is_some(),
+                    "version" => domain.version.replace(parse_str(&item.lit)?).is_some(),
+                    "chain_id" => {
+                        let chain_id = match &item.lit {
+                            Lit::Int(value) => value.base10_parse::<u64>()?,
+                            lit => return Err(Error::new(lit.span(), "Expect integer literal")),
+                        };
+
+                        domain.chain_id.replace(chain_id).is_some()
+                    }
+                    "verifying_contract" => domain
+                        .verifying_contract
+                        .replace(parse_hex(&item.lit, 20)?)
+                        .is_some(),
+                    "salt" => domain.salt.replace(parse_hex(&item.lit, 32)?).is_some(),
+                    _ => {
+                        return Err(Error::new(
+                            item.path.span(),
+                            "Unknown eip712 domain field, expect one of `name`, `version`, `chain_id`, `verifying_contract`, `salt`",
+                        ))
+                    }
+                };
+
+                if duplicate {
+                    return Err(Error::new(
+                        item.path.span(),
+                        format!("Duplicate eip712 domain field `{}`", key),
+                    ));
+                }
+            }
+        }
+
+        Ok(domain)
+    }
+}
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "Eip712 does not support generic structs",
+        ));
+    }
+
+    if !matches!(input.data, syn::Data::Struct(_)) {
+        return Err(Error::new(ident.span(), "Eip712 only supports structs"));
+    }
+
+    let domain = Domain::parse(&input)?;
+
+    let missing = |field: &str| {
+        Error::new(
+            Span::call_site(),
+            format!("Missing `#[eip712({} = ..)]` attribute", field),
+        )
+    };
+
+    let name = domain.name.ok_or_else(|| missing("name"))?;
+    let version = domain.version.ok_or_else(|| missing("version"))?;
+    let chain_id = Literal::u64_suffixed(domain.chain_id.ok_or_else(|| missing("chain_id"))?);
+    let verifying_contract = domain
+        .verifying_contract
+        .ok_or_else(|| missing("verifying_contract"))?;
+
+    let salt = match domain.salt {
+        Some(salt) => quote!(Some(ethers_rs::Bytes32::from([#(#salt),*]))),
+        None => quote!(None),
+    };
+
+    Ok(quote! {
+        impl #ident {
+            /// Returns eip712 signing domain declared by `#[eip712(..)]` attribute.
+            pub fn eip712_domain() -> ethers_rs::eip712::EIP712Domain {
+                ethers_rs::eip712::EIP712Domain {
+                    name: #name.to_owned(),
+                    version: #version.to_owned(),
+                    chain_id: ethers_rs::U256::from(#chain_id),
+                    verifying_contract: ethers_rs::Address([#(#verifying_contract),*]),
+                    salt: #salt,
+                }
+            }
+
+            /// Create eth_signTypedData payload of this message.
+            pub fn into_typed_data(
+                &self,
+            ) -> ::std::result::Result<ethers_rs::eip712::TypedData<&Self>, ethers_rs::Error> {
+                ethers_rs::eip712::eip712_into_request(Self::eip712_domain(), self)
+            }
+
+            /// Returns `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))` to be signed.
+            pub fn eip712_sign_hash(&self) -> ::std::result::Result<ethers_rs::H256, ethers_rs::Error> {
+                self.into_typed_data()?.sign_hash()
+            }
+        }
+    })
+}
@@ -1,29 +1,58 @@
-use std::{env, fs::read_to_string, path::PathBuf};
+use std::{
+    collections::HashMap,
+    env,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
 
-use ethbind::rust::{BindingBuilder, JsonRuntimeBinder, RustGenerator, ToTokenStream};
+use heck::ToSnakeCase;
+use serde_json::{json, Value};
+
+use ethers_bindgen::bind_artifact;
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
-use quote::quote;
-use syn::{parse::Parse, parse_macro_input, LitStr, Token};
+use proc_macro2::{Ident, Span};
+use quote::{format_ident, quote};
+use syn::{parse::Parse, parse_macro_input, DeriveInput, Error, LitStr, Token};
+
+mod abi_type;
+mod artifacts;
+mod eip712;
 
 struct Contract {
-    pub contract_name: String,
-    pub abi_data_path: Option<String>,
+    pub contract_name: Ident,
+    pub abi_data_path: Option<LitStr>,
+    /// Output module name, set by `mod = name` argument.
+    pub module: Option<Ident>,
 }
 
 impl Parse for Contract {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let contract_name: Ident = input.parse()?;
 
-        let abi_data_path = if input.parse::<Option<Token!(,)>>()?.is_some() {
-            Some(input.parse::<LitStr>()?.value())
-        } else {
-            None
-        };
+        let mut abi_data_path = None;
+        let mut module = None;
+
+        while input.parse::<Option<Token!(,)>>()?.is_some() {
+            if input.is_empty() {
+                break;
+            }
+
+            if input.peek(Token!(mod)) {
+                input.parse::<Token!(mod)>()?;
+                input.parse::<Token!(=)>()?;
+
+                module = Some(input.parse::<Ident>()?);
+            } else if abi_data_path.is_none() && module.is_none() {
+                abi_data_path = Some(input.parse::<LitStr>()?);
+            } else {
+                return Err(input.error("Expect `mod = name`"));
+            }
+        }
 
         Ok(Self {
-            contract_name: contract_name.to_string(),
+            contract_name,
             abi_data_path,
+            module,
         })
     }
 }
@@ -32,37 +61,327 @@ mod contract {
     syn::custom_keyword!(hardhat);
 }
 
-fn load_json_file(path: &str) -> String {
-    let dir = env::var("CARGO_MANIFEST_DIR").expect("Find CARGO_MANIFEST_DIR");
+struct AbiContract {
+    pub contract_name: Ident,
+    pub abi_path: LitStr,
+    pub bytecode_path: Option<LitStr>,
+}
 
-    let path = PathBuf::from(dir).join(path);
+impl Parse for AbiContract {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let contract_name: Ident = input.parse()?;
 
-    read_to_string(path.clone()).expect(&format!("Read json file: {:?}", path))
+        input.parse::<Token!(,)>()?;
+
+        let abi_path = input.parse::<LitStr>()?;
+
+        let bytecode_path = if input.parse::<Option<Token!(,)>>()?.is_some() {
+            Some(input.parse::<LitStr>()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            contract_name,
+            abi_path,
+            bytecode_path,
+        })
+    }
 }
 
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("Find CARGO_MANIFEST_DIR"))
+}
+
+/// Read file content, errors are reported at `span` with `path` relative to manifest dir.
+fn read_json_file(path: &Path, span: Span) -> syn::Result<String> {
+    read_to_string(path).map_err(|err| {
+        let path = path.strip_prefix(manifest_dir()).unwrap_or(path);
+
+        Error::new(span, format!("Read file `{}`: {}", path.display(), err))
+    })
+}
+
+fn load_json_file(path: &LitStr) -> syn::Result<String> {
+    read_json_file(&manifest_dir().join(path.value()), path.span())
+}
+
+fn parse_json(data: &str, span: Span) -> syn::Result<Value> {
+    serde_json::from_str(data).map_err(|err| Error::new(span, format!("Parse json: {}", err)))
+}
+
+/// Generate contract binding from hardhat artifact.
+///
+/// The artifact is loaded from `sol/artifacts/contracts/{Name}.sol/{Name}.json` by default,
+/// falls back to searching `{Name}.json` under `sol/artifacts/contracts` for nested sources.
+/// The binding is named by the first argument even if artifact `contractName` differs.
+///
+/// Besides methods generated per abi function, `view`/`pure` functions are grouped under
+/// `contract.calls()` and other functions under `contract.sends()`, e.g.
+/// `contract.calls().get_from(data)` and `contract.sends().withdraw(SendOptions::new())`.
+///
+/// ```ignore
+/// hardhat!(Token);
+/// hardhat!(Token, "sol/artifacts/contracts/token/IToken.sol/IToken.json");
+/// hardhat!(Token, "sol/artifacts/contracts/token/Token.sol/Token.json", mod = my_token);
+/// ```
 #[proc_macro]
 pub fn hardhat(item: TokenStream) -> TokenStream {
     let contract = parse_macro_input!(item as Contract);
 
-    let type_mapping: JsonRuntimeBinder = include_str!("./mapping.json")
-        .parse()
-        .expect("Parse mapping.json");
+    expand_hardhat(contract)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand_hardhat(contract: Contract) -> syn::Result<proc_macro2::TokenStream> {
+    let contract_name = contract.contract_name.to_string();
 
-    let abi_data = if let Some(abi_data_path) = contract.abi_data_path {
-        load_json_file(&abi_data_path)
+    let (abi_data, span) = if let Some(abi_data_path) = &contract.abi_data_path {
+        (load_json_file(abi_data_path)?, abi_data_path.span())
     } else {
-        load_json_file(&format!(
+        let span = contract.contract_name.span();
+
+        let path = manifest_dir().join(format!(
             "sol/artifacts/contracts/{}.sol/{}.json",
-            &contract.contract_name, &contract.contract_name
-        ))
+            contract_name, contract_name
+        ));
+
+        if path.exists() {
+            (read_json_file(&path, span)?, span)
+        } else {
+            let dir = manifest_dir().join("sol/artifacts/contracts");
+
+            let path = artifacts::find_artifact(&dir, &contract_name, span)?.ok_or_else(|| {
+                Error::new(
+                    span,
+                    format!(
+                        "Artifact `{}.json` not found in `sol/artifacts/contracts`",
+                        contract_name
+                    ),
+                )
+            })?;
+
+            (read_json_file(&path, span)?, span)
+        }
+    };
+
+    let mut artifact = parse_json(&abi_data, span)?;
+
+    artifacts::rename_contract(&mut artifact, &contract_name);
+
+    let binding = bind_artifact(&artifact, span)?;
+
+    match contract.module {
+        Some(module) => Ok(quote!(pub mod #module { #binding })),
+        None => Ok(binding),
+    }
+}
+
+/// Generate contract bindings of all artifacts under hardhat artifacts directory, one module per contract.
+///
+/// Modules are named by snake case contract names, e.g. `MyToken` is generated as `my_token::MyToken`.
+///
+/// ```ignore
+/// hardhat_dir!("sol/artifacts/contracts");
+/// ```
+#[proc_macro]
+pub fn hardhat_dir(item: TokenStream) -> TokenStream {
+    let dir = parse_macro_input!(item as LitStr);
+
+    expand_hardhat_dir(dir)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand_hardhat_dir(dir: LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let span = dir.span();
+
+    let dir = manifest_dir().join(dir.value());
+
+    let mut modules = vec![];
+    let mut bound = HashMap::new();
+
+    for path in artifacts::artifact_files(&dir, span)? {
+        let artifact = parse_json(&read_json_file(&path, span)?, span)?;
+
+        let contract_name = match ethers_bindgen::contract_name(&artifact) {
+            Some(contract_name) => contract_name.to_owned(),
+            None => continue,
+        };
+
+        if let Some(prev) = bound.insert(contract_name.clone(), path.clone()) {
+            return Err(Error::new(
+                span,
+                format!(
+                    "Duplicate contract {} in {:?} and {:?}, bind one of them with hardhat!(.., mod = ..)",
+                    contract_name, prev, path
+                ),
+            ));
+        }
+
+        let module = format_ident!("{}", contract_name.to_snake_case());
+
+        let binding = bind_artifact(&artifact, span)?;
+
+        modules.push(quote!(pub mod #module { #binding }));
+    }
+
+    Ok(quote!(#(#modules)*))
+}
+
+/// Convert bare abi json (or json object with `abi`/`bytecode` fields) to hardhat artifact format.
+fn to_hardhat_artifact(
+    contract_name: &str,
+    abi_data: &str,
+    bytecode: Option<String>,
+    span: Span,
+) -> syn::Result<Value> {
+    let value = parse_json(abi_data, span)?;
+
+    let (abi, artifact_bytecode, link_references) = match value {
+        Value::Array(_) => (value, None, json!({})),
+        Value::Object(mut object) => {
+            let abi = object
+                .remove("abi")
+                .ok_or_else(|| Error::new(span, "Abi json object without `abi` field"))?;
+
+            // hardhat: "linkReferences": {..}, foundry: "bytecode": { "linkReferences": {..} }
+            let mut link_references = object.remove("linkReferences");
+
+            // hardhat/truffle: "bytecode": "0x..", foundry: "bytecode": { "object": "0x.." }
+            let bytecode = match object.remove("bytecode") {
+                Some(Value::String(bytecode)) => Some(bytecode),
+                Some(Value::Object(mut bytecode)) => {
+                    link_references = link_references.or(bytecode.remove("linkReferences"));
+
+                    match bytecode.remove("object") {
+                        Some(Value::String(bytecode)) => Some(bytecode),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+
+            (abi, bytecode, link_references.unwrap_or_else(|| json!({})))
+        }
+        _ => return Err(Error::new(span, "Abi json file must be an array or object")),
     };
 
-    let generator = BindingBuilder::new((RustGenerator::default(), type_mapping))
-        .bind_hardhat(abi_data)
-        .finalize()
-        .expect("Generate contract/abi binding code");
+    let bytecode = bytecode
+        .or(artifact_bytecode)
+        .map(|bytecode| {
+            let bytecode = bytecode.trim();
+
+            if bytecode.starts_with("0x") {
+                bytecode.to_owned()
+            } else {
+                format!("0x{}", bytecode)
+            }
+        })
+        .unwrap_or("0x".to_owned());
+
+    Ok(json!({
+        "_format": "hh-sol-artifact-1",
+        "contractName": contract_name,
+        "sourceName": format!("{}.sol", contract_name),
+        "abi": abi,
+        "bytecode": bytecode,
+        "deployedBytecode": "0x",
+        "linkReferences": link_references,
+        "deployedLinkReferences": {},
+    }))
+}
+
+/// Generate contract binding from bare abi json file, with optional bytecode hex file.
+///
+/// Like [`hardhat!`], the binding also contains `SELECTOR_*` constants of functions
+/// and `TOPIC_*` constants of events.
+///
+/// ```ignore
+/// abi!(MyToken, "abi/erc20.json");
+/// abi!(MyToken, "abi/erc20.json", "abi/erc20.bin");
+/// ```
+#[proc_macro]
+pub fn abi(item: TokenStream) -> TokenStream {
+    let contract = parse_macro_input!(item as AbiContract);
+
+    expand_abi(contract)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand_abi(contract: AbiContract) -> syn::Result<proc_macro2::TokenStream> {
+    let abi_data = load_json_file(&contract.abi_path)?;
+
+    let bytecode = contract
+        .bytecode_path
+        .as_ref()
+        .map(load_json_file)
+        .transpose()?;
+
+    let artifact = to_hardhat_artifact(
+        &contract.contract_name.to_string(),
+        &abi_data,
+        bytecode,
+        contract.abi_path.span(),
+    )?;
+
+    bind_artifact(&artifact, contract.abi_path.span())
+}
+
+/// Derive `Serialize` and `Deserialize` mapping struct fields to solidity types.
+///
+/// Fields with `#[ethabi(type = "..")]` are converted into the solidity type mapping, e.g. `Uint<96>`,
+/// via `ToAbiType`/`FromAbiType` of `ethers_primitives`, other fields
+/// are serialized as is. The struct name is kept as eip712 type name.
+///
+/// ```ignore
+/// #[derive(EthAbiType)]
+/// struct Order {
+///     maker: Address,
+///     #[ethabi(type = "uint96")]
+///     amount: u64,
+///     #[ethabi(type = "int24[]")]
+///     ticks: Vec<i32>,
+/// }
+/// ```
+#[proc_macro_derive(EthAbiType, attributes(ethabi))]
+pub fn derive_eth_abi_type(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+
+    abi_type::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
 
-    let contracts = generator.to_token_streams().expect("To token streams");
+/// Derive eip712 signing helpers with the signing domain declared by struct attribute.
+///
+/// Generates `eip712_domain()`, `into_typed_data(&self)` and `eip712_sign_hash(&self)`,
+/// the struct must also implement `Serialize`.
+///
+/// `name`, `version`, `chain_id` and `verifying_contract` are required because
+/// `EIP712Domain` always hashes them, only `salt` is optional.
+///
+/// ```ignore
+/// #[derive(Serialize, Eip712)]
+/// #[eip712(
+///     name = "Ether Mail",
+///     version = "1",
+///     chain_id = 1,
+///     verifying_contract = "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+/// )]
+/// struct Mail {
+///     from: Person,
+///     to: Person,
+///     contents: String,
+/// }
+/// ```
+#[proc_macro_derive(Eip712, attributes(eip712))]
+pub fn derive_eip712(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
 
-    quote!(#(#contracts)*).into()
+    eip712::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
 }
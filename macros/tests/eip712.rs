@@ -0,0 +1,49 @@
+use ethers_rs::{Address, Eip712, Serialize, ToEtherHex};
+
+#[derive(Serialize)]
+struct Person {
+    name: String,
+    wallet: Address,
+}
+
+#[derive(Serialize, Eip712)]
+#[eip712(
+    name = "Ether Mail",
+    version = "1",
+    chain_id = 1,
+    verifying_contract = "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+)]
+struct Mail {
+    from: Person,
+    to: Person,
+    contents: String,
+}
+
+#[test]
+fn test_derive_eip712() {
+    let mail = Mail {
+        from: Person {
+            name: "Cow".to_owned(),
+            wallet: "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+                .try_into()
+                .unwrap(),
+        },
+        to: Person {
+            name: "Bob".to_owned(),
+            wallet: "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+                .try_into()
+                .unwrap(),
+        },
+        contents: "Hello, Bob!".to_owned(),
+    };
+
+    let domain = Mail::eip712_domain();
+
+    assert_eq!(domain.name, "Ether Mail");
+    assert_eq!(domain.salt, None);
+
+    assert_eq!(
+        mail.eip712_sign_hash().unwrap().to_eth_hex(),
+        "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+    );
+}
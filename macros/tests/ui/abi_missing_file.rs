@@ -0,0 +1,5 @@
+use ethers_macros::abi;
+
+abi!(Token, "abi/missing.json");
+
+fn main() {}
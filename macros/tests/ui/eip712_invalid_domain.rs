@@ -0,0 +1,9 @@
+use ethers_macros::Eip712;
+
+#[derive(Eip712)]
+#[eip712(name = "Ether Mail", version = "1", chain_id = 1, verifying_contract = "0x1234")]
+struct Mail {
+    contents: String,
+}
+
+fn main() {}
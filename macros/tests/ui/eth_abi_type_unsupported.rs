@@ -0,0 +1,9 @@
+use ethers_macros::EthAbiType;
+
+#[derive(EthAbiType)]
+struct Order {
+    #[ethabi(type = "uint7")]
+    amount: u64,
+}
+
+fn main() {}
@@ -0,0 +1,5 @@
+use ethers_macros::hardhat;
+
+hardhat!(Token, mod = token, "sol/artifacts/contracts/Token.sol/Token.json");
+
+fn main() {}
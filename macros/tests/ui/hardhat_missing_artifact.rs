@@ -0,0 +1,5 @@
+use ethers_macros::hardhat;
+
+hardhat!(Token, "sol/artifacts/contracts/Missing.sol/Missing.json");
+
+fn main() {}
@@ -0,0 +1,251 @@
+//! Conversions between rust values and solidity type mappings, used by `#[derive(EthAbiType)]`.
+
+use num::{BigInt, BigUint};
+
+use crate::{Address, Bytes, FixedBytes, Int, SignedError, Uint, UintError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum AbiTypeError {
+    #[error("{0}")]
+    Uint(#[from] UintError),
+    #[error("{0}")]
+    Int(#[from] SignedError),
+    #[error("OutOfRange: {value} convert to {target} failed")]
+    OutOfRange { value: String, target: &'static str },
+    #[error("Length: expect {expect} elements, got {got}")]
+    Length { expect: usize, got: usize },
+}
+
+/// Convert rust value into solidity type mapping `M`, e.g. `u64` into `Uint<96>`.
+pub trait ToAbiType<M> {
+    fn to_abi_type(&self) -> Result<M, AbiTypeError>;
+}
+
+/// Convert solidity type mapping `M` back into rust value.
+pub trait FromAbiType<M>: Sized {
+    fn from_abi_type(value: M) -> Result<Self, AbiTypeError>;
+}
+
+macro_rules! abi_type_unsigned {
+    ($($t: ty),+) => {
+        $(
+            impl<const BITS: usize> ToAbiType<Uint<BITS>> for $t {
+                fn to_abi_type(&self) -> Result<Uint<BITS>, AbiTypeError> {
+                    Ok(Uint::new(*self)?)
+                }
+            }
+
+            impl<const BITS: usize> FromAbiType<Uint<BITS>> for $t {
+                fn from_abi_type(value: Uint<BITS>) -> Result<Self, AbiTypeError> {
+                    Option::<$t>::from(value).ok_or_else(|| AbiTypeError::OutOfRange {
+                        value: BigUint::from(value).to_string(),
+                        target: stringify!($t),
+                    })
+                }
+            }
+        )+
+    };
+}
+
+abi_type_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! abi_type_signed {
+    ($($t: ty),+) => {
+        $(
+            impl<const BITS: usize> ToAbiType<Int<BITS>> for $t {
+                fn to_abi_type(&self) -> Result<Int<BITS>, AbiTypeError> {
+                    Ok(Int::new(*self)?)
+                }
+            }
+
+            impl<const BITS: usize> FromAbiType<Int<BITS>> for $t {
+                fn from_abi_type(value: Int<BITS>) -> Result<Self, AbiTypeError> {
+                    Option::<$t>::from(value).ok_or_else(|| AbiTypeError::OutOfRange {
+                        value: BigInt::from(value).to_string(),
+                        target: stringify!($t),
+                    })
+                }
+            }
+        )+
+    };
+}
+
+abi_type_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Width conversions, e.g. [`U256`](crate::U256) field mapped to `uint96`.
+impl<const FROM: usize, const TO: usize> ToAbiType<Uint<TO>> for Uint<FROM> {
+    fn to_abi_type(&self) -> Result<Uint<TO>, AbiTypeError> {
+        Ok(Uint::new(BigUint::from(self))?)
+    }
+}
+
+impl<const FROM: usize, const TO: usize> FromAbiType<Uint<FROM>> for Uint<TO> {
+    fn from_abi_type(value: Uint<FROM>) -> Result<Self, AbiTypeError> {
+        value.to_abi_type()
+    }
+}
+
+impl<const FROM: usize, const TO: usize> ToAbiType<Int<TO>> for Int<FROM> {
+    fn to_abi_type(&self) -> Result<Int<TO>, AbiTypeError> {
+        Ok(Int::new(BigInt::from(self))?)
+    }
+}
+
+impl<const FROM: usize, const TO: usize> FromAbiType<Int<FROM>> for Int<TO> {
+    fn from_abi_type(value: Int<FROM>) -> Result<Self, AbiTypeError> {
+        value.to_abi_type()
+    }
+}
+
+macro_rules! abi_type_identity {
+    ($($t: ty),+) => {
+        $(
+            impl ToAbiType<$t> for $t {
+                fn to_abi_type(&self) -> Result<$t, AbiTypeError> {
+                    Ok(self.clone())
+                }
+            }
+
+            impl FromAbiType<$t> for $t {
+                fn from_abi_type(value: $t) -> Result<Self, AbiTypeError> {
+                    Ok(value)
+                }
+            }
+        )+
+    };
+}
+
+abi_type_identity!(Address, Bytes, bool, String);
+
+impl<const N: usize> ToAbiType<FixedBytes<N>> for FixedBytes<N> {
+    fn to_abi_type(&self) -> Result<FixedBytes<N>, AbiTypeError> {
+        Ok(self.clone())
+    }
+}
+
+impl<const N: usize> FromAbiType<FixedBytes<N>> for FixedBytes<N> {
+    fn from_abi_type(value: FixedBytes<N>) -> Result<Self, AbiTypeError> {
+        Ok(value)
+    }
+}
+
+impl<const N: usize> ToAbiType<FixedBytes<N>> for [u8; N] {
+    fn to_abi_type(&self) -> Result<FixedBytes<N>, AbiTypeError> {
+        Ok(self.into())
+    }
+}
+
+impl<const N: usize> FromAbiType<FixedBytes<N>> for [u8; N] {
+    fn from_abi_type(value: FixedBytes<N>) -> Result<Self, AbiTypeError> {
+        Ok(value.as_bytes().try_into().unwrap())
+    }
+}
+
+impl ToAbiType<Bytes> for Vec<u8> {
+    fn to_abi_type(&self) -> Result<Bytes, AbiTypeError> {
+        Ok(self.clone().into())
+    }
+}
+
+impl FromAbiType<Bytes> for Vec<u8> {
+    fn from_abi_type(value: Bytes) -> Result<Self, AbiTypeError> {
+        Ok(value.into())
+    }
+}
+
+impl<T: ToAbiType<M>, M> ToAbiType<Vec<M>> for Vec<T> {
+    fn to_abi_type(&self) -> Result<Vec<M>, AbiTypeError> {
+        self.iter().map(ToAbiType::to_abi_type).collect()
+    }
+}
+
+impl<T: FromAbiType<M>, M> FromAbiType<Vec<M>> for Vec<T> {
+    fn from_abi_type(value: Vec<M>) -> Result<Self, AbiTypeError> {
+        value.into_iter().map(FromAbiType::from_abi_type).collect()
+    }
+}
+
+impl<T: ToAbiType<M>, M, const N: usize> ToAbiType<[M; N]> for [T; N] {
+    fn to_abi_type(&self) -> Result<[M; N], AbiTypeError> {
+        let values = self
+            .iter()
+            .map(ToAbiType::to_abi_type)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        to_array(values)
+    }
+}
+
+impl<T: FromAbiType<M>, M, const N: usize> FromAbiType<[M; N]> for [T; N] {
+    fn from_abi_type(value: [M; N]) -> Result<Self, AbiTypeError> {
+        let values = value
+            .into_iter()
+            .map(FromAbiType::from_abi_type)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        to_array(values)
+    }
+}
+
+fn to_array<T, const N: usize>(values: Vec<T>) -> Result<[T; N], AbiTypeError> {
+    let got = values.len();
+
+    values
+        .try_into()
+        .map_err(|_| AbiTypeError::Length { expect: N, got })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_ethabi::{from_abi, to_abi};
+
+    use crate::*;
+
+    #[test]
+    fn test_abi_type() {
+        let value: Uint<96> = 1000u64.to_abi_type().unwrap();
+
+        assert_eq!(u64::from_abi_type(value).unwrap(), 1000);
+
+        let value: Uint<8> = U256::new(255u8).unwrap().to_abi_type().unwrap();
+
+        assert_eq!(u8::from_abi_type(value).unwrap(), 255);
+
+        assert!(ToAbiType::<Uint<8>>::to_abi_type(&256u64).is_err());
+        assert!(u8::from_abi_type(Uint::<16>::new(256u16).unwrap()).is_err());
+
+        let value: Vec<Int<24>> = vec![-1i32, 2].to_abi_type().unwrap();
+
+        assert_eq!(Vec::<i32>::from_abi_type(value).unwrap(), vec![-1, 2]);
+
+        let value: FixedBytes<4> = [1u8, 2, 3, 4].to_abi_type().unwrap();
+
+        assert_eq!(value.as_bytes(), &[1, 2, 3, 4]);
+        assert_eq!(<[u8; 4]>::from_abi_type(value).unwrap(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_struct_encode() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Order {
+            maker: Address,
+            amount: Uint<96>,
+            ticks: Vec<Int<24>>,
+        }
+
+        let order = Order {
+            maker: Address::ZERO,
+            amount: 1000u64.to_abi_type().unwrap(),
+            ticks: vec![-1i32, 5].to_abi_type().unwrap(),
+        };
+
+        let data = to_abi(&order).unwrap();
+
+        assert_eq!(
+            data,
+            to_abi(&(&order.maker, &order.amount, &order.ticks)).unwrap()
+        );
+
+        assert_eq!(from_abi::<Order, _>(data).unwrap(), order);
+    }
+}
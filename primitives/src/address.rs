@@ -14,12 +14,13 @@ use k256::SecretKey;
 
 use serde::Deserialize;
 use serde::Serialize;
-
-use sha3::Digest;
-use sha3::Keccak256;
+use subtle::{Choice, ConstantTimeEq};
 
 use crate::hex::FromEtherHex;
 use crate::hex::ToEtherHex;
+use crate::BytesVisitor;
+use crate::H256;
+use crate::{keccak256, keccak256_concat};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -46,8 +47,30 @@ pub struct Address(
 );
 
 impl Address {
+    /// The zero address `0x0000000000000000000000000000000000000000`.
+    pub const ZERO: Address = Address([0; 20]);
+
     pub fn zero_address() -> Address {
-        Address([0; 20])
+        Self::ZERO
+    }
+
+    /// Returns true if this is the zero address.
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    /// Create random address, e.g. for tests.
+    #[cfg(feature = "rand")]
+    pub fn random() -> Address {
+        Address(rand::random())
+    }
+
+    /// Returns [EIP-1014](https://eips.ethereum.org/EIPS/eip-1014) address of contract created
+    /// by `CREATE2` from this deployer, with `salt` and contract `init_code`.
+    pub fn create2(&self, salt: &H256, init_code: &[u8]) -> Address {
+        let digest = keccak256_concat(&[&[0xff], &self.0, &salt.0, &keccak256(init_code)]);
+
+        Self(digest[12..].try_into().unwrap())
     }
 }
 
@@ -78,19 +101,37 @@ impl<'de> Deserialize<'de> for Address {
 
             Address::from_str(&data, false).map_err(serde::de::Error::custom)
         } else {
-            use super::bytes::Bytes32;
+            // abi/rlp deserializers returns address left padded to 32 bytes.
+            let bytes32 =
+                deserializer.deserialize_newtype_struct("address", BytesVisitor::default())?;
 
-            let bytes32 = Bytes32::deserialize(deserializer)?;
+            if bytes32.len() != 32 {
+                return Err(AddressError::Length(bytes32.len().to_string()))
+                    .map_err(serde::de::Error::custom);
+            }
 
             let mut buff = [0u8; 20];
 
-            buff.copy_from_slice(&bytes32.0[12..]);
+            buff.copy_from_slice(&bytes32[12..]);
 
             Ok(Self(buff))
         }
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Address {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self(u.arbitrary()?))
+    }
+}
+
+impl ConstantTimeEq for Address {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0[..].ct_eq(&other.0[..])
+    }
+}
+
 impl Display for Address {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.to_checksum_string())
@@ -117,10 +158,7 @@ impl Eip55 for Address {
     fn to_checksum_string(&self) -> String {
         let mut data = self.0.to_eth_hex();
 
-        let digest: [u8; 32] = Keccak256::new()
-            .chain_update(&data.as_bytes()[2..])
-            .finalize()
-            .into();
+        let digest = keccak256(&data.as_bytes()[2..]);
 
         let addr = unsafe { &mut data.as_bytes_mut()[2..] };
 
@@ -161,10 +199,7 @@ impl From<PublicKey> for Address {
     fn from(value: PublicKey) -> Self {
         let buff = value.to_encoded_point(false);
 
-        let digest: [u8; 32] = Keccak256::new()
-            .chain_update(&buff.as_bytes()[1..])
-            .finalize()
-            .into();
+        let digest = keccak256(&buff.as_bytes()[1..]);
 
         Self(digest[12..].try_into().unwrap())
     }
@@ -183,10 +218,7 @@ impl From<&SecretKey> for Address {
         let value = value.public_key();
         let buff = value.to_encoded_point(false);
 
-        let digest: [u8; 32] = Keccak256::new()
-            .chain_update(&buff.as_bytes()[1..])
-            .finalize()
-            .into();
+        let digest = keccak256(&buff.as_bytes()[1..]);
 
         Self(digest[12..].try_into().unwrap())
     }
@@ -195,7 +227,8 @@ impl From<&SecretKey> for Address {
 #[cfg(test)]
 mod tests {
     use k256::{PublicKey, SecretKey};
-    use serde_ethabi::to_abi;
+    use serde_ethabi::{from_abi, to_abi};
+    use serde_ethrlp::{rlp_decode, rlp_encode};
 
     use super::*;
 
@@ -236,5 +269,66 @@ mod tests {
         buff[12..].copy_from_slice(&address.0);
 
         assert_eq!(to_abi(&address).unwrap(), buff,);
+
+        assert_eq!(from_abi::<Address, _>(buff.to_vec()).unwrap(), address);
+    }
+
+    #[test]
+    fn test_address_rlp() {
+        let address =
+            Address::from_str("0x8d57B06Cb8E7C8a0515C71B76B019EF4F3ed680d", true).unwrap();
+
+        let data = rlp_encode(&address).unwrap();
+
+        assert_eq!(data[0], 0x94);
+
+        assert_eq!(rlp_decode::<Address>(&data).unwrap(), address);
+
+        assert!(rlp_decode::<Address>(&data[..20]).is_err());
+    }
+
+    #[test]
+    fn test_zero() {
+        assert!(Address::ZERO.is_zero());
+        assert_eq!(Address::default(), Address::ZERO);
+
+        let address =
+            Address::from_str("0x8d57B06Cb8E7C8a0515C71B76B019EF4F3ed680d", true).unwrap();
+
+        assert!(!address.is_zero());
+
+        assert!(bool::from(address.ct_eq(&address)));
+        assert!(!bool::from(address.ct_eq(&Address::ZERO)));
+    }
+
+    #[test]
+    fn test_create2() {
+        let init_code = Vec::<u8>::from_eth_hex("0x00").unwrap();
+
+        assert_eq!(
+            Address::zero_address().create2(&H256::default(), &init_code),
+            Address::from_str("0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38", true).unwrap()
+        );
+
+        let deployer =
+            Address::from_str("0xdeadbeef00000000000000000000000000000000", false).unwrap();
+
+        assert_eq!(
+            deployer.create2(&H256::default(), &init_code),
+            Address::from_str("0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3", true).unwrap()
+        );
+
+        let salt: H256 = "0x00000000000000000000000000000000000000000000000000000000cafebabe"
+            .try_into()
+            .unwrap();
+
+        let init_code = Vec::<u8>::from_eth_hex("0xdeadbeef").unwrap();
+
+        assert_eq!(
+            Address::from_str("0x00000000000000000000000000000000deadbeef", false)
+                .unwrap()
+                .create2(&salt, &init_code),
+            Address::from_str("0x60f3f640a8508fC6a86d45DF051962668E1e8AC7", true).unwrap()
+        );
     }
 }
@@ -0,0 +1,284 @@
+//! 2048 bits logs bloom filter of block header and transaction receipt.
+
+use std::fmt::Display;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::hex::{FromEtherHex, ToEtherHex};
+use crate::{keccak256, Address, Bytes, H256};
+
+/// Bloom filter length in bytes
+pub const BLOOM_LEN: usize = 256;
+
+#[derive(Debug, Error)]
+pub enum BloomError {
+    #[error("Invalid bloom length {0}, expect 256")]
+    Length(usize),
+
+    #[error("{0}")]
+    FromHexError(#[from] hex::FromHexError),
+}
+
+/// Log fields accrued into [`Bloom`]
+pub trait BloomLog {
+    fn address(&self) -> &Address;
+
+    fn topics(&self) -> &[H256];
+}
+
+/// 2048 bits logs bloom filter, each accrued input sets 3 bits selected by its keccak256 hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bloom(pub [u8; BLOOM_LEN]);
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self([0; BLOOM_LEN])
+    }
+}
+
+impl Bloom {
+    /// Returns bloom filter with `logs` accrued.
+    pub fn from_logs<'a, L, I>(logs: I) -> Self
+    where
+        L: BloomLog + 'a,
+        I: IntoIterator<Item = &'a L>,
+    {
+        let mut bloom = Self::default();
+
+        for log in logs {
+            bloom.accrue_log(log);
+        }
+
+        bloom
+    }
+
+    /// Returns true if no bit is set.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|b| *b == 0)
+    }
+
+    /// Accrue raw input bytes.
+    pub fn accrue(&mut self, input: &[u8]) {
+        for (index, mask) in Self::bits(input) {
+            self.0[index] |= mask;
+        }
+    }
+
+    /// Returns true if raw input bytes may be accrued into this filter.
+    ///
+    /// False positive is possible, but false negative is not.
+    pub fn contains_input(&self, input: &[u8]) -> bool {
+        Self::bits(input)
+            .iter()
+            .all(|(index, mask)| self.0[*index] & mask == *mask)
+    }
+
+    /// Accrue log address and topics.
+    pub fn accrue_log<L: BloomLog + ?Sized>(&mut self, log: &L) {
+        self.accrue(&log.address().0);
+
+        for topic in log.topics() {
+            self.accrue(&topic.0);
+        }
+    }
+
+    pub fn contains_address(&self, address: &Address) -> bool {
+        self.contains_input(&address.0)
+    }
+
+    pub fn contains_topic(&self, topic: &H256) -> bool {
+        self.contains_input(&topic.0)
+    }
+
+    /// Returns true if all bits of `other` are set in this filter.
+    pub fn contains_bloom(&self, other: &Bloom) -> bool {
+        self.0.iter().zip(other.0.iter()).all(|(a, b)| a & b == *b)
+    }
+
+    /// Returns (byte index, bit mask) of the 3 bits selected by `input`.
+    fn bits(input: &[u8]) -> [(usize, u8); 3] {
+        let hash = keccak256(input);
+
+        let mut bits = [(0usize, 0u8); 3];
+
+        for (i, bit) in bits.iter_mut().enumerate() {
+            let index = ((hash[i * 2] as usize) << 8 | hash[i * 2 + 1] as usize) & 2047;
+
+            *bit = (BLOOM_LEN - 1 - index / 8, 1u8 << (index % 8));
+        }
+
+        bits
+    }
+}
+
+impl BitOrAssign<&Bloom> for Bloom {
+    fn bitor_assign(&mut self, rhs: &Bloom) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *a |= b;
+        }
+    }
+}
+
+impl BitOr for Bloom {
+    type Output = Bloom;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self |= &rhs;
+        self
+    }
+}
+
+impl BitAndAssign<&Bloom> for Bloom {
+    fn bitand_assign(&mut self, rhs: &Bloom) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *a &= b;
+        }
+    }
+}
+
+impl BitAnd for Bloom {
+    type Output = Bloom;
+
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        self &= &rhs;
+        self
+    }
+}
+
+impl Display for Bloom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.to_eth_hex())
+    }
+}
+
+impl TryFrom<&[u8]> for Bloom {
+    type Error = BloomError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self(
+            value
+                .try_into()
+                .map_err(|_| BloomError::Length(value.len()))?,
+        ))
+    }
+}
+
+impl TryFrom<&str> for Bloom {
+    type Error = BloomError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Vec::<u8>::from_eth_hex(value)?.as_slice().try_into()
+    }
+}
+
+impl From<Bloom> for Bytes {
+    fn from(value: Bloom) -> Self {
+        value.0.into()
+    }
+}
+
+impl Serialize for Bloom {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Bytes::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bloom {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let buff = Bytes::deserialize(deserializer)?;
+
+        buff.as_bytes().try_into().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bloom() {
+        let address: Address = "0xa0Ee7A142d267C1f36714E4a8F75612F20a79720"
+            .try_into()
+            .unwrap();
+
+        let topic: H256 = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            .try_into()
+            .unwrap();
+
+        let mut bloom = Bloom::default();
+
+        assert!(bloom.is_empty());
+
+        bloom.accrue(&address.0);
+
+        assert!(bloom.contains_address(&address));
+        assert!(!bloom.contains_topic(&topic));
+        assert_eq!(bloom.0.iter().map(|b| b.count_ones()).sum::<u32>(), 3);
+
+        let mut other = Bloom::default();
+
+        other.accrue(&topic.0);
+
+        let merged = bloom | other;
+
+        assert!(merged.contains_address(&address));
+        assert!(merged.contains_topic(&topic));
+        assert!(merged.contains_bloom(&bloom));
+        assert_eq!(merged & other, other);
+
+        let json = serde_json::to_string(&merged).unwrap();
+
+        assert_eq!(serde_json::from_str::<Bloom>(&json).unwrap(), merged);
+    }
+
+    #[test]
+    fn test_receipt_bloom() {
+        // Receipt `logsBloom` of one log, the known answer vector of parity `ethbloom` tests.
+        let expected: Bloom = concat!(
+            "0x",
+            "00000000000000000000000000000000",
+            "00000000100000000000000000000000",
+            "00000000000000000000000000000000",
+            "00000000000000000000000000000000",
+            "00000000000000000000000000000000",
+            "00000000000000000000000000000000",
+            "00000002020000000000000000000000",
+            "00000000000000000000000800000000",
+            "10000000000000000000000000000000",
+            "00000000000000000000001000000000",
+            "00000000000000000000000000000000",
+            "00000000000000000000000000000000",
+            "00000000000000000000000000000000",
+            "00000000000000000000000000000000",
+            "00000000000000000000000000000000",
+            "00000000000000000000000000000000",
+        )
+        .try_into()
+        .unwrap();
+
+        let address: Address = "0xef2d6d194084c2de36e0dabfce45d046b37d1106"
+            .try_into()
+            .unwrap();
+
+        let topic: H256 = "0x02c69be41d0b7e40352fc85be1cd65eb03d40ef8427a0ca4596b1ead9a00e9fc"
+            .try_into()
+            .unwrap();
+
+        let mut bloom = Bloom::default();
+
+        bloom.accrue(&address.0);
+        bloom.accrue(&topic.0);
+
+        assert_eq!(bloom, expected);
+        assert!(expected.contains_address(&address));
+        assert!(expected.contains_topic(&topic));
+    }
+}
@@ -1,12 +1,13 @@
 //! Contract abi `bytes<M>` and bytes type support. those types can aslo be used with eip715 or tx signature.
 
-use std::fmt::Display;
+use std::{fmt::Display, ops::Deref, ops::RangeBounds};
 
 use crate::hex::{FromEtherHex, ToEtherHex};
 
 use hex::FromHexError;
 // use concat_idents::concat_idents;
 use serde::{de, Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -18,60 +19,110 @@ pub enum BytesErrors {
     FromHexError(#[from] FromHexError),
 }
 
-/// Type mapping for `bytes<M>` of contract abi
+/// ABI type names of `bytes<M>`, indexed by `M`.
+const ABI_NAMES: [&str; 33] = [
+    "bytes0", "bytes1", "bytes2", "bytes3", "bytes4", "bytes5", "bytes6", "bytes7", "bytes8",
+    "bytes9", "bytes10", "bytes11", "bytes12", "bytes13", "bytes14", "bytes15", "bytes16",
+    "bytes17", "bytes18", "bytes19", "bytes20", "bytes21", "bytes22", "bytes23", "bytes24",
+    "bytes25", "bytes26", "bytes27", "bytes28", "bytes29", "bytes30", "bytes31", "bytes32",
+];
+
+/// Type mapping for `bytes<M>` of contract abi, `N` is in `1..=32`.
+///
+/// Stored left aligned in a 32 bytes word, bytes after `N` are always zero.
 #[derive(Debug, PartialEq, Clone, Eq, Hash, Default)]
-pub struct BytesM<const LEN: usize>(pub [u8; 32]);
+pub struct FixedBytes<const N: usize>(pub [u8; 32]);
 
-impl<const LEN: usize> Display for BytesM<LEN> {
+/// Alias of [`FixedBytes`].
+pub type BytesM<const LEN: usize> = FixedBytes<LEN>;
+
+impl<const N: usize> FixedBytes<N> {
+    /// All zero `bytes<M>`.
+    pub const ZERO: Self = Self([0; 32]);
+
+    /// Returns ABI type name, e.g. `bytes4`.
+    pub fn abi_name() -> &'static str {
+        ABI_NAMES[N]
+    }
+
+    /// Returns the `N` bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0[..N]
+    }
+
+    /// Returns true if all bytes are zero.
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 32]
+    }
+
+    /// Create random `bytes<M>`, e.g. salt or nonce.
+    #[cfg(feature = "rand")]
+    pub fn random() -> Self {
+        use rand::Rng;
+
+        let mut buff = [0u8; 32];
+
+        rand::thread_rng().fill(&mut buff[..N]);
+
+        Self(buff)
+    }
+}
+
+impl<const N: usize> ConstantTimeEq for FixedBytes<N> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0[..].ct_eq(&other.0[..])
+    }
+}
+
+impl<const N: usize> Display for FixedBytes<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0.to_eth_hex())
+        write!(f, "{}", self.as_bytes().to_eth_hex())
     }
 }
 
-impl<const LEN: usize> AsRef<[u8]> for BytesM<LEN> {
+impl<const N: usize> AsRef<[u8]> for FixedBytes<N> {
     fn as_ref(&self) -> &[u8] {
-        &self.0
+        self.as_bytes()
     }
 }
 
-impl<const LEN: usize> TryFrom<&str> for BytesM<LEN> {
+impl<const N: usize> TryFrom<&[u8]> for FixedBytes<N> {
     type Error = BytesErrors;
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let buff = Vec::<u8>::from_eth_hex(value)?;
-
-        if buff.len() > 32 {
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.len() > N {
             return Err(BytesErrors::BytesMOutOfRange);
         }
 
-        let mut temp = [0u8; 32];
+        let mut buff = [0u8; 32];
+
+        buff[..value.len()].copy_from_slice(value);
 
-        temp[..LEN].copy_from_slice(&buff[..LEN]);
+        Ok(Self(buff))
+    }
+}
 
-        Ok(Self(temp))
+impl<const N: usize> TryFrom<&str> for FixedBytes<N> {
+    type Error = BytesErrors;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Vec::<u8>::from_eth_hex(value)?.as_slice().try_into()
     }
 }
 
-impl<const LEN: usize> Serialize for BytesM<LEN> {
+impl<const N: usize> Serialize for FixedBytes<N> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         if serializer.is_human_readable() {
-            serializer.serialize_str(&self.0.to_eth_hex())
+            serializer.serialize_str(&self.as_bytes().to_eth_hex())
         } else {
             // AbiSerialize/EIp715Serializer use struct name to handle dispatch.
-            let buff = &self.0;
-
-            let name = format!("bytes{}", LEN);
-
-            let static_name = unsafe { &*(&name as *const String) };
-
-            serializer.serialize_newtype_struct(static_name.as_str(), buff)
+            serializer.serialize_newtype_struct(Self::abi_name(), &self.0)
         }
     }
 }
 
-impl<'de, const LEN: usize> Deserialize<'de> for BytesM<LEN> {
+impl<'de, const N: usize> Deserialize<'de> for FixedBytes<N> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
@@ -81,52 +132,47 @@ impl<'de, const LEN: usize> Deserialize<'de> for BytesM<LEN> {
 
             let buff = Vec::<u8>::from_eth_hex(data).map_err(serde::de::Error::custom)?;
 
-            if buff.len() > 32 {
-                return Err(BytesErrors::BytesMOutOfRange).map_err(serde::de::Error::custom);
-            }
-
-            let mut temp = [0u8; 32];
-
-            temp[..buff.len()].copy_from_slice(&buff[..buff.len()]);
-
-            Ok(Self(temp))
+            buff.as_slice().try_into().map_err(serde::de::Error::custom)
         } else {
-            let name = format!("bytes{}", LEN);
-
-            let static_name = unsafe { &*(&name as *const String) };
-
             let buff = deserializer
-                .deserialize_newtype_struct(static_name.as_str(), BytesVisitor::default())?;
+                .deserialize_newtype_struct(Self::abi_name(), BytesVisitor::default())?;
 
-            if buff.len() > 32 {
-                return Err(BytesErrors::BytesMOutOfRange).map_err(serde::de::Error::custom);
+            if buff.len() < N || buff.len() > 32 {
+                return Err(serde::de::Error::custom(BytesErrors::BytesMOutOfRange));
             }
 
-            let mut temp = [0u8; 32];
-
-            temp[..LEN].copy_from_slice(&buff[..LEN]);
-
-            Ok(Self(temp))
+            buff[..N].try_into().map_err(serde::de::Error::custom)
         }
     }
 }
 
-impl<const LEN: usize> From<[u8; LEN]> for BytesM<LEN> {
-    fn from(value: [u8; LEN]) -> Self {
+impl<const N: usize> From<[u8; N]> for FixedBytes<N> {
+    fn from(value: [u8; N]) -> Self {
         Self::from(&value)
     }
 }
 
-impl<const LEN: usize> From<&[u8; LEN]> for BytesM<LEN> {
-    fn from(value: &[u8; LEN]) -> Self {
+impl<const N: usize> From<&[u8; N]> for FixedBytes<N> {
+    fn from(value: &[u8; N]) -> Self {
         let mut buff = [0u8; 32];
 
-        buff[..LEN].copy_from_slice(value);
+        buff[..N].copy_from_slice(value);
 
         Self(buff)
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a, const N: usize> arbitrary::Arbitrary<'a> for FixedBytes<N> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut buff = [0u8; 32];
+
+        u.fill_buffer(&mut buff[..N])?;
+
+        Ok(Self(buff))
+    }
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct BytesVisitor;
 
@@ -145,20 +191,87 @@ impl<'de> de::Visitor<'de> for BytesVisitor {
     }
 }
 
-/// Type mapping for `bytes` of contract abi
-#[derive(Debug, PartialEq, Clone, Eq, Default)]
-pub struct Bytes(pub Vec<u8>);
+/// Type mapping for `bytes` of contract abi.
+///
+/// Backed by [`bytes::Bytes`], clones and [`slices`](Self::slice) share the underlying buffer.
+#[derive(Debug, PartialEq, Clone, Eq, Hash, Default)]
+pub struct Bytes(pub bytes::Bytes);
+
+impl Bytes {
+    /// Create empty bytes.
+    pub const fn new() -> Self {
+        Self(bytes::Bytes::new())
+    }
+
+    /// Returns the bytes slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns a slice of `range` sharing the underlying buffer, without copying.
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+        Self(self.0.slice(range))
+    }
+
+    /// Copy into a new `Vec<u8>`.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+/// Bytes of different length are never equal, only the length comparison is not constant time.
+impl ConstantTimeEq for Bytes {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.as_bytes().ct_eq(other.as_bytes())
+    }
+}
 
 impl Display for Bytes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0.to_eth_hex())
+        write!(f, "{}", self.as_bytes().to_eth_hex())
+    }
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_bytes()
     }
 }
 
 impl TryFrom<&str> for Bytes {
     type Error = BytesErrors;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        Ok(Self(Vec::<u8>::from_eth_hex(value)?))
+        Ok(Vec::<u8>::from_eth_hex(value)?.into())
+    }
+}
+
+/// Newtype payload of [`Bytes`], serialized by `serialize_bytes` for abi/rlp/eip712 serializers.
+struct BytesPayload<'a>(&'a [u8]);
+
+impl<'a> Serialize for BytesPayload<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(self.0)
     }
 }
 
@@ -168,9 +281,9 @@ impl Serialize for Bytes {
         S: serde::Serializer,
     {
         if serializer.is_human_readable() {
-            serializer.serialize_str(&self.0.to_eth_hex())
+            serializer.serialize_str(&self.as_bytes().to_eth_hex())
         } else {
-            serializer.serialize_newtype_struct("bytes", &self.0)
+            serializer.serialize_newtype_struct("bytes", &BytesPayload(self.as_bytes()))
         }
     }
 }
@@ -185,41 +298,96 @@ impl<'de> Deserialize<'de> for Bytes {
 
             let buff = Vec::<u8>::from_eth_hex(data).map_err(serde::de::Error::custom)?;
 
-            Ok(Self(buff))
+            Ok(buff.into())
         } else {
             let buff = deserializer.deserialize_newtype_struct("bytes", BytesVisitor::default())?;
 
-            Ok(Self(buff))
+            Ok(buff.into())
         }
     }
 }
 
 impl From<&[u8]> for Bytes {
     fn from(value: &[u8]) -> Self {
-        Self(value.to_vec())
+        Self(bytes::Bytes::copy_from_slice(value))
     }
 }
 
 impl From<Vec<u8>> for Bytes {
     fn from(value: Vec<u8>) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<bytes::Bytes> for Bytes {
+    fn from(value: bytes::Bytes) -> Self {
         Self(value)
     }
 }
 
 impl<const LEN: usize> From<&[u8; LEN]> for Bytes {
     fn from(value: &[u8; LEN]) -> Self {
-        Self(value.to_vec())
+        Self::from(&value[..])
     }
 }
 
 impl<const LEN: usize> From<[u8; LEN]> for Bytes {
     fn from(value: [u8; LEN]) -> Self {
-        Self(value.to_vec())
+        Self::from(&value[..])
+    }
+}
+
+impl From<Bytes> for bytes::Bytes {
+    fn from(value: Bytes) -> Self {
+        value.0
+    }
+}
+
+impl From<Bytes> for Vec<u8> {
+    fn from(value: Bytes) -> Self {
+        value.0.into()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Bytes {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Vec::<u8>::arbitrary(u)?.into())
     }
 }
 
-pub type Bytes1 = BytesM<1>;
-pub type Bytes32 = BytesM<32>;
+pub type Bytes1 = FixedBytes<1>;
+pub type Bytes2 = FixedBytes<2>;
+pub type Bytes3 = FixedBytes<3>;
+pub type Bytes4 = FixedBytes<4>;
+pub type Bytes5 = FixedBytes<5>;
+pub type Bytes6 = FixedBytes<6>;
+pub type Bytes7 = FixedBytes<7>;
+pub type Bytes8 = FixedBytes<8>;
+pub type Bytes9 = FixedBytes<9>;
+pub type Bytes10 = FixedBytes<10>;
+pub type Bytes11 = FixedBytes<11>;
+pub type Bytes12 = FixedBytes<12>;
+pub type Bytes13 = FixedBytes<13>;
+pub type Bytes14 = FixedBytes<14>;
+pub type Bytes15 = FixedBytes<15>;
+pub type Bytes16 = FixedBytes<16>;
+pub type Bytes17 = FixedBytes<17>;
+pub type Bytes18 = FixedBytes<18>;
+pub type Bytes19 = FixedBytes<19>;
+pub type Bytes20 = FixedBytes<20>;
+pub type Bytes21 = FixedBytes<21>;
+pub type Bytes22 = FixedBytes<22>;
+pub type Bytes23 = FixedBytes<23>;
+pub type Bytes24 = FixedBytes<24>;
+pub type Bytes25 = FixedBytes<25>;
+pub type Bytes26 = FixedBytes<26>;
+pub type Bytes27 = FixedBytes<27>;
+pub type Bytes28 = FixedBytes<28>;
+pub type Bytes29 = FixedBytes<29>;
+pub type Bytes30 = FixedBytes<30>;
+pub type Bytes31 = FixedBytes<31>;
+pub type Bytes32 = FixedBytes<32>;
 
 #[cfg(test)]
 mod tests {
@@ -239,6 +407,71 @@ mod tests {
 
         assert_eq!(v, expected);
     }
+
+    #[test]
+    fn test_zero() {
+        assert!(Bytes32::ZERO.is_zero());
+        assert!(!BytesM::<4>::from(b"abcd").is_zero());
+
+        assert!(bool::from(Bytes32::ZERO.ct_eq(&Bytes32::default())));
+        assert!(!bool::from(Bytes::from(b"ab").ct_eq(&Bytes::from(b"abc"))));
+    }
+
+    #[test]
+    fn test_fixed_bytes() {
+        let value = Bytes4::try_from("0x12345678").unwrap();
+
+        assert_eq!(value.as_bytes(), &[0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(value.to_string(), "0x12345678");
+        assert_eq!(Bytes4::abi_name(), "bytes4");
+
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#""0x12345678""#);
+        assert_eq!(
+            serde_json::from_str::<Bytes4>(r#""0x12345678""#).unwrap(),
+            value
+        );
+
+        assert!(serde_json::from_str::<Bytes4>(r#""0x1234567890""#).is_err());
+
+        assert_eq!(
+            FixedBytes::<4>::try_from(&[0x12u8, 0x34][..])
+                .unwrap()
+                .as_bytes(),
+            &[0x12, 0x34, 0, 0]
+        );
+
+        assert!(FixedBytes::<2>::try_from(&[1u8, 2, 3][..]).is_err());
+    }
+
+    #[test]
+    fn test_bytes_slice() {
+        let value = Bytes::try_from("0x0102030405").unwrap();
+
+        let slice = value.slice(1..3);
+
+        assert_eq!(slice.as_bytes(), &[2, 3]);
+        assert_eq!(slice.as_ptr(), value[1..].as_ptr());
+        assert_eq!(slice.to_string(), "0x0203");
+
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#""0x0102030405""#);
+        assert_eq!(
+            serde_json::from_str::<Bytes>(r#""0x0102030405""#).unwrap(),
+            value
+        );
+
+        assert_eq!(Vec::<u8>::from(slice), vec![2, 3]);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random() {
+        let value = BytesM::<4>::random();
+
+        assert!(value.0[4..].iter().all(|b| *b == 0));
+
+        assert_ne!(crate::Address::random(), crate::Address::random());
+    }
+
     #[test]
     fn test_bytes() {
         assert_eq!(
@@ -278,4 +511,20 @@ mod tests {
                 ["one".to_owned(), "two".to_owned(), "three".to_owned()].to_vec()
             ),"0x000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000e000000000000000000000000000000000000000000000000000000000000000036f6e650000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000374776f000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000057468726565000000000000000000000000000000000000000000000000000000");
     }
+
+    #[cfg(feature = "arbitrary")]
+    proptest::proptest! {
+        #[test]
+        fn test_arbitrary_abi_roundtrip(data in proptest::collection::vec(proptest::prelude::any::<u8>(), 0..512)) {
+            use crate::{Address, Uint};
+
+            type Value = (Address, Uint<256>, Uint<24>, BytesM<5>, Bytes, Vec<Address>);
+
+            let value: Value = arbitrary::Unstructured::new(&data).arbitrary().unwrap();
+
+            let decoded: Value = from_abi(to_abi(&value).unwrap()).unwrap();
+
+            proptest::prop_assert_eq!(decoded, value);
+        }
+    }
 }
@@ -0,0 +1,92 @@
+//! Keccak-256 hashing, one-shot and incremental.
+
+use sha3::{Digest, Keccak256};
+
+/// Compute the Keccak-256 hash of input bytes.
+pub fn keccak256<S>(bytes: S) -> [u8; 32]
+where
+    S: AsRef<[u8]>,
+{
+    Keccak256::digest(bytes.as_ref()).into()
+}
+
+/// Compute the Keccak-256 hash of `parts` concatenated, without allocating the concatenation.
+pub fn keccak256_concat(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = KeccakHasher::new();
+
+    for part in parts {
+        hasher.update(part);
+    }
+
+    hasher.finalize()
+}
+
+/// Incremental Keccak-256 hasher.
+///
+/// ```
+/// # use ethers_primitives::{keccak256, KeccakHasher};
+/// let mut hasher = KeccakHasher::new();
+///
+/// hasher.update(b"hello ").update(b"world");
+///
+/// assert_eq!(hasher.finalize(), keccak256(b"hello world"));
+/// ```
+#[derive(Clone, Default)]
+pub struct KeccakHasher(Keccak256);
+
+impl KeccakHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed `bytes` into the hasher.
+    pub fn update<S>(&mut self, bytes: S) -> &mut Self
+    where
+        S: AsRef<[u8]>,
+    {
+        self.0.update(bytes.as_ref());
+        self
+    }
+
+    /// Feed `bytes` into the hasher, builder style.
+    pub fn chain<S>(mut self, bytes: S) -> Self
+    where
+        S: AsRef<[u8]>,
+    {
+        self.update(bytes);
+        self
+    }
+
+    /// Returns the hash of all fed bytes.
+    pub fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ToEtherHex;
+
+    use super::*;
+
+    #[test]
+    fn test_keccak256() {
+        assert_eq!(
+            keccak256([]).to_eth_hex(),
+            "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+
+        assert_eq!(
+            keccak256_concat(&[b"hello", b" ", b"world"]),
+            keccak256(b"hello world")
+        );
+
+        assert_eq!(
+            KeccakHasher::new()
+                .chain("hello")
+                .chain(" world")
+                .finalize(),
+            keccak256("hello world")
+        );
+    }
+}
@@ -50,3 +50,199 @@ impl<T: FromHex> FromEtherHex for T {
         }
     }
 }
+
+/// Odd-length hex string handling of [`decode_eth_hex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OddLength {
+    /// Returns [`FromHexError::OddLength`] error.
+    #[default]
+    Reject,
+    /// Left pad with `0`, e.g. `0x123` is decoded as `0x0123`.
+    Pad,
+}
+
+/// Strip optional `0x`/`0X` prefix of hex string `s`.
+pub fn strip_hex_prefix(s: &str) -> &str {
+    s.strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s)
+}
+
+/// Decode 0x-prefixed or bare hex string `s`, odd-length strings are handled as `odd`.
+pub fn decode_eth_hex<S: AsRef<str>>(s: S, odd: OddLength) -> Result<Vec<u8>, FromHexError> {
+    let s = strip_hex_prefix(s.as_ref());
+
+    if !s.len().is_multiple_of(2) {
+        match odd {
+            OddLength::Reject => return Err(FromHexError::OddLength),
+            OddLength::Pad => return decode(format!("0{}", s)),
+        }
+    }
+
+    decode(s)
+}
+
+/// Decode 0x-prefixed or bare hex string `s` into `N` bytes array, `s` must be exactly `2 * N` hex digits.
+pub fn decode_to_array<const N: usize, S: AsRef<str>>(s: S) -> Result<[u8; N], FromHexError> {
+    let mut buff = [0u8; N];
+
+    decode_to_slice(strip_hex_prefix(s.as_ref()), &mut buff)?;
+
+    Ok(buff)
+}
+
+/// Serde helpers of ethereum hex strings, used via `#[serde(with = "...")]`.
+///
+/// ```
+/// use ethers_primitives::serde_hex;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Payload {
+///     #[serde(with = "serde_hex::u64_hex")]
+///     nonce: u64,
+///     #[serde(with = "serde_hex::bytes_hex")]
+///     data: Vec<u8>,
+/// }
+/// ```
+pub mod serde_hex {
+    /// `u64` as quantity hex string, e.g. `"0x1a"`, numbers are accepted when deserializing.
+    pub mod u64_hex {
+        use serde::{de, Deserializer, Serializer};
+
+        use super::super::strip_hex_prefix;
+
+        pub fn serialize<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_str(&format!("{:#x}", value))
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(U64Visitor)
+        }
+
+        struct U64Visitor;
+
+        impl<'de> de::Visitor<'de> for U64Visitor {
+            type Value = u64;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(formatter, "expect u64 or hex string")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(v)
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let digits = strip_hex_prefix(v);
+
+                if digits.is_empty() {
+                    return Err(E::custom(format!("invalid hex quantity {}", v)));
+                }
+
+                u64::from_str_radix(digits, 16).map_err(E::custom)
+            }
+        }
+    }
+
+    /// `Vec<u8>` as 0x-prefixed hex string, bare hex strings are accepted when deserializing.
+    pub mod bytes_hex {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        use super::super::{decode_eth_hex, encode, OddLength};
+
+        pub fn serialize<S, T>(value: T, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+            T: AsRef<[u8]>,
+        {
+            serializer.serialize_str(&format!("0x{}", encode(value)))
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let s = String::deserialize(deserializer)?;
+
+            decode_eth_hex(s, OddLength::Reject).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[test]
+    fn test_decode_eth_hex() {
+        assert_eq!(decode_eth_hex("0x0102", OddLength::Reject), Ok(vec![1, 2]));
+        assert_eq!(decode_eth_hex("0102", OddLength::Reject), Ok(vec![1, 2]));
+        assert_eq!(decode_eth_hex("0X0102", OddLength::Reject), Ok(vec![1, 2]));
+        assert_eq!(decode_eth_hex("0x", OddLength::Reject), Ok(vec![]));
+
+        assert_eq!(
+            decode_eth_hex("0x102", OddLength::Reject),
+            Err(FromHexError::OddLength)
+        );
+
+        assert_eq!(decode_eth_hex("0x102", OddLength::Pad), Ok(vec![1, 2]));
+
+        assert!(decode_eth_hex("0xzz", OddLength::Reject).is_err());
+    }
+
+    #[test]
+    fn test_decode_to_array() {
+        assert_eq!(decode_to_array::<2, _>("0x0102"), Ok([1u8, 2]));
+        assert_eq!(decode_to_array::<2, _>("0102"), Ok([1u8, 2]));
+
+        assert_eq!(
+            decode_to_array::<4, _>("0x0102"),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Payload {
+        #[serde(with = "serde_hex::u64_hex")]
+        nonce: u64,
+        #[serde(with = "serde_hex::bytes_hex")]
+        data: Vec<u8>,
+    }
+
+    #[test]
+    fn test_serde_hex() {
+        let payload = Payload {
+            nonce: 26,
+            data: vec![0xde, 0xad],
+        };
+
+        let json = serde_json::to_string(&payload).unwrap();
+
+        assert_eq!(json, r#"{"nonce":"0x1a","data":"0xdead"}"#);
+
+        assert_eq!(serde_json::from_str::<Payload>(&json).unwrap(), payload);
+
+        assert_eq!(
+            serde_json::from_str::<Payload>(r#"{"nonce":26,"data":"dead"}"#).unwrap(),
+            payload
+        );
+
+        assert!(serde_json::from_str::<Payload>(r#"{"nonce":"0x","data":"0x"}"#).is_err());
+        assert!(serde_json::from_str::<Payload>(r#"{"nonce":"0x1","data":"0xdea"}"#).is_err());
+    }
+}
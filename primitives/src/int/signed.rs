@@ -1,10 +1,10 @@
 use std::{
     fmt::Display,
-    ops::{Add, Mul, Sub},
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
 };
 
-use hex::FromHexError;
-use num::{bigint::ToBigInt, BigInt, FromPrimitive, Signed, ToPrimitive};
+use hex::{FromHexError, ToHex};
+use num::{bigint::ToBigInt, BigInt, FromPrimitive, One, Signed, ToPrimitive, Zero};
 use serde::{de, Deserialize, Serialize};
 
 use crate::{BytesVisitor, FromEtherHex, ToEtherHex};
@@ -24,14 +24,28 @@ pub enum SignedError {
     FromHex(#[from] FromHexError),
 }
 
-/// `int<M>` type mapping
+/// ABI type names of `int<M>`, indexed by `M / 8 - 1`.
+const ABI_NAMES: [&str; 32] = [
+    "int8", "int16", "int24", "int32", "int40", "int48", "int56", "int64", "int72", "int80",
+    "int88", "int96", "int104", "int112", "int120", "int128", "int136", "int144", "int152",
+    "int160", "int168", "int176", "int184", "int192", "int200", "int208", "int216", "int224",
+    "int232", "int240", "int248", "int256",
+];
+
+/// `int<M>` type mapping, `BITS` is in `8..=256` in steps of 8.
+///
+/// Stored as 32 bytes two's complement. Arithmetic operators panic on overflow,
+/// use `checked_*` methods to handle overflow.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Int<const BITS: usize>(pub [u8; 32]);
 
 fn to_bytes32(value: BigInt, bits: usize) -> Result<[u8; 32], SignedError> {
-    if value.bits() as usize > bits {
+    // int<M> range is [-2^(M-1), 2^(M-1) - 1]
+    let bound = BigInt::one() << (bits - 1);
+
+    if value >= bound || value < -bound {
         return Err(SignedError::OutOfRange(format!(
-            "{} convert to uint<{}> failed",
+            "{} convert to int<{}> failed",
             value, bits
         )));
     }
@@ -50,17 +64,100 @@ fn to_bytes32(value: BigInt, bits: usize) -> Result<[u8; 32], SignedError> {
 }
 
 impl<const BITS: usize> Int<BITS> {
+    /// Byte length of `int<BITS>`, checks `BITS` at compile time.
+    const BYTES: usize = {
+        assert!(
+            BITS >= 8 && BITS <= 256 && BITS.is_multiple_of(8),
+            "int<M> requires 0 < M <= 256 and M % 8 == 0"
+        );
+
+        BITS / 8
+    };
+
     /// Create `Unit<BITS>` from [`ToBigInt`].
     /// Returns [`OutOfRange`](SignedError::OutOfRange) or [`ToBigUnit`](SignedError::ToBigUnit) if failed.
     pub fn new<N: ToBigInt + Signed>(value: N) -> Result<Self, SignedError> {
         if let Some(value) = value.to_bigint() {
-            to_bytes32(value, BITS).map(|c| Self(c))
+            to_bytes32(value, Self::BYTES * 8).map(Self)
         } else {
             Err(SignedError::ToBigUnit(
                 "convert input into BigUnit failed".to_owned(),
             ))
         }
     }
+
+    /// Returns ABI type name, e.g. `int256`.
+    pub fn abi_name() -> &'static str {
+        ABI_NAMES[Self::BYTES - 1]
+    }
+
+    /// Returns max value `2^(BITS-1) - 1`.
+    pub fn max_value() -> Self {
+        let mut buff = [0u8; 32];
+
+        buff[(32 - Self::BYTES)..].fill(0xff);
+        buff[32 - Self::BYTES] = 0x7f;
+
+        Self(buff)
+    }
+
+    /// Returns min value `-2^(BITS-1)`.
+    pub fn min_value() -> Self {
+        let mut buff = [0xffu8; 32];
+
+        buff[(32 - Self::BYTES)..].fill(0);
+        buff[32 - Self::BYTES] = 0x80;
+
+        Self(buff)
+    }
+
+    /// Returns true if value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 32]
+    }
+
+    /// Returns true if value is negative.
+    pub fn is_negative(&self) -> bool {
+        self.0[0] & 0x80 != 0
+    }
+
+    /// Checked addition, returns [`None`] if overflow occurred.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Self::new(BigInt::from(self) + BigInt::from(rhs)).ok()
+    }
+
+    /// Checked subtraction, returns [`None`] if overflow occurred.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Self::new(BigInt::from(self) - BigInt::from(rhs)).ok()
+    }
+
+    /// Checked multiplication, returns [`None`] if overflow occurred.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Self::new(BigInt::from(self) * BigInt::from(rhs)).ok()
+    }
+
+    /// Checked division rounding toward zero, returns [`None`] if `rhs` is zero or overflow occurred.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            return None;
+        }
+
+        Self::new(BigInt::from(self) / BigInt::from(rhs)).ok()
+    }
+
+    /// Checked remainder, returns [`None`] if `rhs` is zero.
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            return None;
+        }
+
+        Self::new(BigInt::from(self) % BigInt::from(rhs)).ok()
+    }
+
+    /// Checked negation, returns [`None`] if value is [`min_value`](Self::min_value).
+    pub fn checked_neg(self) -> Option<Self> {
+        Self::new(-BigInt::from(self)).ok()
+    }
 }
 
 impl<const BITS: usize> Display for Int<BITS> {
@@ -68,8 +165,13 @@ impl<const BITS: usize> Display for Int<BITS> {
         let lead_ones = self.0.iter().take_while(|c| **c == 0xff).count();
         let lead_zeros = self.0.iter().take_while(|c| **c == 0x00).count();
 
+        // Keep one leading `0xff` for negative values only, see `TryFrom<&str>`.
         if lead_ones > 0 {
             write!(f, "{}", (&self.0[(lead_ones - 1)..]).to_eth_value_hex())
+        } else if self.is_negative() {
+            write!(f, "0xff{}", (&self.0[..]).encode_hex::<String>())
+        } else if self.0.get(lead_zeros) == Some(&0xff) {
+            write!(f, "{}", (&self.0[(lead_zeros - 1)..]).to_eth_value_hex())
         } else {
             write!(f, "{}", (&self.0[lead_zeros..]).to_eth_value_hex())
         }
@@ -81,24 +183,14 @@ impl<const BITS: usize> TryFrom<&str> for Int<BITS> {
     fn try_from(v: &str) -> Result<Self, Self::Error> {
         let value = Vec::<u8>::from_eth_hex(v)?;
 
-        let lead_ones = value.iter().take_while(|c| **c == 0xff).count();
-
-        if value.len() - lead_ones + 1 > BITS / 8 {
-            return Err(SignedError::OutOfRange(format!(
-                "{} convert to int<{}> failed",
-                v, BITS
-            )));
-        }
-
-        let mut buff = if lead_ones > 0 {
-            [0xffu8; 32]
+        // Negative values are displayed with leading `0xff`, others are treated as positive.
+        let value = if value.first() == Some(&0xff) {
+            BigInt::from_signed_bytes_be(&value)
         } else {
-            [0x0u8; 32]
+            BigInt::from_bytes_be(num::bigint::Sign::Plus, &value)
         };
 
-        buff[(32 - (value.len() - lead_ones))..].copy_from_slice(&value[lead_ones..]);
-
-        Ok(Int(buff))
+        to_bytes32(value, BITS).map(Int)
     }
 }
 
@@ -145,7 +237,7 @@ impl<const BITS: usize> Mul for Int<BITS> {
     type Output = Int<BITS>;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        Self::new(BigInt::from(self) + BigInt::from(rhs)).unwrap()
+        Self::new(BigInt::from(self) * BigInt::from(rhs)).unwrap()
     }
 }
 
@@ -160,6 +252,49 @@ where
     }
 }
 
+impl<const BITS: usize> Div for Int<BITS> {
+    type Output = Int<BITS>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        assert!(!rhs.is_zero(), "Divide by zero");
+
+        Self::new(BigInt::from(self) / BigInt::from(rhs)).unwrap()
+    }
+}
+
+impl<const BITS: usize, N> Div<N> for Int<BITS>
+where
+    N: ToBigInt,
+{
+    type Output = Int<BITS>;
+
+    fn div(self, rhs: N) -> Self::Output {
+        let rhs = rhs.to_bigint().unwrap();
+
+        assert!(!rhs.is_zero(), "Divide by zero");
+
+        Self::new(BigInt::from(self) / rhs).unwrap()
+    }
+}
+
+impl<const BITS: usize> Rem for Int<BITS> {
+    type Output = Int<BITS>;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        // divide by zero will panic
+        self.checked_rem(rhs).expect("Divide by zero")
+    }
+}
+
+impl<const BITS: usize> Neg for Int<BITS> {
+    type Output = Int<BITS>;
+
+    fn neg(self) -> Self::Output {
+        // negate min value will panic
+        self.checked_neg().expect("Overflow")
+    }
+}
+
 impl<const BITS: usize> Add for Int<BITS> {
     type Output = Int<BITS>;
 
@@ -188,11 +323,7 @@ impl<const BITS: usize> Serialize for Int<BITS> {
             serializer.serialize_str(&self.to_string())
         } else {
             // for rlp/eip712/abi serializers
-            let name = format!("int{}", BITS);
-
-            let static_name = unsafe { &*(&name as *const String) };
-
-            serializer.serialize_newtype_struct(&static_name, &self.0)
+            serializer.serialize_newtype_struct(Self::abi_name(), &self.0)
         }
     }
 }
@@ -242,6 +373,22 @@ impl<'de, const BITS: usize> de::Visitor<'de> for IntVisitor<BITS> {
 
         Ok(Int(value))
     }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_i128(v as i128)
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        to_bytes32(BigInt::from(v), BITS)
+            .map(Int)
+            .map_err(de::Error::custom)
+    }
 }
 
 impl<'de, const BITS: usize> Deserialize<'de> for Int<BITS> {
@@ -253,33 +400,19 @@ impl<'de, const BITS: usize> Deserialize<'de> for Int<BITS> {
             deserializer.deserialize_any(IntVisitor)
         } else {
             // for rlp/eip712/abi serializers
-            let name = format!("int{}", BITS);
-
-            let static_name = unsafe { &*(&name as *const String) };
-
             let value = deserializer
-                .deserialize_newtype_struct(static_name.as_str(), BytesVisitor::default())?;
+                .deserialize_newtype_struct(Self::abi_name(), BytesVisitor::default())?;
 
-            let lead_ones = value.iter().take_while(|c| **c == 0xff).count();
-
-            if value.len() - lead_ones + 1 > BITS / 8 {
-                return Err(SignedError::OutOfRange(format!(
-                    "{} convert to uint<{}> failed",
+            if value.len() > 32 {
+                return Err(de::Error::custom(SignedError::OutOfRange(
                     value.to_eth_hex(),
-                    BITS
-                )))
-                .map_err(de::Error::custom);
+                )));
             }
 
-            let mut buff = if lead_ones > 0 {
-                [0xffu8; 32]
-            } else {
-                [0x0u8; 32]
-            };
-
-            buff[(32 - (value.len() - lead_ones))..].copy_from_slice(&value[lead_ones..]);
+            // Two's complement, e.g. abi word is sign extended to 32 bytes.
+            let value = BigInt::from_signed_bytes_be(&value);
 
-            Ok(Int(buff))
+            to_bytes32(value, BITS).map(Int).map_err(de::Error::custom)
         }
     }
 }
@@ -329,7 +462,29 @@ macro_rules! convert_builtin_signed {
 convert_builtin_signed!(Int, isize, i128, i64, i32, i16, i8);
 
 pub type I256 = Int<256>;
+pub type I128 = Int<128>;
 pub type I64 = Int<64>;
+pub type I32 = Int<32>;
+pub type I16 = Int<16>;
+pub type I8 = Int<8>;
+
+#[cfg(feature = "arbitrary")]
+impl<'a, const BITS: usize> arbitrary::Arbitrary<'a> for Int<BITS> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let start = 32 - BITS / 8;
+
+        let mut buff = [0u8; 32];
+
+        u.fill_buffer(&mut buff[start..])?;
+
+        // Sign extend to 32 bytes.
+        if buff[start] & 0x80 != 0 {
+            buff[..start].fill(0xff);
+        }
+
+        Ok(Self(buff))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -363,6 +518,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_checked_arith() {
+        assert_eq!(Int::<8>::max_value(), Int::<8>::from(127i8));
+        assert_eq!(Int::<8>::min_value(), Int::<8>::from(-128i8));
+        assert_eq!(Int::<24>::abi_name(), "int24");
+
+        assert!(Int::<8>::new(128i16).is_err());
+        assert!(Int::<8>::new(-129i16).is_err());
+
+        let max = Int::<8>::max_value();
+        let min = Int::<8>::min_value();
+        let one = Int::<8>::from(1i8);
+
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(min.checked_sub(one), None);
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(min.checked_div(Int::from(-1i8)), None);
+        assert_eq!(max.checked_div(Int::from(0i8)), None);
+
+        assert_eq!(Int::<8>::from(-3i8) * Int::from(4i8), Int::<8>::from(-12i8));
+        assert_eq!(Int::<8>::from(-7i8) / Int::from(2i8), Int::from(-3i8));
+        assert_eq!(Int::<8>::from(-7i8) % Int::from(2i8), Int::from(-1i8));
+        assert_eq!(-max, Int::<8>::from(-127i8));
+
+        assert!(min.is_negative());
+        assert!(!max.is_negative());
+    }
+
     #[test]
     fn test_rlp() {
         _ = pretty_env_logger::try_init();
@@ -399,5 +582,74 @@ mod tests {
             I256::from(-1isize),
             "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
         );
+
+        check(
+            I256::from(69isize),
+            "0x0000000000000000000000000000000000000000000000000000000000000045",
+        );
+
+        check(
+            Int::<8>::min_value(),
+            "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff80",
+        );
+
+        check(
+            Int::<8>::max_value(),
+            "0x000000000000000000000000000000000000000000000000000000000000007f",
+        );
+
+        check(
+            Int::<136>::min_value(),
+            "0xffffffffffffffffffffffffffffff8000000000000000000000000000000000",
+        );
+
+        check(
+            I256::min_value(),
+            "0x8000000000000000000000000000000000000000000000000000000000000000",
+        );
+
+        check(
+            I256::max_value(),
+            "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        );
+
+        // Words not sign extended from int<M> are rejected.
+        let buff = Vec::<u8>::from_eth_hex(
+            "0x0000000000000000000000000000000000000000000000000000000000000080",
+        )
+        .unwrap();
+
+        assert!(from_abi::<Int<8>, _>(buff).is_err());
+
+        let buff = Vec::<u8>::from_eth_hex(
+            "0x00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff80",
+        )
+        .unwrap();
+
+        assert!(from_abi::<Int<8>, _>(buff).is_err());
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(Int::<8>::try_from("0x7f").unwrap(), Int::<8>::max_value());
+        assert_eq!(Int::<8>::try_from("0xff80").unwrap(), Int::<8>::min_value());
+        assert!(Int::<8>::try_from("0x80").is_err());
+
+        fn check<const BITS: usize>(value: Int<BITS>) {
+            assert_eq!(value.to_string().as_str().try_into().ok(), Some(value));
+        }
+
+        check(I256::min_value());
+        check(I256::max_value());
+        check(I256::from(-1isize));
+        check(Int::<16>::from(255isize));
+        check(Int::<16>::from(-129isize));
+
+        assert_eq!(
+            serde_json::from_str::<Int<8>>("-128").unwrap(),
+            Int::<8>::min_value()
+        );
+
+        assert!(serde_json::from_str::<Int<8>>("-129").is_err());
     }
 }
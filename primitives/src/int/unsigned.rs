@@ -1,10 +1,10 @@
 use std::{
     fmt::Display,
-    ops::{Add, Mul, Sub},
+    ops::{Add, Div, Mul, Rem, Sub},
 };
 
 use hex::FromHexError;
-use num::{bigint::ToBigUint, BigUint, FromPrimitive, ToPrimitive, Unsigned};
+use num::{bigint::ToBigUint, BigUint, FromPrimitive, ToPrimitive, Unsigned, Zero};
 use serde::{de, Deserialize, Serialize};
 
 use crate::{BytesVisitor, FromEtherHex, ToEtherHex};
@@ -24,7 +24,17 @@ pub enum UintError {
     FromHex(#[from] FromHexError),
 }
 
-/// `uint<M>` type mapping
+/// ABI type names of `uint<M>`, indexed by `M / 8 - 1`.
+const ABI_NAMES: [&str; 32] = [
+    "uint8", "uint16", "uint24", "uint32", "uint40", "uint48", "uint56", "uint64", "uint72",
+    "uint80", "uint88", "uint96", "uint104", "uint112", "uint120", "uint128", "uint136", "uint144",
+    "uint152", "uint160", "uint168", "uint176", "uint184", "uint192", "uint200", "uint208",
+    "uint216", "uint224", "uint232", "uint240", "uint248", "uint256",
+];
+
+/// `uint<M>` type mapping, `BITS` is in `8..=256` in steps of 8.
+///
+/// Arithmetic operators panic on overflow, use `checked_*` methods to handle overflow.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Default)]
 pub struct Uint<const BITS: usize>(pub [u8; 32]);
 
@@ -46,17 +56,83 @@ fn to_bytes32(value: BigUint, bits: usize) -> Result<[u8; 32], UintError> {
 }
 
 impl<const BITS: usize> Uint<BITS> {
+    /// Byte length of `uint<BITS>`, checks `BITS` at compile time.
+    const BYTES: usize = {
+        assert!(
+            BITS >= 8 && BITS <= 256 && BITS.is_multiple_of(8),
+            "uint<M> requires 0 < M <= 256 and M % 8 == 0"
+        );
+
+        BITS / 8
+    };
+
     /// Create `Unit<BITS>` from [`ToBigUint`].
     /// Returns [`OutOfRange`](UintError::OutOfRange) or [`ToBigUnit`](UintError::ToBigUnit) if failed.
     pub fn new<N: ToBigUint + Unsigned>(value: N) -> Result<Self, UintError> {
         if let Some(value) = value.to_biguint() {
-            to_bytes32(value, BITS).map(|c| Self(c))
+            to_bytes32(value, Self::BYTES * 8).map(Self)
         } else {
             Err(UintError::ToBigUnit(
                 "convert input into BigUnit failed".to_owned(),
             ))
         }
     }
+
+    /// Returns ABI type name, e.g. `uint256`.
+    pub fn abi_name() -> &'static str {
+        ABI_NAMES[Self::BYTES - 1]
+    }
+
+    /// Returns max value `2^BITS - 1`.
+    pub fn max_value() -> Self {
+        let mut buff = [0u8; 32];
+
+        buff[(32 - Self::BYTES)..].fill(0xff);
+
+        Self(buff)
+    }
+
+    /// Returns true if value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 32]
+    }
+
+    /// Checked addition, returns [`None`] if overflow occurred.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Self::new(BigUint::from(self) + BigUint::from(rhs)).ok()
+    }
+
+    /// Checked subtraction, returns [`None`] if underflow occurred.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        if self < rhs {
+            return None;
+        }
+
+        Self::new(BigUint::from(self) - BigUint::from(rhs)).ok()
+    }
+
+    /// Checked multiplication, returns [`None`] if overflow occurred.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Self::new(BigUint::from(self) * BigUint::from(rhs)).ok()
+    }
+
+    /// Checked division, returns [`None`] if `rhs` is zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            return None;
+        }
+
+        Self::new(BigUint::from(self) / BigUint::from(rhs)).ok()
+    }
+
+    /// Checked remainder, returns [`None`] if `rhs` is zero.
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            return None;
+        }
+
+        Self::new(BigUint::from(self) % BigUint::from(rhs)).ok()
+    }
 }
 
 impl<const BITS: usize> Display for Uint<BITS> {
@@ -138,6 +214,39 @@ where
     }
 }
 
+impl<const BITS: usize> Div for Uint<BITS> {
+    type Output = Uint<BITS>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        // divide by zero will panic
+        self.checked_div(rhs).expect("Divide by zero")
+    }
+}
+
+impl<const BITS: usize, N> Div<N> for Uint<BITS>
+where
+    N: ToBigUint,
+{
+    type Output = Uint<BITS>;
+
+    fn div(self, rhs: N) -> Self::Output {
+        let rhs = rhs.to_biguint().unwrap();
+
+        assert!(!rhs.is_zero(), "Divide by zero");
+
+        Self::new(BigUint::from(self) / rhs).unwrap()
+    }
+}
+
+impl<const BITS: usize> Rem for Uint<BITS> {
+    type Output = Uint<BITS>;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        // divide by zero will panic
+        self.checked_rem(rhs).expect("Divide by zero")
+    }
+}
+
 impl<const BITS: usize> Add for Uint<BITS> {
     type Output = Uint<BITS>;
 
@@ -166,11 +275,7 @@ impl<const BITS: usize> Serialize for Uint<BITS> {
             serializer.serialize_str(&self.to_string())
         } else {
             // for rlp/eip712/abi serializers
-            let name = format!("uint{}", BITS);
-
-            let static_name = unsafe { &*(&name as *const String) };
-
-            serializer.serialize_newtype_struct(&static_name, &self.0)
+            serializer.serialize_newtype_struct(Self::abi_name(), &self.0)
         }
     }
 }
@@ -231,12 +336,8 @@ impl<'de, const BITS: usize> Deserialize<'de> for Uint<BITS> {
             deserializer.deserialize_any(UintVisitor)
         } else {
             // for rlp/eip712/abi serializers
-            let name = format!("uint{}", BITS);
-
-            let static_name = unsafe { &*(&name as *const String) };
-
             let buff = deserializer
-                .deserialize_newtype_struct(static_name.as_str(), BytesVisitor::default())?;
+                .deserialize_newtype_struct(Self::abi_name(), BytesVisitor::default())?;
 
             if buff.len() > 32 {
                 return Err(UintError::OutOfRange(buff.to_eth_hex()))
@@ -252,7 +353,22 @@ impl<'de, const BITS: usize> Deserialize<'de> for Uint<BITS> {
 }
 
 pub type U256 = Uint<256>;
+pub type U128 = Uint<128>;
 pub type U64 = Uint<64>;
+pub type U32 = Uint<32>;
+pub type U16 = Uint<16>;
+pub type U8 = Uint<8>;
+
+#[cfg(feature = "arbitrary")]
+impl<'a, const BITS: usize> arbitrary::Arbitrary<'a> for Uint<BITS> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut buff = [0u8; 32];
+
+        u.fill_buffer(&mut buff[(32 - BITS / 8)..])?;
+
+        Ok(Self(buff))
+    }
+}
 
 /// impl from builin num
 macro_rules! convert_builtin_unsigned {
@@ -301,7 +417,7 @@ convert_builtin_unsigned!(Uint, usize, u128, u64, u32, u16, u8);
 #[cfg(test)]
 mod tests {
     use serde_ethabi::{from_abi, to_abi};
-    use serde_ethrlp::rlp_encode;
+    use serde_ethrlp::{rlp_decode, rlp_encode};
     use serde_json::json;
 
     use crate::ToEtherHex;
@@ -318,6 +434,29 @@ mod tests {
         assert_eq!((rhs - lhs), Uint::<8>::new(3u8).unwrap());
     }
 
+    #[test]
+    fn test_checked_arith() {
+        assert_eq!(Uint::<8>::max_value(), Uint::<8>::from(255u8));
+        assert_eq!(Uint::<24>::abi_name(), "uint24");
+        assert_eq!(U256::abi_name(), "uint256");
+
+        let max = Uint::<8>::max_value();
+        let one = Uint::<8>::from(1u8);
+
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(one.checked_sub(max), None);
+        assert_eq!(max.checked_mul(Uint::from(2u8)), None);
+        assert_eq!(max.checked_div(Uint::from(0u8)), None);
+
+        assert_eq!(max.checked_sub(one), Some(Uint::from(254u8)));
+        assert_eq!(max.checked_div(Uint::from(2u8)), Some(Uint::from(127u8)));
+        assert_eq!(max % Uint::from(2u8), one);
+        assert_eq!(U256::from(100u8) / 7u8, U256::from(14u8));
+
+        assert!(Uint::<8>::try_from("0x100").is_err());
+        assert!(Uint::<24>::try_from("0xffffff").is_ok());
+    }
+
     #[test]
     fn test_rlp() {
         _ = pretty_env_logger::try_init();
@@ -334,6 +473,17 @@ mod tests {
 
         assert_eq!(rlp_encode(&U256::from(0usize)).unwrap(), [0x80]);
         assert_eq!(rlp_encode(&U256::from(15usize)).unwrap(), [0x0f]);
+
+        assert_eq!(rlp_decode::<U256>(&[0x80]).unwrap(), U256::from(0usize));
+        assert_eq!(rlp_decode::<U256>(&[0x0f]).unwrap(), U256::from(15usize));
+        assert_eq!(
+            rlp_decode::<U256>(&rlp_encode(&U256::from(100000usize)).unwrap()).unwrap(),
+            U256::from(100000usize)
+        );
+
+        // Leading zeros are rejected in strict mode.
+        assert!(rlp_decode::<U256>(&[0x82, 0x00, 0x0f]).is_err());
+        assert!(rlp_decode::<Uint<8>>(&[0x82, 0x01, 0x00]).is_err());
     }
 
     #[test]
@@ -22,3 +22,17 @@ pub use fixed::*;
 
 mod unit;
 pub use unit::*;
+
+mod topic;
+pub use topic::*;
+
+mod bloom;
+pub use bloom::*;
+
+mod hash;
+pub use hash::*;
+
+mod abi_type;
+pub use abi_type::*;
+
+pub use subtle::{Choice, ConstantTimeEq};
@@ -1,13 +1,13 @@
 use std::{fmt::Display, str::FromStr};
 
 #[cfg(feature = "rust_crypto")]
-use k256::ecdsa::{RecoveryId, Signature};
+use k256::ecdsa::{RecoveryId, Signature as EcdsaSignature};
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 
 use crate::{FromEtherHex, ToEtherHex};
 
-use super::U256;
+use super::{Uint, U256};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Eip1559SigError {
@@ -19,7 +19,7 @@ pub enum Eip1559SigError {
 
 /// Ethereum signature structure.
 ///
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct Eip1559Signature {
     pub v: u8,
     pub r: U256,
@@ -39,10 +39,10 @@ impl Display for Eip1559Signature {
     }
 }
 
-/// Convert tuple ([`Signature`], [`RecoveryId`]) to [`Eip1559Signature`]
+/// Convert tuple ([`EcdsaSignature`], [`RecoveryId`]) to [`Eip1559Signature`]
 #[cfg(feature = "rust_crypto")]
-impl From<(Signature, RecoveryId)> for Eip1559Signature {
-    fn from(value: (Signature, RecoveryId)) -> Self {
+impl From<(EcdsaSignature, RecoveryId)> for Eip1559Signature {
+    fn from(value: (EcdsaSignature, RecoveryId)) -> Self {
         Self {
             v: value.1.to_byte(),
             r: U256::new(BigUint::from_bytes_be(&value.0.r().to_bytes())).unwrap(),
@@ -52,9 +52,9 @@ impl From<(Signature, RecoveryId)> for Eip1559Signature {
 }
 
 #[cfg(feature = "rust_crypto")]
-impl TryFrom<Eip1559Signature> for (Signature, RecoveryId) {
+impl TryFrom<Eip1559Signature> for (EcdsaSignature, RecoveryId) {
     type Error = Eip1559SigError;
-    fn try_from(sig: Eip1559Signature) -> Result<(Signature, RecoveryId), Self::Error> {
+    fn try_from(sig: Eip1559Signature) -> Result<(EcdsaSignature, RecoveryId), Self::Error> {
         let mut buff = [0u8; 64];
 
         buff[..32].copy_from_slice(&sig.r.0);
@@ -62,7 +62,7 @@ impl TryFrom<Eip1559Signature> for (Signature, RecoveryId) {
 
         let recover_id = sig.v;
 
-        let sig = Signature::try_from(buff.as_slice())?;
+        let sig = EcdsaSignature::try_from(buff.as_slice())?;
 
         let recover_id = RecoveryId::from_byte(recover_id)
             .ok_or(Eip1559SigError::InvalidRecoveryId(recover_id))?;
@@ -87,3 +87,325 @@ impl FromStr for Eip1559Signature {
         })
     }
 }
+
+/// secp256k1 curve order n
+const SECP256K1_N: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// secp256k1 curve order n / 2
+const SECP256K1_HALF_N: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureError {
+    #[error("Invalid signature length {0}, expect 64 or 65")]
+    Length(usize),
+    #[error("Invalid signature v {0}")]
+    InvalidV(u64),
+    #[error("Signature {0} out of range, expect 0 < {0} < n")]
+    OutOfRange(&'static str),
+    #[error("{0}")]
+    FromHexError(#[from] hex::FromHexError),
+}
+
+/// Canonical ethereum ECDSA signature.
+///
+/// The 65 bytes format is `r || s || v`, where `v` is `27/28`.
+/// [`Eip1559Signature`] converts from/to this type without loss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Signature {
+    r: U256,
+    s: U256,
+    y_parity: bool,
+}
+
+/// Check `0 < value < n`.
+fn check_scalar(name: &'static str, value: &U256) -> Result<(), SignatureError> {
+    if value.0 == [0u8; 32] || value.0 >= SECP256K1_N {
+        return Err(SignatureError::OutOfRange(name));
+    }
+
+    Ok(())
+}
+
+impl Signature {
+    /// Create signature, returns [`SignatureError::OutOfRange`] unless `0 < r, s < n`.
+    pub fn new(r: U256, s: U256, y_parity: bool) -> Result<Self, SignatureError> {
+        check_scalar("r", &r)?;
+        check_scalar("s", &s)?;
+
+        Ok(Self { r, s, y_parity })
+    }
+
+    /// Create signature from `v` of any supported encoding: `0/1`, `27/28` or EIP-155 `chain_id * 2 + 35/36`.
+    pub fn from_v(r: U256, s: U256, v: u64) -> Result<Self, SignatureError> {
+        let y_parity = match v {
+            0 | 27 => false,
+            1 | 28 => true,
+            v if v >= 35 => (v - 35) % 2 == 1,
+            _ => return Err(SignatureError::InvalidV(v)),
+        };
+
+        Self::new(r, s, y_parity)
+    }
+
+    pub fn r(&self) -> U256 {
+        self.r
+    }
+
+    pub fn s(&self) -> U256 {
+        self.s
+    }
+
+    /// Returns the parity of the y-value of the signature point.
+    pub fn y_parity(&self) -> bool {
+        self.y_parity
+    }
+
+    /// Returns recovery id `0/1`.
+    pub fn recovery_id(&self) -> u8 {
+        self.y_parity as u8
+    }
+
+    /// Returns pre EIP-155 `v`, aka `27/28`.
+    pub fn v(&self) -> u8 {
+        27 + self.recovery_id()
+    }
+
+    /// Returns EIP-155 `v`, aka `chain_id * 2 + 35 + recovery_id`, or none if it overflows.
+    pub fn eip155_v(&self, chain_id: u64) -> Option<u64> {
+        chain_id
+            .checked_mul(2)?
+            .checked_add(35 + self.recovery_id() as u64)
+    }
+
+    /// Returns chain id encoded in EIP-155 `v`, or none for `0/1/27/28`.
+    pub fn eip155_chain_id(v: u64) -> Option<u64> {
+        if v >= 35 {
+            Some((v - 35) / 2)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if `s` is in the lower half of the curve order, as required by EIP-2.
+    pub fn is_low_s(&self) -> bool {
+        self.s.0 <= SECP256K1_HALF_N
+    }
+
+    /// Returns the equivalent signature with low `s`, aka `n - s` and flipped y parity if `s` is high.
+    pub fn normalize_s(&self) -> Self {
+        if self.is_low_s() {
+            return *self;
+        }
+
+        let s = BigUint::from_bytes_be(&SECP256K1_N) - BigUint::from(self.s);
+
+        Self {
+            r: self.r,
+            s: U256::new(s).unwrap(),
+            y_parity: !self.y_parity,
+        }
+    }
+
+    /// Returns 65 bytes format `r || s || v`.
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut buff = [0u8; 65];
+
+        buff[..32].copy_from_slice(&self.r.0);
+        buff[32..64].copy_from_slice(&self.s.0);
+        buff[64] = self.v();
+
+        buff
+    }
+
+    /// Returns EIP-2098 compact format `r || y_parity << 255 | s`.
+    pub fn to_compact(&self) -> [u8; 64] {
+        let mut buff = [0u8; 64];
+
+        buff[..32].copy_from_slice(&self.r.0);
+        buff[32..].copy_from_slice(&self.s.0);
+
+        if self.y_parity {
+            buff[32] |= 0x80;
+        }
+
+        buff
+    }
+
+    /// Create signature from EIP-2098 compact format.
+    pub fn from_compact(buff: &[u8; 64]) -> Result<Self, SignatureError> {
+        let mut s = [0u8; 32];
+
+        s.copy_from_slice(&buff[32..]);
+
+        let y_parity = s[0] & 0x80 != 0;
+
+        s[0] &= 0x7f;
+
+        Self::new(Uint(buff[..32].try_into().unwrap()), Uint(s), y_parity)
+    }
+}
+
+/// Parse 65 bytes format(`v` is `0/1/27/28`) or EIP-2098 compact format.
+impl TryFrom<&[u8]> for Signature {
+    type Error = SignatureError;
+
+    fn try_from(buff: &[u8]) -> Result<Self, Self::Error> {
+        match buff.len() {
+            64 => Self::from_compact(buff.try_into().unwrap()),
+            65 => Self::from_v(
+                Uint(buff[..32].try_into().unwrap()),
+                Uint(buff[32..64].try_into().unwrap()),
+                buff[64] as u64,
+            ),
+            len => Err(SignatureError::Length(len)),
+        }
+    }
+}
+
+impl FromStr for Signature {
+    type Err = SignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Vec::<u8>::from_eth_hex(s)?.as_slice().try_into()
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_bytes().to_eth_hex())
+    }
+}
+
+impl Serialize for Signature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = String::deserialize(deserializer)?;
+
+        data.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl TryFrom<Eip1559Signature> for Signature {
+    type Error = SignatureError;
+
+    fn try_from(value: Eip1559Signature) -> Result<Self, Self::Error> {
+        Self::new(value.r, value.s, value.v % 2 == 1)
+    }
+}
+
+impl From<Signature> for Eip1559Signature {
+    fn from(value: Signature) -> Self {
+        Self {
+            v: value.recovery_id(),
+            r: value.r,
+            s: value.s,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature() {
+        let sig: Signature = "0x6c7e1e13070e6f10e51d7d20e986c59fd080fc6afc5508f44e8b0a84a58b7d1a13c20fa2b6d77ae6814a41b674946387dde6401c73eb0cab2246a2981c48e3441c"
+            .parse()
+            .unwrap();
+
+        assert!(sig.y_parity());
+        assert_eq!(sig.v(), 28);
+        assert_eq!(sig.eip155_v(1), Some(38));
+        assert_eq!(sig.eip155_v(u64::MAX / 2), None);
+        assert_eq!(Signature::eip155_chain_id(38), Some(1));
+        assert_eq!(Signature::from_v(sig.r(), sig.s(), 38).unwrap(), sig);
+
+        assert_eq!(Signature::from_compact(&sig.to_compact()).unwrap(), sig);
+
+        let eip1559: Eip1559Signature = sig.into();
+
+        assert_eq!(eip1559.v, 1);
+        assert_eq!(Signature::try_from(eip1559).unwrap(), sig);
+
+        assert_eq!(
+            serde_json::from_str::<Signature>(&serde_json::to_string(&sig).unwrap()).unwrap(),
+            sig
+        );
+    }
+
+    #[test]
+    fn test_normalize_s() {
+        let low = Signature::new(U256::new(1u8).unwrap(), U256::new(1u8).unwrap(), false).unwrap();
+
+        assert!(low.is_low_s());
+
+        let high = Signature::new(
+            low.r(),
+            U256::new(BigUint::from_bytes_be(&SECP256K1_N) - 1u8).unwrap(),
+            true,
+        )
+        .unwrap();
+
+        assert!(!high.is_low_s());
+        assert_eq!(high.normalize_s(), low);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let one = U256::new(1u8).unwrap();
+        let n = Uint(SECP256K1_N);
+
+        assert!(matches!(
+            Signature::new(one, n, false),
+            Err(SignatureError::OutOfRange("s"))
+        ));
+        assert!(matches!(
+            Signature::new(U256::default(), one, false),
+            Err(SignatureError::OutOfRange("r"))
+        ));
+
+        // s = 0xff..ff > n from wire input is rejected instead of panicking in `normalize_s`.
+        let mut buff = [0xffu8; 65];
+
+        buff[64] = 27;
+
+        assert!(matches!(
+            Signature::try_from(buff.as_slice()),
+            Err(SignatureError::OutOfRange("r"))
+        ));
+
+        buff[..32].copy_from_slice(&one.0);
+
+        assert!(matches!(
+            Signature::try_from(buff.as_slice()),
+            Err(SignatureError::OutOfRange("s"))
+        ));
+
+        let mut compact = [0u8; 64];
+
+        compact[..32].copy_from_slice(&SECP256K1_N);
+        compact[63] = 1;
+
+        assert!(matches!(
+            Signature::from_compact(&compact),
+            Err(SignatureError::OutOfRange("r"))
+        ));
+    }
+}
@@ -0,0 +1,121 @@
+//! Event signature hashing and indexed parameter topic encoding
+
+use crate::{keccak256, Address, Bytes, BytesM, Int, Uint, H256};
+
+/// Returns event topic0, aka keccak256 hash of the event signature, e.g. `Transfer(address,address,uint256)`.
+///
+/// Whitespaces of `signature` are ignored.
+pub fn event_signature(signature: &str) -> H256 {
+    let signature = signature
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+
+    keccak256(signature).into()
+}
+
+/// Encode indexed event parameter as log topic.
+pub trait ToTopic {
+    fn to_topic(&self) -> H256;
+}
+
+impl ToTopic for Address {
+    fn to_topic(&self) -> H256 {
+        let mut buff = [0u8; 32];
+
+        buff[12..].copy_from_slice(&self.0);
+
+        buff.into()
+    }
+}
+
+impl<const BITS: usize> ToTopic for Uint<BITS> {
+    fn to_topic(&self) -> H256 {
+        self.0.into()
+    }
+}
+
+impl<const BITS: usize> ToTopic for Int<BITS> {
+    fn to_topic(&self) -> H256 {
+        self.0.into()
+    }
+}
+
+impl<const LEN: usize> ToTopic for BytesM<LEN> {
+    fn to_topic(&self) -> H256 {
+        self.0.into()
+    }
+}
+
+impl ToTopic for bool {
+    fn to_topic(&self) -> H256 {
+        let mut buff = [0u8; 32];
+
+        buff[31] = *self as u8;
+
+        buff.into()
+    }
+}
+
+/// Indexed `bytes` parameter topic is the keccak256 hash of the value.
+impl ToTopic for Bytes {
+    fn to_topic(&self) -> H256 {
+        keccak256(&self.0).into()
+    }
+}
+
+/// Indexed `string` parameter topic is the keccak256 hash of the utf8 value.
+impl ToTopic for str {
+    fn to_topic(&self) -> H256 {
+        keccak256(self).into()
+    }
+}
+
+impl<T: ToTopic + ?Sized> ToTopic for &T {
+    fn to_topic(&self) -> H256 {
+        (*self).to_topic()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::U256;
+
+    #[test]
+    fn test_event_signature() {
+        assert_eq!(
+            event_signature("Transfer(address, address, uint256)").to_string(),
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+    }
+
+    #[test]
+    fn test_to_topic() {
+        let address: Address = "0xa0Ee7A142d267C1f36714E4a8F75612F20a79720"
+            .try_into()
+            .unwrap();
+
+        assert_eq!(
+            address.to_topic().to_string(),
+            "0x000000000000000000000000a0ee7a142d267c1f36714e4a8f75612f20a79720"
+        );
+
+        let value: U256 = "0x64".try_into().unwrap();
+
+        assert_eq!(
+            value.to_topic().to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000064"
+        );
+
+        assert_eq!(
+            true.to_topic().to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000001"
+        );
+
+        assert_eq!(
+            "hello".to_topic().to_string(),
+            "0x1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8"
+        );
+    }
+}
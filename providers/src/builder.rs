@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use ethers_core::{Address, InputField};
+use jsonrpc_rs::{map_error, RPCResult};
+
+use crate::{
+    impls::http::{self, HttpOptions},
+    Provider, ResponseCache,
+};
+
+/// Default max in-flight requests of one http provider.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 32;
+
+/// Builder to create [`Provider`] with custom transport options.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use ethers_provider::ProviderBuilder;
+/// let provider = ProviderBuilder::new()
+///     .max_concurrent_requests(8)
+///     .request_timeout(Duration::from_secs(10))
+///     .connect_http("http://localhost:8545")
+///     .expect("Create http provider");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProviderBuilder {
+    max_concurrent_requests: Option<usize>,
+    request_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    cache_size: Option<usize>,
+    cache_ttl: Option<Duration>,
+    input_field: InputField,
+    ens: Option<Address>,
+}
+
+impl Default for ProviderBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProviderBuilder {
+    /// Create builder with at most [`DEFAULT_MAX_CONCURRENT_REQUESTS`] in-flight requests and no request timeout.
+    pub fn new() -> Self {
+        Self {
+            max_concurrent_requests: Some(DEFAULT_MAX_CONCURRENT_REQUESTS),
+            request_timeout: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            cache_size: None,
+            cache_ttl: None,
+            input_field: InputField::default(),
+            ens: None,
+        }
+    }
+
+    /// Set max in-flight requests, extra requests are queued until one of in-flight requests completes.
+    ///
+    /// `0` means unlimited.
+    pub fn max_concurrent_requests(mut self, limit: usize) -> Self {
+        self.max_concurrent_requests = if limit == 0 { None } else { Some(limit) };
+        self
+    }
+
+    /// Set timeout of one request, including reading response body.
+    ///
+    /// Timed out requests returns error, the connection is dropped.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Set max idle connections kept alive per host for reusing, `0` disables connection reusing.
+    ///
+    /// Ignored on `wasm32` targets, connections are managed by browser.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set how long an idle connection is kept alive in the pool.
+    ///
+    /// Ignored on `wasm32` targets, connections are managed by browser.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Enable [`ResponseCache`] holding at most `size` immutable query responses.
+    pub fn cache(mut self, size: usize) -> Self {
+        self.cache_size = Some(size);
+        self
+    }
+
+    /// Set time to live of cached responses, only takes effect if [`cache`](Self::cache) is enabled.
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Set json field name of call data in sent transaction requests, e.g. [`InputField::Input`]
+    /// for nodes rejecting `data`.
+    pub fn input_field(mut self, field: InputField) -> Self {
+        self.input_field = field;
+        self
+    }
+
+    /// Set ENS registry address to resolve names passed as [`NameOrAddress`](crate::NameOrAddress),
+    /// e.g. [`ENS_REGISTRY_ADDRESS`](crate::ENS_REGISTRY_ADDRESS).
+    pub fn ens(mut self, registry: Address) -> Self {
+        self.ens = Some(registry);
+        self
+    }
+
+    /// Use http/https protocol connect to ethereum node, see [`http::connect_to`].
+    pub fn connect_http<S>(&self, url: S) -> RPCResult<Provider>
+    where
+        S: AsRef<str> + Clone + Send + 'static + Sync,
+    {
+        #[allow(unused_mut)]
+        let mut builder = reqwest::Client::builder();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(max) = self.pool_max_idle_per_host {
+                builder = builder.pool_max_idle_per_host(max);
+            }
+
+            if let Some(timeout) = self.pool_idle_timeout {
+                builder = builder.pool_idle_timeout(timeout);
+            }
+        }
+
+        let client = builder.build().map_err(map_error)?;
+
+        let options = HttpOptions {
+            max_concurrent_requests: self.max_concurrent_requests,
+            request_timeout: self.request_timeout,
+        };
+
+        let mut provider = self
+            .with_cache(http::connect_with(url, client, &options))
+            .with_input_field(self.input_field);
+
+        if let Some(registry) = self.ens {
+            provider = provider.with_ens(registry);
+        }
+
+        Ok(provider)
+    }
+
+    fn with_cache(&self, provider: Provider) -> Provider {
+        if let Some(size) = self.cache_size {
+            let mut cache = ResponseCache::new(size);
+
+            if let Some(ttl) = self.cache_ttl {
+                cache = cache.ttl(ttl);
+            }
+
+            provider.with_cache(cache)
+        } else {
+            provider
+        }
+    }
+}
@@ -8,4 +8,19 @@ pub enum ProviderError {
 
     #[error("Parse syncing status err, should always return false if not syncing")]
     Syncing,
+
+    #[error("ENS registry is not configured, see `Provider::with_ens`")]
+    EnsNotConfigured,
+
+    #[error("ENS name {0} is invalid or not resolved")]
+    EnsName(String),
+
+    #[error("Parse txpool_inspect summary error, {0}")]
+    TxpoolInspect(String),
+
+    #[error("Invalid hydrated block, {0}")]
+    HydratedBlock(String),
+
+    #[error("Quorum {quorum} is out of range 1..={providers}")]
+    Quorum { quorum: usize, providers: usize },
 }
@@ -3,3 +3,13 @@ pub mod http;
 pub mod ws;
 
 pub mod channel;
+
+pub mod fallback;
+
+pub mod quorum;
+
+pub mod mock;
+
+mod dispatch;
+
+mod trace;
@@ -1,17 +1,16 @@
 use futures::channel::mpsc::{Receiver, SendError, Sender};
 use jsonrpc_rs::channel::RPCData;
 use jsonrpc_rs::{channel::TransportChannel, RPCResult};
-use once_cell::sync::OnceCell;
-use tokio::runtime::Runtime;
 
-/// Ethererum network provider tokio io event driver channel.
+/// Ethererum network provider io event driver channel.
 ///
-pub(crate) struct TokioProviderChannel {
+/// Futures are driven by the executor selected by [`ethers_runtime`] features.
+pub(crate) struct ProviderChannel {
     pub(crate) receiver: Receiver<RPCResult<RPCData>>,
     pub(crate) sender: Sender<RPCData>,
 }
 
-impl TransportChannel for TokioProviderChannel {
+impl TransportChannel for ProviderChannel {
     type StreamError = jsonrpc_rs::RPCError;
 
     type SinkError = SendError;
@@ -24,11 +23,7 @@ impl TransportChannel for TokioProviderChannel {
     where
         Fut: futures::Future<Output = RPCResult<()>> + Send + 'static,
     {
-        static INSTANCE: OnceCell<tokio::runtime::Runtime> = OnceCell::new();
-
-        let executor = INSTANCE.get_or_init(|| Runtime::new().unwrap());
-
-        _ = executor.spawn(async move {
+        ethers_runtime::spawn(async move {
             let result = future.await;
 
             match result {
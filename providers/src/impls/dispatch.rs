@@ -0,0 +1,155 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use futures::{
+    channel::mpsc::{self, Receiver, Sender},
+    Future, SinkExt, StreamExt,
+};
+use jsonrpc_rs::{channel::RPCData, ErrorCode, RPCError, RPCResult};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::Provider;
+
+use super::channel::ProviderChannel;
+
+/// Raw jsonrpc request sent by [`jsonrpc_rs::Client`]
+#[derive(Debug, Deserialize)]
+struct Request {
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Create provider whose requests are handled by `handler` instead of network transport.
+///
+/// `handler` is called with the request method name and params, and returns the `result` field of response.
+pub(crate) fn connect_with<F, Fut>(tag: String, handler: F) -> Provider
+where
+    F: FnMut(String, Value) -> Fut + Send + 'static,
+    Fut: Future<Output = RPCResult<Value>> + Send + 'static,
+{
+    let (client_output, dispatcher_input) = mpsc::channel::<RPCData>(20);
+    let (dispatcher_output, client_input) = mpsc::channel::<RPCResult<RPCData>>(20);
+
+    let client_transport = ProviderChannel {
+        receiver: client_input,
+        sender: client_output,
+    };
+
+    let dispatcher_tag = tag.clone();
+
+    ethers_runtime::spawn(async move {
+        dispatch_loop(handler, dispatcher_input, dispatcher_output).await;
+
+        log::trace!("dispatch_loop stop,{}", dispatcher_tag);
+    });
+
+    Provider::new(tag.clone(), jsonrpc_rs::Client::new(tag, client_transport))
+}
+
+async fn dispatch_loop<F, Fut>(
+    mut handler: F,
+    dispatcher_input: Receiver<RPCData>,
+    dispatcher_output: Sender<RPCResult<RPCData>>,
+) where
+    F: FnMut(String, Value) -> Fut,
+    Fut: Future<Output = RPCResult<Value>>,
+{
+    dispatcher_input
+        .for_each_concurrent(None, |message| {
+            let mut response_input = dispatcher_output.clone();
+
+            let request = serde_json::from_slice::<Request>(&message);
+
+            let response =
+                request.map(|request| (request.id, handler(request.method, request.params)));
+
+            async move {
+                let (id, response) = match response {
+                    Ok(response) => response,
+                    Err(err) => {
+                        log::error!("dispatch invalid request, {}", err);
+                        return;
+                    }
+                };
+
+                let response = match response.await {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    Err(err) => json!({ "jsonrpc": "2.0", "id": id, "error": err }),
+                };
+
+                let response = serde_json::to_vec(&response).expect("Serialize jsonrpc response");
+
+                if let Err(err) = response_input.send(Ok(response.into())).await {
+                    log::error!("dispatch response error, {}", err);
+                }
+            }
+        })
+        .await;
+}
+
+/// Create [`ErrorCode::InternalError`] error with `message`
+pub(crate) fn internal_error<S: Into<String>>(message: S) -> RPCError {
+    RPCError {
+        code: ErrorCode::InternalError,
+        message: message.into(),
+        data: None,
+    }
+}
+
+/// Methods installing filters, the returned filter id is only known by the provider installed it.
+const NEW_FILTER_METHODS: &[&str] = &[
+    "eth_newFilter",
+    "eth_newBlockFilter",
+    "eth_newPendingTransactionFilter",
+];
+
+/// Methods taking filter id as the first param.
+const FILTER_ID_METHODS: &[&str] = &[
+    "eth_getFilterChanges",
+    "eth_getFilterLogs",
+    "eth_uninstallFilter",
+];
+
+/// Returns true if `method` installs or polls filters, which must be sent to one provider.
+pub(crate) fn is_filter_method(method: &str) -> bool {
+    NEW_FILTER_METHODS.contains(&method) || FILTER_ID_METHODS.contains(&method)
+}
+
+/// Filter ids pinned to the index of provider which installed them, shared by clones.
+#[derive(Clone, Default)]
+pub(crate) struct FilterPins(Arc<Mutex<HashMap<String, usize>>>);
+
+impl FilterPins {
+    /// Returns index of provider installed the filter polled by `method`, [`None`] if `method` takes no filter id.
+    ///
+    /// Unknown filter ids are pinned to the first provider.
+    pub(crate) fn pinned(&self, method: &str, params: &Value) -> Option<usize> {
+        if !FILTER_ID_METHODS.contains(&method) {
+            return None;
+        }
+
+        let index = params[0]
+            .as_str()
+            .and_then(|id| self.0.lock().unwrap().get(id).copied());
+
+        Some(index.unwrap_or(0))
+    }
+
+    /// Record filter id installed by provider `index`, or forget the uninstalled one.
+    pub(crate) fn update(&self, method: &str, params: &Value, result: &Value, index: usize) {
+        if NEW_FILTER_METHODS.contains(&method) {
+            if let Some(id) = result.as_str() {
+                self.0.lock().unwrap().insert(id.to_owned(), index);
+            }
+        } else if method == "eth_uninstallFilter" {
+            if let Some(id) = params[0].as_str() {
+                self.0.lock().unwrap().remove(id);
+            }
+        }
+    }
+}
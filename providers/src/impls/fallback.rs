@@ -0,0 +1,237 @@
+use jsonrpc_rs::{RPCError, RPCResult};
+use serde_json::Value;
+
+use crate::Provider;
+
+use super::dispatch::{self, internal_error, FilterPins};
+
+/// Error messages raised by transports instead of nodes, e.g. connection failures,
+/// request timeouts and http gateway errors.
+const TRANSPORT_ERRORS: &[&str] = &[
+    "error sending request",
+    "error decoding response",
+    "connection",
+    "timed out",
+    "timeout:",
+    "broken pipe",
+    "channel closed",
+    "disconnected",
+    "too many requests",
+    "bad gateway",
+    "service unavailable",
+    "gateway timeout",
+];
+
+/// Returns true if `err` is raised by transport, a node response error returns false.
+pub fn is_transport_error(err: &RPCError) -> bool {
+    let message = err.message.to_lowercase();
+
+    TRANSPORT_ERRORS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Provider composed from multiple providers with ordered failover.
+///
+/// Each request is sent to the first provider, and retried on the next one if failed by
+/// [transport errors](is_transport_error), node errors like `execution reverted` are returned directly.
+/// Filters are polled on the provider which installed them.
+#[derive(Clone)]
+pub struct FallbackProvider {
+    providers: Vec<Provider>,
+}
+
+impl FallbackProvider {
+    /// Create fallback provider, `providers` are tried in order.
+    pub fn new(providers: Vec<Provider>) -> Self {
+        Self { providers }
+    }
+
+    /// Convert into [`Provider`], all [`Provider`] apis are available on the result.
+    pub fn into_provider(self) -> Provider {
+        let tag = format!(
+            "eth-provider-fallback_[{}]",
+            self.providers
+                .iter()
+                .map(|provider| provider.id())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+
+        let pins = FilterPins::default();
+
+        dispatch::connect_with(tag, move |method, params| {
+            let providers = self.providers.clone();
+            let pins = pins.clone();
+
+            async move {
+                // Other providers don't know the filter id, so polling is not failed over.
+                if let Some(index) = pins.pinned(&method, &params) {
+                    let mut provider = providers
+                        .get(index)
+                        .cloned()
+                        .ok_or_else(|| internal_error("FallbackProvider: empty providers"))?;
+
+                    let result: Value = provider.client().call(&method, params.clone()).await?;
+
+                    pins.update(&method, &params, &result, index);
+
+                    return Ok(result);
+                }
+
+                let (index, result) = call_in_order(providers, &method, params.clone()).await?;
+
+                pins.update(&method, &params, &result, index);
+
+                Ok(result)
+            }
+        })
+    }
+}
+
+impl From<FallbackProvider> for Provider {
+    fn from(value: FallbackProvider) -> Self {
+        value.into_provider()
+    }
+}
+
+/// Call `providers` in order until one succeeds or fails by node error,
+/// returns the index of provider and its result.
+async fn call_in_order(
+    providers: Vec<Provider>,
+    method: &str,
+    params: Value,
+) -> RPCResult<(usize, Value)> {
+    let mut last_error = None;
+
+    for (index, mut provider) in providers.into_iter().enumerate() {
+        let result: RPCResult<Value> = provider.client().call(method, params.clone()).await;
+
+        match result {
+            Ok(result) => return Ok((index, result)),
+            Err(err) => {
+                log::debug!(
+                    "fallback {} on {} failed, {}",
+                    method,
+                    provider.id(),
+                    err.message
+                );
+
+                // Node errors, e.g. `execution reverted`, are returned by other providers too.
+                if !is_transport_error(&err) {
+                    return Err(err);
+                }
+
+                last_error = Some(err);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| internal_error("FallbackProvider: empty providers")))
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpc_rs::ErrorCode;
+    use serde_json::json;
+
+    use crate::providers::mock::MockProvider;
+
+    use super::*;
+
+    fn error(message: &str) -> RPCError {
+        RPCError {
+            code: ErrorCode::InternalError,
+            message: message.to_owned(),
+            data: None,
+        }
+    }
+
+    #[test]
+    fn test_transport_error() {
+        assert!(is_transport_error(&error(
+            "error sending request for url (http://localhost:8545/)"
+        )));
+        assert!(is_transport_error(&error("503 Service Unavailable")));
+        assert!(is_transport_error(&error(
+            "Timeout: future not completed within 30s"
+        )));
+
+        assert!(!is_transport_error(&error("execution reverted")));
+        assert!(!is_transport_error(&error("nonce too low")));
+    }
+
+    #[async_std::test]
+    async fn test_failover() {
+        let first = MockProvider::new();
+        let second = MockProvider::new();
+
+        first
+            .push_error("eth_blockNumber", error("error sending request"))
+            .push_error("eth_call", error("execution reverted"));
+
+        second
+            .push("eth_blockNumber", "0x10")
+            .unwrap()
+            .push("eth_call", "0x")
+            .unwrap();
+
+        let mut provider =
+            FallbackProvider::new(vec![first.provider(), second.provider()]).into_provider();
+
+        assert_eq!(
+            provider.eth_block_number().await.unwrap(),
+            ethers_core::U256::from(16u64)
+        );
+
+        // Node errors are not failed over.
+        let result: RPCResult<Value> = provider.client().call("eth_call", json!([])).await;
+
+        assert_eq!(result.unwrap_err().message, "execution reverted");
+        assert_eq!(second.pending(), vec!["eth_call".to_owned()]);
+    }
+
+    #[async_std::test]
+    async fn test_filter_pinned() {
+        let first = MockProvider::new();
+        let second = MockProvider::new();
+
+        first.push_error("eth_newBlockFilter", error("connection refused"));
+
+        second
+            .push("eth_newBlockFilter", "0x1")
+            .unwrap()
+            .push("eth_getFilterChanges", json!([]))
+            .unwrap()
+            .push("eth_uninstallFilter", true)
+            .unwrap();
+
+        let mut provider =
+            FallbackProvider::new(vec![first.provider(), second.provider()]).into_provider();
+
+        let id: Value = provider
+            .client()
+            .call("eth_newBlockFilter", json!([]))
+            .await
+            .unwrap();
+
+        // Polled on the provider installed the filter.
+        let changes: Value = provider
+            .client()
+            .call("eth_getFilterChanges", json!([id]))
+            .await
+            .unwrap();
+
+        assert_eq!(changes, json!([]));
+
+        let removed: Value = provider
+            .client()
+            .call("eth_uninstallFilter", json!([id]))
+            .await
+            .unwrap();
+
+        assert_eq!(removed, json!(true));
+        assert_eq!(first.requests().len(), 1);
+        assert!(second.pending().is_empty());
+    }
+}
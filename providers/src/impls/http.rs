@@ -1,13 +1,15 @@
+use std::{sync::Arc, time::Duration};
+
 use futures::{
     channel::mpsc::{self, Receiver, Sender},
     SinkExt, StreamExt,
 };
-use jsonrpc_rs::{channel::TransportChannel, map_error, ErrorCode, RPCError, RPCResult};
+use jsonrpc_rs::{map_error, ErrorCode, RPCError, RPCResult};
 use reqwest::header::{HeaderValue, CONTENT_TYPE};
 
 use crate::Provider;
 
-use super::channel::TokioProviderChannel;
+use super::{channel::ProviderChannel, trace::RpcTracer};
 
 use jsonrpc_rs::channel::RPCData;
 
@@ -15,32 +17,55 @@ use jsonrpc_rs::channel::RPCData;
 ///
 /// Visit [`Ethereum JSON-RPC Specification`](https://ethereum.github.io/execution-apis/api-documentation/) for more details
 ///
+/// Use [`ProviderBuilder`](crate::ProviderBuilder) to customize concurrency limit, connection pool and request timeout.
+///
 /// # Parameters
 ///
 /// * `url` - Ethereum node public JSONRPC server url
 pub fn connect_to<S: AsRef<str> + Clone + Send + 'static + Sync>(url: S) -> Provider {
+    connect_with(url, reqwest::Client::new(), &HttpOptions::default())
+}
+
+/// Http transport options, see [`ProviderBuilder`](crate::ProviderBuilder).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HttpOptions {
+    /// Max in-flight requests, [`None`] means unlimited.
+    pub(crate) max_concurrent_requests: Option<usize>,
+    /// Timeout of one request, including reading response body.
+    pub(crate) request_timeout: Option<Duration>,
+}
+
+/// Connect to ethereum node with `client`, all requests share `client` connection pool.
+pub(crate) fn connect_with<S: AsRef<str> + Clone + Send + 'static + Sync>(
+    url: S,
+    client: reqwest::Client,
+    options: &HttpOptions,
+) -> Provider {
     let (client_output, dispatcher_input) = mpsc::channel::<RPCData>(20);
     let (dispatcher_output, client_input) = mpsc::channel::<RPCResult<RPCData>>(20);
 
     // Create mpsc transport, real send/recv network message are in procedure dispatcher.
-    let client_transport = TokioProviderChannel {
+    let client_transport = ProviderChannel {
         receiver: client_input,
         sender: client_output,
     };
 
     let dispatcher_url = url.clone();
-
-    TokioProviderChannel::spawn(async move {
-        match requester_loop(dispatcher_url.clone(), dispatcher_input, dispatcher_output).await {
-            Err(err) => {
-                log::error!("requester_loop err, {}", err);
-            }
-            _ => {
-                log::trace!("requester_loop stop,{}", dispatcher_url.as_ref());
-            }
-        }
-
-        Ok(())
+    let options = options.clone();
+    let tracer = Arc::new(RpcTracer::new(url.as_ref()));
+
+    ethers_runtime::spawn(async move {
+        requester_loop(
+            dispatcher_url.clone(),
+            client,
+            options,
+            tracer,
+            dispatcher_input,
+            dispatcher_output,
+        )
+        .await;
+
+        log::trace!("requester_loop stop,{}", dispatcher_url.as_ref());
     });
 
     let tag = format!("eth-provider-https_{}", url.as_ref());
@@ -48,81 +73,77 @@ pub fn connect_to<S: AsRef<str> + Clone + Send + 'static + Sync>(url: S) -> Prov
     Provider::new(tag.clone(), jsonrpc_rs::Client::new(tag, client_transport))
 }
 
-#[allow(unused)]
 async fn requester_loop<S>(
     dispatcher_url: S,
-    mut dispatcher_input: Receiver<RPCData>,
+    client: reqwest::Client,
+    options: HttpOptions,
+    tracer: Arc<RpcTracer>,
+    dispatcher_input: Receiver<RPCData>,
     dispatcher_output: Sender<RPCResult<RPCData>>,
-) -> RPCResult<()>
-where
+) where
     S: AsRef<str> + Clone + Send + 'static + Sync,
 {
-    while let Some(message) = dispatcher_input.next().await {
-        let response_input = dispatcher_output.clone();
-        let request_url = dispatcher_url.clone();
-
-        // panic!("hello");
+    let request_timeout = options.request_timeout;
+
+    // Requests beyond `max_concurrent_requests` are queued in `dispatcher_input`.
+    dispatcher_input
+        .for_each_concurrent(options.max_concurrent_requests, |message| {
+            let mut response_input = dispatcher_output.clone();
+            let request = send_and_recv(
+                client.clone(),
+                dispatcher_url.as_ref(),
+                tracer.clone(),
+                message,
+            );
+
+            async move {
+                let result = match request_timeout {
+                    Some(duration) => ethers_runtime::timeout(duration, request)
+                        .await
+                        .map_err(map_error)
+                        .and_then(|result| result),
+                    None => request.await,
+                };
 
-        TokioProviderChannel::spawn(async move {
-            match send_and_recv(request_url, message, response_input).await {
-                Err(err) => {
+                if let Err(err) = &result {
                     log::error!("send_and_recv error, {}", err);
                 }
-                _ => {}
-            }
-            Ok(())
-        });
-    }
 
-    Ok(())
+                if let Err(err) = response_input.send(result).await {
+                    log::error!("dispatch response error, {}", err);
+                }
+            }
+        })
+        .await;
 }
 
-async fn send_and_recv<S>(
-    request_url: S,
+async fn send_and_recv(
+    client: reqwest::Client,
+    request_url: &str,
+    tracer: Arc<RpcTracer>,
     message: RPCData,
-    mut response_input: Sender<RPCResult<RPCData>>,
-) -> RPCResult<()>
-where
-    S: AsRef<str> + Clone + Send + 'static + Sync,
-{
-    let client = reqwest::Client::new();
-
-    log::trace!("try send message: {:?}", message);
+) -> RPCResult<RPCData> {
+    let trace_id = tracer.request(&message);
 
     let response = client
-        .post(request_url.as_ref())
+        .post(request_url)
         .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
         .body(message)
         .send()
         .await
-        .map_err(map_error);
-
-    log::trace!("response {:?}", response);
+        .map_err(map_error)?;
 
-    match response {
-        Ok(response) => {
-            if response.status().is_success() {
-                let recv_data = response.bytes().await.map_err(map_error);
+    if !response.status().is_success() {
+        return Err(RPCError {
+            code: ErrorCode::InternalError,
+            message: response.status().to_string(),
+            data: None,
+        });
+    }
 
-                if let Ok(recv_data) = &recv_data {
-                    log::trace!("recv response {}", String::from_utf8_lossy(&recv_data));
-                }
+    let recv_data = response.bytes().await.map_err(map_error)?;
 
-                response_input.send(recv_data).await?;
-            } else {
-                let err = RPCError {
-                    code: ErrorCode::InternalError,
-                    message: response.status().to_string(),
-                    data: None,
-                };
-
-                response_input.send(Err(err)).await?;
-            }
-        }
-        Err(err) => {
-            response_input.send(Err(err)).await?;
-        }
-    }
+    tracer.response(trace_id, &recv_data);
 
-    Ok(())
+    Ok(recv_data)
 }
@@ -0,0 +1,178 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use jsonrpc_rs::{map_error, RPCError, RPCResult};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::Provider;
+
+use super::dispatch::{self, internal_error};
+
+type Responder = Box<dyn FnMut(Value) -> RPCResult<Value> + Send>;
+
+#[derive(Default)]
+struct MockState {
+    expectations: VecDeque<(String, Responder)>,
+    requests: Vec<(String, Value)>,
+}
+
+impl MockState {
+    /// Record request and respond with the first expectation of `method`.
+    fn respond(&mut self, method: String, params: Value) -> RPCResult<Value> {
+        self.requests.push((method.clone(), params.clone()));
+
+        let index = self
+            .expectations
+            .iter()
+            .position(|(expected, _)| *expected == method)
+            .ok_or_else(|| internal_error(format!("MockProvider: unexpected call {}", method)))?;
+
+        let (_, mut responder) = self.expectations.remove(index).expect("Expectation index");
+
+        responder(params)
+    }
+}
+
+/// In-memory provider transport for unit tests, responding enqueued canned responses.
+///
+/// Each request consumes the first enqueued expectation with the same method, calls without
+/// expectation returns error. All received requests are recorded for assertions.
+///
+/// ```no_run
+/// # use ethers_provider::providers::mock::MockProvider;
+/// # async fn example() -> jsonrpc_rs::RPCResult<()> {
+/// let mock = MockProvider::new();
+///
+/// mock.push("eth_chainId", "0x1")?;
+///
+/// let chain_id = mock.provider().eth_chain_id().await?;
+///
+/// assert_eq!(mock.requests()[0].0, "eth_chainId");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct MockProvider {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enqueue expected call of `method`, responding `result`.
+    pub fn push<R>(&self, method: &str, result: R) -> RPCResult<&Self>
+    where
+        R: Serialize,
+    {
+        let result = serde_json::to_value(result).map_err(map_error)?;
+
+        Ok(self.push_fn(method, move |_| Ok(result.clone())))
+    }
+
+    /// Enqueue expected call of `method`, responding `error`.
+    pub fn push_error(&self, method: &str, error: RPCError) -> &Self {
+        let mut error = Some(error);
+
+        self.push_fn(method, move |_| {
+            Err(error
+                .take()
+                .unwrap_or_else(|| internal_error("MockProvider: error consumed")))
+        })
+    }
+
+    /// Enqueue expected call of `method`, responding the result of `responder` called with request params.
+    pub fn push_fn<F>(&self, method: &str, responder: F) -> &Self
+    where
+        F: FnMut(Value) -> RPCResult<Value> + Send + 'static,
+    {
+        self.state
+            .lock()
+            .unwrap()
+            .expectations
+            .push_back((method.to_owned(), Box::new(responder)));
+
+        self
+    }
+
+    /// Returns received requests as `(method, params)` in order.
+    pub fn requests(&self) -> Vec<(String, Value)> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Returns methods of expectations not called yet.
+    pub fn pending(&self) -> Vec<String> {
+        self.state
+            .lock()
+            .unwrap()
+            .expectations
+            .iter()
+            .map(|(method, _)| method.clone())
+            .collect()
+    }
+
+    /// Create [`Provider`] whose requests are handled by this mock.
+    pub fn provider(&self) -> Provider {
+        let state = self.state.clone();
+
+        dispatch::connect_with("eth-provider-mock".to_owned(), move |method, params| {
+            let result = state.lock().unwrap().respond(method, params);
+
+            async move { result }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_respond() {
+        let mock = MockProvider::new();
+
+        mock.push("eth_blockNumber", "0x1")
+            .unwrap()
+            .push("eth_chainId", "0x5")
+            .unwrap()
+            .push_fn("eth_getBalance", |params| Ok(params[0].clone()))
+            .push_error("eth_chainId", internal_error("boom"));
+
+        let mut state = mock.state.lock().unwrap();
+
+        // Expectations of different methods are matched out of order.
+        assert_eq!(
+            state.respond("eth_chainId".to_owned(), json!([])).unwrap(),
+            json!("0x5")
+        );
+
+        assert_eq!(
+            state
+                .respond("eth_getBalance".to_owned(), json!(["0xabc"]))
+                .unwrap(),
+            json!("0xabc")
+        );
+
+        assert_eq!(
+            state
+                .respond("eth_chainId".to_owned(), json!([]))
+                .unwrap_err()
+                .message,
+            "boom"
+        );
+
+        assert!(state.respond("eth_gasPrice".to_owned(), json!([])).is_err());
+
+        assert_eq!(state.requests.len(), 4);
+
+        drop(state);
+
+        assert_eq!(mock.pending(), vec!["eth_blockNumber".to_owned()]);
+    }
+}
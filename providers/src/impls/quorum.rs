@@ -0,0 +1,250 @@
+use futures::future::join_all;
+use jsonrpc_rs::RPCResult;
+use serde_json::Value;
+
+use crate::{error::ProviderError, Provider};
+
+use super::dispatch::{self, internal_error, is_filter_method};
+
+/// Methods broadcasting to all providers instead of waiting for agreement.
+const BROADCAST_METHODS: &[&str] = &["eth_sendRawTransaction"];
+
+/// Provider composed from multiple providers, requiring N-of-M agreement.
+///
+/// Read requests are sent to all providers concurrently, the response returned by at least
+/// `quorum` providers is returned. `eth_sendRawTransaction` is broadcast to all providers,
+/// and succeeds if any of them accepted the transaction. Filter methods are sent to the first
+/// provider only, filter ids are not shared by providers.
+#[derive(Clone)]
+pub struct QuorumProvider {
+    providers: Vec<Provider>,
+    quorum: usize,
+}
+
+impl QuorumProvider {
+    /// Create quorum provider, requiring agreement of the majority of `providers`.
+    pub fn new(providers: Vec<Provider>) -> Result<Self, ProviderError> {
+        let quorum = providers.len() / 2 + 1;
+
+        Self::with_quorum(providers, quorum)
+    }
+
+    /// Create quorum provider, requiring agreement of `quorum` providers,
+    /// returns error unless `0 < quorum <= providers.len()`.
+    pub fn with_quorum(providers: Vec<Provider>, quorum: usize) -> Result<Self, ProviderError> {
+        if quorum == 0 || quorum > providers.len() {
+            return Err(ProviderError::Quorum {
+                quorum,
+                providers: providers.len(),
+            });
+        }
+
+        Ok(Self { providers, quorum })
+    }
+
+    /// Convert into [`Provider`], all [`Provider`] apis are available on the result.
+    pub fn into_provider(self) -> Provider {
+        let tag = format!(
+            "eth-provider-quorum_{}_[{}]",
+            self.quorum,
+            self.providers
+                .iter()
+                .map(|provider| provider.id())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+
+        dispatch::connect_with(tag, move |method, params| {
+            let providers = self.providers.clone();
+            let quorum = self.quorum;
+
+            async move {
+                if is_filter_method(&method) {
+                    let mut provider = providers[0].clone();
+
+                    return provider.client().call(&method, params).await;
+                }
+
+                let results: Vec<RPCResult<Value>> =
+                    join_all(providers.into_iter().map(|mut provider| {
+                        let method = method.clone();
+                        let params = params.clone();
+
+                        async move { provider.client().call(&method, params).await }
+                    }))
+                    .await;
+
+                if BROADCAST_METHODS.contains(&method.as_str()) {
+                    first_success(results)
+                } else {
+                    quorum_result(results, quorum)
+                }
+            }
+        })
+    }
+}
+
+impl From<QuorumProvider> for Provider {
+    fn from(value: QuorumProvider) -> Self {
+        value.into_provider()
+    }
+}
+
+/// Returns the first success result, or the first error if all results are errors.
+fn first_success(results: Vec<RPCResult<Value>>) -> RPCResult<Value> {
+    let mut first_error = None;
+
+    for result in results {
+        match result {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    Err(first_error.unwrap_or_else(|| internal_error("QuorumProvider: empty providers")))
+}
+
+/// Returns the value returned by at least `quorum` results.
+fn quorum_result(results: Vec<RPCResult<Value>>, quorum: usize) -> RPCResult<Value> {
+    let mut votes: Vec<(Value, usize)> = vec![];
+    let mut errors = vec![];
+
+    for result in results {
+        match result {
+            Ok(value) => {
+                if let Some((_, count)) = votes.iter_mut().find(|(v, _)| *v == value) {
+                    *count += 1;
+                } else {
+                    votes.push((value, 1));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+
+    if let Some(index) = votes.iter().position(|(_, count)| *count >= quorum) {
+        return Ok(votes.swap_remove(index).0);
+    }
+
+    // Every provider failed, e.g. `execution reverted`, returns the first error.
+    if votes.is_empty() && errors.len() >= quorum {
+        return Err(errors.swap_remove(0));
+    }
+
+    Err(internal_error(format!(
+        "QuorumProvider: quorum {} not reached, {} distinct responses, {} errors",
+        quorum,
+        votes.len(),
+        errors.len()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::providers::mock::MockProvider;
+
+    use super::*;
+
+    #[test]
+    fn test_quorum_result() {
+        let results = || vec![Ok(json!("0x1")), Ok(json!("0x2")), Ok(json!("0x1"))];
+
+        assert_eq!(quorum_result(results(), 2).unwrap(), json!("0x1"));
+
+        quorum_result(results(), 3).expect_err("quorum not reached");
+
+        let results = vec![
+            Err(internal_error("timeout")),
+            Ok(json!("0x1")),
+            Ok(json!("0x1")),
+        ];
+
+        assert_eq!(quorum_result(results, 2).unwrap(), json!("0x1"));
+
+        let results = vec![
+            Err(internal_error("execution reverted")),
+            Err(internal_error("execution reverted")),
+        ];
+
+        assert_eq!(
+            quorum_result(results, 2).unwrap_err().message,
+            "execution reverted"
+        );
+    }
+
+    #[test]
+    fn test_with_quorum() {
+        let providers = || {
+            vec![
+                MockProvider::new().provider(),
+                MockProvider::new().provider(),
+            ]
+        };
+
+        assert_eq!(QuorumProvider::new(providers()).unwrap().quorum, 2);
+        assert_eq!(
+            QuorumProvider::with_quorum(providers(), 1).unwrap().quorum,
+            1
+        );
+
+        assert!(matches!(
+            QuorumProvider::with_quorum(providers(), 0),
+            Err(ProviderError::Quorum { quorum: 0, .. })
+        ));
+        assert!(matches!(
+            QuorumProvider::with_quorum(providers(), 3),
+            Err(ProviderError::Quorum {
+                quorum: 3,
+                providers: 2
+            })
+        ));
+        assert!(QuorumProvider::new(vec![]).is_err());
+    }
+
+    #[async_std::test]
+    async fn test_filter_pinned() {
+        let first = MockProvider::new();
+        let second = MockProvider::new();
+
+        first
+            .push("eth_newBlockFilter", "0x1")
+            .unwrap()
+            .push("eth_getFilterChanges", json!([]))
+            .unwrap();
+
+        let mut provider = QuorumProvider::new(vec![first.provider(), second.provider()])
+            .unwrap()
+            .into_provider();
+
+        let id: Value = provider
+            .client()
+            .call("eth_newBlockFilter", json!([]))
+            .await
+            .unwrap();
+
+        let changes: Value = provider
+            .client()
+            .call("eth_getFilterChanges", json!([id]))
+            .await
+            .unwrap();
+
+        assert_eq!(changes, json!([]));
+        assert!(first.pending().is_empty());
+        assert!(second.requests().is_empty());
+    }
+
+    #[test]
+    fn test_first_success() {
+        let results = vec![Err(internal_error("already known")), Ok(json!("0x1"))];
+
+        assert_eq!(first_success(results).unwrap(), json!("0x1"));
+
+        let results = vec![Err(internal_error("nonce too low"))];
+
+        assert_eq!(first_success(results).unwrap_err().message, "nonce too low");
+    }
+}
@@ -1,15 +1,23 @@
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{Debug, Display},
+    sync::Arc,
+};
 
 use futures::{
     channel::mpsc::{self, Receiver, Sender},
     Sink, SinkExt, Stream, StreamExt, TryStreamExt,
 };
-use jsonrpc_rs::{bytes::Bytes, channel::TransportChannel, RPCResult};
+use jsonrpc_rs::{bytes::Bytes, RPCResult};
+
+#[cfg(not(target_arch = "wasm32"))]
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 
+#[cfg(target_arch = "wasm32")]
+use gloo_net::websocket::{futures::WebSocket, Message};
+
 use crate::Provider;
 
-use super::channel::TokioProviderChannel;
+use super::{channel::ProviderChannel, trace::RpcTracer};
 
 use jsonrpc_rs::channel::RPCData;
 
@@ -20,21 +28,29 @@ pub async fn connect_to<S: AsRef<str> + Clone + Send + 'static + Sync>(
     let (dispatcher_output, client_input) = mpsc::channel::<RPCResult<RPCData>>(20);
 
     // Create mpsc transport, real send/recv network message are in procedure dispatcher.
-    let client_transport = TokioProviderChannel {
+    let client_transport = ProviderChannel {
         receiver: client_input,
         sender: client_output,
     };
 
+    #[cfg(not(target_arch = "wasm32"))]
     let (ws_stream, _) = connect_async(url.as_ref())
         .await
         .map_err(jsonrpc_rs::map_error)?;
 
+    // Browser `WebSocket` handshake completes in the background.
+    #[cfg(target_arch = "wasm32")]
+    let ws_stream = WebSocket::open(url.as_ref()).map_err(jsonrpc_rs::map_error)?;
+
     let (write, read) = ws_stream.split();
 
+    let tracer = Arc::new(RpcTracer::new(url.as_ref()));
+
     let requester_url = url.clone();
+    let requester_tracer = tracer.clone();
 
-    TokioProviderChannel::spawn(async move {
-        match requester_loop(write, dispatcher_input).await {
+    ethers_runtime::spawn(async move {
+        match requester_loop(write, requester_tracer, dispatcher_input).await {
             Err(err) => {
                 log::error!("requester_loop err, {}", err);
             }
@@ -42,14 +58,12 @@ pub async fn connect_to<S: AsRef<str> + Clone + Send + 'static + Sync>(
                 log::trace!("requester_loop stop,{}", requester_url.as_ref());
             }
         }
-
-        Ok(())
     });
 
     let responser_url = url.clone();
 
-    TokioProviderChannel::spawn(async move {
-        match responser_loop(read, dispatcher_output).await {
+    ethers_runtime::spawn(async move {
+        match responser_loop(read, tracer, dispatcher_output).await {
             Err(err) => {
                 log::error!("responser_loop err, {}", err);
             }
@@ -57,8 +71,6 @@ pub async fn connect_to<S: AsRef<str> + Clone + Send + 'static + Sync>(
                 log::trace!("responser_loop stop,{}", responser_url.as_ref());
             }
         }
-
-        Ok(())
     });
     let tag = format!("eth-provider-ws_{}", url.as_ref());
 
@@ -69,12 +81,18 @@ pub async fn connect_to<S: AsRef<str> + Clone + Send + 'static + Sync>(
 }
 
 #[allow(unused)]
-async fn requester_loop<S>(mut sink: S, mut dispatcher_input: Receiver<RPCData>) -> RPCResult<()>
+async fn requester_loop<S>(
+    mut sink: S,
+    tracer: Arc<RpcTracer>,
+    mut dispatcher_input: Receiver<RPCData>,
+) -> RPCResult<()>
 where
     S: Sink<Message> + Unpin,
     S::Error: Display + Debug,
 {
     while let Some(message) = dispatcher_input.next().await {
+        tracer.request(&message);
+
         sink.send(Message::Text(String::from_utf8_lossy(&message).to_string()))
             .await
             .map_err(jsonrpc_rs::map_error);
@@ -87,6 +105,7 @@ where
 
 async fn responser_loop<S, E>(
     mut stream: S,
+    tracer: Arc<RpcTracer>,
     mut response_input: Sender<RPCResult<RPCData>>,
 ) -> RPCResult<()>
 where
@@ -96,10 +115,12 @@ where
     while let Some(message) = stream.try_next().await.map_err(jsonrpc_rs::map_error)? {
         match message {
             Message::Text(data) => {
+                tracer.response(None, data.as_bytes());
+
                 response_input.send(Ok(Bytes::from(data))).await?;
             }
             _ => {
-                log::trace!("skip handle ws recv msg, {}", message);
+                log::trace!("skip handle ws recv msg, {:?}", message);
             }
         }
     }
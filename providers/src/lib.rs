@@ -6,6 +6,9 @@ pub use provider::*;
 mod types;
 pub use types::*;
 
+mod builder;
+pub use builder::*;
+
 mod impls;
 
 /// Reexport impls as providers mod
@@ -1,9 +1,41 @@
 use std::sync::{Arc, Mutex};
 
+use ethers_core::{Address, InputField};
+
 mod event;
 pub use event::*;
 mod rpc;
 pub use rpc::*;
+mod broadcast;
+pub use broadcast::*;
+mod dev;
+pub use dev::*;
+mod simulate;
+pub use simulate::*;
+mod private;
+pub use private::*;
+mod watcher;
+pub use watcher::*;
+mod blocks;
+pub use blocks::*;
+mod cache;
+pub use cache::*;
+mod metrics;
+pub use metrics::*;
+mod fee;
+pub use fee::*;
+mod ens;
+pub use ens::*;
+mod logs;
+pub use logs::*;
+mod revert;
+pub use revert::*;
+mod txpool;
+pub use txpool::*;
+mod net;
+pub use net::*;
+mod bundler;
+pub use bundler::*;
 
 /// Ether network api provider
 ///
@@ -12,18 +44,28 @@ pub use rpc::*;
 pub struct Provider {
     id: String,
     rpc_client: jsonrpc_rs::Client,
-    pub(crate) oneshot: OneshotCompleteQ,
-    pub(crate) channel: ChannelCompleteQ,
-    pub(crate) events: Arc<Mutex<Vec<EventType>>>,
+    pub(crate) events: Arc<Mutex<Vec<EventListener>>>,
+    pub(crate) cache: Option<Arc<ResponseCache>>,
+    pub(crate) instrument: Option<Arc<dyn RpcInstrument>>,
+    pub(crate) input_field: InputField,
+    pub(crate) ens: Option<Address>,
+    pub(crate) max_log_splits: usize,
+    pub(crate) check_receipt_status: bool,
+    pub(crate) strict_blocks: bool,
 }
 
 impl Provider {
     pub fn new(id: String, rpc_client: jsonrpc_rs::Client) -> Self {
         let this = Self {
             id,
-            oneshot: OneshotCompleteQ::new(),
-            channel: ChannelCompleteQ::new(),
             events: Default::default(),
+            cache: None,
+            instrument: None,
+            input_field: InputField::default(),
+            ens: None,
+            max_log_splits: DEFAULT_MAX_LOG_SPLITS,
+            check_receipt_status: false,
+            strict_blocks: false,
             rpc_client,
         };
 
@@ -40,6 +82,12 @@ impl Provider {
     pub fn client(&mut self) -> &mut jsonrpc_rs::Client {
         &mut self.rpc_client
     }
+
+    /// Set json field name of call data in sent transaction requests, default is `data`.
+    pub fn with_input_field(mut self, field: InputField) -> Self {
+        self.input_field = field;
+        self
+    }
 }
 
 // #[cfg(test)]
@@ -0,0 +1,369 @@
+use std::{collections::VecDeque, time::Duration};
+
+use futures::Stream;
+use jsonrpc_rs::RPCResult;
+
+use super::Provider;
+use crate::{error::ProviderError, types::*};
+use ethers_core::*;
+
+/// Default count of recent canonical blocks tracked by [`BlockWatcher`] to detect reorgs.
+pub const DEFAULT_REORG_DEPTH: usize = 64;
+
+/// Events yielded by [`BlockWatcher`].
+#[derive(Debug, Clone)]
+pub enum BlockEvent {
+    /// New canonical block, in ascending block number order.
+    Block(Block),
+    /// Chain reorganization, emitted before blocks of the new chain segment.
+    Reorg {
+        /// Hashes of previously emitted blocks removed from canonical chain, in ascending order.
+        removed: Vec<H256>,
+        /// Hashes of new canonical chain segment, in ascending order.
+        added: Vec<H256>,
+    },
+}
+
+/// Recent canonical chain hashes and emitted block hashes.
+#[derive(Debug, Default)]
+struct ChainTracker {
+    chain: VecDeque<H256>,
+    emitted: VecDeque<H256>,
+    depth: usize,
+}
+
+impl ChainTracker {
+    fn new(depth: usize) -> Self {
+        Self {
+            depth: depth.max(1),
+            ..Default::default()
+        }
+    }
+
+    fn contains(&self, hash: &H256) -> bool {
+        self.chain.contains(hash)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.chain.is_empty()
+    }
+
+    /// Append ascending `segment` after `ancestor`, returns hashes removed from canonical chain.
+    ///
+    /// If `ancestor` is unknown, aka the gap is deeper than tracked chain, the chain is reset without
+    /// reporting removed hashes.
+    fn extend(&mut self, ancestor: &H256, segment: &[H256]) -> Vec<H256> {
+        let removed = match self.chain.iter().position(|hash| hash == ancestor) {
+            Some(index) => self.chain.drain(index + 1..).collect(),
+            None => {
+                self.chain.clear();
+                vec![]
+            }
+        };
+
+        self.chain.extend(segment.iter().cloned());
+
+        while self.chain.len() > self.depth {
+            self.chain.pop_front();
+        }
+
+        removed
+    }
+
+    /// Mark `hash` emitted, returns false if it was emitted already.
+    fn mark_emitted(&mut self, hash: &H256) -> bool {
+        if self.emitted.contains(hash) {
+            return false;
+        }
+
+        self.emitted.push_back(hash.clone());
+
+        while self.emitted.len() > self.depth * 2 {
+            self.emitted.pop_front();
+        }
+
+        true
+    }
+}
+
+/// Block polling service, yields full [`Block`]s of canonical chain.
+///
+/// Blocks skipped between two polls are fetched by parent hash, so every canonical block is yielded once.
+/// Blocks already yielded are not yielded again after reorgs.
+pub struct BlockWatcher {
+    provider: Provider,
+    hydrated: bool,
+    interval: Duration,
+    emit_reorgs: bool,
+    tracker: ChainTracker,
+}
+
+impl BlockWatcher {
+    /// Create new watcher with default poll interval 5s, `hydrated` blocks include full transactions.
+    pub fn new(provider: Provider, hydrated: bool) -> Self {
+        Self {
+            provider,
+            hydrated,
+            interval: Duration::from_secs(5),
+            emit_reorgs: false,
+            tracker: ChainTracker::new(DEFAULT_REORG_DEPTH),
+        }
+    }
+
+    /// Set poll interval duration.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Emit [`BlockEvent::Reorg`] notifications, default is false.
+    pub fn emit_reorgs(mut self, enabled: bool) -> Self {
+        self.emit_reorgs = enabled;
+        self
+    }
+
+    /// Set count of recent blocks tracked to detect reorgs, default is [`DEFAULT_REORG_DEPTH`].
+    pub fn reorg_depth(mut self, depth: usize) -> Self {
+        self.tracker = ChainTracker::new(depth);
+        self
+    }
+
+    /// Poll latest block once, returns events in order.
+    pub async fn poll(&mut self) -> RPCResult<Vec<BlockEvent>> {
+        let head = match self
+            .provider
+            .eth_get_block_by_number("latest", self.hydrated)
+            .await?
+        {
+            Some(head) => head,
+            None => return Ok(vec![]),
+        };
+
+        let head_hash = match &head.hash {
+            Some(hash) if !self.tracker.contains(hash) => hash.clone(),
+            _ => return Ok(vec![]),
+        };
+
+        // Walk back to the last tracked block.
+        let mut segment = vec![(head_hash, head)];
+
+        while !self.tracker.is_empty() && segment.len() < self.tracker.depth {
+            let parent_hash = segment.last().unwrap().1.parent_hash.clone();
+
+            if self.tracker.contains(&parent_hash) {
+                break;
+            }
+
+            match self
+                .provider
+                .eth_get_block_by_hash(parent_hash.clone(), self.hydrated)
+                .await?
+            {
+                Some(parent) => segment.push((parent_hash, parent)),
+                None => break,
+            }
+        }
+
+        segment.reverse();
+
+        let ancestor = segment[0].1.parent_hash.clone();
+
+        let hashes = segment
+            .iter()
+            .map(|(hash, _)| hash.clone())
+            .collect::<Vec<_>>();
+
+        let removed = self.tracker.extend(&ancestor, &hashes);
+
+        let mut events = vec![];
+
+        if !removed.is_empty() {
+            log::debug!("reorg detected, removed {} blocks", removed.len());
+
+            if self.emit_reorgs {
+                events.push(BlockEvent::Reorg {
+                    removed,
+                    added: hashes,
+                });
+            }
+        }
+
+        for (hash, block) in segment {
+            if self.tracker.mark_emitted(&hash) {
+                events.push(BlockEvent::Block(block));
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Convert watcher into a stream of block events.
+    ///
+    /// Poll errors are yielded as stream items, the stream keeps polling after an error.
+    pub fn into_stream(self) -> impl Stream<Item = RPCResult<BlockEvent>> {
+        futures::stream::unfold(
+            (self, VecDeque::new(), true),
+            |(mut watcher, mut pending, mut first)| async move {
+                loop {
+                    if let Some(event) = pending.pop_front() {
+                        return Some((Ok(event), (watcher, pending, first)));
+                    }
+
+                    if !first {
+                        ethers_runtime::sleep(watcher.interval).await;
+                    }
+
+                    first = false;
+
+                    match watcher.poll().await {
+                        Ok(events) => pending.extend(events),
+                        Err(err) => return Some((Err(err), (watcher, pending, first))),
+                    }
+                }
+            },
+        )
+    }
+}
+
+impl Provider {
+    /// Enable strict check of blocks requested with `hydrated = true`, default is false.
+    ///
+    /// If enabled, every transaction of hydrated blocks must have a hash and belong to the block,
+    /// see [`Block::check_hydrated`]. Hydrated blocks returned with transaction hashes are always rejected.
+    pub fn with_strict_blocks(mut self, enabled: bool) -> Self {
+        self.strict_blocks = enabled;
+        self
+    }
+
+    /// Check `block` returned by `eth_getBlockBy*` methods.
+    pub(crate) fn check_block(
+        &self,
+        block: Option<Block>,
+        hydrated: bool,
+    ) -> RPCResult<Option<Block>> {
+        if let (true, Some(block)) = (hydrated, &block) {
+            if self.strict_blocks {
+                block.check_hydrated().map_err(jsonrpc_rs::map_error)?;
+            } else if block.transactions.as_full().is_none() {
+                return Err(jsonrpc_rs::map_error(ProviderError::HydratedBlock(
+                    "transactions are not hydrated".to_owned(),
+                )));
+            }
+        }
+
+        Ok(block)
+    }
+
+    /// Create [`BlockWatcher`] for new canonical blocks, `hydrated` blocks include full transactions.
+    pub fn watch_blocks(&self, hydrated: bool) -> BlockWatcher {
+        BlockWatcher::new(self.clone(), hydrated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(v: u8) -> H256 {
+        [v; 32].into()
+    }
+
+    #[test]
+    fn test_chain_tracker() {
+        let mut tracker = ChainTracker::new(4);
+
+        assert!(tracker
+            .extend(&hash(0), &[hash(1), hash(2), hash(3)])
+            .is_empty());
+
+        // Reorg of block 3.
+        assert_eq!(
+            tracker.extend(&hash(2), &[hash(13), hash(14)]),
+            vec![hash(3)]
+        );
+
+        assert!(tracker.contains(&hash(14)));
+        assert!(!tracker.contains(&hash(3)));
+
+        // Depth is bounded.
+        tracker.extend(&hash(14), &[hash(15)]);
+
+        assert!(!tracker.contains(&hash(1)));
+
+        // Unknown ancestor resets chain.
+        assert!(tracker.extend(&hash(99), &[hash(100)]).is_empty());
+        assert!(!tracker.contains(&hash(15)));
+
+        assert!(tracker.mark_emitted(&hash(1)));
+        assert!(!tracker.mark_emitted(&hash(1)));
+    }
+
+    fn block(transactions: serde_json::Value) -> serde_json::Result<Block> {
+        serde_json::from_value(serde_json::json!({
+            "hash": "0x0101010101010101010101010101010101010101010101010101010101010101",
+            "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "miner": "0x0000000000000000000000000000000000000000",
+            "stateRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "transactionsRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "receiptsRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "number": "0x10",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "timestamp": "0x0",
+            "extraData": "0x",
+            "mixHash": null,
+            "nonce": null,
+            "size": "0x0",
+            "transactions": transactions,
+            "uncles": [],
+        }))
+    }
+
+    #[test]
+    fn test_block_transactions() {
+        let tx = serde_json::json!({
+            "hash": "0x0202020202020202020202020202020202020202020202020202020202020202",
+            "blockHash": "0x0101010101010101010101010101010101010101010101010101010101010101",
+            "blockNumber": "0x10",
+            "input": "0x",
+        });
+
+        let hashes = block(serde_json::json!([
+            "0x0202020202020202020202020202020202020202020202020202020202020202"
+        ]))
+        .unwrap();
+
+        assert!(!hashes.transactions.is_full());
+        assert!(hashes.check_hydrated().is_err());
+
+        let full = block(serde_json::json!([tx])).unwrap();
+
+        assert_eq!(full.transactions.len(), 1);
+        assert_eq!(full.transactions.hashes(), vec![hash(2)]);
+        assert!(full.check_hydrated().is_ok());
+
+        // Malformed transactions are not skipped.
+        let err = block(serde_json::json!([tx, { "hash": "0x02" }])).unwrap_err();
+
+        assert!(err.to_string().contains("transaction 1"), "{}", err);
+
+        // Mixed hashes and transactions.
+        assert!(block(serde_json::json!([tx, hash(2)])).is_err());
+
+        // Transaction of other block.
+        let mut other = tx.clone();
+        other["blockNumber"] = serde_json::json!("0x11");
+
+        let err = block(serde_json::json!([other]))
+            .unwrap()
+            .check_hydrated()
+            .unwrap_err();
+
+        assert!(err.to_string().contains("block number mismatch"), "{}", err);
+
+        assert!(block(serde_json::json!([]))
+            .unwrap()
+            .check_hydrated()
+            .is_ok());
+    }
+}
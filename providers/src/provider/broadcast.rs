@@ -0,0 +1,233 @@
+use std::fmt::{Debug, Display};
+
+use ethers_core::*;
+use jsonrpc_rs::{RPCError, RPCResult};
+
+use super::Provider;
+
+/// Max broadcast attempts of [`broadcast_raw_transaction`](Provider::broadcast_raw_transaction)
+const BROADCAST_RETRY_TIMES: usize = 3;
+
+/// Node error messages indicating that the broadcasting tx may already be in the tx pool.
+const ALREADY_KNOWN_ERRORS: &[&str] = &[
+    "already known",
+    "known transaction",
+    "already imported",
+    "replacement transaction underpriced",
+    "replacement underpriced",
+    "nonce too low",
+];
+
+/// Returns true if `err` is a node response which may be caused by rebroadcasting the same tx.
+pub fn is_already_known_error(err: &RPCError) -> bool {
+    let message = err.message.to_lowercase();
+
+    ALREADY_KNOWN_ERRORS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Deterministic rejections of `eth_sendRawTransaction`, rebroadcasting the same tx fails again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxRejection {
+    /// Tx nonce is lower than the sender account nonce.
+    NonceTooLow,
+    /// Sender balance can't cover `gas * price + value`.
+    InsufficientFunds,
+    /// Gas price is lower than node minimum or block base fee, or replacement tx doesn't bump fees enough.
+    Underpriced,
+}
+
+impl TxRejection {
+    /// Classify node response `err` of `eth_sendRawTransaction`, [`None`] if it may be transient.
+    pub fn classify(err: &RPCError) -> Option<Self> {
+        let message = err.message.to_lowercase();
+
+        if message.contains("nonce too low") || message.contains("nonce has already been used") {
+            Some(Self::NonceTooLow)
+        } else if message.contains("insufficient funds") {
+            Some(Self::InsufficientFunds)
+        } else if message.contains("underpriced") || message.contains("less than block base fee") {
+            Some(Self::Underpriced)
+        } else {
+            None
+        }
+    }
+}
+
+impl Provider {
+    /// Submit a raw transaction, tolerating rebroadcasting of the same tx.
+    ///
+    /// Unlike [`eth_send_raw_transaction`](Provider::eth_send_raw_transaction),
+    /// "already known"/"replacement underpriced" node responses are treated as success
+    /// if the node knows the tx hash calculated from `raw`, which is verified by `eth_getTransactionByHash`.
+    /// [Deterministic rejections](TxRejection) are returned directly, other failures are retried up to `3` times.
+    ///
+    /// Returns the canonical hash of the tx, aka keccak256 of `raw`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                provider = %self.id,
+                tx_hash = tracing::field::Empty,
+                attempt = tracing::field::Empty
+            )
+        )
+    )]
+    pub async fn broadcast_raw_transaction<B>(&mut self, raw: B) -> RPCResult<H256>
+    where
+        B: TryInto<Bytes>,
+        B::Error: Debug + Display,
+    {
+        let raw: Bytes = raw.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        let tx_hash: H256 = keccak256(&raw.0).into();
+
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("tx_hash", tracing::field::display(&tx_hash));
+
+        let mut last_error = None;
+
+        for i in 0..BROADCAST_RETRY_TIMES {
+            #[cfg(feature = "tracing")]
+            tracing::Span::current().record("attempt", i);
+
+            match self.eth_send_raw_transaction(raw.clone()).await {
+                Ok(hash) => {
+                    if hash != tx_hash {
+                        log::warn!(
+                            "eth_sendRawTransaction returns hash {}, expect {}",
+                            hash,
+                            tx_hash
+                        );
+                    }
+
+                    return Ok(tx_hash);
+                }
+                Err(err) => {
+                    log::debug!("broadcast tx {} failed({}), {}", tx_hash, i, err.message);
+
+                    if let Ok(Some(_)) = self.eth_get_transaction_by_hash(tx_hash.clone()).await {
+                        log::debug!("broadcast tx {} already known by node", tx_hash);
+
+                        return Ok(tx_hash);
+                    }
+
+                    // The node rejected the tx, and it is not in the tx pool.
+                    if is_already_known_error(&err) || TxRejection::classify(&err).is_some() {
+                        return Err(err);
+                    }
+
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        Err(last_error.unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpc_rs::{ErrorCode, RPCError};
+    use serde_json::Value;
+
+    use crate::providers::mock::MockProvider;
+
+    use super::*;
+
+    fn error(message: &str) -> RPCError {
+        RPCError {
+            code: ErrorCode::InternalError,
+            message: message.to_owned(),
+            data: None,
+        }
+    }
+
+    #[test]
+    fn test_already_known_error() {
+        assert!(is_already_known_error(&error("already known")));
+        assert!(is_already_known_error(&error(
+            "ALREADY_EXISTS: already known"
+        )));
+        assert!(is_already_known_error(&error(
+            "replacement transaction underpriced"
+        )));
+        assert!(is_already_known_error(&error("Known transaction: 0x1234")));
+
+        assert!(!is_already_known_error(&error(
+            "insufficient funds for gas * price + value"
+        )));
+    }
+
+    #[test]
+    fn test_tx_rejection() {
+        let cases = [
+            (
+                "nonce too low: next nonce 3, tx nonce 2",
+                Some(TxRejection::NonceTooLow),
+            ),
+            (
+                "insufficient funds for gas * price + value",
+                Some(TxRejection::InsufficientFunds),
+            ),
+            ("transaction underpriced", Some(TxRejection::Underpriced)),
+            (
+                "max fee per gas less than block base fee",
+                Some(TxRejection::Underpriced),
+            ),
+            ("connection reset by peer", None),
+        ];
+
+        for (message, expect) in cases {
+            assert_eq!(
+                TxRejection::classify(&error(message)),
+                expect,
+                "{}",
+                message
+            );
+        }
+    }
+
+    #[async_std::test]
+    async fn test_broadcast_rejected() {
+        let mock = MockProvider::new();
+
+        mock.push_error(
+            "eth_sendRawTransaction",
+            error("insufficient funds for gas * price + value"),
+        )
+        .push("eth_getTransactionByHash", Value::Null)
+        .unwrap();
+
+        let err = mock
+            .provider()
+            .broadcast_raw_transaction("0x01")
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            TxRejection::classify(&err),
+            Some(TxRejection::InsufficientFunds)
+        );
+
+        // Not retried.
+        assert_eq!(mock.requests().len(), 2);
+
+        let mock = MockProvider::new();
+
+        mock.push_error("eth_sendRawTransaction", error("connection reset by peer"))
+            .push("eth_getTransactionByHash", Value::Null)
+            .unwrap()
+            .push("eth_sendRawTransaction", H256::default())
+            .unwrap();
+
+        mock.provider()
+            .broadcast_raw_transaction("0x01")
+            .await
+            .unwrap();
+
+        assert!(mock.pending().is_empty());
+    }
+}
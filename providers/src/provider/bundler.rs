@@ -0,0 +1,69 @@
+use std::fmt::{Debug, Display};
+
+use ethers_core::*;
+use jsonrpc_rs::RPCResult;
+
+use super::Provider;
+use crate::types::*;
+
+/// ERC-4337 bundler rpc methods, the provider must be connected to a bundler endpoint.
+impl Provider {
+    /// Submit `user_op` to bundler mempool, returns `userOpHash`.
+    pub async fn eth_send_user_operation<U, A>(
+        &mut self,
+        user_op: U,
+        entry_point: A,
+    ) -> RPCResult<H256>
+    where
+        U: TryInto<UserOperation>,
+        U::Error: Debug + Display,
+        A: TryInto<Address>,
+        A::Error: Debug + Display,
+    {
+        let user_op = user_op.try_into().map_err(jsonrpc_rs::map_error)?;
+        let entry_point = entry_point.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("eth_sendUserOperation", (user_op, entry_point))
+            .await
+    }
+
+    /// Estimate gas limits of `user_op`, signature may be a dummy one.
+    pub async fn eth_estimate_user_operation_gas<U, A>(
+        &mut self,
+        user_op: U,
+        entry_point: A,
+    ) -> RPCResult<UserOperationGasEstimate>
+    where
+        U: TryInto<UserOperation>,
+        U::Error: Debug + Display,
+        A: TryInto<Address>,
+        A::Error: Debug + Display,
+    {
+        let user_op = user_op.try_into().map_err(jsonrpc_rs::map_error)?;
+        let entry_point = entry_point.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("eth_estimateUserOperationGas", (user_op, entry_point))
+            .await
+    }
+
+    /// Returns receipt of user operation `user_op_hash`, [`None`] if not included yet.
+    pub async fn eth_get_user_operation_receipt<H>(
+        &mut self,
+        user_op_hash: H,
+    ) -> RPCResult<Option<UserOperationReceipt>>
+    where
+        H: TryInto<H256>,
+        H::Error: Debug + Display,
+    {
+        let user_op_hash = user_op_hash.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("eth_getUserOperationReceipt", vec![user_op_hash])
+            .await
+    }
+
+    /// Returns entry point addresses supported by bundler.
+    pub async fn eth_supported_entry_points(&mut self) -> RPCResult<Vec<Address>> {
+        self.cached_call("eth_supportedEntryPoints", Vec::<String>::new(), |_| true)
+            .await
+    }
+}
@@ -0,0 +1,280 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use ethers_runtime::Instant;
+use jsonrpc_rs::RPCResult;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use super::Provider;
+
+/// Default max entries of [`ResponseCache`]
+pub const DEFAULT_CACHE_SIZE: usize = 1024;
+
+/// LRU cache of immutable rpc query responses.
+///
+/// Only responses which can't change are cached:
+///
+/// - `eth_chainId`
+/// - `eth_getBlockByHash`, if the block is found
+/// - `eth_getTransactionByHash`, if the tx is mined
+/// - `eth_getCode`, if queried by block number
+///
+/// Enable it with [`Provider::with_cache`] or [`ProviderBuilder::cache`](crate::ProviderBuilder::cache).
+#[derive(Debug)]
+pub struct ResponseCache {
+    capacity: usize,
+    ttl: Option<Duration>,
+    inner: Mutex<CacheInner>,
+}
+
+#[derive(Debug, Default)]
+struct CacheInner {
+    entries: HashMap<String, CacheEntry>,
+    /// Keys by last access tick, the least recently used first.
+    order: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    value: Value,
+    expired_at: Option<Instant>,
+    /// Last access tick, key of `order`.
+    tick: u64,
+}
+
+impl CacheInner {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Mark `key` as the most recently used.
+    fn touch(&mut self, key: &str) {
+        let tick = self.next_tick();
+
+        if let Some(entry) = self.entries.get_mut(key) {
+            if let Some(key) = self.order.remove(&entry.tick) {
+                self.order.insert(tick, key);
+            }
+
+            entry.tick = tick;
+        }
+    }
+
+    fn insert(&mut self, key: String, value: Value, expired_at: Option<Instant>) {
+        let tick = self.next_tick();
+
+        let entry = CacheEntry {
+            value,
+            expired_at,
+            tick,
+        };
+
+        if let Some(prev) = self.entries.insert(key.clone(), entry) {
+            self.order.remove(&prev.tick);
+        }
+
+        self.order.insert(tick, key);
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.tick);
+        }
+    }
+
+    /// Evict the least recently used entries until at most `capacity` are left.
+    fn evict(&mut self, capacity: usize) {
+        while self.entries.len() > capacity {
+            match self.order.pop_first() {
+                Some((_, key)) => {
+                    self.entries.remove(&key);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_SIZE)
+    }
+}
+
+impl ResponseCache {
+    /// Create cache holding at most `capacity` responses, entries never expire.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ttl: None,
+            inner: Default::default(),
+        }
+    }
+
+    /// Set time to live of cached responses.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Returns cached entries number, including expired ones not evicted yet.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all cached responses.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.entries.clear();
+        inner.order.clear();
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<Value> {
+        let mut inner = self.inner.lock().unwrap();
+
+        let entry = inner.entries.get(key)?;
+
+        if matches!(entry.expired_at, Some(expired_at) if expired_at <= Instant::now()) {
+            inner.remove(key);
+            return None;
+        }
+
+        let value = entry.value.clone();
+
+        inner.touch(key);
+
+        Some(value)
+    }
+
+    pub(crate) fn insert(&self, key: String, value: Value) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let expired_at = self.ttl.map(|ttl| Instant::now() + ttl);
+
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.insert(key, value, expired_at);
+
+        inner.evict(self.capacity);
+    }
+}
+
+impl Provider {
+    /// Enable response cache for immutable queries, the cache is shared by cloned providers.
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Returns response cache if enabled.
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_deref()
+    }
+
+    /// Call `method` and cache the response if `immutable` returns true.
+    pub(crate) async fn cached_call<P, R, F>(
+        &mut self,
+        method: &'static str,
+        params: P,
+        immutable: F,
+    ) -> RPCResult<R>
+    where
+        P: Serialize,
+        R: Serialize + DeserializeOwned,
+        F: FnOnce(&R) -> bool,
+    {
+        let cache = match self.cache.clone() {
+            Some(cache) => cache,
+            None => return self.call(method, params).await,
+        };
+
+        let key = format!(
+            "{}{}",
+            method,
+            serde_json::to_string(&params).map_err(jsonrpc_rs::map_error)?
+        );
+
+        if let Some(value) = cache.get(&key) {
+            if let Ok(result) = serde_json::from_value(value) {
+                log::trace!("cache hit, {}", key);
+                return Ok(result);
+            }
+        }
+
+        let result: R = self.call(method, params).await?;
+
+        if immutable(&result) {
+            if let Ok(value) = serde_json::to_value(&result) {
+                cache.insert(key, value);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::ResponseCache;
+
+    #[test]
+    fn test_lru_evict() {
+        let cache = ResponseCache::new(2);
+
+        cache.insert("a".to_owned(), json!(1));
+        cache.insert("b".to_owned(), json!(2));
+
+        // `a` becomes the most recently used.
+        assert_eq!(cache.get("a"), Some(json!(1)));
+
+        cache.insert("c".to_owned(), json!(3));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("a"), Some(json!(1)));
+        assert_eq!(cache.get("c"), Some(json!(3)));
+
+        // Replacing `a` makes it the most recently used.
+        cache.insert("a".to_owned(), json!(4));
+        cache.insert("d".to_owned(), json!(5));
+
+        assert_eq!(cache.get("c"), None);
+        assert_eq!(cache.get("a"), Some(json!(4)));
+        assert_eq!(cache.get("d"), Some(json!(5)));
+
+        cache.clear();
+
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_ttl() {
+        let cache = ResponseCache::new(2).ttl(Duration::from_millis(10));
+
+        cache.insert("a".to_owned(), json!(1));
+
+        assert_eq!(cache.get("a"), Some(json!(1)));
+
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(cache.get("a"), None);
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt::{Debug, Display};
+
+use ethers_core::*;
+use jsonrpc_rs::RPCResult;
+
+use super::Provider;
+
+/// Development node rpc methods, supported by hardhat network/anvil/ganache.
+impl Provider {
+    /// Snapshot the state of the blockchain at the current block, returns snapshot id.
+    pub async fn evm_snapshot(&mut self) -> RPCResult<U256> {
+        self.call("evm_snapshot", Vec::<String>::new()).await
+    }
+
+    /// Revert the state of the blockchain to a previous snapshot.
+    ///
+    /// Snapshot can be reverted only once, take a new snapshot after reverting if needed.
+    pub async fn evm_revert<N>(&mut self, snapshot_id: N) -> RPCResult<bool>
+    where
+        N: TryInto<U256>,
+        N::Error: Debug + Display,
+    {
+        let snapshot_id = snapshot_id.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("evm_revert", vec![snapshot_id]).await
+    }
+
+    /// Jump forward in time by `seconds`, returns the total time adjustment in seconds.
+    pub async fn evm_increase_time(&mut self, seconds: u64) -> RPCResult<u64> {
+        self.call("evm_increaseTime", vec![seconds]).await
+    }
+
+    /// Set the timestamp of the next block.
+    pub async fn evm_set_next_block_timestamp(&mut self, timestamp: u64) -> RPCResult<()> {
+        let _: serde_json::Value = self
+            .call("evm_setNextBlockTimestamp", vec![timestamp])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Force a block to be mined.
+    pub async fn evm_mine(&mut self) -> RPCResult<()> {
+        let _: serde_json::Value = self.call("evm_mine", Vec::<String>::new()).await?;
+
+        Ok(())
+    }
+
+    /// Modify the balance of an account.
+    pub async fn hardhat_set_balance<A, B>(&mut self, address: A, balance: B) -> RPCResult<bool>
+    where
+        A: TryInto<Address>,
+        A::Error: Debug + Display,
+        B: TryInto<U256>,
+        B::Error: Debug + Display,
+    {
+        let address = address.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        let balance = balance.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("hardhat_setBalance", (address, balance)).await
+    }
+
+    /// Send transactions as the given `address` without its private key.
+    pub async fn hardhat_impersonate_account<A>(&mut self, address: A) -> RPCResult<bool>
+    where
+        A: TryInto<Address>,
+        A::Error: Debug + Display,
+    {
+        let address = address.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("hardhat_impersonateAccount", vec![address]).await
+    }
+
+    /// Stop impersonating the given `address`.
+    pub async fn hardhat_stop_impersonating_account<A>(&mut self, address: A) -> RPCResult<bool>
+    where
+        A: TryInto<Address>,
+        A::Error: Debug + Display,
+    {
+        let address = address.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("hardhat_stopImpersonatingAccount", vec![address])
+            .await
+    }
+
+    /// Write `value` into storage `slot` of `address`.
+    pub async fn hardhat_set_storage_at<A, P, V>(
+        &mut self,
+        address: A,
+        slot: P,
+        value: V,
+    ) -> RPCResult<bool>
+    where
+        A: TryInto<Address>,
+        A::Error: Debug + Display,
+        P: TryInto<U256>,
+        P::Error: Debug + Display,
+        V: TryInto<H256>,
+        V::Error: Debug + Display,
+    {
+        let address = address.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        let slot = slot.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        let value = value.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("hardhat_setStorageAt", (address, slot, value))
+            .await
+    }
+
+    /// Replace runtime code of `address`.
+    pub async fn hardhat_set_code<A, C>(&mut self, address: A, code: C) -> RPCResult<bool>
+    where
+        A: TryInto<Address>,
+        A::Error: Debug + Display,
+        C: TryInto<Bytes>,
+        C::Error: Debug + Display,
+    {
+        let address = address.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        let code = code.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("hardhat_setCode", (address, code)).await
+    }
+}
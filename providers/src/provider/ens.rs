@@ -0,0 +1,127 @@
+use std::fmt::{Debug, Display};
+
+use ethers_core::*;
+use jsonrpc_rs::RPCResult;
+
+use super::Provider;
+use crate::{error::ProviderError, types::BlockNumberOrTag};
+
+/// ENS registry address, deployed at the same address on mainnet and testnets.
+pub const ENS_REGISTRY_ADDRESS: &str = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+
+/// Selector of registry method `resolver(bytes32)`.
+const RESOLVER_SELECTOR: [u8; 4] = [0x01, 0x78, 0xb8, 0xbf];
+
+/// Selector of resolver method `addr(bytes32)`.
+const ADDR_SELECTOR: [u8; 4] = [0x3b, 0x3b, 0x57, 0xde];
+
+/// Returns ENS namehash of `name`, labels are lowercased before hashing.
+pub fn namehash(name: &str) -> H256 {
+    let mut node = [0u8; 32];
+
+    if name.is_empty() {
+        return node.into();
+    }
+
+    for label in name.rsplit('.') {
+        node = keccak256_concat(&[&node, &keccak256(label.to_lowercase())]);
+    }
+
+    node.into()
+}
+
+/// ENS name resolving
+impl Provider {
+    /// Set ENS registry `address` used to resolve [`NameOrAddress::Name`], see [`ENS_REGISTRY_ADDRESS`].
+    pub fn with_ens(mut self, registry: Address) -> Self {
+        self.ens = Some(registry);
+        self
+    }
+
+    /// Resolve ENS `name` to address via registry resolver `addr(bytes32)`.
+    pub async fn resolve_name(&mut self, name: &str) -> RPCResult<Address> {
+        let registry = self
+            .ens
+            .ok_or_else(|| jsonrpc_rs::map_error(ProviderError::EnsNotConfigured))?;
+
+        let node = namehash(name);
+
+        let resolver = self.ens_query(registry, RESOLVER_SELECTOR, &node).await?;
+
+        if resolver.is_zero() {
+            return Err(jsonrpc_rs::map_error(ProviderError::EnsName(
+                name.to_owned(),
+            )));
+        }
+
+        let address = self.ens_query(resolver, ADDR_SELECTOR, &node).await?;
+
+        if address.is_zero() {
+            return Err(jsonrpc_rs::map_error(ProviderError::EnsName(
+                name.to_owned(),
+            )));
+        }
+
+        Ok(address)
+    }
+
+    /// Convert `name_or_address` into address, resolving ENS names.
+    pub async fn resolve<A>(&mut self, name_or_address: A) -> RPCResult<Address>
+    where
+        A: TryInto<NameOrAddress>,
+        A::Error: Debug + Display,
+    {
+        match name_or_address.try_into().map_err(jsonrpc_rs::map_error)? {
+            NameOrAddress::Address(address) => Ok(address),
+            NameOrAddress::Name(name) => self.resolve_name(&name).await,
+        }
+    }
+
+    /// Call `selector(bytes32 node)` of `to`, returns address result.
+    async fn ens_query(
+        &mut self,
+        to: Address,
+        selector: [u8; 4],
+        node: &H256,
+    ) -> RPCResult<Address> {
+        let mut data = selector.to_vec();
+
+        data.extend_from_slice(&node.0);
+
+        let tx = LegacyTransactionRequest {
+            to: Some(to),
+            data: Some(data.into()),
+            ..Default::default()
+        };
+
+        let result = self.eth_call(tx, None::<BlockNumberOrTag>).await?;
+
+        if result.0.len() < 32 {
+            return Ok(Address::ZERO);
+        }
+
+        Ok(Address(result.0[12..32].try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namehash() {
+        assert_eq!(namehash(""), H256::from([0u8; 32]));
+
+        assert_eq!(
+            namehash("eth").to_string(),
+            "0x93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"
+        );
+
+        assert_eq!(
+            namehash("foo.eth").to_string(),
+            "0xde9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"
+        );
+
+        assert_eq!(namehash("Foo.ETH"), namehash("foo.eth"));
+    }
+}
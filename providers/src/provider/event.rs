@@ -1,67 +1,27 @@
 use std::{fmt::Display, sync::Arc, time::Duration};
 
-use async_timer_rs::{hashed::Timeout, Timer};
-use completeq_rs::{error::CompleteQError, result::EmitResult, user_event::UserEvent};
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt, StreamExt,
+};
 
-use futures::{executor::ThreadPool, task::SpawnExt};
-use once_cell::sync::OnceCell;
-
-use super::Provider;
+use super::{Provider, TxReverted};
 use crate::types::*;
-use ethers_primitives::*;
-
-/// Ether client support event types
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum EventType {
-    /// Transaction mint event
-    Transaction(H256),
-    /// filter.
-    Filter(Filter),
+use ethers_core::*;
+
+/// Event listener registered to provider, moved into events poller by next poll.
+pub(crate) enum EventListener {
+    /// Transaction mint listener, completed with [`TransactionReceipt`]
+    Transaction(H256, oneshot::Sender<TransactionReceipt>),
+    /// Filter listener, fed with `eth_getLogs` results.
+    Filter(Filter, mpsc::Sender<Vec<Log>>),
 }
 
-impl Display for EventType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Transaction(hash) => {
-                write!(f, "event_tx: {}", hash)
-            }
-            Self::Filter(filter) => {
-                write!(f, "event_filter: {:?}", filter)
-            }
-        }
-    }
-}
-
-/// Event emit arg
-pub enum EventArg {
-    /// Transaction mint event return value [`TransactionReceipt`]
-    Transaction(TransactionReceipt),
-    /// `get_ethLogs`/`eth_getFilterLogs` returns
-    Log(Vec<Log>),
-}
-
-#[derive(Default, Clone)]
-pub struct Event;
-
-impl UserEvent for Event {
-    type Argument = EventArg;
-    type ID = EventType;
-}
-
-pub(crate) type OneshotCompleteQ = completeq_rs::oneshot::CompleteQ<Event>;
-pub(crate) type ChannelCompleteQ = completeq_rs::channel::CompleteQ<Event>;
-
 impl Provider {
     pub(crate) fn start_event_poll(&self) {
-        static THREAD_POOL: OnceCell<ThreadPool> = OnceCell::new();
-
         let mut poller = Poller::new(self.clone());
 
-        let thread_pool = THREAD_POOL.get_or_init(|| ThreadPool::new().unwrap());
-
-        thread_pool
-            .spawn(async move { poller.poll_loop().await })
-            .unwrap();
+        ethers_runtime::spawn(async move { poller.poll_loop().await });
     }
 
     pub fn register_filter_listener<A, T>(
@@ -94,14 +54,14 @@ impl Provider {
             topics: topic_filter,
         };
 
-        let event_type = EventType::Filter(filter.clone());
+        let (sender, receiver) = mpsc::channel(100);
 
-        self.events.lock().unwrap().push(event_type.clone());
+        self.events
+            .lock()
+            .unwrap()
+            .push(EventListener::Filter(filter.clone(), sender));
 
-        Ok(FilterReceiver {
-            filter,
-            receiver: self.channel.wait_for(event_type, 100),
-        })
+        Ok(FilterReceiver { filter, receiver })
     }
 
     pub fn register_transaction_listener<H>(
@@ -114,13 +74,18 @@ impl Provider {
     {
         let tx_hash = tx_hash.try_into()?;
 
-        let event_type = EventType::Transaction(tx_hash.clone());
+        let (sender, receiver) = oneshot::channel();
 
-        self.events.lock().unwrap().push(event_type.clone());
+        self.events
+            .lock()
+            .unwrap()
+            .push(EventListener::Transaction(tx_hash.clone(), sender));
 
         Ok(TransactionReceipter {
             tx: tx_hash,
-            receiver: self.oneshot.wait_for(event_type),
+            receiver,
+            provider: self.clone(),
+            check_status: self.check_receipt_status,
         })
     }
 }
@@ -130,8 +95,8 @@ struct Poller {
     last_poll_block_number: U256,
     poll_interval_duration: Duration,
     provider: Provider,
-    tx_listeners: Vec<H256>,
-    filter_listeners: Vec<Filter>,
+    tx_listeners: Vec<(H256, oneshot::Sender<TransactionReceipt>)>,
+    filter_listeners: Vec<(Filter, mpsc::Sender<Vec<Log>>)>,
 }
 
 impl Poller {
@@ -164,9 +129,7 @@ impl Poller {
                 self.poll_interval_duration
             );
 
-            let timer = Timeout::new(self.poll_interval_duration);
-
-            timer.await;
+            ethers_runtime::sleep(self.poll_interval_duration).await;
         }
     }
 
@@ -241,25 +204,32 @@ impl Poller {
 
         for listener in new_listeners {
             match listener {
-                EventType::Transaction(tx_hash) => {
-                    self.tx_listeners.push(tx_hash);
+                EventListener::Transaction(tx_hash, sender) => {
+                    self.tx_listeners.push((tx_hash, sender));
                 }
-                EventType::Filter(filter_id) => {
-                    self.filter_listeners.push(filter_id);
+                EventListener::Filter(filter, sender) => {
+                    self.filter_listeners.push((filter, sender));
                 }
             }
         }
     }
 
     async fn fetch_and_emit_tx_events(&mut self) -> anyhow::Result<()> {
+        let mut receipts = vec![];
+
+        // Fetch all receipts before taking listeners, so no listener is lost on error.
+        for (tx_hash, _) in &self.tx_listeners {
+            receipts.push(
+                self.provider
+                    .eth_get_transaction_receipt(tx_hash.clone())
+                    .await?,
+            );
+        }
+
         let mut remaining = vec![];
 
-        for tx_hash in &self.tx_listeners {
-            match self
-                .provider
-                .eth_get_transaction_receipt(tx_hash.clone())
-                .await?
-            {
+        for ((tx_hash, sender), receipt) in self.tx_listeners.drain(..).zip(receipts) {
+            match receipt {
                 Some(receipt) => {
                     log::trace!(
                         "Get tx {} receipt returns {}",
@@ -268,14 +238,11 @@ impl Poller {
                     );
 
                     // Oneshot event ignore to checking returns value
-                    self.provider.oneshot.complete_one(
-                        EventType::Transaction(tx_hash.clone()),
-                        EventArg::Transaction(receipt),
-                    );
+                    let _ = sender.send(receipt);
                 }
                 None => {
                     log::trace!("Get tx receipt return None");
-                    remaining.push(tx_hash.clone());
+                    remaining.push((tx_hash, sender));
                 }
             }
         }
@@ -286,9 +253,7 @@ impl Poller {
     }
 
     async fn fetch_and_emit_filter_events(&mut self, block_number: U256) -> anyhow::Result<()> {
-        let mut remaining = vec![];
-
-        for filter in &self.filter_listeners {
+        for (filter, sender) in &mut self.filter_listeners {
             let mut filter_send = filter.clone();
 
             let from_block = if block_number > self.max_filter_block_range {
@@ -312,29 +277,22 @@ impl Poller {
 
             match logs {
                 FilterEvents::Logs(logs) if logs.len() > 0 => {
-                    let result = self
-                        .provider
-                        .channel
-                        .complete_one(EventType::Filter(filter.clone()), EventArg::Log(logs))
-                        .await;
-
-                    match result {
-                        EmitResult::Closed => {
-                            log::debug!("Remove filter listener {:?}", filter);
-                            continue;
-                        }
-                        _ => {}
-                    }
+                    // Returns error if the receiver is dropped, which is removed below.
+                    let _ = sender.send(logs).await;
                 }
                 _ => {
                     log::debug!("poll empty logs for filter {:?}", filter);
                 }
             }
-
-            remaining.push(filter.clone());
         }
 
-        self.filter_listeners = remaining;
+        self.filter_listeners.retain(|(filter, sender)| {
+            if sender.is_closed() {
+                log::debug!("Remove filter listener {:?}", filter);
+            }
+
+            !sender.is_closed()
+        });
 
         Ok(())
     }
@@ -349,52 +307,69 @@ impl Poller {
 }
 
 /// Transaction instance provide extra wait fn
-pub struct TransactionReceipter<T: Timer> {
+pub struct TransactionReceipter {
     /// Transaction id
     pub tx: H256,
 
-    receiver: completeq_rs::oneshot::EventReceiver<Event, T>,
+    receiver: oneshot::Receiver<TransactionReceipt>,
+
+    provider: Provider,
+
+    check_status: bool,
 }
 
-impl<T: Timer> TransactionReceipter<T>
-where
-    T: Unpin,
-{
+impl TransactionReceipter {
+    /// Enable or disable receipt status check of [`wait`](Self::wait), default is
+    /// [`Provider::with_receipt_status_check`] setting.
+    pub fn check_status(mut self, enabled: bool) -> Self {
+        self.check_status = enabled;
+        self
+    }
+
+    /// Wait transaction mint and returns receipt.
+    ///
+    /// If status check is enabled, failed transaction returns [`TxReverted`] error with
+    /// revert reason replayed by `eth_call` at the inclusion block.
     pub async fn wait(&mut self) -> anyhow::Result<TransactionReceipt> {
-        let value = (&mut self.receiver).await.success()?;
+        // Canceled if the events poller is stopped.
+        let receipt = (&mut self.receiver).await?;
 
-        match value {
-            Some(EventArg::Transaction(receipt)) => Ok(receipt),
-            None => Err(CompleteQError::PipeBroken.into()),
-            _ => {
-                panic!("Inner error, returns event arg type error!!!")
-            }
+        if !self.check_status || !matches!(receipt.status, Some(Status::Failure)) {
+            return Ok(receipt);
         }
+
+        let reason = self
+            .provider
+            .replay_revert_reason(self.tx.clone(), receipt.block_number)
+            .await
+            .unwrap_or_else(|err| {
+                log::debug!("Replay reverted tx {} failed, {}", self.tx, err);
+                None
+            });
+
+        Err(TxReverted {
+            tx_hash: self.tx.clone(),
+            block_number: receipt.block_number,
+            reason,
+            receipt,
+        }
+        .into())
     }
 }
 
-pub type DefaultTransactionReceipter = TransactionReceipter<Timeout>;
+pub type DefaultTransactionReceipter = TransactionReceipter;
 
-pub struct FilterReceiver<T: Timer> {
+pub struct FilterReceiver {
     pub filter: Filter,
 
-    receiver: completeq_rs::channel::EventReceiver<Event, T>,
+    receiver: mpsc::Receiver<Vec<Log>>,
 }
 
-impl<T: Timer> FilterReceiver<T>
-where
-    T: Unpin,
-{
+impl FilterReceiver {
+    /// Returns next non-empty logs batch, or `None` if the events poller is stopped.
     pub async fn try_next(&mut self) -> anyhow::Result<Option<Vec<Log>>> {
-        Ok((&mut self.receiver).await.success().map(|c| {
-            c.map(|c| match c {
-                EventArg::Log(logs) => logs,
-                _ => {
-                    panic!("Inner error, returns event arg type error!!!")
-                }
-            })
-        })?)
+        Ok(self.receiver.next().await)
     }
 }
 
-pub type DefaultFilterReceiver = FilterReceiver<Timeout>;
+pub type DefaultFilterReceiver = FilterReceiver;
@@ -0,0 +1,176 @@
+use ethers_core::*;
+use jsonrpc_rs::RPCResult;
+
+use crate::FeeHistory;
+
+use super::Provider;
+
+/// Default number of recent blocks sampled by [`FeeEstimator`].
+pub const DEFAULT_FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// Reward percentiles requested from `eth_feeHistory`, indexed by [`Priority`].
+const REWARD_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+
+/// Fee suggestion preset of [`FeeEstimator::suggest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Priority {
+    /// 10th percentile priority fee, max fee covers 10% base fee increase.
+    Slow,
+    /// 50th percentile priority fee, max fee covers 50% base fee increase.
+    #[default]
+    Standard,
+    /// 90th percentile priority fee, max fee covers doubled base fee.
+    Fast,
+}
+
+impl Priority {
+    fn reward_index(&self) -> usize {
+        match self {
+            Self::Slow => 0,
+            Self::Standard => 1,
+            Self::Fast => 2,
+        }
+    }
+
+    /// Base fee multiplier in percent.
+    fn base_fee_percent(&self) -> u128 {
+        match self {
+            Self::Slow => 110,
+            Self::Standard => 150,
+            Self::Fast => 200,
+        }
+    }
+}
+
+/// EIP-1559 fee pair returned by [`FeeEstimator::suggest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeSuggestion {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+/// EIP-1559 fee estimator based on `eth_feeHistory` of recent blocks.
+///
+/// ```no_run
+/// # async fn example(provider: ethers_provider::Provider) -> jsonrpc_rs::RPCResult<()> {
+/// use ethers_provider::{FeeEstimator, Priority};
+///
+/// let fee = FeeEstimator::new(provider).suggest(Priority::Fast).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FeeEstimator {
+    provider: Provider,
+    blocks: u64,
+}
+
+impl FeeEstimator {
+    /// Create estimator sampling [`DEFAULT_FEE_HISTORY_BLOCKS`] recent blocks.
+    pub fn new(provider: Provider) -> Self {
+        Self {
+            provider,
+            blocks: DEFAULT_FEE_HISTORY_BLOCKS,
+        }
+    }
+
+    /// Set number of recent blocks to sample.
+    pub fn blocks(mut self, blocks: u64) -> Self {
+        self.blocks = blocks;
+        self
+    }
+
+    /// Returns max fee and max priority fee per gas for `priority`.
+    pub async fn suggest(&mut self, priority: Priority) -> RPCResult<FeeSuggestion> {
+        let history = self
+            .provider
+            .eth_fee_history(self.blocks, "latest", REWARD_PERCENTILES)
+            .await?;
+
+        Ok(suggest_from_history(&history, priority))
+    }
+}
+
+/// Calculate fee suggestion from fee `history`.
+///
+/// Priority fee is the median of per block reward percentile selected by `priority`,
+/// max fee is the pending block base fee multiplied by preset multiplier, plus priority fee.
+pub fn suggest_from_history(history: &FeeHistory, priority: Priority) -> FeeSuggestion {
+    let mut rewards = history
+        .reward
+        .iter()
+        .filter_map(|rewards| rewards.get(priority.reward_index()))
+        .map(to_u128)
+        .collect::<Vec<_>>();
+
+    rewards.sort_unstable();
+
+    let priority_fee = rewards.get(rewards.len() / 2).copied().unwrap_or(0);
+
+    // The last item is the base fee of the pending block.
+    let base_fee = history.base_fee_per_gas.last().map(to_u128).unwrap_or(0);
+
+    let max_fee =
+        (base_fee.saturating_mul(priority.base_fee_percent()) / 100).saturating_add(priority_fee);
+
+    FeeSuggestion {
+        max_fee_per_gas: max_fee.into(),
+        max_priority_fee_per_gas: priority_fee.into(),
+    }
+}
+
+fn to_u128(value: &U256) -> u128 {
+    Option::<u128>::from(*value).unwrap_or(u128::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> FeeHistory {
+        let u = |v: u64| U256::from(v);
+
+        FeeHistory {
+            oldest_block: u(100),
+            base_fee_per_gas: vec![u(90), u(95), u(100), u(100)],
+            reward: vec![
+                vec![u(1), u(2), u(5)],
+                vec![u(1), u(3), u(9)],
+                vec![u(2), u(4), u(7)],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_suggest_from_history() {
+        let history = history();
+
+        let slow = suggest_from_history(&history, Priority::Slow);
+
+        assert_eq!(slow.max_priority_fee_per_gas, U256::from(1u64));
+        assert_eq!(slow.max_fee_per_gas, U256::from(111u64));
+
+        let standard = suggest_from_history(&history, Priority::Standard);
+
+        assert_eq!(standard.max_priority_fee_per_gas, U256::from(3u64));
+        assert_eq!(standard.max_fee_per_gas, U256::from(153u64));
+
+        let fast = suggest_from_history(&history, Priority::Fast);
+
+        assert_eq!(fast.max_priority_fee_per_gas, U256::from(7u64));
+        assert_eq!(fast.max_fee_per_gas, U256::from(207u64));
+    }
+
+    #[test]
+    fn test_suggest_empty_history() {
+        let history = FeeHistory {
+            oldest_block: U256::from(0u64),
+            base_fee_per_gas: vec![],
+            reward: vec![],
+        };
+
+        let fee = suggest_from_history(&history, Priority::Standard);
+
+        assert_eq!(fee.max_fee_per_gas, U256::from(0u64));
+    }
+}
@@ -0,0 +1,283 @@
+use jsonrpc_rs::{ErrorCode, RPCError, RPCResult};
+
+use super::Provider;
+use crate::types::*;
+
+/// Default max bisecting depth of `eth_getLogs` block range, see [`Provider::with_max_log_splits`].
+pub const DEFAULT_MAX_LOG_SPLITS: usize = 8;
+
+/// Error messages of providers rejecting too large `eth_getLogs` queries.
+const RANGE_TOO_LARGE_MESSAGES: &[&str] = &[
+    "query returned more than",
+    "block range",
+    "range is too",
+    "response size",
+];
+
+/// Returns true if `error` is returned for too large `eth_getLogs` query.
+pub fn is_log_range_too_large(error: &RPCError) -> bool {
+    let message = error.message.to_lowercase();
+
+    RANGE_TOO_LARGE_MESSAGES
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Split inclusive block range `[from, to]` into two halves, returns [`None`] if range is a single block.
+fn bisect(from: u64, to: u64) -> Option<((u64, u64), (u64, u64))> {
+    if from >= to {
+        return None;
+    }
+
+    let mid = from + (to - from) / 2;
+
+    Some(((from, mid), (mid + 1, to)))
+}
+
+impl Provider {
+    /// Set max bisecting depth of `eth_getLogs` block range, `0` disables range splitting.
+    ///
+    /// Queries rejected for being too large are split into at most `2^depth` sub-ranges.
+    pub fn with_max_log_splits(mut self, depth: usize) -> Self {
+        self.max_log_splits = depth;
+        self
+    }
+
+    /// Query logs of `filter`, bisecting block range and merging results if provider rejects the query as too large.
+    pub(crate) async fn get_logs_with_split(&mut self, filter: Filter) -> RPCResult<FilterEvents> {
+        let err = match self.call("eth_getLogs", vec![&filter]).await {
+            Err(err) if self.max_log_splits > 0 && is_log_range_too_large(&err) => err,
+            result => return result,
+        };
+
+        let from = match filter.from_block.map(Option::<u64>::from) {
+            Some(Some(from)) => from,
+            _ => return Err(err),
+        };
+
+        let to = match filter.to_block {
+            Some(to) => to,
+            None => self.eth_block_number().await?,
+        };
+
+        let to = match Option::<u64>::from(to) {
+            Some(to) => to,
+            None => return Err(err),
+        };
+
+        let (low, high) = match bisect(from, to) {
+            Some(halves) => halves,
+            None => return Err(err),
+        };
+
+        log::debug!(
+            "eth_getLogs range {}..={} too large, split, {}",
+            from,
+            to,
+            err.message
+        );
+
+        // Stack of ranges to query, lower range is popped first to keep logs order.
+        let mut ranges = vec![(high, 1usize), (low, 1usize)];
+
+        let mut logs = vec![];
+
+        while let Some(((from, to), depth)) = ranges.pop() {
+            let mut chunk = filter.clone();
+
+            chunk.from_block = Some(from.into());
+            chunk.to_block = Some(to.into());
+
+            match self.call("eth_getLogs", vec![&chunk]).await {
+                Ok(FilterEvents::Logs(mut chunk)) => logs.append(&mut chunk),
+                // Empty array is deserialized as the first untagged variant.
+                Ok(FilterEvents::BlocksOrTransactions(hashes)) if hashes.is_empty() => {}
+                // Returning the chunk would drop logs collected so far.
+                Ok(_) => {
+                    return Err(RPCError {
+                        code: ErrorCode::InternalError,
+                        message: format!(
+                            "eth_getLogs returns non-log events for range {}..={}",
+                            from, to
+                        ),
+                        data: None,
+                    })
+                }
+                Err(err) if depth < self.max_log_splits && is_log_range_too_large(&err) => {
+                    let (low, high) = bisect(from, to).ok_or(err)?;
+
+                    log::debug!("eth_getLogs range {}..={} too large, split", from, to);
+
+                    ranges.push((high, depth + 1));
+                    ranges.push((low, depth + 1));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(FilterEvents::Logs(logs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers_core::U256;
+    use serde_json::{json, Value};
+
+    use crate::providers::mock::MockProvider;
+
+    use super::*;
+
+    fn too_large() -> RPCError {
+        RPCError {
+            code: ErrorCode::InternalError,
+            message: "query returned more than 10000 results".to_owned(),
+            data: None,
+        }
+    }
+
+    fn log_json(block: u64) -> Value {
+        json!({
+            "removed": false,
+            "logIndex": "0x0",
+            "transactionIndex": "0x0",
+            "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000002",
+            "blockNumber": format!("{:#x}", block),
+            "address": "0x0000000000000000000000000000000000000000",
+            "data": "0x",
+            "topics": []
+        })
+    }
+
+    /// Returns block range of `eth_getLogs` params.
+    fn range(params: &Value) -> (u64, u64) {
+        let number = |field: &str| {
+            let number: U256 = serde_json::from_value(params[0][field].clone()).unwrap();
+
+            Option::<u64>::from(number).unwrap()
+        };
+
+        (number("fromBlock"), number("toBlock"))
+    }
+
+    /// Enqueue `calls` of `eth_getLogs` rejecting ranges wider than `max_blocks`,
+    /// accepted range returns one log per block.
+    fn mock_logs(calls: usize, max_blocks: u64) -> MockProvider {
+        let mock = MockProvider::new();
+
+        for _ in 0..calls {
+            mock.push_fn("eth_getLogs", move |params| {
+                let (from, to) = range(&params);
+
+                if to - from + 1 > max_blocks {
+                    return Err(too_large());
+                }
+
+                Ok(Value::Array((from..=to).map(log_json).collect()))
+            });
+        }
+
+        mock
+    }
+
+    fn filter() -> Filter {
+        serde_json::from_value(json!({ "fromBlock": "0x0", "toBlock": "0x7" })).unwrap()
+    }
+
+    #[async_std::test]
+    async fn test_get_logs_with_split() {
+        // 0..=7, 0..=3, 0..=1, 2..=3, 4..=7, 4..=5, 6..=7
+        let mock = mock_logs(7, 2);
+
+        let logs = match mock.provider().eth_get_logs(filter()).await.unwrap() {
+            FilterEvents::Logs(logs) => logs,
+            events => panic!("unexpected {:?}", events),
+        };
+
+        assert_eq!(
+            logs.iter().map(|log| log.block_number).collect::<Vec<_>>(),
+            (0..8u64).map(U256::from).collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            mock.requests()
+                .iter()
+                .map(|(_, params)| range(params))
+                .collect::<Vec<_>>(),
+            vec![(0, 7), (0, 3), (0, 1), (2, 3), (4, 7), (4, 5), (6, 7)]
+        );
+
+        assert!(mock.pending().is_empty());
+    }
+
+    #[async_std::test]
+    async fn test_max_log_splits() {
+        let mock = mock_logs(2, 2);
+
+        let err = mock
+            .provider()
+            .with_max_log_splits(1)
+            .eth_get_logs(filter())
+            .await
+            .unwrap_err();
+
+        assert!(is_log_range_too_large(&err));
+
+        // 0..=7 split once, 0..=3 is not split again.
+        assert_eq!(mock.requests().len(), 2);
+
+        let mock = mock_logs(1, 2);
+
+        mock.provider()
+            .with_max_log_splits(0)
+            .eth_get_logs(filter())
+            .await
+            .unwrap_err();
+
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[async_std::test]
+    async fn test_non_log_chunk() {
+        let mock = MockProvider::new();
+
+        mock.push_error("eth_getLogs", too_large())
+            .push_fn("eth_getLogs", |_| Ok(json!([log_json(0)])))
+            .push(
+                "eth_getLogs",
+                ["0x0000000000000000000000000000000000000000000000000000000000000003"],
+            )
+            .unwrap();
+
+        let err = mock.provider().eth_get_logs(filter()).await.unwrap_err();
+
+        assert!(err.message.contains("non-log events"), "{}", err.message);
+    }
+
+    #[test]
+    fn test_bisect() {
+        assert_eq!(bisect(0, 9), Some(((0, 4), (5, 9))));
+        assert_eq!(bisect(10, 11), Some(((10, 10), (11, 11))));
+        assert_eq!(bisect(7, 7), None);
+    }
+
+    #[test]
+    fn test_range_too_large() {
+        let error = |message: &str| RPCError {
+            code: ErrorCode::InternalError,
+            message: message.to_owned(),
+            data: None,
+        };
+
+        assert!(is_log_range_too_large(&error(
+            "query returned more than 10000 results"
+        )));
+
+        assert!(is_log_range_too_large(&error(
+            "eth_getLogs is limited to a 10,000 block range"
+        )));
+
+        assert!(!is_log_range_too_large(&error("execution reverted")));
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use jsonrpc_rs::{RPCError, RPCResult};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::Provider;
+
+/// Instrumentation hooks invoked around each rpc call of [`Provider`].
+///
+/// Responses served by [`ResponseCache`](super::ResponseCache) are not reported.
+pub trait RpcInstrument: Send + Sync {
+    /// Called after rpc `method` completed, `error` is [`None`] if the call succeeded.
+    fn on_response(&self, method: &str, elapsed: Duration, error: Option<&RPCError>);
+}
+
+impl Provider {
+    /// Report each rpc call of this provider to `instrument`, shared by cloned providers.
+    ///
+    /// Call latency is measured by [`Instant`], which is unsupported on `wasm32` targets.
+    pub fn with_instrument<I>(mut self, instrument: I) -> Self
+    where
+        I: RpcInstrument + 'static,
+    {
+        self.instrument = Some(Arc::new(instrument));
+        self
+    }
+
+    /// Send rpc request `method`, and report it to instrument if any.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "rpc", skip_all, fields(method = method, provider = %self.id))
+    )]
+    pub(crate) async fn call<P, R>(&mut self, method: &'static str, params: P) -> RPCResult<R>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        // `Instant::now` panics on `wasm32`, measure only if required.
+        let start = self.instrument.as_ref().map(|_| Instant::now());
+
+        let result = self.rpc_client.call(method, params).await;
+
+        #[cfg(feature = "tracing")]
+        if let Err(err) = &result {
+            tracing::debug!(error = %err.message, "rpc call failed");
+        }
+
+        if let (Some(instrument), Some(start)) = (&self.instrument, start) {
+            instrument.on_response(method, start.elapsed(), result.as_ref().err());
+        }
+
+        result
+    }
+}
+
+/// [`RpcInstrument`] recording prometheus metrics:
+///
+/// - `ethers_rpc_requests_total{method, status}`, counter of rpc calls, `status` is `success` or `error`.
+/// - `ethers_rpc_request_duration_seconds{method}`, histogram of rpc call latency.
+#[cfg(feature = "metrics")]
+#[derive(Clone)]
+pub struct PrometheusInstrument {
+    requests: prometheus::IntCounterVec,
+    duration: prometheus::HistogramVec,
+}
+
+#[cfg(feature = "metrics")]
+impl PrometheusInstrument {
+    /// Create instrument and register metrics to `registry`.
+    pub fn new(registry: &prometheus::Registry) -> prometheus::Result<Self> {
+        let requests = prometheus::IntCounterVec::new(
+            prometheus::Opts::new("ethers_rpc_requests_total", "Number of rpc calls"),
+            &["method", "status"],
+        )?;
+
+        let duration = prometheus::HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "ethers_rpc_request_duration_seconds",
+                "Latency of rpc calls in seconds",
+            ),
+            &["method"],
+        )?;
+
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(duration.clone()))?;
+
+        Ok(Self { requests, duration })
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl RpcInstrument for PrometheusInstrument {
+    fn on_response(&self, method: &str, elapsed: Duration, error: Option<&RPCError>) {
+        let status = if error.is_some() { "error" } else { "success" };
+
+        self.requests.with_label_values(&[method, status]).inc();
+
+        self.duration
+            .with_label_values(&[method])
+            .observe(elapsed.as_secs_f64());
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_prometheus_instrument() {
+        let registry = prometheus::Registry::new();
+
+        let instrument = PrometheusInstrument::new(&registry).unwrap();
+
+        instrument.on_response("eth_chainId", Duration::from_millis(10), None);
+
+        let families = registry.gather();
+
+        assert_eq!(families.len(), 2);
+
+        assert_eq!(
+            instrument
+                .requests
+                .with_label_values(&["eth_chainId", "success"])
+                .get(),
+            1
+        );
+
+        // Register twice returns error.
+        assert!(PrometheusInstrument::new(&registry).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use std::fmt::{Debug, Display};
+
+use ethers_core::*;
+use jsonrpc_rs::RPCResult;
+
+use super::Provider;
+
+/// `net` and `web3` namespace rpc methods.
+impl Provider {
+    /// Returns the current network id, e.g. `"1"` for mainnet.
+    pub async fn net_version(&mut self) -> RPCResult<String> {
+        self.cached_call("net_version", Vec::<String>::new(), |_| true)
+            .await
+    }
+
+    /// Returns number of peers currently connected to the client.
+    pub async fn net_peer_count(&mut self) -> RPCResult<U64> {
+        self.call("net_peerCount", Vec::<String>::new()).await
+    }
+
+    /// Returns true if client is actively listening for network connections.
+    pub async fn net_listening(&mut self) -> RPCResult<bool> {
+        self.call("net_listening", Vec::<String>::new()).await
+    }
+
+    /// Returns the current client version.
+    pub async fn web3_client_version(&mut self) -> RPCResult<String> {
+        self.call("web3_clientVersion", Vec::<String>::new()).await
+    }
+
+    /// Returns Keccak-256 (not the standardized SHA3-256) of the given data, computed by the client.
+    pub async fn web3_sha3<B>(&mut self, data: B) -> RPCResult<H256>
+    where
+        B: TryInto<Bytes>,
+        B::Error: Debug + Display,
+    {
+        let data = data.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("web3_sha3", vec![data]).await
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt::{Debug, Display};
+
+use ethers_core::*;
+use jsonrpc_rs::RPCResult;
+use serde::{Deserialize, Serialize};
+
+use super::Provider;
+use crate::types::*;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CancelPrivateTransaction {
+    tx_hash: H256,
+}
+
+/// Private transaction relay rpc methods, supported by Flashbots Protect style endpoints.
+impl Provider {
+    /// Send a signed transaction to block builders only, bypassing the public mempool.
+    ///
+    /// Returns the transaction hash.
+    pub async fn eth_send_private_transaction<T>(&mut self, transaction: T) -> RPCResult<H256>
+    where
+        T: TryInto<PrivateTransaction>,
+        T::Error: Debug + Display,
+    {
+        let transaction = transaction.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("eth_sendPrivateTransaction", vec![transaction])
+            .await
+    }
+
+    /// Stop the relay from sending a private transaction to builders.
+    ///
+    /// Returns true if the transaction was found and cancelled.
+    pub async fn eth_cancel_private_transaction<H>(&mut self, tx_hash: H) -> RPCResult<bool>
+    where
+        H: TryInto<H256>,
+        H::Error: Debug + Display,
+    {
+        let tx_hash = tx_hash.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call(
+            "eth_cancelPrivateTransaction",
+            vec![CancelPrivateTransaction { tx_hash }],
+        )
+        .await
+    }
+}
@@ -0,0 +1,165 @@
+use ethers_core::*;
+use jsonrpc_rs::RPCError;
+use serde_json::Value;
+
+use super::Provider;
+use crate::types::*;
+
+/// Selector of `Error(string)`.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of `Panic(uint256)`.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Error returned by [`TransactionReceipter::wait`](super::TransactionReceipter::wait) if receipt status is `0x0`
+/// and status check is enabled.
+#[derive(Debug, thiserror::Error)]
+#[error("TxReverted: transaction {tx_hash} reverted in block {block_number}, reason: {}", reason.as_deref().unwrap_or("unknown"))]
+pub struct TxReverted {
+    pub tx_hash: H256,
+    pub block_number: U256,
+    /// Revert reason replayed by `eth_call`, [`None`] if not available.
+    pub reason: Option<String>,
+    pub receipt: TransactionReceipt,
+}
+
+/// Decode revert `data` of `Error(string)`, `Panic(uint256)` or custom error.
+pub fn decode_revert_data(data: &[u8]) -> Option<String> {
+    if data.len() < 4 {
+        return None;
+    }
+
+    let (selector, body) = data.split_at(4);
+
+    if selector == ERROR_SELECTOR {
+        let word = |offset: usize| -> Option<usize> {
+            let word = body.get(offset..offset.checked_add(32)?)?;
+
+            if word[..24].iter().any(|b| *b != 0) {
+                return None;
+            }
+
+            Some(u64::from_be_bytes(word[24..].try_into().unwrap()) as usize)
+        };
+
+        let offset = word(0)?;
+        let len = word(offset)?;
+        let start = offset.checked_add(32)?;
+
+        let reason = body.get(start..start.checked_add(len)?)?;
+
+        return Some(String::from_utf8_lossy(reason).into_owned());
+    }
+
+    if selector == PANIC_SELECTOR && body.len() >= 32 {
+        return Some(format!("Panic(0x{:02x})", body[31]));
+    }
+
+    Some(format!("custom error {}", data.to_eth_hex()))
+}
+
+/// Extract raw revert data from `eth_call`/`eth_estimateGas` error, returns [`None`] if not present.
+pub fn revert_data(err: &RPCError) -> Option<Vec<u8>> {
+    let data = match &err.data {
+        Some(Value::String(data)) => Some(data.as_str()),
+        Some(Value::Object(object)) => object.get("data").and_then(Value::as_str),
+        _ => None,
+    };
+
+    data.and_then(|data| Vec::<u8>::from_eth_hex(data).ok())
+}
+
+/// Extract revert reason from `eth_call` error, returns [`None`] if `err` is not a revert.
+pub fn revert_reason(err: &RPCError) -> Option<String> {
+    if let Some(reason) = revert_data(err).and_then(|data| decode_revert_data(&data)) {
+        return Some(reason);
+    }
+
+    if err.message.contains("revert") {
+        Some(err.message.clone())
+    } else {
+        None
+    }
+}
+
+impl Provider {
+    /// Enable receipt status check of [`TransactionReceipter`](super::TransactionReceipter) created by this provider.
+    pub fn with_receipt_status_check(mut self, enabled: bool) -> Self {
+        self.check_receipt_status = enabled;
+        self
+    }
+
+    /// Replay transaction `tx_hash` via `eth_call` at `block_number`, returns revert reason.
+    pub async fn replay_revert_reason(
+        &mut self,
+        tx_hash: H256,
+        block_number: U256,
+    ) -> anyhow::Result<Option<String>> {
+        let tx = match self.eth_get_transaction_by_hash(tx_hash).await? {
+            Some(tx) => tx,
+            None => return Ok(None),
+        };
+
+        let request = LegacyTransactionRequest {
+            from: tx.from,
+            to: tx.to,
+            gas: tx.gas,
+            value: tx.value,
+            data: Some(tx.input),
+            ..Default::default()
+        };
+
+        match self.eth_call(request, Some(block_number)).await {
+            Ok(_) => Ok(None),
+            Err(err) => Ok(revert_reason(&err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpc_rs::ErrorCode;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_revert_data() {
+        let data = Vec::<u8>::from_eth_hex("0x08c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000001a4e6f7420656e6f7567682045746865722070726f76696465642e000000000000").unwrap();
+
+        assert_eq!(
+            decode_revert_data(&data),
+            Some("Not enough Ether provided.".to_owned())
+        );
+
+        let data = Vec::<u8>::from_eth_hex(
+            "0x4e487b710000000000000000000000000000000000000000000000000000000000000011",
+        )
+        .unwrap();
+
+        assert_eq!(decode_revert_data(&data), Some("Panic(0x11)".to_owned()));
+
+        assert_eq!(decode_revert_data(&[]), None);
+    }
+
+    #[test]
+    fn test_revert_reason() {
+        let err = RPCError {
+            code: ErrorCode::InternalError,
+            message: "execution reverted".to_owned(),
+            data: Some(json!(
+                "0x4e487b710000000000000000000000000000000000000000000000000000000000000001"
+            )),
+        };
+
+        assert_eq!(revert_reason(&err), Some("Panic(0x01)".to_owned()));
+
+        let err = RPCError {
+            code: ErrorCode::InternalError,
+            message: "nonce too low".to_owned(),
+            data: None,
+        };
+
+        assert_eq!(revert_reason(&err), None);
+    }
+}
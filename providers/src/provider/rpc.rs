@@ -2,24 +2,20 @@ use super::Provider;
 
 use std::fmt::{Debug, Display};
 
-use ethers_eip2718::TypedTransactionRequest;
 use jsonrpc_rs::RPCResult;
 
-use crate::types::*;
-use ethers_primitives::*;
+use crate::{types::*, NameOrAddress};
+use ethers_core::*;
 
 impl Provider {
     /// Returns the number of most recent block.
     pub async fn eth_block_number(&mut self) -> RPCResult<U256> {
-        self.rpc_client
-            .call("eth_blockNumber", Vec::<String>::new())
-            .await
+        self.call("eth_blockNumber", Vec::<String>::new()).await
     }
 
     /// Returns the chain ID of the current network
     pub async fn eth_chain_id(&mut self) -> RPCResult<U64> {
-        self.rpc_client
-            .call("eth_chainId", Vec::<String>::new())
+        self.cached_call("eth_chainId", Vec::<String>::new(), |_| true)
             .await
     }
 
@@ -35,9 +31,15 @@ impl Provider {
     {
         let block_hash = block_hash.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client
-            .call("eth_getBlockByHash", (block_hash, hydrated))
-            .await
+        let block = self
+            .cached_call(
+                "eth_getBlockByHash",
+                (block_hash, hydrated),
+                |block: &Option<Block>| block.is_some(),
+            )
+            .await?;
+
+        self.check_block(block, hydrated)
     }
 
     /// Returns information about a block by number
@@ -54,9 +56,11 @@ impl Provider {
             .try_into()
             .map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client
+        let block = self
             .call("eth_getBlockByNumber", (block_number_or_tag, hydrated))
-            .await
+            .await?;
+
+        self.check_block(block, hydrated)
     }
 
     /// Returns the number of transactions in a block from a block matching the given block hash
@@ -67,8 +71,7 @@ impl Provider {
     {
         let hash = hash.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client
-            .call("eth_getBlockTransactionCountByNumber", vec![hash])
+        self.call("eth_getBlockTransactionCountByNumber", vec![hash])
             .await
     }
 
@@ -80,9 +83,7 @@ impl Provider {
     {
         let hash = hash.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client
-            .call("eth_getUncleCountByBlockHash", vec![hash])
-            .await
+        self.call("eth_getUncleCountByBlockHash", vec![hash]).await
     }
 
     /// Returns the number of uncles in a block from a block matching the given block hash
@@ -98,33 +99,27 @@ impl Provider {
             .try_into()
             .map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client
-            .call("eth_getUncleCountByBlockNumber", vec![block_number_or_tag])
+        self.call("eth_getUncleCountByBlockNumber", vec![block_number_or_tag])
             .await
     }
 
     /// Returns an object with data about the sync status or false
     pub async fn eth_syncing(&mut self) -> RPCResult<SyncingStatus> {
-        self.rpc_client
-            .call("eth_syncing", Vec::<String>::new())
-            .await
+        self.call("eth_syncing", Vec::<String>::new()).await
     }
 
     /// Returns the client coinbase address.
     pub async fn eth_coinbase(&mut self) -> RPCResult<Address> {
-        self.rpc_client
-            .call("eth_coinbase", Vec::<String>::new())
-            .await
+        self.call("eth_coinbase", Vec::<String>::new()).await
     }
 
     /// Returns a list of addresses owned by client.
     pub async fn eth_accounts(&mut self) -> RPCResult<Vec<Address>> {
-        self.rpc_client
-            .call("eth_accounts", Vec::<String>::new())
-            .await
+        self.call("eth_accounts", Vec::<String>::new()).await
     }
 
-    /// Executes a new message call immediately without creating a transaction on the block chain.
+    /// Executes a new message call immediately without creating a transaction on the block chain,
+    /// at `block_number_or_tag`, default is latest block.
     pub async fn eth_call<TX, BT>(
         &mut self,
         transaction: TX,
@@ -136,19 +131,17 @@ impl Provider {
         BT: TryInto<BlockNumberOrTag>,
         BT::Error: Debug + Display,
     {
-        let transaction = transaction.try_into().map_err(jsonrpc_rs::map_error)?;
+        let transaction: TypedTransactionRequest =
+            transaction.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        if let Some(block_number_or_tag) = block_number_or_tag {
-            let block_number_or_tag = block_number_or_tag
-                .try_into()
-                .map_err(jsonrpc_rs::map_error)?;
+        let transaction = transaction
+            .to_json_with(self.input_field)
+            .map_err(jsonrpc_rs::map_error)?;
 
-            self.rpc_client
-                .call("eth_call", (transaction, block_number_or_tag))
-                .await
-        } else {
-            self.rpc_client.call("eth_call", vec![transaction]).await
-        }
+        let block_number_or_tag = Self::block_or_latest(block_number_or_tag)?;
+
+        self.call("eth_call", (transaction, block_number_or_tag))
+            .await
     }
 
     /// Generates and returns an estimate of how much gas is necessary to allow the transaction to complete.
@@ -163,20 +156,22 @@ impl Provider {
         BT: TryInto<BlockNumberOrTag>,
         BT::Error: Debug + Display,
     {
-        let transaction = transaction.try_into().map_err(jsonrpc_rs::map_error)?;
+        let transaction: TypedTransactionRequest =
+            transaction.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        let transaction = transaction
+            .to_json_with(self.input_field)
+            .map_err(jsonrpc_rs::map_error)?;
 
         if let Some(block_number_or_tag) = block_number_or_tag {
             let block_number_or_tag = block_number_or_tag
                 .try_into()
                 .map_err(jsonrpc_rs::map_error)?;
 
-            self.rpc_client
-                .call("eth_estimateGas", (transaction, block_number_or_tag))
+            self.call("eth_estimateGas", (transaction, block_number_or_tag))
                 .await
         } else {
-            self.rpc_client
-                .call("eth_estimateGas", vec![transaction])
-                .await
+            self.call("eth_estimateGas", vec![transaction]).await
         }
     }
 
@@ -199,27 +194,21 @@ impl Provider {
                 .try_into()
                 .map_err(jsonrpc_rs::map_error)?;
 
-            self.rpc_client
-                .call("eth_createAccessList", (transaction, block_number_or_tag))
+            self.call("eth_createAccessList", (transaction, block_number_or_tag))
                 .await
         } else {
-            self.rpc_client
-                .call("eth_createAccessList", vec![transaction])
-                .await
+            self.call("eth_createAccessList", vec![transaction]).await
         }
     }
 
     /// Returns the current price gas in wei.
     pub async fn eth_gas_price(&mut self) -> RPCResult<U256> {
-        self.rpc_client
-            .call("eth_gasPrice", Vec::<String>::new())
-            .await
+        self.call("eth_gasPrice", Vec::<String>::new()).await
     }
 
     /// Returns the current maxPriorityFeePerGas per gas in wei.
     pub async fn eth_max_priority_fee_per_gas(&mut self) -> RPCResult<U256> {
-        self.rpc_client
-            .call("eth_maxPriorityFeePerGas", Vec::<String>::new())
+        self.call("eth_maxPriorityFeePerGas", Vec::<String>::new())
             .await
     }
 
@@ -241,12 +230,11 @@ impl Provider {
 
         let newest_block = newest_block.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client
-            .call(
-                "eth_feeHistory",
-                (block_count, newest_block, reward_percentiles.as_ref()),
-            )
-            .await
+        self.call(
+            "eth_feeHistory",
+            (block_count, newest_block, reward_percentiles.as_ref()),
+        )
+        .await
     }
 
     /// Returns transaction base fee per gas and effective priority fee per gas for the requested/supported block range.
@@ -257,20 +245,17 @@ impl Provider {
     {
         let filter = filter.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client.call("eth_newFilter", vec![filter]).await
+        self.call("eth_newFilter", vec![filter]).await
     }
 
     /// Creates new filter in the node,to notify when a new block arrives.
     pub async fn eth_new_block_filter(&mut self) -> RPCResult<U256> {
-        self.rpc_client
-            .call("eth_newBlockFilter", Vec::<String>::new())
-            .await
+        self.call("eth_newBlockFilter", Vec::<String>::new()).await
     }
 
     /// Creates new filter in the node,to notify when new pending transactions arrive.
     pub async fn eth_new_pending_transaction_filter(&mut self) -> RPCResult<U256> {
-        self.rpc_client
-            .call("eth_newPendingTransactionFilter", Vec::<String>::new())
+        self.call("eth_newPendingTransactionFilter", Vec::<String>::new())
             .await
     }
 
@@ -282,7 +267,7 @@ impl Provider {
     {
         let id = id.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client.call("eth_uninstallFilter", vec![id]).await
+        self.call("eth_uninstallFilter", vec![id]).await
     }
 
     /// Polling method for a filter, which returns an arrya of logs which occurred since last poll
@@ -294,7 +279,7 @@ impl Provider {
     {
         let id = id.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client.call("eth_getFilterChanges", vec![id]).await
+        self.call("eth_getFilterChanges", vec![id]).await
     }
 
     /// Returns any arrays of all logs matching filter with given id
@@ -305,10 +290,13 @@ impl Provider {
     {
         let id = id.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client.call("eth_getFilterLogs", vec![id]).await
+        self.call("eth_getFilterLogs", vec![id]).await
     }
 
     /// Returns an array of all logs matching filter with filter description
+    ///
+    /// Block range rejected by provider for being too large is bisected and queried again,
+    /// see [`with_max_log_splits`](Self::with_max_log_splits).
     pub async fn eth_get_logs<F>(&mut self, filter: F) -> RPCResult<FilterEvents>
     where
         F: TryInto<Filter>,
@@ -316,21 +304,21 @@ impl Provider {
     {
         let filter = filter.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client.call("eth_getLogs", vec![filter]).await
+        self.get_logs_with_split(filter).await
     }
 
     /// Returns an EIP-191 signature over the provided data
-    pub async fn eth_sign<A, M>(&mut self, address: A, message: M) -> RPCResult<Eip1559Signature>
+    pub async fn eth_sign<A, M>(&mut self, address: A, message: M) -> RPCResult<Signature>
     where
-        A: TryInto<Address>,
+        A: TryInto<NameOrAddress>,
         A::Error: Debug + Display,
         M: TryInto<Bytes>,
         M::Error: Debug + Display,
     {
-        let address = address.try_into().map_err(jsonrpc_rs::map_error)?;
+        let address = self.resolve(address).await?;
         let message = message.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client.call("eth_sign", (address, message)).await
+        self.call("eth_sign", (address, message)).await
     }
 
     /// Returns an RLP encoded transaction signed by the specified account.
@@ -341,32 +329,130 @@ impl Provider {
     {
         let transaction = transaction.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client
-            .call("eth_signTransaction", vec![transaction])
+        self.call("eth_signTransaction", vec![transaction]).await
+    }
+
+    /// Returns the balance of the account given address or ENS name at `block_number_or_tag`,
+    /// default is latest block.
+    pub async fn eth_get_balance<A, BT>(
+        &mut self,
+        address: A,
+        block_number_or_tag: Option<BT>,
+    ) -> RPCResult<U256>
+    where
+        A: TryInto<NameOrAddress>,
+        A::Error: Debug + Display,
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
+    {
+        let address = self.resolve(address).await?;
+
+        let block_number_or_tag = Self::block_or_latest(block_number_or_tag)?;
+
+        self.call("eth_getBalance", (address, block_number_or_tag))
             .await
     }
 
-    /// Returns the balance of the account given address.
-    pub async fn eth_get_balance<A>(&mut self, address: A) -> RPCResult<U256>
+    /// Returns the balance of the account of given address at `block_number_or_tag`.
+    #[deprecated(note = "use `eth_get_balance(address, Some(block))` instead")]
+    pub async fn eth_get_balance_at<A, BT>(
+        &mut self,
+        address: A,
+        block_number_or_tag: BT,
+    ) -> RPCResult<U256>
     where
-        A: TryInto<Address>,
+        A: TryInto<NameOrAddress>,
         A::Error: Debug + Display,
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
     {
-        let address = address.try_into().map_err(jsonrpc_rs::map_error)?;
+        self.eth_get_balance(address, Some(block_number_or_tag))
+            .await
+    }
 
-        self.rpc_client.call("eth_getBalance", vec![address]).await
+    /// Returns the value from a storage position at a given address.
+    pub async fn eth_get_storage_at<A, P, BT>(
+        &mut self,
+        address: A,
+        position: P,
+        block_number_or_tag: Option<BT>,
+    ) -> RPCResult<H256>
+    where
+        A: TryInto<NameOrAddress>,
+        A::Error: Debug + Display,
+        P: TryInto<U256>,
+        P::Error: Debug + Display,
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
+    {
+        let address = self.resolve(address).await?;
+
+        let position = position.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        let block_number_or_tag = Self::block_or_latest(block_number_or_tag)?;
+
+        self.call("eth_getStorageAt", (address, position, block_number_or_tag))
+            .await
+    }
+
+    /// Returns code at a given address.
+    pub async fn eth_get_code<A, BT>(
+        &mut self,
+        address: A,
+        block_number_or_tag: Option<BT>,
+    ) -> RPCResult<Bytes>
+    where
+        A: TryInto<NameOrAddress>,
+        A::Error: Debug + Display,
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
+    {
+        let address = self.resolve(address).await?;
+
+        let block_number_or_tag = Self::block_or_latest(block_number_or_tag)?;
+
+        // Code at a fixed block never changes.
+        let immutable = matches!(block_number_or_tag, BlockNumberOrTag::U256(_));
+
+        self.cached_call("eth_getCode", (address, block_number_or_tag), |_| immutable)
+            .await
     }
 
-    /// Returns the number of transactions sent from an address
-    pub async fn eth_get_transaction_count<A>(&mut self, address: A) -> RPCResult<U256>
+    /// Returns the number of transactions sent from an address at `block_number_or_tag`,
+    /// default is latest block.
+    pub async fn eth_get_transaction_count<A, BT>(
+        &mut self,
+        address: A,
+        block_number_or_tag: Option<BT>,
+    ) -> RPCResult<U256>
     where
-        A: TryInto<Address>,
+        A: TryInto<NameOrAddress>,
         A::Error: Debug + Display,
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
     {
-        let address = address.try_into().map_err(jsonrpc_rs::map_error)?;
+        let address = self.resolve(address).await?;
 
-        self.rpc_client
-            .call("eth_getTransactionCount", vec![address])
+        let block_number_or_tag = Self::block_or_latest(block_number_or_tag)?;
+
+        self.call("eth_getTransactionCount", (address, block_number_or_tag))
+            .await
+    }
+
+    /// Returns the number of transactions sent from an address at `block_number_or_tag`.
+    #[deprecated(note = "use `eth_get_transaction_count(address, Some(block))` instead")]
+    pub async fn eth_get_transaction_count_at<A, BT>(
+        &mut self,
+        address: A,
+        block_number_or_tag: BT,
+    ) -> RPCResult<U256>
+    where
+        A: TryInto<NameOrAddress>,
+        A::Error: Debug + Display,
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
+    {
+        self.eth_get_transaction_count(address, Some(block_number_or_tag))
             .await
     }
 
@@ -378,9 +464,23 @@ impl Provider {
     {
         let raw = raw.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client
-            .call("eth_sendRawTransaction", vec![raw])
-            .await
+        self.call("eth_sendRawTransaction", vec![raw]).await
+    }
+
+    /// Send transaction signed by node, `from` must be unlocked or impersonated account of node.
+    pub async fn eth_send_transaction<TX>(&mut self, transaction: TX) -> RPCResult<H256>
+    where
+        TX: TryInto<TypedTransactionRequest>,
+        TX::Error: Debug + Display,
+    {
+        let transaction: TypedTransactionRequest =
+            transaction.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        let transaction = transaction
+            .to_json_with(self.input_field)
+            .map_err(jsonrpc_rs::map_error)?;
+
+        self.call("eth_sendTransaction", vec![transaction]).await
     }
 
     pub async fn eth_get_transaction_by_hash<H>(
@@ -393,9 +493,12 @@ impl Provider {
     {
         let tx_hash = tx_hash.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client
-            .call("eth_getTransactionByHash", vec![tx_hash])
-            .await
+        self.cached_call(
+            "eth_getTransactionByHash",
+            vec![tx_hash],
+            |tx: &Option<Transaction>| matches!(tx, Some(tx) if tx.block_hash.is_some()),
+        )
+        .await
     }
 
     /// Returns the receipt of a transaction by transaction hash
@@ -409,8 +512,20 @@ impl Provider {
     {
         let tx_hash = tx_hash.try_into().map_err(jsonrpc_rs::map_error)?;
 
-        self.rpc_client
-            .call("eth_getTransactionReceipt", vec![tx_hash])
-            .await
+        self.call("eth_getTransactionReceipt", vec![tx_hash]).await
+    }
+
+    /// Convert optional block parameter, default is latest block.
+    fn block_or_latest<BT>(block_number_or_tag: Option<BT>) -> RPCResult<BlockNumberOrTag>
+    where
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
+    {
+        match block_number_or_tag {
+            Some(block_number_or_tag) => block_number_or_tag
+                .try_into()
+                .map_err(jsonrpc_rs::map_error),
+            None => Ok(BlockNumberOrTag::default()),
+        }
     }
 }
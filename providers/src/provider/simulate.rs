@@ -0,0 +1,38 @@
+use std::fmt::{Debug, Display};
+
+use jsonrpc_rs::RPCResult;
+
+use super::Provider;
+use crate::types::*;
+
+impl Provider {
+    /// Simulate a sequence of blocks and calls on top of `block_number_or_tag`, without
+    /// creating transactions on chain.
+    ///
+    /// Each [`BlockStateCall`] may override block header fields and account states,
+    /// the returned blocks contain return data, logs and gas usage of every call.
+    pub async fn eth_simulate_v1<P, BT>(
+        &mut self,
+        payload: P,
+        block_number_or_tag: Option<BT>,
+    ) -> RPCResult<Vec<SimulatedBlock>>
+    where
+        P: TryInto<SimulatePayload>,
+        P::Error: Debug + Display,
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
+    {
+        let payload = payload.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        let block_number_or_tag = if let Some(block_number_or_tag) = block_number_or_tag {
+            block_number_or_tag
+                .try_into()
+                .map_err(jsonrpc_rs::map_error)?
+        } else {
+            BlockNumberOrTag::default()
+        };
+
+        self.call("eth_simulateV1", (payload, block_number_or_tag))
+            .await
+    }
+}
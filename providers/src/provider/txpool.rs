@@ -0,0 +1,102 @@
+use std::fmt::{Debug, Display};
+
+use ethers_core::*;
+use jsonrpc_rs::RPCResult;
+
+use super::Provider;
+use crate::types::*;
+
+/// Geth `txpool` namespace rpc methods.
+impl Provider {
+    /// Returns all pending and queued transactions, keyed by sender and nonce.
+    pub async fn txpool_content(&mut self) -> RPCResult<TxpoolContent> {
+        self.call("txpool_content", Vec::<String>::new()).await
+    }
+
+    /// Returns pending and queued transactions sent by `address`, keyed by nonce.
+    pub async fn txpool_content_from<A>(&mut self, address: A) -> RPCResult<TxpoolContentFrom>
+    where
+        A: TryInto<Address>,
+        A::Error: Debug + Display,
+    {
+        let address = address.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.call("txpool_contentFrom", vec![address]).await
+    }
+
+    /// Returns numbers of pending and queued transactions.
+    pub async fn txpool_status(&mut self) -> RPCResult<TxpoolStatus> {
+        self.call("txpool_status", Vec::<String>::new()).await
+    }
+
+    /// Returns summaries of pending and queued transactions, keyed by sender and nonce.
+    pub async fn txpool_inspect(&mut self) -> RPCResult<TxpoolInspect> {
+        self.call("txpool_inspect", Vec::<String>::new()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_inspect_summary() {
+        let summary: TxpoolInspectSummary =
+            "0x3b3B57de1e2B1a1E4F8e0F2b7E1a0d0c9f2e4a61: 1000000000000000000 wei + 21000 gas × 20000000000 wei"
+                .parse()
+                .unwrap();
+
+        assert!(summary.to.is_some());
+        assert_eq!(summary.gas, U256::from(21000u64));
+        assert_eq!(summary.gas_price, U256::from(20000000000u64));
+        assert_eq!(summary.value, U256::from(1000000000000000000u64));
+
+        let summary: TxpoolInspectSummary = "contract creation: 0 wei + 1000000 gas × 1 wei"
+            .parse()
+            .unwrap();
+
+        assert_eq!(summary.to, None);
+
+        assert_eq!(
+            serde_json::to_value(&summary).unwrap(),
+            json!("contract creation: 0 wei + 1000000 gas × 1 wei")
+        );
+
+        assert!("0x00: 1 wei".parse::<TxpoolInspectSummary>().is_err());
+    }
+
+    #[test]
+    fn test_inspect() {
+        let inspect: TxpoolInspect = json!({
+            "pending": {
+                "0x26588a9301b0428d95e6fc3a5024fce8bec12d51": {
+                    "31813": "0x3375ee30428b2a71c428afa5e89e427905f95f7e: 0 wei + 500000 gas × 20000000000 wei"
+                }
+            },
+            "queued": {
+                "0x976a3fc5d6f7d259ebfb4cc2ae75115475e9867c": {
+                    "2": "0x8b35c4a6e0c0a7e5b2c6e1a0f51f3d2a6b3f5c7d: 1 wei + 21000 gas × 21000000000 wei",
+                    "10": "contract creation: 0 wei + 90000 gas × 20000000000 wei"
+                }
+            }
+        })
+        .try_into()
+        .unwrap();
+
+        let address: Address = "0x976a3fc5d6f7d259ebfb4cc2ae75115475e9867c"
+            .try_into()
+            .unwrap();
+
+        let nonces = inspect.queued[&address].keys().collect::<Vec<_>>();
+
+        assert_eq!(nonces, vec![&2, &10]);
+
+        let status: TxpoolStatus = json!({ "pending": "0xa", "queued": "0x7" })
+            .try_into()
+            .unwrap();
+
+        assert_eq!(status.pending, U64::from(10u64));
+    }
+}
@@ -0,0 +1,366 @@
+use std::{collections::VecDeque, time::Duration};
+
+use futures::Stream;
+use jsonrpc_rs::{RPCError, RPCResult};
+
+use super::Provider;
+use crate::types::*;
+use ethers_core::*;
+
+/// Node error messages indicating that the filter was expired or uninstalled.
+const FILTER_NOT_FOUND_ERRORS: &[&str] = &["filter not found", "filter does not exist"];
+
+/// Returns true if `err` is a node response for polling an unknown filter id.
+pub fn is_filter_not_found_error(err: &RPCError) -> bool {
+    let message = err.message.to_lowercase();
+
+    FILTER_NOT_FOUND_ERRORS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Filter kinds watched by [`FilterWatcher`]
+#[derive(Debug, Clone)]
+pub enum WatchedFilter {
+    /// `eth_newFilter`
+    Logs(Filter),
+    /// `eth_newBlockFilter`
+    Blocks,
+    /// `eth_newPendingTransactionFilter`
+    PendingTransactions,
+}
+
+/// Log change yielded by [`FilterWatcher::into_log_stream`].
+#[derive(Debug, Clone)]
+pub enum LogEvent {
+    /// Log of canonical chain.
+    Added(Log),
+    /// Previously yielded log removed by chain reorganization, aka `removed: true`.
+    Removed(Log),
+}
+
+impl From<Log> for LogEvent {
+    fn from(log: Log) -> Self {
+        if log.removed {
+            Self::Removed(log)
+        } else {
+            Self::Added(log)
+        }
+    }
+}
+
+/// Logs waiting for confirmations.
+#[derive(Debug, Default)]
+struct ConfirmationBuffer {
+    confirmations: u64,
+    pending: Vec<Log>,
+}
+
+impl ConfirmationBuffer {
+    /// Push log changes, returns events to yield immediately.
+    fn push(&mut self, log: Log) -> Option<LogEvent> {
+        if self.confirmations == 0 {
+            return Some(log.into());
+        }
+
+        if !log.removed {
+            self.pending.push(log);
+            return None;
+        }
+
+        let before = self.pending.len();
+
+        // Removed log not yielded yet is dropped silently.
+        self.pending.retain(|pending| {
+            pending.block_hash != log.block_hash || pending.log_index != log.log_index
+        });
+
+        if self.pending.len() < before {
+            None
+        } else {
+            Some(LogEvent::Removed(log))
+        }
+    }
+
+    /// Release logs with at least `confirmations` blocks mined on top of, at chain `head`.
+    fn release(&mut self, head: u64) -> Vec<LogEvent> {
+        let confirmations = self.confirmations;
+
+        let (confirmed, pending) = self.pending.drain(..).partition::<Vec<_>, _>(|log| {
+            Option::<u64>::from(log.block_number)
+                .map(|number| number.saturating_add(confirmations) <= head)
+                .unwrap_or(true)
+        });
+
+        self.pending = pending;
+
+        confirmed.into_iter().map(LogEvent::Added).collect()
+    }
+}
+
+/// Filter polling service, which polls `eth_getFilterChanges` on an interval.
+///
+/// If the node reports "filter not found", aka the filter was expired by node,
+/// the filter is re-created transparently. Changes occurring between the expiration
+/// and the re-creation can't be recovered.
+pub struct FilterWatcher {
+    provider: Provider,
+    filter: WatchedFilter,
+    id: Option<U256>,
+    interval: Duration,
+    confirmations: u64,
+}
+
+impl FilterWatcher {
+    /// Create new watcher with default poll interval 5s, the filter is installed lazily on first poll.
+    pub fn new(provider: Provider, filter: WatchedFilter) -> Self {
+        Self {
+            provider,
+            filter,
+            id: None,
+            interval: Duration::from_secs(5),
+            confirmations: 0,
+        }
+    }
+
+    /// Set poll interval duration.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Buffer logs of [`into_log_stream`](Self::into_log_stream) until `confirmations` blocks are mined on top of,
+    /// default is 0, aka yield logs immediately.
+    pub fn confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Current installed filter id.
+    pub fn id(&self) -> Option<U256> {
+        self.id
+    }
+
+    async fn install(&mut self) -> RPCResult<U256> {
+        let id = match &self.filter {
+            WatchedFilter::Logs(filter) => self.provider.eth_new_filter(filter.clone()).await?,
+            WatchedFilter::Blocks => self.provider.eth_new_block_filter().await?,
+            WatchedFilter::PendingTransactions => {
+                self.provider.eth_new_pending_transaction_filter().await?
+            }
+        };
+
+        log::debug!("install filter {} for {:?}", id, self.filter);
+
+        self.id = Some(id);
+
+        Ok(id)
+    }
+
+    /// Poll filter changes once, re-creating the filter if needed.
+    pub async fn poll(&mut self) -> RPCResult<FilterEvents> {
+        let id = match self.id {
+            Some(id) => id,
+            None => self.install().await?,
+        };
+
+        let changes = match self.provider.eth_get_filter_changes(id).await {
+            Ok(changes) => changes,
+            Err(err) if is_filter_not_found_error(&err) => {
+                log::debug!("filter {} expired, {}", id, err.message);
+
+                self.id = None;
+
+                let id = self.install().await?;
+
+                self.provider.eth_get_filter_changes(id).await?
+            }
+            Err(err) => return Err(err),
+        };
+
+        Ok(changes.unwrap_or(FilterEvents::BlocksOrTransactions(vec![])))
+    }
+
+    /// Uninstall the filter from node.
+    pub async fn uninstall(mut self) -> RPCResult<bool> {
+        if let Some(id) = self.id.take() {
+            self.provider.eth_uninstall_filter(id).await
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Convert watcher into a stream of non-empty filter changes.
+    ///
+    /// Poll errors are yielded as stream items, the stream keeps polling after an error.
+    pub fn into_stream(self) -> impl Stream<Item = RPCResult<FilterEvents>> {
+        futures::stream::unfold((self, true), |(mut watcher, first)| async move {
+            if !first {
+                ethers_runtime::sleep(watcher.interval).await;
+            }
+
+            loop {
+                match watcher.poll().await {
+                    Ok(changes) if is_empty(&changes) => {
+                        ethers_runtime::sleep(watcher.interval).await;
+                    }
+                    result => return Some((result, (watcher, false))),
+                }
+            }
+        })
+    }
+
+    /// Convert logs watcher into a stream of [`LogEvent`], logs removed by reorgs are yielded as [`LogEvent::Removed`].
+    ///
+    /// If [`confirmations`](Self::confirmations) is set, logs are buffered until confirmed, logs removed before
+    /// confirmed are never yielded.
+    pub fn into_log_stream(self) -> impl Stream<Item = RPCResult<LogEvent>> {
+        let buffer = ConfirmationBuffer {
+            confirmations: self.confirmations,
+            pending: vec![],
+        };
+
+        futures::stream::unfold(
+            (self, buffer, VecDeque::new(), true),
+            |(mut watcher, mut buffer, mut events, mut first)| async move {
+                loop {
+                    if let Some(event) = events.pop_front() {
+                        return Some((Ok(event), (watcher, buffer, events, first)));
+                    }
+
+                    if !first {
+                        ethers_runtime::sleep(watcher.interval).await;
+                    }
+
+                    first = false;
+
+                    match watcher.poll().await {
+                        Ok(FilterEvents::Logs(logs)) => {
+                            events.extend(logs.into_iter().filter_map(|log| buffer.push(log)))
+                        }
+                        Ok(FilterEvents::BlocksOrTransactions(_)) => {}
+                        Err(err) => return Some((Err(err), (watcher, buffer, events, first))),
+                    }
+
+                    if !buffer.pending.is_empty() {
+                        match watcher.provider.eth_block_number().await {
+                            Ok(head) => events.extend(
+                                buffer.release(Option::<u64>::from(head).unwrap_or(u64::MAX)),
+                            ),
+                            Err(err) => return Some((Err(err), (watcher, buffer, events, first))),
+                        }
+                    }
+                }
+            },
+        )
+    }
+}
+
+fn is_empty(changes: &FilterEvents) -> bool {
+    match changes {
+        FilterEvents::BlocksOrTransactions(hashes) => hashes.is_empty(),
+        FilterEvents::Logs(logs) => logs.is_empty(),
+    }
+}
+
+impl Provider {
+    /// Create [`FilterWatcher`] for logs matching `filter`.
+    pub fn watch_logs<F>(&self, filter: F) -> anyhow::Result<FilterWatcher>
+    where
+        F: TryInto<Filter>,
+        F::Error: std::error::Error + Sync + Send + 'static,
+    {
+        Ok(FilterWatcher::new(
+            self.clone(),
+            WatchedFilter::Logs(filter.try_into()?),
+        ))
+    }
+
+    /// Create [`FilterWatcher`] for new block hashes, see [`watch_blocks`](Self::watch_blocks) for full blocks.
+    pub fn watch_block_hashes(&self) -> FilterWatcher {
+        FilterWatcher::new(self.clone(), WatchedFilter::Blocks)
+    }
+
+    /// Create [`FilterWatcher`] for new pending transaction hashes.
+    pub fn watch_pending_transactions(&self) -> FilterWatcher {
+        FilterWatcher::new(self.clone(), WatchedFilter::PendingTransactions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpc_rs::{ErrorCode, RPCError};
+
+    use super::*;
+
+    fn log(block_number: u64, log_index: u64, removed: bool) -> Log {
+        Log {
+            removed,
+            log_index: U256::from(log_index),
+            transaction_index: U256::from(0u64),
+            transaction_hash: U256::from(0u64),
+            block_hash: [block_number as u8; 32].into(),
+            block_number: U256::from(block_number),
+            address: Address::ZERO,
+            data: Bytes::from(vec![]),
+            topics: vec![],
+        }
+    }
+
+    #[test]
+    fn test_confirmation_buffer() {
+        let mut buffer = ConfirmationBuffer {
+            confirmations: 2,
+            pending: vec![],
+        };
+
+        assert!(buffer.push(log(10, 0, false)).is_none());
+        assert!(buffer.push(log(11, 0, false)).is_none());
+
+        // Removed before confirmed, dropped silently.
+        assert!(buffer.push(log(11, 0, true)).is_none());
+
+        assert!(buffer.release(11).is_empty());
+
+        let released = buffer.release(12);
+
+        assert_eq!(released.len(), 1);
+        assert!(
+            matches!(&released[0], LogEvent::Added(log) if log.block_number == U256::from(10u64))
+        );
+
+        assert!(buffer.pending.is_empty());
+
+        // Removed after yielded.
+        assert!(matches!(
+            buffer.push(log(10, 0, true)),
+            Some(LogEvent::Removed(_))
+        ));
+
+        let mut buffer = ConfirmationBuffer::default();
+
+        assert!(matches!(
+            buffer.push(log(10, 0, false)),
+            Some(LogEvent::Added(_))
+        ));
+    }
+
+    #[test]
+    fn test_filter_not_found_error() {
+        fn error(message: &str) -> RPCError {
+            RPCError {
+                code: ErrorCode::InternalError,
+                message: message.to_owned(),
+                data: None,
+            }
+        }
+
+        assert!(is_filter_not_found_error(&error("filter not found")));
+        assert!(is_filter_not_found_error(&error("Filter not found")));
+        assert!(is_filter_not_found_error(&error(
+            "Filter does not exist: 0x1"
+        )));
+
+        assert!(!is_filter_not_found_error(&error("header not found")));
+    }
+}
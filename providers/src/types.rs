@@ -1,9 +1,14 @@
-use ethers_primitives::*;
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+use ethers_core::*;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::error::ProviderError;
 
-pub use ethers_eip2718::AccessList;
+pub use ethers_core::{AccessList, NameOrAddress};
 
 macro_rules! from_json {
     ($name: ident) => {
@@ -49,8 +54,47 @@ macro_rules! from_json {
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct TopicFilter(Topic, Topic, Topic, Topic);
 
+macro_rules! topic_setter {
+    ($index: tt, $this: ident, $one_of: ident) => {
+        /// Match only `value` in this topic position.
+        pub fn $this<T: ToTopic + ?Sized>(mut self, value: &T) -> Self {
+            self.$index = Topic::This(value.to_topic());
+            self
+        }
+
+        /// Match any of `values` in this topic position.
+        pub fn $one_of<I>(mut self, values: I) -> Self
+        where
+            I: IntoIterator,
+            I::Item: ToTopic,
+        {
+            self.$index = Topic::OneOf(values.into_iter().map(|v| v.to_topic()).collect());
+            self
+        }
+    };
+}
+
+impl TopicFilter {
+    /// Create filter matches any topics.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Match event by signature, e.g. `Transfer(address,address,uint256)`.
+    pub fn event(mut self, signature: &str) -> Self {
+        self.0 = Topic::This(event_signature(signature));
+        self
+    }
+
+    topic_setter!(0, topic0, topic0_one_of);
+    topic_setter!(1, topic1, topic1_one_of);
+    topic_setter!(2, topic2, topic2_one_of);
+    topic_setter!(3, topic3, topic3_one_of);
+}
+
 /// Topic filter expr
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(untagged)]
 pub enum Topic {
     /// Not filtered at all and any value matches
     Unset,
@@ -125,8 +169,38 @@ impl From<(Vec<Address>, TopicFilter)> for Filter {
 
 from_json!(Filter);
 
+impl Filter {
+    /// Returns false if no log matching this filter can be included in the block/receipt with `bloom`,
+    /// which can be used to skip `eth_getLogs` calls.
+    pub fn matches_bloom(&self, bloom: &Bloom) -> bool {
+        let address_matched = match &self.address {
+            None => true,
+            Some(AddressFilter::Address(address)) => bloom.contains_address(address),
+            Some(AddressFilter::Addresses(addresses)) => addresses
+                .iter()
+                .any(|address| bloom.contains_address(address)),
+        };
+
+        if !address_matched {
+            return false;
+        }
+
+        if let Some(topics) = &self.topics {
+            [&topics.0, &topics.1, &topics.2, &topics.3]
+                .iter()
+                .all(|topic| match topic {
+                    Topic::Unset => true,
+                    Topic::This(topic) => bloom.contains_topic(topic),
+                    Topic::OneOf(topics) => topics.iter().any(|topic| bloom.contains_topic(topic)),
+                })
+        } else {
+            true
+        }
+    }
+}
+
 /// eth_getBlockByNumber parameter `Block`
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 #[serde(untagged)]
 pub enum BlockNumberOrTag {
     U256(U256),
@@ -152,7 +226,7 @@ impl Default for BlockNumberOrTag {
 }
 
 /// eth_getBlockByNumber parameter `Block` valid tag enum
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum BlockTag {
     Earliest,
@@ -190,7 +264,7 @@ pub struct FeeHistory {
     pub reward: Vec<Vec<U256>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Log {
     pub removed: bool,
@@ -212,6 +286,16 @@ pub struct Log {
     pub topics: Vec<H256>,
 }
 
+impl BloomLog for Log {
+    fn address(&self) -> &Address {
+        &self.address
+    }
+
+    fn topics(&self) -> &[H256] {
+        &self.topics
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
 pub enum FilterEvents {
@@ -293,10 +377,42 @@ pub struct TransactionReceipt {
     pub status: Option<Status>,
     /// Logs
     pub logs: Vec<Log>,
-    /// Logs bloom filter string
-    pub logs_bloom: Bytes,
+    /// Logs bloom filter
+    pub logs_bloom: Bloom,
     /// Only include before the Byzantium upgrade
     pub root: Option<H256>,
+    /// OP-stack fee paid for posting transaction data to L1.
+    #[cfg(feature = "l2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_fee: Option<U256>,
+    /// OP-stack L1 gas price used to calculate `l1_fee`.
+    #[cfg(feature = "l2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_gas_price: Option<U256>,
+    /// OP-stack L1 gas used to calculate `l1_fee`.
+    #[cfg(feature = "l2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_gas_used: Option<U256>,
+    /// OP-stack L1 fee scalar in decimal format, e.g. `"0.684"`, removed since Ecotone upgrade.
+    #[cfg(feature = "l2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_fee_scalar: Option<String>,
+    /// OP-stack nonce of deposit transaction sender, deposit transactions only.
+    #[cfg(feature = "l2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_nonce: Option<U256>,
+    /// OP-stack deposit receipt version, deposit transactions only.
+    #[cfg(feature = "l2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_receipt_version: Option<U256>,
+    /// Arbitrum gas used for posting transaction data to L1, included in `gas_used`.
+    #[cfg(feature = "l2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_used_for_l1: Option<U256>,
+    /// Arbitrum L1 block number of L2 block including this transaction.
+    #[cfg(feature = "l2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_block_number: Option<U256>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -309,7 +425,7 @@ pub enum Status {
     Failure,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Block {
     /// Current block hash value
@@ -340,11 +456,11 @@ pub struct Block {
     /// Bloom filter
     #[serde(rename = "logsBloom")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub logs_bloom: Option<Bytes>,
+    pub logs_bloom: Option<Bloom>,
 
-    /// Difficulty
+    /// Difficulty, always zero after the merge
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub difficulty: Option<Bytes>,
+    pub difficulty: Option<U256>,
 
     /// U256
     pub number: Option<U256>,
@@ -372,10 +488,10 @@ pub struct Block {
     /// Nonce
     pub nonce: Option<U256>,
 
-    /// Total difficult
-    #[serde(rename = "totalDeffficult")]
+    /// Total difficulty
+    #[serde(rename = "totalDifficulty")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub total_deffficult: Option<Bytes>,
+    pub total_difficulty: Option<U256>,
 
     /// Base fee per gas
     #[serde(rename = "baseFeePerGas")]
@@ -385,14 +501,48 @@ pub struct Block {
     /// Block size
     pub size: U256,
 
-    /// transactions
-    pub transactions: Vec<TransactionOrHash>,
+    /// Transaction hashes, or full transactions if the block is requested with `hydrated = true`
+    pub transactions: BlockTransactions,
 
     /// Uncles
     pub uncles: Vec<H256>,
+
+    /// Withdrawals root, since Shanghai
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<H256>,
+
+    /// Validator withdrawals, since Shanghai
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+
+    /// Total blob gas consumed by the block transactions, since Cancun
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_gas_used: Option<U64>,
+
+    /// Running total of blob gas consumed in excess of the target, since Cancun
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excess_blob_gas: Option<U64>,
+
+    /// Parent beacon block root(EIP-4788), since Cancun
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_beacon_block_root: Option<H256>,
 }
 
-#[derive(Serialize, Deserialize)]
+/// Validator withdrawal(EIP-4895)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+    /// Monotonically increasing withdrawal index
+    pub index: U64,
+    /// Validator index
+    pub validator_index: U64,
+    /// Withdrawal target address
+    pub address: Address,
+    /// Withdrawal amount in Gwei
+    pub amount: U64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum TransactionOrHash {
     Null,
@@ -400,17 +550,160 @@ pub enum TransactionOrHash {
     Transaction(Transaction),
 }
 
+/// Transactions of [`Block`], hashes or full transactions depending on the `hydrated` request flag.
+///
+/// Unlike [`TransactionOrHash`], every element must be of the same kind, and deserializing fails
+/// on the first element which can't be parsed instead of falling back to another variant.
+#[derive(Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum BlockTransactions {
+    Hashes(Vec<H256>),
+    Full(Vec<Transaction>),
+}
+
+impl Default for BlockTransactions {
+    fn default() -> Self {
+        Self::Hashes(vec![])
+    }
+}
+
+impl BlockTransactions {
+    /// Returns the count of transactions.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Hashes(hashes) => hashes.len(),
+            Self::Full(txs) => txs.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if block transactions are full transactions, empty lists are both.
+    pub fn is_full(&self) -> bool {
+        matches!(self, Self::Full(_)) || self.is_empty()
+    }
+
+    /// Returns transaction hashes, full transactions without hash are skipped.
+    pub fn hashes(&self) -> Vec<H256> {
+        match self {
+            Self::Hashes(hashes) => hashes.clone(),
+            Self::Full(txs) => txs.iter().filter_map(|tx| tx.hash.clone()).collect(),
+        }
+    }
+
+    /// Returns full transactions, [`None`] if block is not hydrated.
+    pub fn as_full(&self) -> Option<&[Transaction]> {
+        match self {
+            Self::Full(txs) => Some(txs),
+            Self::Hashes(hashes) if hashes.is_empty() => Some(&[]),
+            Self::Hashes(_) => None,
+        }
+    }
+
+    /// Convert into full transactions, [`None`] if block is not hydrated.
+    pub fn into_full(self) -> Option<Vec<Transaction>> {
+        match self {
+            Self::Full(txs) => Some(txs),
+            Self::Hashes(hashes) if hashes.is_empty() => Some(vec![]),
+            Self::Hashes(_) => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockTransactions {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let values = Vec::<serde_json::Value>::deserialize(deserializer)?;
+
+        if values
+            .first()
+            .map(|value| value.is_string())
+            .unwrap_or(true)
+        {
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    H256::deserialize(value).map_err(|err| {
+                        D::Error::custom(format!("transaction hash {}: {}", index, err))
+                    })
+                })
+                .collect::<Result<_, _>>()
+                .map(Self::Hashes)
+        } else {
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    Transaction::deserialize(value)
+                        .map_err(|err| D::Error::custom(format!("transaction {}: {}", index, err)))
+                })
+                .collect::<Result<_, _>>()
+                .map(Self::Full)
+        }
+    }
+}
+
+impl Block {
+    /// Check block returned with `hydrated = true`.
+    ///
+    /// Every transaction must be a full transaction with hash, and belong to this block.
+    pub fn check_hydrated(&self) -> Result<(), ProviderError> {
+        let txs = self.transactions.as_full().ok_or_else(|| {
+            ProviderError::HydratedBlock("transactions are not hydrated".to_owned())
+        })?;
+
+        for (index, tx) in txs.iter().enumerate() {
+            if tx.hash.is_none() {
+                return Err(ProviderError::HydratedBlock(format!(
+                    "transaction {} without hash",
+                    index
+                )));
+            }
+
+            if self.hash.is_some() && tx.block_hash != self.hash {
+                return Err(ProviderError::HydratedBlock(format!(
+                    "transaction {} block hash mismatch",
+                    index
+                )));
+            }
+
+            if self.number.is_some() && tx.block_number != self.number {
+                return Err(ProviderError::HydratedBlock(format!(
+                    "transaction {} block number mismatch",
+                    index
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TransactionType {
     // 0x00
-    #[serde(rename = "0x00")]
+    #[serde(rename = "0x00", alias = "0x0")]
     Legacy,
     // 0x01
-    #[serde(rename = "0x01")]
+    #[serde(rename = "0x01", alias = "0x1")]
     Eip2930,
     // 0x02
-    #[serde(rename = "0x02")]
+    #[serde(rename = "0x02", alias = "0x2")]
     Eip1559,
+    // 0x03
+    #[serde(rename = "0x03", alias = "0x3")]
+    Eip4844,
+    // 0x7e, OP-stack deposit transaction
+    #[cfg(feature = "l2")]
+    #[serde(rename = "0x7e", alias = "0x7E")]
+    Deposit,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -425,8 +718,12 @@ pub struct Transaction {
     /// transaction nonce
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce: Option<U256>,
-    /// To address
-    pub to: Address,
+    /// From address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<Address>,
+    /// To address, `None` for contract creation transaction.
+    #[serde(default)]
+    pub to: Option<Address>,
     /// Gas limit
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gas: Option<U256>,
@@ -454,6 +751,7 @@ pub struct Transaction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<U256>,
     /// Input data to call contract.
+    #[serde(alias = "data")]
     pub input: Bytes,
     /// Maximum fee per gas the sender is willing to pay to miners in wei
     #[serde(rename = "maxPriorityFeePerGas")]
@@ -465,21 +763,615 @@ pub struct Transaction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_fee_per_gas: Option<U256>,
     /// EIP-2930 access list
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "accessList")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub access_list: Option<AccessList>,
+    /// Maximum fee per blob gas the sender is willing to pay(EIP-4844)
+    #[serde(rename = "maxFeePerBlobGas")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    /// Versioned hashes of blobs carried by this transaction(EIP-4844)
+    #[serde(rename = "blobVersionedHashes")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
     /// Chain ID tha this transaction is valid on
     #[serde(rename = "chainId")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chain_id: Option<U256>,
-    /// The parity(0 for even, 1 for odd) of the y-value of the secp256k1 signature.
+    /// The parity(0 for even, 1 for odd) of the y-value of the secp256k1 signature,
+    /// or `chain_id * 2 + 35 + y_parity` for legacy transactions.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub v: Option<U256>,
+    /// The parity(0 for even, 1 for odd) of the y-value of the secp256k1 signature, typed transactions only.
+    #[serde(rename = "yParity")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y_parity: Option<U64>,
     /// r-value of the secp256k1
     #[serde(skip_serializing_if = "Option::is_none")]
     pub r: Option<U256>,
     /// s-value of the secp256k1
     #[serde(skip_serializing_if = "Option::is_none")]
     pub s: Option<U256>,
+    /// OP-stack hash uniquely identifying the origin of deposit transaction.
+    #[cfg(feature = "l2")]
+    #[serde(rename = "sourceHash")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_hash: Option<H256>,
+    /// OP-stack ETH value minted on L2 by deposit transaction.
+    #[cfg(feature = "l2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mint: Option<U256>,
+    /// OP-stack flag of system deposit transaction, removed since Regolith upgrade.
+    #[cfg(feature = "l2")]
+    #[serde(rename = "isSystemTx")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_system_tx: Option<bool>,
 }
 
 from_json!(Transaction);
+
+/// Block header fields overridden by `eth_simulateV1`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_randao: Option<H256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob_base_fee: Option<U256>,
+}
+
+/// Account state overridden before executing simulated calls.
+///
+/// `state` replaces the whole account storage, `state_diff` only patches the given slots,
+/// they are mutually exclusive.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<HashMap<H256, H256>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<HashMap<H256, H256>>,
+    /// Move precompile contract at this address to the given address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub move_precompile_to_address: Option<Address>,
+}
+
+/// State overrides keyed by account address.
+pub type StateOverrides = HashMap<Address, AccountOverride>;
+
+/// One simulated block, calls are executed in order on top of the overridden state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStateCall {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverrides>,
+    pub calls: Vec<TypedTransactionRequest>,
+}
+
+/// `eth_simulateV1` request payload.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    pub block_state_calls: Vec<BlockStateCall>,
+    /// Adds ETH transfers as ERC20 transfer logs to the results.
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Enable nonce/balance/base fee checks like a real transaction.
+    #[serde(default)]
+    pub validation: bool,
+    /// Returns full transaction objects instead of hashes in the simulated blocks.
+    #[serde(default)]
+    pub return_full_transactions: bool,
+}
+
+impl From<Vec<BlockStateCall>> for SimulatePayload {
+    fn from(block_state_calls: Vec<BlockStateCall>) -> Self {
+        Self {
+            block_state_calls,
+            ..Default::default()
+        }
+    }
+}
+
+from_json!(SimulatePayload);
+
+/// Error of one simulated call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulateCallError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
+
+/// Result of one simulated call.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+    /// Call return data, or revert data if the call failed.
+    pub return_data: Bytes,
+    /// Logs emitted by this call.
+    #[serde(default)]
+    pub logs: Vec<Log>,
+    pub gas_used: U256,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateCallError>,
+}
+
+impl SimulatedCall {
+    /// Returns true if the call executed successfully.
+    pub fn is_success(&self) -> bool {
+        matches!(self.status, Status::Success)
+    }
+}
+
+/// Simulated block returned by `eth_simulateV1`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    pub number: U256,
+    pub hash: H256,
+    pub timestamp: U256,
+    pub gas_limit: U256,
+    pub gas_used: U256,
+    pub miner: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+    /// Full transactions or hashes depending on [`SimulatePayload::return_full_transactions`]
+    #[serde(default)]
+    pub transactions: Vec<TransactionOrHash>,
+    /// Call results in the same order as [`BlockStateCall::calls`]
+    pub calls: Vec<SimulatedCall>,
+}
+
+/// Transaction data shared to searchers/builders by private transaction relays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyHint {
+    Calldata,
+    ContractAddress,
+    Logs,
+    FunctionSelector,
+    Hash,
+    TxHash,
+}
+
+/// Privacy preferences of [`PrivateTransaction`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrivacyPreferences {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hints: Vec<PrivacyHint>,
+    /// Builders allowed to receive the transaction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub builders: Vec<String>,
+}
+
+/// MEV refund receiver.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Refund {
+    pub address: Address,
+    /// Refund percent, 0 ~ 100
+    pub percent: u64,
+}
+
+/// Validity preferences of [`PrivateTransaction`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidityPreferences {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub refund: Vec<Refund>,
+}
+
+/// Preferences of [`PrivateTransaction`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrivateTransactionPreferences {
+    /// Send to all builders registered by the relay.
+    #[serde(default)]
+    pub fast: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privacy: Option<PrivacyPreferences>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validity: Option<ValidityPreferences>,
+}
+
+/// `eth_sendPrivateTransaction` request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateTransaction {
+    /// Signed raw transaction
+    pub tx: Bytes,
+    /// Highest block number the tx can be included in, defaults to current block + 25.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_block_number: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferences: Option<PrivateTransactionPreferences>,
+}
+
+impl From<Bytes> for PrivateTransaction {
+    fn from(tx: Bytes) -> Self {
+        Self {
+            tx,
+            ..Default::default()
+        }
+    }
+}
+
+from_json!(PrivateTransaction);
+
+/// Transactions of one account in txpool, keyed by nonce.
+pub type TxpoolNonceMap<T> = BTreeMap<u64, T>;
+
+/// `txpool_content` result, transactions keyed by sender address and nonce.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TxpoolContent {
+    /// Transactions ready to be included in the next block.
+    #[serde(default)]
+    pub pending: HashMap<Address, TxpoolNonceMap<Transaction>>,
+    /// Transactions scheduled for future execution, e.g. nonce gap.
+    #[serde(default)]
+    pub queued: HashMap<Address, TxpoolNonceMap<Transaction>>,
+}
+
+from_json!(TxpoolContent);
+
+/// `txpool_contentFrom` result, transactions of one sender keyed by nonce.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TxpoolContentFrom {
+    #[serde(default)]
+    pub pending: TxpoolNonceMap<Transaction>,
+    #[serde(default)]
+    pub queued: TxpoolNonceMap<Transaction>,
+}
+
+from_json!(TxpoolContentFrom);
+
+/// `txpool_status` result, numbers of pending and queued transactions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TxpoolStatus {
+    pub pending: U64,
+    pub queued: U64,
+}
+
+from_json!(TxpoolStatus);
+
+/// Transaction summary of `txpool_inspect`, e.g. `0x..: 1 wei + 21000 gas × 20000000000 wei`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxpoolInspectSummary {
+    /// Recipient address, `None` for contract creation.
+    pub to: Option<Address>,
+    pub value: U256,
+    pub gas: U256,
+    pub gas_price: U256,
+}
+
+fn parse_decimal_u256(value: &str) -> Option<U256> {
+    if value.is_empty() {
+        return None;
+    }
+
+    value.bytes().try_fold(U256::default(), |acc, c| {
+        let digit = c.checked_sub(b'0').filter(|d| *d < 10)?;
+
+        acc.checked_mul(U256::from(10u8))?
+            .checked_add(U256::from(digit))
+    })
+}
+
+impl FromStr for TxpoolInspectSummary {
+    type Err = ProviderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ProviderError::TxpoolInspect(s.to_owned());
+
+        let (to, rest) = s.split_once(": ").ok_or_else(invalid)?;
+
+        let to = if to == "contract creation" {
+            None
+        } else {
+            Some(Address::try_from(to).map_err(|_| invalid())?)
+        };
+
+        let (value, rest) = rest.split_once(" wei + ").ok_or_else(invalid)?;
+        let (gas, gas_price) = rest.split_once(" gas × ").ok_or_else(invalid)?;
+        let gas_price = gas_price.strip_suffix(" wei").ok_or_else(invalid)?;
+
+        Ok(Self {
+            to,
+            value: parse_decimal_u256(value).ok_or_else(invalid)?,
+            gas: parse_decimal_u256(gas).ok_or_else(invalid)?,
+            gas_price: parse_decimal_u256(gas_price).ok_or_else(invalid)?,
+        })
+    }
+}
+
+impl Serialize for TxpoolInspectSummary {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let to = match &self.to {
+            Some(to) => to.to_checksum_string(),
+            None => "contract creation".to_owned(),
+        };
+
+        serializer.serialize_str(&format!(
+            "{}: {} wei + {} gas × {} wei",
+            to,
+            num_to_decimal(&self.value),
+            num_to_decimal(&self.gas),
+            num_to_decimal(&self.gas_price)
+        ))
+    }
+}
+
+fn num_to_decimal(value: &U256) -> String {
+    let mut digits = vec![];
+    let mut value = *value;
+    let ten = U256::from(10u8);
+
+    loop {
+        let digit = Option::<u8>::from(value % ten).unwrap();
+
+        digits.push(b'0' + digit);
+
+        value = value / ten;
+
+        if value.is_zero() {
+            break;
+        }
+    }
+
+    digits.reverse();
+
+    String::from_utf8(digits).unwrap()
+}
+
+impl<'de> Deserialize<'de> for TxpoolInspectSummary {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let summary = String::deserialize(deserializer)?;
+
+        summary.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// `txpool_inspect` result, transaction summaries keyed by sender address and nonce.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TxpoolInspect {
+    #[serde(default)]
+    pub pending: HashMap<Address, TxpoolNonceMap<TxpoolInspectSummary>>,
+    #[serde(default)]
+    pub queued: HashMap<Address, TxpoolNonceMap<TxpoolInspectSummary>>,
+}
+
+from_json!(TxpoolInspect);
+
+/// Gas limits estimated by `eth_estimateUserOperationGas`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGasEstimate {
+    pub pre_verification_gas: U256,
+    pub verification_gas_limit: U256,
+    pub call_gas_limit: U256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+}
+
+/// User operation receipt returned by `eth_getUserOperationReceipt`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationReceipt {
+    pub user_op_hash: H256,
+    pub entry_point: Address,
+    pub sender: Address,
+    pub nonce: U256,
+    #[serde(default)]
+    pub paymaster: Option<Address>,
+    pub actual_gas_cost: U256,
+    pub actual_gas_used: U256,
+    /// False if the user operation execution reverted.
+    pub success: bool,
+    /// Revert reason, if available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Logs emitted by this user operation.
+    pub logs: Vec<Log>,
+    /// Receipt of the bundle transaction including this user operation.
+    pub receipt: TransactionReceipt,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_cancun_block() {
+        // `eth_getBlockByNumber` response of a post-Cancun geth node.
+        let block: Block = serde_json::from_value(json!({
+            "baseFeePerGas": "0x3fb9f1b1",
+            "blobGasUsed": "0x40000",
+            "difficulty": "0x0",
+            "excessBlobGas": "0x4b80000",
+            "extraData": "0x6265617665726275696c642e6f7267",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x9a5c8d",
+            "hash": "0x4a57e0d1e5a8a8dbc2f0c3c5ad33d6a28d6c7b1d0e0f2e16a9d0a6c6e0b3f1a2",
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "miner": "0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5",
+            "mixHash": "0x2e2ab5d4e6b6fd7e2b4d4e1b5e1f1c1b8e3ab3f8a9d9e0c5f6a1b2c3d4e5f601",
+            "nonce": "0x0000000000000000",
+            "number": "0x12f1a7a",
+            "parentBeaconBlockRoot": "0x8b1b6d2e7a5f5f3a4e0c8d1e4a8c1b5f3e6d8a2c4b6e8f0a1c3e5b7d9f1a3c5e",
+            "parentHash": "0x1f6b9a2d3c4e5f60718293a4b5c6d7e8f9011223344556677889900aabbccdde",
+            "receiptsRoot": "0x7c5a1b2d3e4f5061728394a5b6c7d8e9fa0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "size": "0x1d3a4",
+            "stateRoot": "0x3a6f1b2c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f8",
+            "timestamp": "0x65f6b1e3",
+            "totalDifficulty": "0xc70d815d562d3cfa955",
+            "transactions": [
+                "0x6b3a2c9e8f1d4b5a7c6e0f2d1a3b5c7e9f0a2b4c6d8e0f1a3b5c7d9e1f2a4b6c"
+            ],
+            "transactionsRoot": "0x5d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e",
+            "uncles": [],
+            "withdrawals": [
+                {
+                    "index": "0x2a8a0a1",
+                    "validatorIndex": "0x10b2f5",
+                    "address": "0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f",
+                    "amount": "0x11d3a9f"
+                },
+                {
+                    "index": "0x2a8a0a2",
+                    "validatorIndex": "0x10b2f6",
+                    "address": "0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f",
+                    "amount": "0x11c8e01"
+                }
+            ],
+            "withdrawalsRoot": "0x9e2a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708"
+        }))
+        .unwrap();
+
+        assert_eq!(block.base_fee_per_gas, Some(U256::from(0x3fb9f1b1u64)));
+        assert_eq!(block.difficulty, Some(U256::from(0u64)));
+
+        assert_eq!(
+            block.withdrawals_root,
+            Some(
+                "0x9e2a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708"
+                    .try_into()
+                    .unwrap()
+            )
+        );
+
+        let withdrawals = block.withdrawals.as_ref().unwrap();
+
+        assert_eq!(withdrawals.len(), 2);
+        assert_eq!(withdrawals[0].index, U64::from(0x2a8a0a1u64));
+        assert_eq!(withdrawals[0].validator_index, U64::from(0x10b2f5u64));
+        assert_eq!(
+            withdrawals[0].address,
+            "0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f"
+                .try_into()
+                .unwrap()
+        );
+        assert_eq!(withdrawals[1].amount, U64::from(0x11c8e01u64));
+
+        assert_eq!(block.blob_gas_used, Some(U64::from(0x40000u64)));
+        assert_eq!(block.excess_blob_gas, Some(U64::from(0x4b80000u64)));
+        assert_eq!(
+            block.parent_beacon_block_root,
+            Some(
+                "0x8b1b6d2e7a5f5f3a4e0c8d1e4a8c1b5f3e6d8a2c4b6e8f0a1c3e5b7d9f1a3c5e"
+                    .try_into()
+                    .unwrap()
+            )
+        );
+
+        // Fork fields are kept by serialization round trip.
+        let value = serde_json::to_value(&block).unwrap();
+
+        assert_eq!(value["withdrawals"][1]["validatorIndex"], "0x10b2f6");
+        assert_eq!(value["blobGasUsed"], "0x40000");
+        assert_eq!(value["excessBlobGas"], "0x4b80000");
+
+        // Pre-Shanghai blocks have none of them.
+        let mut value = value;
+
+        for field in [
+            "withdrawals",
+            "withdrawalsRoot",
+            "blobGasUsed",
+            "excessBlobGas",
+            "parentBeaconBlockRoot",
+        ] {
+            value.as_object_mut().unwrap().remove(field);
+        }
+
+        let block: Block = serde_json::from_value(value).unwrap();
+
+        assert!(block.withdrawals.is_none());
+        assert!(block.withdrawals_root.is_none());
+        assert!(block.blob_gas_used.is_none());
+        assert!(block.excess_blob_gas.is_none());
+        assert!(block.parent_beacon_block_root.is_none());
+    }
+
+    #[cfg(feature = "l2")]
+    #[test]
+    fn test_op_deposit_transaction() {
+        let tx: Transaction = json!({
+            "type": "0x7e",
+            "sourceHash": "0x8b1b6d2e7a5f5f3a4e0c8d1e4a8c1b5f3e6d8a2c4b6e8f0a1c3e5b7d9f1a3c5e",
+            "from": "0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001",
+            "to": "0x4200000000000000000000000000000000000015",
+            "mint": "0x0",
+            "value": "0x0",
+            "gas": "0xf4240",
+            "isSystemTx": false,
+            "input": "0x",
+            "nonce": "0x1"
+        })
+        .try_into()
+        .unwrap();
+
+        assert!(matches!(tx.r#type, Some(TransactionType::Deposit)));
+        assert_eq!(tx.is_system_tx, Some(false));
+        assert!(tx.source_hash.is_some());
+    }
+
+    #[cfg(feature = "l2")]
+    #[test]
+    fn test_l2_receipt() {
+        let receipt: TransactionReceipt = serde_json::from_value(json!({
+            "from": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "to": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "contractAddress": null,
+            "gasUsed": "0x5208",
+            "cumulativeGasUsed": "0x5208",
+            "effectiveGasPrice": "0x1",
+            "transactionIndex": "0x1",
+            "blockHash": "0x8b1b6d2e7a5f5f3a4e0c8d1e4a8c1b5f3e6d8a2c4b6e8f0a1c3e5b7d9f1a3c5e",
+            "blockNumber": "0x10",
+            "status": "0x1",
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "l1Fee": "0x1f4",
+            "l1GasPrice": "0x3b9aca00",
+            "l1GasUsed": "0x640",
+            "l1FeeScalar": "0.684",
+            "gasUsedForL1": "0x10",
+            "l1BlockNumber": "0x100"
+        }))
+        .unwrap();
+
+        assert_eq!(receipt.l1_fee, Some(U256::from(500u64)));
+        assert_eq!(receipt.l1_fee_scalar.as_deref(), Some("0.684"));
+        assert_eq!(receipt.gas_used_for_l1, Some(U256::from(16u64)));
+        assert_eq!(receipt.l1_block_number, Some(U256::from(256u64)));
+    }
+}
@@ -0,0 +1,23 @@
+use std::{future::Future, time::Duration};
+
+/// Spawn a background future onto the async-std global executor.
+pub fn spawn<Fut>(future: Fut)
+where
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    // Dropping the join handle detaches the task.
+    _ = async_std::task::spawn(future);
+}
+
+/// Suspend current task for `duration`.
+pub async fn sleep(duration: Duration) {
+    async_std::task::sleep(duration).await;
+}
+
+/// Run `future` to completion, blocking current thread.
+pub fn block_on<Fut>(future: Fut) -> Fut::Output
+where
+    Fut: Future,
+{
+    async_std::task::block_on(future)
+}
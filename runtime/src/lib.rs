@@ -0,0 +1,131 @@
+//! Async runtime abstraction shared by ethers-rs crates.
+//!
+//! The executor backend is selected by feature flags:
+//!
+//! - `tokio` (default), futures run on the ambient tokio runtime, or on a shared one if called outside of tokio.
+//! - `async-std`, takes precedence over `tokio` if both features are enabled.
+//!
+//! On `wasm32` targets futures are driven by the browser event loop regardless of features.
+
+use std::{future::Future, time::Duration};
+
+use futures::{
+    future::{select, Either},
+    pin_mut, Stream,
+};
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    not(feature = "tokio"),
+    not(feature = "async-std")
+))]
+compile_error!("ethers_runtime requires feature `tokio` or `async-std`");
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    feature = "tokio",
+    not(feature = "async-std")
+))]
+mod tokio_rt;
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    feature = "tokio",
+    not(feature = "async-std")
+))]
+pub use tokio_rt::*;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "async-std"))]
+mod async_std_rt;
+#[cfg(all(not(target_arch = "wasm32"), feature = "async-std"))]
+pub use async_std_rt::*;
+
+#[cfg(target_arch = "wasm32")]
+mod wasm_rt;
+#[cfg(target_arch = "wasm32")]
+pub use wasm_rt::*;
+
+/// Monotonic clock, backed by `performance.now()` on `wasm32` targets where `std::time::Instant::now` panics.
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::Instant;
+
+#[cfg(target_arch = "wasm32")]
+pub use instant::Instant;
+
+/// Runtime errors
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum RuntimeError {
+    #[error("Timeout: future not completed within {0:?}")]
+    Timeout(Duration),
+}
+
+/// Await `future` at most `duration`, returns [`RuntimeError::Timeout`] if the deadline is reached first.
+pub async fn timeout<Fut>(duration: Duration, future: Fut) -> Result<Fut::Output, RuntimeError>
+where
+    Fut: Future,
+{
+    let delay = sleep(duration);
+
+    pin_mut!(future);
+    pin_mut!(delay);
+
+    match select(future, delay).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(RuntimeError::Timeout(duration)),
+    }
+}
+
+/// Create a stream yielding every `period`.
+///
+/// The next tick is scheduled once the previous one is consumed, so slow consumers never get bursts of ticks.
+pub fn interval(period: Duration) -> impl Stream<Item = ()> {
+    futures::stream::unfold((), move |_| async move {
+        sleep(period).await;
+
+        Some(((), ()))
+    })
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use futures::{channel::oneshot, StreamExt};
+
+    use super::*;
+
+    #[test]
+    fn test_spawn() {
+        let (sender, receiver) = oneshot::channel();
+
+        spawn(async move {
+            sender.send(1).unwrap();
+        });
+
+        assert_eq!(block_on(receiver), Ok(1));
+    }
+
+    #[test]
+    fn test_timeout() {
+        block_on(async {
+            assert_eq!(timeout(Duration::from_secs(1), async { 1 }).await, Ok(1));
+
+            assert_eq!(
+                timeout(Duration::from_millis(10), sleep(Duration::from_secs(10))).await,
+                Err(RuntimeError::Timeout(Duration::from_millis(10)))
+            );
+        });
+    }
+
+    #[test]
+    fn test_interval() {
+        let start = Instant::now();
+
+        block_on(async {
+            let ticks = interval(Duration::from_millis(10)).take(3);
+
+            assert_eq!(ticks.count().await, 3);
+        });
+
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+}
@@ -0,0 +1,51 @@
+use std::{future::Future, time::Duration};
+
+use once_cell::sync::OnceCell;
+use tokio::runtime::{Handle, Runtime};
+
+/// Shared runtime, used when called outside of a tokio runtime context.
+fn runtime() -> &'static Runtime {
+    static INSTANCE: OnceCell<Runtime> = OnceCell::new();
+
+    INSTANCE.get_or_init(|| Runtime::new().unwrap())
+}
+
+/// Spawn a background future onto the current tokio runtime, or the shared one if there isn't any.
+pub fn spawn<Fut>(future: Fut)
+where
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    match Handle::try_current() {
+        Ok(handle) => {
+            _ = handle.spawn(future);
+        }
+        Err(_) => {
+            _ = runtime().spawn(future);
+        }
+    }
+}
+
+/// Suspend current task for `duration`.
+///
+/// Can be awaited from non tokio executors, in which case the timer is driven by the shared runtime.
+pub async fn sleep(duration: Duration) {
+    if Handle::try_current().is_ok() {
+        tokio::time::sleep(duration).await;
+    } else {
+        _ = runtime()
+            .spawn(async move { tokio::time::sleep(duration).await })
+            .await;
+    }
+}
+
+/// Run `future` to completion on the shared runtime, blocking current thread.
+///
+/// # Panics
+///
+/// Panics if called from within an asynchronous execution context.
+pub fn block_on<Fut>(future: Fut) -> Fut::Output
+where
+    Fut: Future,
+{
+    runtime().block_on(future)
+}
@@ -0,0 +1,16 @@
+use std::{future::Future, time::Duration};
+
+/// Spawn a background future onto the browser event loop.
+///
+/// Browser futures (fetch, WebSocket) are `!Send`, so no `Send` bound is required.
+pub fn spawn<Fut>(future: Fut)
+where
+    Fut: Future<Output = ()> + 'static,
+{
+    wasm_bindgen_futures::spawn_local(future);
+}
+
+/// Suspend current task for `duration`.
+pub async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
@@ -2,9 +2,18 @@
 
 use std::collections::HashMap;
 
+use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{ser, Deserialize, Serialize, Serializer};
 
+/// Matches `bytes<M>` newtype names, compiled once rather than on every newtype call.
+pub(crate) static BYTES_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^bytes(\d{1,2})$").expect("Compile bytes regex"));
+
+/// Matches `int<M>` and `uint<M>` newtype names.
+pub(crate) static INT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(u)?int(\d{1,3})$").expect("Compile int regex"));
+
 #[derive(Debug, thiserror::Error)]
 pub enum TypeDefinitionError {
     #[error("{0}")]
@@ -79,11 +88,31 @@ impl TupleEncoder {
         Ok(fields)
     }
 
-    fn pop_field(&mut self) {
+    /// Append array suffix to the last field type, `T` -> `T[]` or `T[N]`.
+    fn append_array_suffix(&mut self, fixed_len: Option<usize>) -> Result<(), TypeDefinitionError> {
+        if self.field_names.len() != self.field_types.len() {
+            return Err(TypeDefinitionError::FiledNameTypePair);
+        }
+
+        let type_name = self
+            .field_types
+            .last_mut()
+            .ok_or(TypeDefinitionError::AppendFieldName)?;
+
+        match fixed_len {
+            Some(len) => type_name.push_str(&format!("[{}]", len)),
+            None => type_name.push_str("[]"),
+        }
+
+        Ok(())
+    }
+
+    fn pop_field(&mut self) -> Result<(), TypeDefinitionError> {
         self.field_names
             .pop()
-            .ok_or(TypeDefinitionError::FiledNameTypePair)
-            .unwrap();
+            .ok_or(TypeDefinitionError::FiledNameTypePair)?;
+
+        Ok(())
     }
 }
 
@@ -118,8 +147,7 @@ impl EIP712TypeDefinition {
     /// Call this function only when serializing none
     fn pop_field(&mut self) -> Result<(), TypeDefinitionError> {
         if let Some(tuple) = self.tuples.last_mut() {
-            tuple.pop_field();
-            Ok(())
+            tuple.pop_field()
         } else {
             return Err(TypeDefinitionError::InvalidPrimary);
         }
@@ -137,6 +165,18 @@ impl EIP712TypeDefinition {
         }
     }
 
+    /// Append array suffix to the last field type of parent structure, `T` -> `T[]` or `T[N]`.
+    pub fn append_array_suffix(
+        &mut self,
+        fixed_len: Option<usize>,
+    ) -> Result<(), TypeDefinitionError> {
+        if let Some(tuple) = self.tuples.last_mut() {
+            tuple.append_array_suffix(fixed_len)
+        } else {
+            Err(TypeDefinitionError::InvalidPrimary)
+        }
+    }
+
     /// Start serialize one new tuple, if returns true.
     /// If returns false, no further serialization of this tuple is required.
     pub fn start_tuple(&mut self, name: &str) -> Result<bool, TypeDefinitionError> {
@@ -188,8 +228,8 @@ impl EIP712TypeDefinition {
 impl<'a> Serializer for &'a mut EIP712TypeDefinition {
     type Ok = ();
     type Error = TypeDefinitionError;
-    type SerializeSeq = Self;
-    type SerializeTuple = Self;
+    type SerializeSeq = EIP712TypeDefinitionSerializeSeq<'a>;
+    type SerializeTuple = EIP712TypeDefinitionSerializeSeq<'a>;
     type SerializeTupleStruct = Self;
     type SerializeTupleVariant = Self;
     type SerializeMap = Self;
@@ -200,23 +240,23 @@ impl<'a> Serializer for &'a mut EIP712TypeDefinition {
         false
     }
     fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        self.append_field_type("bool")
     }
 
     fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        self.append_field_type("bytes")
     }
 
     fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("Contract abi don't support rust char")
+        Err(TypeDefinitionError::UnsupportType("char".to_owned()))
     }
 
     fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("EIP712 don't support f32")
+        Err(TypeDefinitionError::UnsupportType("f32".to_owned()))
     }
 
     fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("EIP712 don't support f64")
+        Err(TypeDefinitionError::UnsupportType("f64".to_owned()))
     }
 
     fn serialize_i128(self, _v: i128) -> Result<Self::Ok, Self::Error> {
@@ -232,7 +272,7 @@ impl<'a> Serializer for &'a mut EIP712TypeDefinition {
     }
 
     fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
-        self.append_field_type("int16")
+        self.append_field_type("int64")
     }
 
     fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
@@ -240,7 +280,7 @@ impl<'a> Serializer for &'a mut EIP712TypeDefinition {
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        unimplemented!("EIP712 don't support map")
+        Err(TypeDefinitionError::UnsupportType("map".to_owned()))
     }
 
     fn serialize_newtype_struct<T: ?Sized>(
@@ -255,17 +295,14 @@ impl<'a> Serializer for &'a mut EIP712TypeDefinition {
             "bytes" => self.append_field_type("bytes"),
             "address" => self.append_field_type("address"),
             _ => {
-                let bytes_regex = Regex::new(r"^bytes(\d{1,2})$").unwrap();
-                let int_regex = Regex::new(r"^(u)?int(\d{1,3})$").unwrap();
-
-                if let Some(caps) = bytes_regex.captures(name) {
+                if let Some(caps) = BYTES_REGEX.captures(name) {
                     let len: usize = caps[1].parse().unwrap();
                     if len <= 32 {
                         return self.append_field_type(name);
                     }
                 }
 
-                if let Some(caps) = int_regex.captures(name) {
+                if let Some(caps) = INT_REGEX.captures(name) {
                     let len: usize = caps[2].parse().unwrap();
                     if len <= 256 {
                         return self.append_field_type(name);
@@ -298,7 +335,11 @@ impl<'a> Serializer for &'a mut EIP712TypeDefinition {
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        unimplemented!("EIP712 don't support rust enum")
+        Ok(EIP712TypeDefinitionSerializeSeq {
+            serializer: self,
+            fixed_len: None,
+            elements: 0,
+        })
     }
 
     fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
@@ -332,11 +373,16 @@ impl<'a> Serializer for &'a mut EIP712TypeDefinition {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        unimplemented!("EIP712 don't support rust enum")
+        Err(TypeDefinitionError::UnsupportType("enum".to_owned()))
     }
 
-    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        unimplemented!("EIP712 don't support serialize tuple")
+    /// Rust array `[T; N]` is mapped to `T[N]`.
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(EIP712TypeDefinitionSerializeSeq {
+            serializer: self,
+            fixed_len: Some(len),
+            elements: 0,
+        })
     }
 
     fn serialize_tuple_struct(
@@ -344,7 +390,9 @@ impl<'a> Serializer for &'a mut EIP712TypeDefinition {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        unimplemented!("EIP712 don't support serialize tuple_struct")
+        Err(TypeDefinitionError::UnsupportType(
+            "tuple struct".to_owned(),
+        ))
     }
 
     fn serialize_tuple_variant(
@@ -354,7 +402,7 @@ impl<'a> Serializer for &'a mut EIP712TypeDefinition {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(TypeDefinitionError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_u128(self, _v: u128) -> Result<Self::Ok, Self::Error> {
@@ -391,7 +439,7 @@ impl<'a> Serializer for &'a mut EIP712TypeDefinition {
         _variant_index: u32,
         _variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(TypeDefinitionError::UnsupportType("enum".to_owned()))
     }
 }
 
@@ -400,37 +448,58 @@ impl<'a> ser::SerializeMap for &'a mut EIP712TypeDefinition {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(TypeDefinitionError::UnsupportType("map".to_owned()))
     }
 
     fn serialize_key<T: ?Sized>(&mut self, _key: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(TypeDefinitionError::UnsupportType("map".to_owned()))
     }
 
     fn serialize_value<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(TypeDefinitionError::UnsupportType("map".to_owned()))
     }
 }
 
-impl<'a> ser::SerializeSeq for &'a mut EIP712TypeDefinition {
+/// Array serializer, element type is derived from the first element.
+pub struct EIP712TypeDefinitionSerializeSeq<'a> {
+    serializer: &'a mut EIP712TypeDefinition,
+    /// Length of fixed array `T[N]`, [`None`] for dynamic array `T[]`.
+    fixed_len: Option<usize>,
+    elements: usize,
+}
+
+impl<'a> ser::SerializeSeq for EIP712TypeDefinitionSerializeSeq<'a> {
     type Error = TypeDefinitionError;
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        if self.elements == 0 {
+            return Err(TypeDefinitionError::UnsupportType(
+                "empty array, element type is unknown".to_owned(),
+            ));
+        }
+
+        self.serializer.append_array_suffix(self.fixed_len)
     }
 
-    fn serialize_element<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        // All elements have the same type, only the first one is needed.
+        if self.elements == 0 {
+            value.serialize(&mut *self.serializer)?;
+        }
+
+        self.elements += 1;
+
+        Ok(())
     }
 }
 
@@ -473,7 +542,7 @@ impl<'a> ser::SerializeStructVariant for &'a mut EIP712TypeDefinition {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(TypeDefinitionError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_field<T: ?Sized>(
@@ -484,23 +553,23 @@ impl<'a> ser::SerializeStructVariant for &'a mut EIP712TypeDefinition {
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(TypeDefinitionError::UnsupportType("enum".to_owned()))
     }
 }
 
-impl<'a> ser::SerializeTuple for &'a mut EIP712TypeDefinition {
+impl<'a> ser::SerializeTuple for EIP712TypeDefinitionSerializeSeq<'a> {
     type Error = TypeDefinitionError;
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        ser::SerializeSeq::end(self)
     }
 
-    fn serialize_element<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        ser::SerializeSeq::serialize_element(self, value)
     }
 }
 
@@ -509,14 +578,14 @@ impl<'a> ser::SerializeTupleVariant for &'a mut EIP712TypeDefinition {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(TypeDefinitionError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_field<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(TypeDefinitionError::UnsupportType("enum".to_owned()))
     }
 }
 
@@ -525,14 +594,18 @@ impl<'a> ser::SerializeTupleStruct for &'a mut EIP712TypeDefinition {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(TypeDefinitionError::UnsupportType(
+            "tuple struct".to_owned(),
+        ))
     }
 
     fn serialize_field<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(TypeDefinitionError::UnsupportType(
+            "tuple struct".to_owned(),
+        ))
     }
 }
 
@@ -2,10 +2,11 @@
 
 use std::collections::HashMap;
 
-use regex::Regex;
 use serde::{ser, Serialize, Serializer};
 use sha3::{Digest, Keccak256};
 
+use crate::definition::{BYTES_REGEX, INT_REGEX};
+
 #[derive(Debug, thiserror::Error)]
 pub enum EncodeTypeError {
     #[error("{0}")]
@@ -77,11 +78,31 @@ impl TupleEncoder {
         Ok(format!("{}({})", self.name, parameters.join(",")))
     }
 
-    fn pop_field(&mut self) {
+    /// Append array suffix to the last field type, `T` -> `T[]` or `T[N]`.
+    fn append_array_suffix(&mut self, fixed_len: Option<usize>) -> Result<(), EncodeTypeError> {
+        if self.field_names.len() != self.field_types.len() {
+            return Err(EncodeTypeError::FiledNameTypePair);
+        }
+
+        let type_name = self
+            .field_types
+            .last_mut()
+            .ok_or(EncodeTypeError::AppendFieldName)?;
+
+        match fixed_len {
+            Some(len) => type_name.push_str(&format!("[{}]", len)),
+            None => type_name.push_str("[]"),
+        }
+
+        Ok(())
+    }
+
+    fn pop_field(&mut self) -> Result<(), EncodeTypeError> {
         self.field_names
             .pop()
-            .ok_or(EncodeTypeError::FiledNameTypePair)
-            .unwrap();
+            .ok_or(EncodeTypeError::FiledNameTypePair)?;
+
+        Ok(())
     }
 }
 
@@ -102,7 +123,11 @@ impl EIP712TypeEncoder {
         // Remove primary type first, concat string must start with primary type.
         // visit [`Definition of encodeType`](https://eips.ethereum.org/EIPS/eip-712)
         // for more information
-        let primary_type = self.handled.remove(&self.primary_type).unwrap().unwrap();
+        let primary_type = self
+            .handled
+            .remove(&self.primary_type)
+            .flatten()
+            .ok_or(EncodeTypeError::InvalidPrimary)?;
 
         let mut keys = self.handled.keys().collect::<Vec<_>>();
 
@@ -111,8 +136,11 @@ impl EIP712TypeEncoder {
         let mut sorted = vec![primary_type];
 
         for key in keys {
-            let value = self.handled.get(key.as_str());
-            sorted.push(value.unwrap().clone().unwrap());
+            let value = self.handled[key.as_str()]
+                .clone()
+                .ok_or(EncodeTypeError::UnclosedTuple)?;
+
+            sorted.push(value);
         }
 
         Ok(sorted.join(""))
@@ -132,8 +160,7 @@ impl EIP712TypeEncoder {
     /// Call this function only when serializing none
     fn pop_field(&mut self) -> Result<(), EncodeTypeError> {
         if let Some(tuple) = self.tuples.last_mut() {
-            tuple.pop_field();
-            Ok(())
+            tuple.pop_field()
         } else {
             return Err(EncodeTypeError::InvalidPrimary);
         }
@@ -151,6 +178,15 @@ impl EIP712TypeEncoder {
         }
     }
 
+    /// Append array suffix to the last field type of parent structure, `T` -> `T[]` or `T[N]`.
+    pub fn append_array_suffix(&mut self, fixed_len: Option<usize>) -> Result<(), EncodeTypeError> {
+        if let Some(tuple) = self.tuples.last_mut() {
+            tuple.append_array_suffix(fixed_len)
+        } else {
+            Err(EncodeTypeError::InvalidPrimary)
+        }
+    }
+
     /// Start serialize one new tuple, if returns true.
     /// If returns false, no further serialization of this tuple is required.
     pub fn start_tuple(&mut self, name: &str) -> Result<bool, EncodeTypeError> {
@@ -202,8 +238,8 @@ impl EIP712TypeEncoder {
 impl<'a> Serializer for &'a mut EIP712TypeEncoder {
     type Ok = ();
     type Error = EncodeTypeError;
-    type SerializeSeq = Self;
-    type SerializeTuple = Self;
+    type SerializeSeq = EIP712TypeEncoderSerializeSeq<'a>;
+    type SerializeTuple = EIP712TypeEncoderSerializeSeq<'a>;
     type SerializeTupleStruct = Self;
     type SerializeTupleVariant = Self;
     type SerializeMap = Self;
@@ -214,23 +250,23 @@ impl<'a> Serializer for &'a mut EIP712TypeEncoder {
         false
     }
     fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        self.append_field_type("bool")
     }
 
     fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        self.append_field_type("bytes")
     }
 
     fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("Contract abi don't support rust char")
+        Err(EncodeTypeError::UnsupportType("char".to_owned()))
     }
 
     fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("EIP712 don't support f32")
+        Err(EncodeTypeError::UnsupportType("f32".to_owned()))
     }
 
     fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("EIP712 don't support f64")
+        Err(EncodeTypeError::UnsupportType("f64".to_owned()))
     }
 
     fn serialize_i128(self, _v: i128) -> Result<Self::Ok, Self::Error> {
@@ -246,7 +282,7 @@ impl<'a> Serializer for &'a mut EIP712TypeEncoder {
     }
 
     fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
-        self.append_field_type("int16")
+        self.append_field_type("int64")
     }
 
     fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
@@ -254,7 +290,7 @@ impl<'a> Serializer for &'a mut EIP712TypeEncoder {
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        unimplemented!("EIP712 don't support map")
+        Err(EncodeTypeError::UnsupportType("map".to_owned()))
     }
 
     fn serialize_newtype_struct<T: ?Sized>(
@@ -269,17 +305,14 @@ impl<'a> Serializer for &'a mut EIP712TypeEncoder {
             "bytes" => self.append_field_type("bytes"),
             "address" => self.append_field_type("address"),
             _ => {
-                let bytes_regex = Regex::new(r"^bytes(\d{1,2})$").unwrap();
-                let int_regex = Regex::new(r"^(u)?int(\d{1,3})$").unwrap();
-
-                if let Some(caps) = bytes_regex.captures(name) {
+                if let Some(caps) = BYTES_REGEX.captures(name) {
                     let len: usize = caps[1].parse().unwrap();
                     if len <= 32 {
                         return self.append_field_type(name);
                     }
                 }
 
-                if let Some(caps) = int_regex.captures(name) {
+                if let Some(caps) = INT_REGEX.captures(name) {
                     let len: usize = caps[2].parse().unwrap();
                     if len <= 256 {
                         return self.append_field_type(name);
@@ -312,7 +345,11 @@ impl<'a> Serializer for &'a mut EIP712TypeEncoder {
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        unimplemented!("EIP712 don't support rust enum")
+        Ok(EIP712TypeEncoderSerializeSeq {
+            serializer: self,
+            fixed_len: None,
+            elements: 0,
+        })
     }
 
     fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
@@ -346,11 +383,16 @@ impl<'a> Serializer for &'a mut EIP712TypeEncoder {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        unimplemented!("EIP712 don't support rust enum")
+        Err(EncodeTypeError::UnsupportType("enum".to_owned()))
     }
 
-    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        unimplemented!("EIP712 don't support serialize tuple")
+    /// Rust array `[T; N]` is mapped to `T[N]`.
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(EIP712TypeEncoderSerializeSeq {
+            serializer: self,
+            fixed_len: Some(len),
+            elements: 0,
+        })
     }
 
     fn serialize_tuple_struct(
@@ -358,7 +400,7 @@ impl<'a> Serializer for &'a mut EIP712TypeEncoder {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        unimplemented!("EIP712 don't support serialize tuple_struct")
+        Err(EncodeTypeError::UnsupportType("tuple struct".to_owned()))
     }
 
     fn serialize_tuple_variant(
@@ -368,7 +410,7 @@ impl<'a> Serializer for &'a mut EIP712TypeEncoder {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(EncodeTypeError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_u128(self, _v: u128) -> Result<Self::Ok, Self::Error> {
@@ -405,7 +447,7 @@ impl<'a> Serializer for &'a mut EIP712TypeEncoder {
         _variant_index: u32,
         _variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(EncodeTypeError::UnsupportType("enum".to_owned()))
     }
 }
 
@@ -414,37 +456,58 @@ impl<'a> ser::SerializeMap for &'a mut EIP712TypeEncoder {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(EncodeTypeError::UnsupportType("map".to_owned()))
     }
 
     fn serialize_key<T: ?Sized>(&mut self, _key: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(EncodeTypeError::UnsupportType("map".to_owned()))
     }
 
     fn serialize_value<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(EncodeTypeError::UnsupportType("map".to_owned()))
     }
 }
 
-impl<'a> ser::SerializeSeq for &'a mut EIP712TypeEncoder {
+/// Array serializer, element type is derived from the first element.
+pub struct EIP712TypeEncoderSerializeSeq<'a> {
+    serializer: &'a mut EIP712TypeEncoder,
+    /// Length of fixed array `T[N]`, [`None`] for dynamic array `T[]`.
+    fixed_len: Option<usize>,
+    elements: usize,
+}
+
+impl<'a> ser::SerializeSeq for EIP712TypeEncoderSerializeSeq<'a> {
     type Error = EncodeTypeError;
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        if self.elements == 0 {
+            return Err(EncodeTypeError::UnsupportType(
+                "empty array, element type is unknown".to_owned(),
+            ));
+        }
+
+        self.serializer.append_array_suffix(self.fixed_len)
     }
 
-    fn serialize_element<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        // All elements have the same type, only the first one is needed.
+        if self.elements == 0 {
+            value.serialize(&mut *self.serializer)?;
+        }
+
+        self.elements += 1;
+
+        Ok(())
     }
 }
 
@@ -487,7 +550,7 @@ impl<'a> ser::SerializeStructVariant for &'a mut EIP712TypeEncoder {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(EncodeTypeError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_field<T: ?Sized>(
@@ -498,23 +561,23 @@ impl<'a> ser::SerializeStructVariant for &'a mut EIP712TypeEncoder {
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(EncodeTypeError::UnsupportType("enum".to_owned()))
     }
 }
 
-impl<'a> ser::SerializeTuple for &'a mut EIP712TypeEncoder {
+impl<'a> ser::SerializeTuple for EIP712TypeEncoderSerializeSeq<'a> {
     type Error = EncodeTypeError;
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        ser::SerializeSeq::end(self)
     }
 
-    fn serialize_element<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        ser::SerializeSeq::serialize_element(self, value)
     }
 }
 
@@ -523,14 +586,14 @@ impl<'a> ser::SerializeTupleVariant for &'a mut EIP712TypeEncoder {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(EncodeTypeError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_field<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(EncodeTypeError::UnsupportType("enum".to_owned()))
     }
 }
 
@@ -539,14 +602,14 @@ impl<'a> ser::SerializeTupleStruct for &'a mut EIP712TypeEncoder {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(EncodeTypeError::UnsupportType("tuple struct".to_owned()))
     }
 
     fn serialize_field<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(EncodeTypeError::UnsupportType("tuple struct".to_owned()))
     }
 }
 
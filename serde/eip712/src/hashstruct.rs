@@ -1,13 +1,21 @@
 //! EIP712 `encodeData` implementation using the serde [`Serialize`] framework.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use ethers_primitives::{Address, FromEtherHex, ToEtherHex};
+use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{ser, Serialize, Serializer};
 use sha3::{Digest, Keccak256};
 
-use crate::TypeDefinition;
+use crate::{
+    definition::{BYTES_REGEX, INT_REGEX},
+    TypeDefinition,
+};
+
+/// Matches json string map keys.
+static STRING_KEY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^"[^"]*"$"#).expect("Compile string key regex"));
 
 #[derive(Debug, thiserror::Error)]
 pub enum EncodeDataError {
@@ -29,6 +37,9 @@ pub enum EncodeDataError {
     #[error("Encode data is empty")]
     Empty,
 
+    #[error("MissingField: field {0} is not serialized")]
+    MissingField(String),
+
     #[error("Bytes32OutofRange: {0}")]
     Bytes32OutofRange(String),
 
@@ -49,6 +60,8 @@ impl ser::Error for EncodeDataError {
 struct TupleEncoder {
     names: Vec<String>,
     fields: Vec<[u8; 32]>,
+    /// Element type if encoding array, e.g. `Person` of `Person[]`.
+    element_type: Option<String>,
 }
 
 impl TupleEncoder {
@@ -65,7 +78,11 @@ impl TupleEncoder {
         self.names.push(name.to_owned());
     }
 
-    fn finalize(self, type_hash: [u8; 32], definition: TypeDefinition) -> [u8; 32] {
+    fn finalize(
+        self,
+        type_hash: [u8; 32],
+        definition: TypeDefinition,
+    ) -> Result<[u8; 32], EncodeDataError> {
         let mut hasher = Keccak256::new();
 
         hasher.update(&type_hash);
@@ -77,7 +94,7 @@ impl TupleEncoder {
                 .enumerate()
                 .find(|(_, n)| n.as_str() == field.name)
                 .map(|(index, _)| index)
-                .unwrap();
+                .ok_or_else(|| EncodeDataError::MissingField(field.name.clone()))?;
 
             log::debug!(
                 "hash update element {} with index {}: {:x?}",
@@ -89,6 +106,17 @@ impl TupleEncoder {
             hasher.update(&self.fields[index])
         }
 
+        Ok(hasher.finalize().into())
+    }
+
+    /// `keccak256(encodeData(e1) ‖ encodeData(e2) ‖ ..)` of array elements.
+    fn finalize_array(self) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+
+        for field in &self.fields {
+            hasher.update(field);
+        }
+
         hasher.finalize().into()
     }
 }
@@ -102,14 +130,18 @@ pub struct EIP712StructHasher<'a> {
     type_stack: Vec<(String, TypeDefinition)>,
 }
 
-fn extract_type_name(name: &str) -> String {
-    let regex = Regex::new(r#"^([^\[]+)\[\d*\]$"#).unwrap();
+/// Strip array suffixes of type name, e.g. `Person` of `Person[2][]`.
+fn extract_type_name(name: &str) -> &str {
+    name.split('[').next().unwrap_or(name)
+}
 
-    if let Some(caps) = regex.captures(name) {
-        caps[1].to_owned()
-    } else {
-        name.to_owned()
+/// Returns element type of array type name, e.g. `Person[2]` of `Person[2][]`.
+fn array_element_type(name: &str) -> Option<&str> {
+    if !name.ends_with(']') {
+        return None;
     }
+
+    name.rsplit_once('[').map(|(element, _)| element)
 }
 
 impl<'a> EIP712StructHasher<'a> {
@@ -140,12 +172,17 @@ impl<'a> EIP712StructHasher<'a> {
             }
         }
 
-        let parent_type = self.type_stack.last().unwrap();
+        let parent_type = self.type_stack.last().ok_or(EncodeDataError::StartTuple)?;
 
         if let Some(type_definition) = parent_type.1.iter().find(|d| d.name == name) {
             log::debug!("start encode type {}", type_definition.r#type);
 
-            let type_name = extract_type_name(&type_definition.r#type);
+            // Array element types are pushed per element, see `SerializeSeq`.
+            if array_element_type(&type_definition.r#type).is_some() {
+                return Ok(());
+            }
+
+            let type_name = type_definition.r#type.clone();
 
             if let Some(definition) = self.types.get(&type_name) {
                 self.type_stack.push((type_name, definition.to_owned()));
@@ -181,38 +218,31 @@ impl<'a> EIP712StructHasher<'a> {
             }
         }
 
-        let primary_type = self.type_stack.last().unwrap();
+        let (primary_name, primary_definition) =
+            self.type_stack.last().ok_or(EncodeDataError::StartTuple)?;
 
-        let mut types = HashMap::<String, (String, TypeDefinition)>::new();
+        // Referenced types sorted by name, each type is visited once to support recursive types.
+        let mut dependencies = BTreeMap::<&str, &TypeDefinition>::new();
 
-        let mut stack = vec![primary_type];
+        let mut stack = vec![primary_definition];
 
-        while !stack.is_empty() {
-            let current = stack.pop().unwrap();
-            for field in &current.1 {
+        while let Some(definition) = stack.pop() {
+            for field in definition {
                 let type_name = extract_type_name(&field.r#type);
 
-                if !types.contains_key(&type_name) {
-                    if let Some(definition) = self.types.get(&type_name) {
-                        types.insert(type_name.clone(), (type_name, definition.to_owned()));
-                    }
+                if type_name == primary_name || dependencies.contains_key(type_name) {
+                    continue;
                 }
-            }
-        }
-
-        let mut keys = types.keys().collect::<Vec<_>>();
-
-        keys.sort_by(|a, b| a.cmp(b));
 
-        let mut sorted = vec![primary_type];
-
-        for key in keys {
-            let value = types.get(key.as_str());
-            sorted.push(value.unwrap());
+                if let Some((name, definition)) = self.types.get_key_value(type_name) {
+                    dependencies.insert(name, definition);
+                    stack.push(definition);
+                }
+            }
         }
 
-        let encode_type = sorted
-            .iter()
+        let encode_type = std::iter::once((primary_name.as_str(), primary_definition))
+            .chain(dependencies)
             .map(|(name, fields)| {
                 let fields = fields
                     .iter()
@@ -242,7 +272,11 @@ impl<'a> EIP712StructHasher<'a> {
 
     pub fn end_tuple(&mut self) -> Result<(), EncodeDataError> {
         if let Some(tuple) = self.tuple_stack.pop() {
-            let encode_data = tuple.finalize(self.type_hash()?, self.type_stack.pop().unwrap().1);
+            let type_hash = self.type_hash()?;
+
+            let (_, definition) = self.type_stack.pop().ok_or(EncodeDataError::StartTuple)?;
+
+            let encode_data = tuple.finalize(type_hash, definition)?;
 
             if let Some(tuple) = self.tuple_stack.last_mut() {
                 tuple.append_element(encode_data);
@@ -274,10 +308,57 @@ impl<'a> EIP712StructHasher<'a> {
         }
     }
 
+    /// Start encode array of type `type_name`, e.g. `Person[]`.
+    fn start_array(&mut self, type_name: Option<String>) -> Result<(), EncodeDataError> {
+        let element_type = type_name
+            .as_deref()
+            .and_then(array_element_type)
+            .ok_or_else(|| {
+                EncodeDataError::UnsupportType(format!(
+                    "sequence value of type {}",
+                    type_name.as_deref().unwrap_or("unknown")
+                ))
+            })?;
+
+        self.tuple_stack.push(TupleEncoder {
+            element_type: Some(element_type.to_owned()),
+            ..Default::default()
+        });
+
+        Ok(())
+    }
+
+    /// Push struct type of next array element, if any.
+    fn start_array_element(&mut self) -> Result<(), EncodeDataError> {
+        let element_type = self
+            .tuple_stack
+            .last()
+            .and_then(|tuple| tuple.element_type.clone())
+            .ok_or(EncodeDataError::EndTuple)?;
+
+        if let Some(definition) = self.types.get(&element_type) {
+            self.type_stack.push((element_type, definition.to_owned()));
+        }
+
+        Ok(())
+    }
+
+    fn end_array(&mut self) -> Result<(), EncodeDataError> {
+        let tuple = self.tuple_stack.pop().ok_or(EncodeDataError::EndTuple)?;
+
+        let encode_data = tuple.finalize_array();
+
+        self.append_element(encode_data)
+    }
+
     fn element_type_name(&self) -> Result<Option<String>, EncodeDataError> {
         if let Some(tuple) = self.tuple_stack.last() {
+            if let Some(element_type) = &tuple.element_type {
+                return Ok(Some(element_type.clone()));
+            }
+
             if let Some(name) = tuple.last_name() {
-                let (_, fields) = self.type_stack.last().unwrap();
+                let (_, fields) = self.type_stack.last().ok_or(EncodeDataError::StartTuple)?;
 
                 if let Some(field) = fields.iter().find(|f| f.name.as_str() == name) {
                     return Ok(Some(field.r#type.clone()));
@@ -305,8 +386,12 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
     fn is_human_readable(&self) -> bool {
         false
     }
-    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        let mut buff = [0u8; 32];
+
+        buff[31] = v as u8;
+
+        self.append_element(buff)
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
@@ -314,22 +399,22 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
     }
 
     fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("Contract abi don't support rust char")
+        Err(EncodeDataError::UnsupportType("char".to_owned()))
     }
 
     fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("EIP712 don't support f32")
+        Err(EncodeDataError::UnsupportType("f32".to_owned()))
     }
 
     fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("EIP712 don't support f64")
+        Err(EncodeDataError::UnsupportType("f64".to_owned()))
     }
 
     fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
         let mut buff = if v.is_negative() {
-            [0u8; 32]
-        } else {
             [0xffu8; 32]
+        } else {
+            [0u8; 32]
         };
 
         buff[16..].copy_from_slice(&v.to_be_bytes());
@@ -369,9 +454,8 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
     {
         match name {
             "bytes" => {
-                let bytes = unsafe { (value as *const T).cast::<Vec<u8>>().as_ref().unwrap() };
-
-                self.append_element(Keccak256::new().chain_update(bytes).finalize().into())
+                // Payload is written by `serialize_bytes`.
+                value.serialize(self)
             }
             "address" => {
                 let bytes = unsafe { (value as *const T).cast::<[u8; 32]>().as_ref().unwrap() };
@@ -379,10 +463,7 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
                 self.append_element(bytes.to_owned())
             }
             _ => {
-                let bytes_regex = Regex::new(r"^bytes(\d{1,2})$").unwrap();
-                let int_regex = Regex::new(r"^(u)?int(\d{1,3})$").unwrap();
-
-                if let Some(caps) = bytes_regex.captures(name) {
+                if let Some(caps) = BYTES_REGEX.captures(name) {
                     let len: usize = caps[1].parse().unwrap();
                     if len <= 32 {
                         let bytes =
@@ -392,7 +473,7 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
                     }
                 }
 
-                if let Some(caps) = int_regex.captures(name) {
+                if let Some(caps) = INT_REGEX.captures(name) {
                     let len: usize = caps[2].parse().unwrap();
                     if len <= 256 {
                         let bytes =
@@ -428,7 +509,11 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        unimplemented!("EIP712 don't support rust enum")
+        let type_name = self.element_type_name()?;
+
+        self.start_array(type_name)?;
+
+        Ok(self)
     }
 
     fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
@@ -459,10 +544,7 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
                     return self.append_element(bytes);
                 }
                 _ => {
-                    let bytes_regex = Regex::new(r"^bytes(\d{1,2})$").unwrap();
-                    let int_regex = Regex::new(r"^(u)?int(\d{1,3})$").unwrap();
-
-                    if let Some(caps) = bytes_regex.captures(&type_name) {
+                    if let Some(caps) = BYTES_REGEX.captures(&type_name) {
                         let len: usize = caps[1].parse().unwrap();
                         if len <= 32 {
                             let buff = Vec::<u8>::from_eth_hex(v).map_err(ser::Error::custom)?;
@@ -473,26 +555,21 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
 
                             let mut bytes = [0u8; 32];
 
-                            bytes.copy_from_slice(&buff);
+                            bytes[..buff.len()].copy_from_slice(&buff);
 
                             return self.append_element(bytes);
                         }
                     }
 
-                    if let Some(caps) = int_regex.captures(&type_name) {
+                    if let Some(caps) = INT_REGEX.captures(&type_name) {
                         let len: usize = caps[2].parse().unwrap();
                         if len <= 256 {
                             let buff = Vec::<u8>::from_eth_hex(v).map_err(ser::Error::custom)?;
 
-                            if len < buff.len() * 8 {
-                                return Err(EncodeDataError::IntOutofRange(v.to_owned()));
-                            }
-
-                            let mut bytes = [0u8; 32];
-
-                            bytes[(32 - buff.len())..].copy_from_slice(&buff);
+                            let bytes = int_hex_word(&buff, caps.get(1).is_none(), len)
+                                .ok_or_else(|| EncodeDataError::IntOutofRange(v.to_owned()))?;
 
-                            return self.append_element(bytes.to_owned());
+                            return self.append_element(bytes);
                         }
                     }
                 }
@@ -524,11 +601,16 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        unimplemented!("EIP712 don't support rust enum")
+        Err(EncodeDataError::UnsupportType("enum".to_owned()))
     }
 
+    /// Fixed size arrays, e.g. `[Person; 2]`.
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        unimplemented!("EIP712 don't support serialize tuple")
+        let type_name = self.element_type_name()?;
+
+        self.start_array(type_name)?;
+
+        Ok(self)
     }
 
     fn serialize_tuple_struct(
@@ -536,7 +618,7 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        unimplemented!("EIP712 don't support serialize tuple_struct")
+        Err(EncodeDataError::UnsupportType("tuple struct".to_owned()))
     }
 
     fn serialize_tuple_variant(
@@ -546,7 +628,7 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(EncodeDataError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
@@ -587,7 +669,7 @@ impl<'a, 'b> Serializer for &'a mut EIP712StructHasher<'b> {
         _variant_index: u32,
         _variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(EncodeDataError::UnsupportType("enum".to_owned()))
     }
 }
 
@@ -626,9 +708,7 @@ impl<'a, 'b> ser::SerializeMap for &'a mut EIP712StructHasher<'b> {
     {
         let name = serde_json::to_string(key).map_err(ser::Error::custom)?;
 
-        let reg = Regex::new(r#"^"[^"]*"$"#).unwrap();
-
-        if !reg.is_match(&name) {
+        if !STRING_KEY_REGEX.is_match(&name) {
             return Err(EncodeDataError::Unknown(
                 "HashStruct only support map with string key".to_owned(),
             ));
@@ -650,14 +730,15 @@ impl<'a, 'b> ser::SerializeSeq for &'a mut EIP712StructHasher<'b> {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        self.end_array()
     }
 
-    fn serialize_element<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        self.start_array_element()?;
+        value.serialize(&mut **self)
     }
 }
 
@@ -666,7 +747,7 @@ impl<'a, 'b> ser::SerializeStructVariant for &'a mut EIP712StructHasher<'b> {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(EncodeDataError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_field<T: ?Sized>(
@@ -677,7 +758,7 @@ impl<'a, 'b> ser::SerializeStructVariant for &'a mut EIP712StructHasher<'b> {
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(EncodeDataError::UnsupportType("enum".to_owned()))
     }
 }
 
@@ -686,14 +767,15 @@ impl<'a, 'b> ser::SerializeTuple for &'a mut EIP712StructHasher<'b> {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        self.end_array()
     }
 
-    fn serialize_element<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        self.start_array_element()?;
+        value.serialize(&mut **self)
     }
 }
 
@@ -702,14 +784,14 @@ impl<'a, 'b> ser::SerializeTupleVariant for &'a mut EIP712StructHasher<'b> {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(EncodeDataError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_field<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(EncodeDataError::UnsupportType("enum".to_owned()))
     }
 }
 
@@ -718,17 +800,51 @@ impl<'a, 'b> ser::SerializeTupleStruct for &'a mut EIP712StructHasher<'b> {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(EncodeDataError::UnsupportType("tuple struct".to_owned()))
     }
 
     fn serialize_field<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(EncodeDataError::UnsupportType("tuple struct".to_owned()))
     }
 }
 
+/// Returns 32 bytes word of `intN`/`uintN` hex value `buff`, or [`None`] if out of range.
+///
+/// Like `Int` of `ethers_primitives`, signed values with leading `0xff` are negative two's
+/// complement values, e.g. `0xff80` is `-128`, the others are positive.
+pub(crate) fn int_hex_word(buff: &[u8], signed: bool, bits: usize) -> Option<[u8; 32]> {
+    if bits == 0 || bits > 256 || !bits.is_multiple_of(8) {
+        return None;
+    }
+
+    let negative = signed && buff.first() == Some(&0xff);
+
+    let fill = if negative { 0xff } else { 0x00 };
+
+    let buff = match buff.len().checked_sub(32) {
+        Some(extra) if buff[..extra].iter().all(|b| *b == fill) => &buff[extra..],
+        Some(_) => return None,
+        None => buff,
+    };
+
+    let mut word = [fill; 32];
+
+    word[32 - buff.len()..].copy_from_slice(buff);
+
+    let start = 32 - bits / 8;
+
+    // Leading bytes are sign extension, the sign bit of `bits` width value must match.
+    if word[..start].iter().any(|b| *b != fill) || (signed && (word[start] & 0x80 != 0) != negative)
+    {
+        return None;
+    }
+
+    Some(word)
+}
+
 /// Calculate struct hash,
 /// see [`Definition of hashStruct`](https://eips.ethereum.org/EIPS/eip-712) for more information
 pub fn eip712_hash_struct<S: Serialize>(
@@ -748,3 +864,92 @@ pub fn eip712_hash_struct<S: Serialize>(
 
     hasher.finalize()
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+
+    #[test]
+    fn test_unsupported_types() {
+        #[derive(Serialize)]
+        struct Mail {
+            tag: char,
+        }
+
+        let types: HashMap<String, TypeDefinition> = serde_json::from_value(serde_json::json!({
+            "Mail": [{ "name": "tag", "type": "string" }]
+        }))
+        .unwrap();
+
+        assert!(matches!(
+            eip712_hash_struct("Mail", &types, &Mail { tag: 'a' }),
+            Err(EncodeDataError::UnsupportType(name)) if name == "char"
+        ));
+
+        // Field of type definition is not serialized.
+        #[derive(Serialize)]
+        struct Empty {}
+
+        assert!(matches!(
+            eip712_hash_struct("Mail", &types, &Empty {}),
+            Err(EncodeDataError::MissingField(name)) if name == "tag"
+        ));
+    }
+
+    #[test]
+    fn test_signed_hex() {
+        let types: HashMap<String, TypeDefinition> = serde_json::from_value(serde_json::json!({
+            "Int8": [{ "name": "value", "type": "int8" }],
+            "Uint8": [{ "name": "value", "type": "uint8" }],
+            "Int256": [{ "name": "value", "type": "int256" }],
+        }))
+        .unwrap();
+
+        let hash = |primary_type: &str, value: serde_json::Value| {
+            eip712_hash_struct(primary_type, &types, &serde_json::json!({ "value": value }))
+        };
+
+        // Hex values hash the same as numbers at the width boundaries.
+        for (hex, number) in [("0xff80", -128), ("0xff", -1), ("0x7f", 127), ("0x00", 0)] {
+            assert_eq!(
+                hash("Int8", hex.into()).unwrap(),
+                hash("Int8", number.into()).unwrap(),
+                "{}",
+                hex
+            );
+        }
+
+        assert_eq!(
+            hash("Uint8", "0xff".into()).unwrap(),
+            hash("Uint8", 255.into()).unwrap()
+        );
+
+        for (primary_type, hex) in [
+            ("Int8", "0x80"),
+            ("Int8", "0xff7f"),
+            ("Int8", "0x0100"),
+            ("Uint8", "0x0100"),
+        ] {
+            assert!(
+                matches!(
+                    hash(primary_type, hex.into()),
+                    Err(EncodeDataError::IntOutofRange(_))
+                ),
+                "{} {}",
+                primary_type,
+                hex
+            );
+        }
+
+        let min = format!("0xff80{}", "00".repeat(31));
+
+        assert!(hash("Int256", min.into()).is_ok());
+        assert!(hash("Int256", format!("0x80{}", "00".repeat(31)).into()).is_err());
+        assert_eq!(
+            hash("Int256", format!("0x{}", "ff".repeat(32)).into()).unwrap(),
+            hash("Int256", (-1).into()).unwrap()
+        );
+    }
+}
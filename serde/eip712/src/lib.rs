@@ -1,7 +1,9 @@
 mod definition;
 mod enctype;
 mod hashstruct;
+mod validate;
 
 pub use definition::*;
 pub use enctype::*;
 pub use hashstruct::*;
+pub use validate::*;
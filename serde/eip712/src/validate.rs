@@ -0,0 +1,423 @@
+//! Validate untyped EIP712 messages against type definitions before hashing.
+
+use std::collections::HashMap;
+
+use ethers_primitives::FromEtherHex;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    definition::{BYTES_REGEX, INT_REGEX},
+    hashstruct::int_hex_word,
+    TypeDefinition,
+};
+
+/// Matches array type names and captures element type and optional fixed length.
+static ARRAY_TYPE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(.+)\[(\d*)\]$").expect("Compile array type regex"));
+
+#[derive(Debug, thiserror::Error)]
+pub enum ValidateError {
+    #[error("TypeNotFound: `{0}` is neither builtin type nor defined in types")]
+    TypeNotFound(String),
+
+    #[error("MissingField: `{path}` of type `{r#type}` is missing")]
+    MissingField { path: String, r#type: String },
+
+    #[error("UnknownField: `{path}` is not defined in type `{r#type}`")]
+    UnknownField { path: String, r#type: String },
+
+    #[error("InvalidValue: `{path}` expect `{r#type}`, {reason}, got {value}")]
+    InvalidValue {
+        path: String,
+        r#type: String,
+        reason: String,
+        value: String,
+    },
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+}
+
+struct Validator<'a> {
+    types: &'a HashMap<String, TypeDefinition>,
+}
+
+impl<'a> Validator<'a> {
+    fn invalid(path: &str, type_name: &str, reason: &str, value: &Value) -> ValidateError {
+        ValidateError::InvalidValue {
+            path: path.to_owned(),
+            r#type: type_name.to_owned(),
+            reason: reason.to_owned(),
+            value: value.to_string(),
+        }
+    }
+
+    fn validate(&self, path: &str, type_name: &str, value: &Value) -> Result<(), ValidateError> {
+        if let Some(caps) = ARRAY_TYPE_REGEX.captures(type_name) {
+            return self.validate_array(path, type_name, &caps[1], &caps[2], value);
+        }
+
+        if let Some(definition) = self.types.get(type_name) {
+            return self.validate_struct(path, type_name, definition, value);
+        }
+
+        match type_name {
+            "bool" => match value {
+                Value::Bool(_) => Ok(()),
+                _ => Err(Self::invalid(path, type_name, "expect json bool", value)),
+            },
+            "string" => match value {
+                Value::String(_) => Ok(()),
+                _ => Err(Self::invalid(path, type_name, "expect json string", value)),
+            },
+            "address" => {
+                let hex = Self::hex(path, type_name, value)?;
+
+                if hex.len() != 40 {
+                    return Err(Self::invalid(path, type_name, "expect 20 bytes", value));
+                }
+
+                Ok(())
+            }
+            "bytes" => {
+                if Self::hex(path, type_name, value)?.len() % 2 != 0 {
+                    return Err(Self::invalid(
+                        path,
+                        type_name,
+                        "odd length hex string",
+                        value,
+                    ));
+                }
+
+                Ok(())
+            }
+            _ => {
+                if let Some(caps) = BYTES_REGEX.captures(type_name) {
+                    let len: usize = caps[1].parse().unwrap();
+
+                    if (1..=32).contains(&len) {
+                        let hex = Self::hex(path, type_name, value)?;
+
+                        if hex.len() != len * 2 {
+                            return Err(Self::invalid(
+                                path,
+                                type_name,
+                                &format!("expect {} bytes", len),
+                                value,
+                            ));
+                        }
+
+                        return Ok(());
+                    }
+                }
+
+                if let Some(caps) = INT_REGEX.captures(type_name) {
+                    let bits: usize = caps[2].parse().unwrap();
+
+                    if bits > 0 && bits <= 256 && bits.is_multiple_of(8) {
+                        return self.validate_int(
+                            path,
+                            type_name,
+                            caps.get(1).is_none(),
+                            bits,
+                            value,
+                        );
+                    }
+                }
+
+                Err(ValidateError::TypeNotFound(type_name.to_owned()))
+            }
+        }
+    }
+
+    /// Returns hex digits of `0x` prefixed hex string, odd length is allowed.
+    fn hex<'v>(path: &str, type_name: &str, value: &'v Value) -> Result<&'v str, ValidateError> {
+        let hex = value
+            .as_str()
+            .and_then(|value| value.strip_prefix("0x"))
+            .ok_or_else(|| {
+                Self::invalid(path, type_name, "expect 0x prefixed hex string", value)
+            })?;
+
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Self::invalid(path, type_name, "invalid hex string", value));
+        }
+
+        Ok(hex)
+    }
+
+    fn validate_int(
+        &self,
+        path: &str,
+        type_name: &str,
+        signed: bool,
+        bits: usize,
+        value: &Value,
+    ) -> Result<(), ValidateError> {
+        match value {
+            Value::Number(number) => {
+                let out_of_range = if let Some(v) = number.as_u64() {
+                    let width = bits - signed as usize;
+
+                    width < 64 && v >> width != 0
+                } else if let Some(v) = number.as_i64() {
+                    !signed || (bits < 64 && v < -(1i64 << (bits - 1)))
+                } else {
+                    return Err(Self::invalid(path, type_name, "expect integer", value));
+                };
+
+                if out_of_range {
+                    return Err(Self::invalid(path, type_name, "out of range", value));
+                }
+
+                Ok(())
+            }
+            Value::String(_) => {
+                let hex = Self::hex(path, type_name, value).map_err(|_| {
+                    Self::invalid(
+                        path,
+                        type_name,
+                        "expect json number or 0x prefixed hex string",
+                        value,
+                    )
+                })?;
+
+                let buff = Vec::<u8>::from_eth_hex(hex)
+                    .map_err(|_| Self::invalid(path, type_name, "invalid hex string", value))?;
+
+                // Same range as hashing, negative values have leading `0xff`.
+                if int_hex_word(&buff, signed, bits).is_none() {
+                    return Err(Self::invalid(path, type_name, "out of range", value));
+                }
+
+                Ok(())
+            }
+            _ => Err(Self::invalid(
+                path,
+                type_name,
+                "expect json number or 0x prefixed hex string",
+                value,
+            )),
+        }
+    }
+
+    fn validate_array(
+        &self,
+        path: &str,
+        type_name: &str,
+        element_type: &str,
+        len: &str,
+        value: &Value,
+    ) -> Result<(), ValidateError> {
+        let elements = value
+            .as_array()
+            .ok_or_else(|| Self::invalid(path, type_name, "expect json array", value))?;
+
+        if !len.is_empty() && len.parse::<usize>().ok() != Some(elements.len()) {
+            return Err(Self::invalid(
+                path,
+                type_name,
+                &format!("expect {} elements", len),
+                value,
+            ));
+        }
+
+        for (index, element) in elements.iter().enumerate() {
+            self.validate(&format!("{}[{}]", path, index), element_type, element)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_struct(
+        &self,
+        path: &str,
+        type_name: &str,
+        definition: &TypeDefinition,
+        value: &Value,
+    ) -> Result<(), ValidateError> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| Self::invalid(path, type_name, "expect json object", value))?;
+
+        for field in definition {
+            let field_path = format!("{}.{}", path, field.name);
+
+            let field_value =
+                object
+                    .get(&field.name)
+                    .ok_or_else(|| ValidateError::MissingField {
+                        path: field_path.clone(),
+                        r#type: field.r#type.clone(),
+                    })?;
+
+            self.validate(&field_path, &field.r#type, field_value)?;
+        }
+
+        if let Some(key) = object
+            .keys()
+            .find(|key| !definition.iter().any(|field| &field.name == *key))
+        {
+            return Err(ValidateError::UnknownField {
+                path: format!("{}.{}", path, key),
+                r#type: type_name.to_owned(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Validate `value` of `primary_type` against type definitions `types`, returns error on
+/// missing fields, unknown fields, and values which do not match their declared types.
+///
+/// `value` is checked in json form, integers are either json numbers or `0x` prefixed hex strings.
+pub fn eip712_validate<S: Serialize>(
+    primary_type: &str,
+    types: &HashMap<String, TypeDefinition>,
+    value: &S,
+) -> Result<(), ValidateError> {
+    if !types.contains_key(primary_type) {
+        return Err(ValidateError::TypeNotFound(primary_type.to_owned()));
+    }
+
+    let value = serde_json::to_value(value)?;
+
+    Validator { types }.validate(primary_type, primary_type, &value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn types() -> HashMap<String, TypeDefinition> {
+        serde_json::from_value(json!({
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallets", "type": "address[]" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person[2]" },
+                { "name": "amount", "type": "uint8" },
+                { "name": "tag", "type": "bytes4" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        let types = types();
+
+        let person =
+            json!({ "name": "Cow", "wallets": ["0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"] });
+
+        let mut mail = json!({
+            "from": person,
+            "to": [person, person],
+            "amount": 255,
+            "tag": "0x01020304"
+        });
+
+        eip712_validate("Mail", &types, &mail).unwrap();
+
+        mail["amount"] = json!(256);
+
+        assert!(matches!(
+            eip712_validate("Mail", &types, &mail),
+            Err(ValidateError::InvalidValue { path, .. }) if path == "Mail.amount"
+        ));
+
+        mail["amount"] = json!("100");
+
+        assert!(eip712_validate("Mail", &types, &mail).is_err());
+
+        mail["amount"] = json!("0x0100");
+
+        assert!(eip712_validate("Mail", &types, &mail).is_err());
+
+        mail["amount"] = json!("0xff");
+        mail["to"][1]["wallets"][0] = json!("0x1234");
+
+        assert!(matches!(
+            eip712_validate("Mail", &types, &mail),
+            Err(ValidateError::InvalidValue { path, .. }) if path == "Mail.to[1].wallets[0]"
+        ));
+
+        mail["to"][1] = person.clone();
+        mail["from"].as_object_mut().unwrap().remove("name");
+
+        assert!(matches!(
+            eip712_validate("Mail", &types, &mail),
+            Err(ValidateError::MissingField { path, .. }) if path == "Mail.from.name"
+        ));
+
+        mail["from"] = person;
+        mail["cc"] = json!("Bob");
+
+        assert!(matches!(
+            eip712_validate("Mail", &types, &mail),
+            Err(ValidateError::UnknownField { path, .. }) if path == "Mail.cc"
+        ));
+
+        assert!(matches!(
+            eip712_validate("Letter", &types, &mail),
+            Err(ValidateError::TypeNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_signed() {
+        let types: HashMap<String, TypeDefinition> = serde_json::from_value(json!({
+            "Delta": [
+                { "name": "small", "type": "int8" },
+                { "name": "large", "type": "int256" }
+            ]
+        }))
+        .unwrap();
+
+        let valid = [
+            (json!(-128), json!(-1)),
+            (
+                json!(127),
+                json!("0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"),
+            ),
+            (json!("0xff80"), json!(format!("0xff80{}", "00".repeat(31)))),
+            (json!("0xff"), json!(format!("0x{}", "ff".repeat(32)))),
+            (json!("0x7f"), json!("0x00")),
+        ];
+
+        for (small, large) in valid {
+            eip712_validate("Delta", &types, &json!({ "small": small, "large": large })).unwrap();
+        }
+
+        let invalid = [
+            (json!(-129), json!(0)),
+            (json!(128), json!(0)),
+            (json!("0x80"), json!(0)),
+            (json!("0xff7f"), json!(0)),
+            (json!("0x0100"), json!(0)),
+            (json!(0), json!(format!("0x80{}", "00".repeat(31)))),
+            (json!(0), json!(format!("0x01{}", "00".repeat(32)))),
+        ];
+
+        for (small, large) in invalid {
+            let value = json!({ "small": small, "large": large });
+
+            assert!(
+                matches!(
+                    eip712_validate("Delta", &types, &value),
+                    Err(ValidateError::InvalidValue { reason, .. }) if reason == "out of range"
+                ),
+                "{}",
+                value
+            );
+        }
+    }
+}
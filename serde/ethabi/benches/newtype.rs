@@ -0,0 +1,121 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_ethabi::{from_abi, to_abi};
+
+/// Fixed size abi value serialized as newtype, like `ethers_primitives` types.
+#[derive(Clone, Copy)]
+struct Word<const NAME: u8>([u8; 32]);
+
+impl<const NAME: u8> Word<NAME> {
+    fn name() -> &'static str {
+        match NAME {
+            0 => "uint256",
+            1 => "int64",
+            _ => "bytes32",
+        }
+    }
+}
+
+impl<const NAME: u8> Serialize for Word<NAME> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(Self::name(), &self.0)
+    }
+}
+
+impl<'de, const NAME: u8> Deserialize<'de> for Word<NAME> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(Self::name(), WordVisitor::<NAME>)
+    }
+}
+
+struct WordVisitor<const NAME: u8>;
+
+impl<'de, const NAME: u8> de::Visitor<'de> for WordVisitor<NAME> {
+    type Value = Word<NAME>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "expect 32 bytes")
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.try_into()
+            .map(Word)
+            .map_err(|_| de::Error::custom("expect 32 bytes"))
+    }
+}
+
+type U256 = Word<0>;
+type I64 = Word<1>;
+type Bytes32 = Word<2>;
+
+/// Order fields, nested structs are serialized as tuples.
+type Order = (U256, U256, U256, I64, Bytes32, U256);
+
+/// Function call parameters `(nonce, deadline, orders, salt)`.
+type Batch = (U256, U256, Vec<Order>, Bytes32);
+
+fn word(i: u32) -> [u8; 32] {
+    let mut word = [0u8; 32];
+
+    word[28..].copy_from_slice(&i.to_be_bytes());
+
+    word
+}
+
+fn batch(orders: u32) -> Batch {
+    (
+        Word(word(1)),
+        Word(word(u32::MAX)),
+        (0..orders)
+            .map(|i| {
+                (
+                    Word(word(i)),
+                    Word(word(i + 1)),
+                    Word(word(i * 2)),
+                    Word(word(i)),
+                    Word([i as u8; 32]),
+                    Word(word(u32::MAX - i)),
+                )
+            })
+            .collect(),
+        Word([0x5a; 32]),
+    )
+}
+
+fn bench_newtype(c: &mut Criterion) {
+    let values = (0..1000u32).map(|i| Word(word(i))).collect::<Vec<U256>>();
+
+    c.bench_function("to_abi uint256[1000]", |b| {
+        b.iter(|| to_abi(black_box(&values)).unwrap())
+    });
+
+    let data = to_abi(&values).unwrap();
+
+    c.bench_function("from_abi uint256[1000]", |b| {
+        b.iter(|| from_abi::<Vec<U256>, _>(black_box(data.clone())).unwrap())
+    });
+
+    let batch = batch(100);
+
+    c.bench_function("to_abi 100 order tuples", |b| {
+        b.iter(|| to_abi(black_box(&batch)).unwrap())
+    });
+
+    let data = to_abi(&batch).unwrap();
+
+    c.bench_function("from_abi 100 order tuples", |b| {
+        b.iter(|| from_abi::<Batch, _>(black_box(data.clone())).unwrap())
+    });
+}
+
+criterion_group!(benches, bench_newtype);
+criterion_main!(benches);
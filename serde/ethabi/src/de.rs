@@ -1,8 +1,9 @@
 use bytes::{Buf, Bytes};
-use regex::Regex;
 use serde::{de, Deserialize};
 use thiserror::Error;
 
+use crate::ser::{BYTES_REGEX, INT_REGEX};
+
 /// Abi serializer error variant
 #[derive(Debug, Error)]
 pub enum AbiDeError {
@@ -16,6 +17,9 @@ pub enum AbiDeError {
 
     #[error("Next element is static type,{0}")]
     NextIsStatic(String),
+
+    #[error("Unsupport deserialize type, {0}")]
+    UnsupportType(String),
 }
 
 impl de::Error for AbiDeError {
@@ -141,7 +145,7 @@ fn read_bytes(buff: Bytes) -> Result<(Bytes, usize), AbiDeError> {
 
     let len: usize = decoder.read_usize()?;
 
-    let padding_zeros = 32 - len % 32;
+    let padding_zeros = (32 - len % 32) % 32;
 
     let end = 32 + len;
 
@@ -189,7 +193,7 @@ impl AbiDeserializer {
             if let Some(parent) = self.tuple_stacks.last_mut() {
                 parent.end_read_tuple(len)?;
             } else {
-                _ = self.root_buff.advance(len);
+                self.root_buff.advance(len.min(self.root_buff.len()));
             }
 
             Ok(())
@@ -262,9 +266,9 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut AbiDeserializer {
     where
         V: de::Visitor<'de>,
     {
-        unimplemented!(
-            "The ethereum contract abi is not a self-describing format. don't call deserialize_any"
-        )
+        Err(AbiDeError::UnsupportType(
+            "any, contract abi is not a self-describing format".to_owned(),
+        ))
     }
 
     fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -298,7 +302,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut AbiDeserializer {
     where
         V: de::Visitor<'de>,
     {
-        unimplemented!("Contract abi don't support rust char")
+        Err(AbiDeError::UnsupportType("char".to_owned()))
     }
 
     fn deserialize_enum<V>(
@@ -310,21 +314,21 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut AbiDeserializer {
     where
         V: de::Visitor<'de>,
     {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(AbiDeError::UnsupportType("enum".to_owned()))
     }
 
     fn deserialize_f32<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unimplemented!("Contract abi don't support rust f32")
+        Err(AbiDeError::UnsupportType("f32".to_owned()))
     }
 
     fn deserialize_f64<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unimplemented!("Contract abi don't support rust f64")
+        Err(AbiDeError::UnsupportType("f64".to_owned()))
     }
 
     fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -386,21 +390,21 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut AbiDeserializer {
     where
         V: de::Visitor<'de>,
     {
-        unimplemented!()
+        Err(AbiDeError::UnsupportType("identifier".to_owned()))
     }
 
     fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unimplemented!()
+        Err(AbiDeError::UnsupportType("ignored any".to_owned()))
     }
 
     fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unimplemented!("Contract abi don't support rust map")
+        Err(AbiDeError::UnsupportType("map".to_owned()))
     }
 
     fn deserialize_newtype_struct<V>(
@@ -423,10 +427,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut AbiDeserializer {
                 return visitor.visit_byte_buf(buff.to_vec());
             }
             _ => {
-                let bytes_regex = Regex::new(r"^bytes(\d{1,2})$").unwrap();
-                let int_regex = Regex::new(r"^(u)?int(\d{1,3})$").unwrap();
-
-                if let Some(caps) = bytes_regex.captures(name) {
+                if let Some(caps) = BYTES_REGEX.captures(name) {
                     let len: usize = caps[1].parse().unwrap();
                     if len <= 32 {
                         let buff = self.read_static()?;
@@ -435,7 +436,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut AbiDeserializer {
                     }
                 }
 
-                if let Some(caps) = int_regex.captures(name) {
+                if let Some(caps) = INT_REGEX.captures(name) {
                     let len: usize = caps[2].parse().unwrap();
                     if len <= 256 {
                         let buff = self.read_static()?;
@@ -453,7 +454,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut AbiDeserializer {
     where
         V: de::Visitor<'de>,
     {
-        unimplemented!("Contract abi don't support rust Option")
+        Err(AbiDeError::UnsupportType("option".to_owned()))
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -464,7 +465,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut AbiDeserializer {
             .start_read_tuple(true)?
             .ok_or(AbiDeError::NextIsStatic("deserialize seq".to_owned()))?;
 
-        visitor.visit_seq(TupleAccess { de: self, len })
+        visitor.visit_seq(TupleAccess::new(self, len))
     }
 
     fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -501,7 +502,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut AbiDeserializer {
     {
         self.start_read_tuple(false)?;
 
-        visitor.visit_seq(TupleAccess { de: self, len })
+        visitor.visit_seq(TupleAccess::new(self, len))
     }
 
     fn deserialize_tuple_struct<V>(
@@ -593,6 +594,28 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut AbiDeserializer {
 struct TupleAccess<'a> {
     de: &'a mut AbiDeserializer,
     len: usize,
+    closed: bool,
+}
+
+impl<'a> TupleAccess<'a> {
+    fn new(de: &'a mut AbiDeserializer, len: usize) -> Self {
+        Self {
+            de,
+            len,
+            closed: false,
+        }
+    }
+
+    /// Close tuple once, fixed length visitors (e.g, rust tuple or struct) never read past the last element.
+    fn close(&mut self) -> Result<(), AbiDeError> {
+        if !self.closed {
+            self.closed = true;
+
+            self.de.end_read_tuple()?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<'de, 'a> de::SeqAccess<'de> for TupleAccess<'a> {
@@ -604,13 +627,24 @@ impl<'de, 'a> de::SeqAccess<'de> for TupleAccess<'a> {
     {
         if self.len > 0 {
             self.len -= 1;
-            seed.deserialize(&mut *self.de).map(|c| Some(c))
+
+            let value = seed.deserialize(&mut *self.de)?;
+
+            if self.len == 0 {
+                self.close()?;
+            }
+
+            Ok(Some(value))
         } else {
-            self.de.end_read_tuple()?;
+            self.close()?;
 
             Ok(None)
         }
     }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
 }
 
 /// Deserialize rust value from contract abi format.
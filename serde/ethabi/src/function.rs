@@ -0,0 +1,130 @@
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+use crate::{from_abi, to_abi, AbiDeError, ConstructorFragment};
+
+/// Calculate the 4-byte function selector of `signature`, e.g. `transfer(address,uint256)`.
+///
+/// Whitespaces in `signature` are ignored.
+pub fn function_selector(signature: &str) -> [u8; 4] {
+    let signature = signature
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+
+    let mut hasher = Keccak256::new();
+
+    hasher.update(signature.as_bytes());
+
+    let hash = hasher.finalize();
+
+    let mut selector = [0u8; 4];
+
+    selector.copy_from_slice(&hash[0..4]);
+
+    selector
+}
+
+/// Encode contract function call data, aka function selector followed by abi encoding `args`.
+///
+/// `args` should be a tuple of function parameters, e.g. `(to, value)`.
+pub fn encode_function_call<S: Serialize>(signature: &str, args: &S) -> anyhow::Result<Vec<u8>> {
+    let mut call_data = function_selector(signature).to_vec();
+
+    call_data.append(&mut to_abi(args)?);
+
+    Ok(call_data)
+}
+
+/// Encode contract deploy data, aka contract `bytecode` followed by abi encoding constructor `args`.
+///
+/// `args` should be a tuple of `constructor` parameters, e.g. `(name, decimals)`.
+pub fn encode_constructor<S: Serialize>(
+    bytecode: &[u8],
+    constructor: &ConstructorFragment,
+    args: &S,
+) -> anyhow::Result<Vec<u8>> {
+    let mut deploy_data = bytecode.to_vec();
+
+    deploy_data.append(&mut constructor.encode_args(args)?);
+
+    Ok(deploy_data)
+}
+
+/// Decode contract function returns data from abi format.
+pub fn decode_function_output<'de, D: Deserialize<'de>, B: Into<Bytes>>(
+    data: B,
+) -> Result<D, AbiDeError> {
+    from_abi(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function_selector() {
+        assert_eq!(
+            function_selector("transfer(address,uint256)"),
+            [0xa9, 0x05, 0x9c, 0xbb]
+        );
+
+        assert_eq!(
+            function_selector("transfer(address, uint256)"),
+            function_selector("transfer(address,uint256)")
+        );
+    }
+
+    #[test]
+    fn test_encode_function_call() {
+        let call_data = encode_function_call("transfer(address,uint256)", &(1u64, 2u64)).unwrap();
+
+        assert_eq!(call_data.len(), 4 + 64);
+        assert_eq!(&call_data[0..4], &[0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(call_data[4 + 31], 1);
+        assert_eq!(call_data[4 + 63], 2);
+
+        let (a, b): (u64, u64) = decode_function_output(call_data[4..].to_vec()).unwrap();
+
+        assert_eq!((a, b), (1, 2));
+    }
+
+    #[test]
+    fn test_encode_constructor() {
+        let constructor: ConstructorFragment =
+            "constructor(string name, uint8 decimals)".parse().unwrap();
+
+        let deploy_data =
+            encode_constructor(&[0x60, 0x80], &constructor, &("USDT".to_owned(), 6u8)).unwrap();
+
+        // bytecode, offset of `name`, `decimals`, length of `name` and padded `name`
+        let mut expected = vec![0x60, 0x80];
+
+        for word in [0x40u8, 6, 4] {
+            expected.extend([0u8; 31]);
+            expected.push(word);
+        }
+
+        expected.extend(b"USDT");
+        expected.extend([0u8; 28]);
+
+        assert_eq!(deploy_data, expected);
+
+        // missing `decimals`
+        let err = encode_constructor(&[0x60, 0x80], &constructor, &("USDT".to_owned(),))
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("constructor(string,uint8)"), "{}", err);
+
+        // contract without constructor
+        let constructor = ConstructorFragment::default();
+
+        assert_eq!(
+            encode_constructor(&[0x60, 0x80], &constructor, &()).unwrap(),
+            vec![0x60, 0x80]
+        );
+        assert!(encode_constructor(&[0x60, 0x80], &constructor, &(1u8,)).is_err());
+    }
+}
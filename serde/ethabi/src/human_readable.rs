@@ -0,0 +1,732 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use thiserror::Error;
+
+use crate::{from_abi, function_selector, to_abi};
+
+/// Human-readable abi parsing errors.
+#[derive(Debug, Error)]
+pub enum HumanReadableError {
+    #[error("Unknown fragment type, expect function/event, {0}")]
+    UnknownFragment(String),
+    #[error("Invalid fragment name, {0}")]
+    InvalidName(String),
+    #[error("Unbalanced parentheses, {0}")]
+    UnbalancedParentheses(String),
+    #[error("Invalid parameter, {0}")]
+    InvalidParam(String),
+    #[error("Unknown function modifier, {0}")]
+    UnknownModifier(String),
+    #[error("Log topics/data mismatch with event, {0}")]
+    LogMismatch(String),
+    #[error("Arguments mismatch with constructor, {0}")]
+    ArgsMismatch(String),
+}
+
+/// Function/event parameter of human-readable abi.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamFragment {
+    /// Parameter name, maybe empty.
+    pub name: String,
+    /// Canonical parameter type, e.g. `uint256`, `(address,bytes)[]`.
+    pub kind: String,
+    /// Only valid for event parameters.
+    pub indexed: bool,
+}
+
+/// Function state mutability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StateMutability {
+    Pure,
+    View,
+    #[default]
+    NonPayable,
+    Payable,
+}
+
+/// Human-readable function fragment, e.g. `function balanceOf(address) view returns (uint256)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionFragment {
+    pub name: String,
+    pub inputs: Vec<ParamFragment>,
+    pub outputs: Vec<ParamFragment>,
+    pub state_mutability: StateMutability,
+}
+
+impl FunctionFragment {
+    /// Canonical function signature, e.g. `transfer(address,uint256)`.
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, join_types(&self.inputs))
+    }
+
+    /// 4-byte function selector.
+    pub fn selector(&self) -> [u8; 4] {
+        function_selector(&self.signature())
+    }
+}
+
+/// Human-readable event fragment, e.g. `event Transfer(address indexed from, address indexed to, uint256 value)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventFragment {
+    pub name: String,
+    pub inputs: Vec<ParamFragment>,
+    pub anonymous: bool,
+}
+
+impl EventFragment {
+    /// Canonical event signature, e.g. `Transfer(address,address,uint256)`.
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, join_types(&self.inputs))
+    }
+
+    /// Event topic0, aka keccak256 of event signature.
+    pub fn topic(&self) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+
+        hasher.update(self.signature().as_bytes());
+
+        hasher.finalize().into()
+    }
+
+    /// Decode all event parameters from log `topics` and `data`, indexed parameters are read from topics.
+    ///
+    /// `T` is the tuple(or struct) of all event parameters in declaration order.
+    /// Indexed dynamic parameters are stored as keccak256 hash in topics, so they should be decoded as `bytes32`.
+    pub fn decode_log<'de, T, H>(&self, topics: &[H], data: &[u8]) -> anyhow::Result<T>
+    where
+        T: Deserialize<'de>,
+        H: AsRef<[u8]>,
+    {
+        let mut topics = topics.iter();
+
+        if !self.anonymous {
+            let topic0 = topics
+                .next()
+                .ok_or_else(|| HumanReadableError::LogMismatch("topics is empty".to_owned()))?;
+
+            if topic0.as_ref() != self.topic() {
+                return Err(HumanReadableError::LogMismatch(format!(
+                    "topic0 is not {}",
+                    self.signature()
+                ))
+                .into());
+            }
+        }
+
+        let indexed = self.inputs.iter().filter(|p| p.indexed).count();
+
+        let mut heads = vec![];
+        let mut data_offset = 0;
+
+        for param in &self.inputs {
+            if param.indexed {
+                let topic = topics.next().ok_or_else(|| {
+                    HumanReadableError::LogMismatch(format!("topic of {} not found", param))
+                })?;
+
+                heads.extend_from_slice(topic.as_ref());
+
+                continue;
+            }
+
+            let size = head_size(&param.kind)?;
+
+            if data.len() < data_offset + size {
+                return Err(HumanReadableError::LogMismatch(format!(
+                    "data of {} not found",
+                    param
+                ))
+                .into());
+            }
+
+            let head = &data[data_offset..data_offset + size];
+
+            if is_dynamic_type(&param.kind)? {
+                // Tails are moved back by the inserted indexed heads.
+                let mut offset = [0u8; 8];
+
+                offset.copy_from_slice(&head[24..]);
+
+                let offset = u64::from_be_bytes(offset) + (indexed * 32) as u64;
+
+                let mut word = [0u8; 32];
+
+                word[24..].copy_from_slice(&offset.to_be_bytes());
+
+                heads.extend_from_slice(&word);
+            } else {
+                heads.extend_from_slice(head);
+            }
+
+            data_offset += size;
+        }
+
+        heads.extend_from_slice(&data[data_offset..]);
+
+        Ok(from_abi(heads)?)
+    }
+}
+
+/// Human-readable constructor fragment, e.g. `constructor(string name, uint8 decimals)`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConstructorFragment {
+    pub inputs: Vec<ParamFragment>,
+    pub state_mutability: StateMutability,
+}
+
+impl ConstructorFragment {
+    /// Canonical constructor signature, e.g. `constructor(string,uint8)`.
+    pub fn signature(&self) -> String {
+        format!("constructor({})", join_types(&self.inputs))
+    }
+
+    /// Abi encode constructor `args`, the tuple of constructor parameters.
+    ///
+    /// Returns error if heads of encoded `args` mismatch with constructor parameters.
+    pub fn encode_args<S: Serialize>(&self, args: &S) -> anyhow::Result<Vec<u8>> {
+        let data = to_abi(args)?;
+
+        let mut size = 0;
+        let mut first_dynamic = None;
+
+        for param in &self.inputs {
+            if first_dynamic.is_none() && is_dynamic_type(&param.kind)? {
+                first_dynamic = Some(size);
+            }
+
+            size += head_size(&param.kind)?;
+        }
+
+        let matched = match first_dynamic {
+            // Tails follow heads immediately, so the first dynamic offset is heads length.
+            Some(index) => {
+                data.len() >= size
+                    && data[index..index + 24].iter().all(|b| *b == 0)
+                    && data[index + 24..index + 32] == (size as u64).to_be_bytes()
+            }
+            None => data.len() == size,
+        };
+
+        if !matched {
+            return Err(HumanReadableError::ArgsMismatch(format!(
+                "{}, encoded args length {}",
+                self.signature(),
+                data.len()
+            ))
+            .into());
+        }
+
+        Ok(data)
+    }
+}
+
+impl FromStr for ConstructorFragment {
+    type Err = HumanReadableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let body = s
+            .strip_prefix("constructor")
+            .ok_or_else(|| HumanReadableError::UnknownFragment(s.to_owned()))?;
+
+        let (inputs, remaining) = split_group(body.trim_start())?;
+
+        let mut state_mutability = StateMutability::NonPayable;
+
+        for token in remaining.split_whitespace() {
+            match token {
+                "payable" => state_mutability = StateMutability::Payable,
+                "nonpayable" | "public" | "internal" => {}
+                _ => return Err(HumanReadableError::UnknownModifier(token.to_owned())),
+            }
+        }
+
+        Ok(Self {
+            inputs: parse_params(inputs, false)?,
+            state_mutability,
+        })
+    }
+}
+
+/// Returns true if abi type `kind` is dynamic, e.g. `bytes`, `uint256[]`, `(string,uint256)`.
+pub fn is_dynamic_type(kind: &str) -> Result<bool, HumanReadableError> {
+    if let Some(inner) = kind.strip_suffix(']') {
+        let index = inner
+            .rfind('[')
+            .ok_or_else(|| HumanReadableError::InvalidParam(kind.to_owned()))?;
+
+        if index + 1 == inner.len() {
+            return Ok(true);
+        }
+
+        return is_dynamic_type(&inner[..index]);
+    }
+
+    if kind.starts_with('(') {
+        let (components, _) = split_group(kind)?;
+
+        for component in split_params(components)? {
+            if is_dynamic_type(component.trim())? {
+                return Ok(true);
+            }
+        }
+
+        return Ok(false);
+    }
+
+    Ok(kind == "bytes" || kind == "string")
+}
+
+/// Returns abi head size of type `kind` in bytes.
+fn head_size(kind: &str) -> Result<usize, HumanReadableError> {
+    if is_dynamic_type(kind)? {
+        return Ok(32);
+    }
+
+    if let Some(inner) = kind.strip_suffix(']') {
+        let index = inner
+            .rfind('[')
+            .ok_or_else(|| HumanReadableError::InvalidParam(kind.to_owned()))?;
+
+        let len: usize = inner[index + 1..]
+            .parse()
+            .map_err(|_| HumanReadableError::InvalidParam(kind.to_owned()))?;
+
+        return Ok(len * head_size(&inner[..index])?);
+    }
+
+    if kind.starts_with('(') {
+        let (components, _) = split_group(kind)?;
+
+        let mut size = 0;
+
+        for component in split_params(components)? {
+            size += head_size(component.trim())?;
+        }
+
+        return Ok(size);
+    }
+
+    Ok(32)
+}
+
+/// Human-readable abi fragment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fragment {
+    Function(FunctionFragment),
+    Event(EventFragment),
+}
+
+impl FromStr for Fragment {
+    type Err = HumanReadableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some(body) = s.strip_prefix("function ") {
+            let (name, inputs, remaining) = parse_header(body)?;
+
+            let mut tokens = remaining.trim();
+
+            let mut state_mutability = StateMutability::NonPayable;
+
+            let mut outputs = vec![];
+
+            while !tokens.is_empty() {
+                let index = tokens
+                    .find(|c: char| c.is_whitespace() || c == '(')
+                    .unwrap_or(tokens.len());
+
+                let (token, rest) = tokens.split_at(index);
+
+                match token {
+                    "pure" => state_mutability = StateMutability::Pure,
+                    "view" | "constant" => state_mutability = StateMutability::View,
+                    "payable" => state_mutability = StateMutability::Payable,
+                    "nonpayable" => state_mutability = StateMutability::NonPayable,
+                    "public" | "external" => {}
+                    "returns" => {
+                        let rest = rest.trim_start();
+
+                        let (types, rest) = split_group(rest)?;
+
+                        outputs = parse_params(types, false)?;
+
+                        tokens = rest.trim();
+
+                        continue;
+                    }
+                    _ => return Err(HumanReadableError::UnknownModifier(token.to_owned())),
+                }
+
+                tokens = rest.trim();
+            }
+
+            Ok(Fragment::Function(FunctionFragment {
+                name,
+                inputs: parse_params(inputs, false)?,
+                outputs,
+                state_mutability,
+            }))
+        } else if let Some(body) = s.strip_prefix("event ") {
+            let (name, inputs, remaining) = parse_header(body)?;
+
+            let anonymous = match remaining.trim() {
+                "" => false,
+                "anonymous" => true,
+                modifier => return Err(HumanReadableError::UnknownModifier(modifier.to_owned())),
+            };
+
+            Ok(Fragment::Event(EventFragment {
+                name,
+                inputs: parse_params(inputs, true)?,
+                anonymous,
+            }))
+        } else {
+            Err(HumanReadableError::UnknownFragment(s.to_owned()))
+        }
+    }
+}
+
+impl Display for ParamFragment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        if self.indexed {
+            write!(f, " indexed")?;
+        }
+
+        if !self.name.is_empty() {
+            write!(f, " {}", self.name)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn join_types(params: &[ParamFragment]) -> String {
+    params
+        .iter()
+        .map(|p| p.kind.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parse `name(params) remaining`, returns `(name, params, remaining)`.
+fn parse_header(body: &str) -> Result<(String, &str, &str), HumanReadableError> {
+    let body = body.trim();
+
+    let index = body
+        .find('(')
+        .ok_or_else(|| HumanReadableError::UnbalancedParentheses(body.to_owned()))?;
+
+    let name = body[..index].trim();
+
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    {
+        return Err(HumanReadableError::InvalidName(name.to_owned()));
+    }
+
+    let (params, remaining) = split_group(&body[index..])?;
+
+    Ok((name.to_owned(), params, remaining))
+}
+
+/// Split `(inner)remaining` into `(inner, remaining)`.
+fn split_group(s: &str) -> Result<(&str, &str), HumanReadableError> {
+    if !s.starts_with('(') {
+        return Err(HumanReadableError::UnbalancedParentheses(s.to_owned()));
+    }
+
+    let mut depth = 0;
+
+    for (index, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Ok((&s[1..index], &s[index + 1..]));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Err(HumanReadableError::UnbalancedParentheses(s.to_owned()))
+}
+
+/// Split `s` by top level commas.
+fn split_params(s: &str) -> Result<Vec<&str>, HumanReadableError> {
+    let mut params = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (index, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                if depth == 0 {
+                    return Err(HumanReadableError::UnbalancedParentheses(s.to_owned()));
+                }
+
+                depth -= 1
+            }
+            ',' if depth == 0 => {
+                params.push(&s[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 {
+        return Err(HumanReadableError::UnbalancedParentheses(s.to_owned()));
+    }
+
+    params.push(&s[start..]);
+
+    Ok(params)
+}
+
+fn parse_params(s: &str, event: bool) -> Result<Vec<ParamFragment>, HumanReadableError> {
+    if s.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    split_params(s)?
+        .into_iter()
+        .map(|param| parse_param(param.trim(), event))
+        .collect()
+}
+
+fn parse_param(s: &str, event: bool) -> Result<ParamFragment, HumanReadableError> {
+    if s.is_empty() {
+        return Err(HumanReadableError::InvalidParam(s.to_owned()));
+    }
+
+    let s = s.strip_prefix("tuple").unwrap_or(s);
+
+    // Split type and rest tokens
+    let (kind, rest) = if s.starts_with('(') {
+        let (inner, rest) = split_group(s)?;
+
+        let suffix_len = rest.find(|c: char| c.is_whitespace()).unwrap_or(rest.len());
+
+        let kind = format!(
+            "({}){}",
+            parse_params(inner, false)?
+                .iter()
+                .map(|p| p.kind.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+            &rest[..suffix_len]
+        );
+
+        (kind, &rest[suffix_len..])
+    } else {
+        let index = s.find(|c: char| c.is_whitespace()).unwrap_or(s.len());
+
+        (canonical_type(&s[..index]), &s[index..])
+    };
+
+    let mut indexed = false;
+    let mut name = String::new();
+
+    for token in rest.split_whitespace() {
+        match token {
+            "indexed" if event => indexed = true,
+            "memory" | "calldata" | "storage" | "payable" => {}
+            _ if name.is_empty() => name = token.to_owned(),
+            _ => return Err(HumanReadableError::InvalidParam(s.to_owned())),
+        }
+    }
+
+    Ok(ParamFragment {
+        name,
+        kind,
+        indexed,
+    })
+}
+
+/// Expand type alias, e.g. `uint[]` => `uint256[]`.
+fn canonical_type(kind: &str) -> String {
+    let index = kind.find('[').unwrap_or(kind.len());
+
+    let (base, suffix) = kind.split_at(index);
+
+    match base {
+        "uint" => format!("uint256{}", suffix),
+        "int" => format!("int256{}", suffix),
+        "byte" => format!("bytes1{}", suffix),
+        _ => kind.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function_fragment() {
+        let fragment: Fragment = "function balanceOf(address owner) view returns (uint)"
+            .parse()
+            .unwrap();
+
+        match fragment {
+            Fragment::Function(f) => {
+                assert_eq!(f.signature(), "balanceOf(address)");
+                assert_eq!(f.state_mutability, StateMutability::View);
+                assert_eq!(f.inputs[0].name, "owner");
+                assert_eq!(f.outputs[0].kind, "uint256");
+                assert_eq!(f.selector(), [0x70, 0xa0, 0x82, 0x31]);
+            }
+            _ => panic!("expect function fragment"),
+        }
+
+        let fragment: Fragment =
+            "function get_from(tuple(uint256 from, uint to) data) public pure returns (uint256)"
+                .parse()
+                .unwrap();
+
+        match fragment {
+            Fragment::Function(f) => {
+                assert_eq!(f.signature(), "get_from((uint256,uint256))");
+                assert_eq!(f.state_mutability, StateMutability::Pure);
+            }
+            _ => panic!("expect function fragment"),
+        }
+
+        let fragment: Fragment = "function withdraw()".parse().unwrap();
+
+        match fragment {
+            Fragment::Function(f) => {
+                assert_eq!(f.signature(), "withdraw()");
+                assert_eq!(f.state_mutability, StateMutability::NonPayable);
+                assert!(f.outputs.is_empty());
+            }
+            _ => panic!("expect function fragment"),
+        }
+    }
+
+    #[test]
+    fn test_event_fragment() {
+        let fragment: Fragment =
+            "event Transfer(address indexed from, address indexed to, uint256 value)"
+                .parse()
+                .unwrap();
+
+        match fragment {
+            Fragment::Event(e) => {
+                assert_eq!(e.signature(), "Transfer(address,address,uint256)");
+                assert!(e.inputs[0].indexed);
+                assert!(!e.inputs[2].indexed);
+                assert_eq!(e.topic()[0..4], [0xdd, 0xf2, 0x52, 0xad]);
+            }
+            _ => panic!("expect event fragment"),
+        }
+    }
+
+    #[test]
+    fn test_decode_log() {
+        let fragment: Fragment =
+            "event Transfer(address indexed from, uint256 indexed value, string memo, uint256 fee)"
+                .parse()
+                .unwrap();
+
+        let event = match fragment {
+            Fragment::Event(e) => e,
+            _ => panic!("expect event fragment"),
+        };
+
+        let mut from_topic = [0u8; 32];
+        from_topic[31] = 1;
+
+        let mut value_topic = [0u8; 32];
+        value_topic[31] = 2;
+
+        let data = crate::to_abi(&("hello".to_owned(), 3u64)).unwrap();
+
+        let (from, value, memo, fee): (u64, u64, String, u64) = event
+            .decode_log(&[event.topic(), from_topic, value_topic], &data)
+            .unwrap();
+
+        assert_eq!((from, value, memo.as_str(), fee), (1, 2, "hello", 3));
+
+        // topic0 mismatch
+        assert!(event
+            .decode_log::<(u64, u64, String, u64), _>(&[[0u8; 32], from_topic, value_topic], &data)
+            .is_err());
+    }
+
+    #[test]
+    fn test_dynamic_type() {
+        assert!(is_dynamic_type("bytes").unwrap());
+        assert!(is_dynamic_type("uint256[]").unwrap());
+        assert!(is_dynamic_type("string[2]").unwrap());
+        assert!(is_dynamic_type("(uint256,bytes)").unwrap());
+        assert!(!is_dynamic_type("uint256[2]").unwrap());
+        assert!(!is_dynamic_type("(uint256,address)[3]").unwrap());
+
+        assert_eq!(head_size("(uint256,address)[3]").unwrap(), 192);
+        assert_eq!(head_size("uint256[]").unwrap(), 32);
+    }
+
+    #[test]
+    fn test_constructor_fragment() {
+        let constructor: ConstructorFragment = "constructor(string memory name, uint8 decimals)"
+            .parse()
+            .unwrap();
+
+        assert_eq!(constructor.signature(), "constructor(string,uint8)");
+        assert_eq!(constructor.state_mutability, StateMutability::NonPayable);
+
+        let data = constructor.encode_args(&("USDT".to_owned(), 6u8)).unwrap();
+
+        assert_eq!(data, crate::to_abi(&("USDT".to_owned(), 6u8)).unwrap());
+
+        // missing `decimals`
+        assert!(constructor.encode_args(&(6u8,)).is_err());
+
+        let constructor: ConstructorFragment = "constructor() payable".parse().unwrap();
+
+        assert_eq!(constructor.state_mutability, StateMutability::Payable);
+        assert!(constructor.encode_args(&()).unwrap().is_empty());
+        assert!(constructor.encode_args(&(1u8,)).is_err());
+
+        // extra `decimals`
+        assert!(constructor
+            .encode_args(&("USDT".to_owned(), 6u8, 1u8))
+            .is_err());
+
+        // static parameters, extra args are rejected
+        let constructor: ConstructorFragment = "constructor(address owner, uint256 supply)"
+            .parse()
+            .unwrap();
+
+        assert_eq!(constructor.encode_args(&(1u8, 2u8)).unwrap().len(), 64);
+        assert!(constructor.encode_args(&(1u8, 2u8, 3u8)).is_err());
+
+        assert!("function constructor()"
+            .parse::<ConstructorFragment>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_fragment() {
+        assert!("constructor()".parse::<Fragment>().is_err());
+        assert!("function transfer(address,uint256"
+            .parse::<Fragment>()
+            .is_err());
+        assert!("function transfer(address) foo"
+            .parse::<Fragment>()
+            .is_err());
+    }
+}
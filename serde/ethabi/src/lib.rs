@@ -1,5 +1,12 @@
 pub mod de;
+pub mod function;
+pub mod human_readable;
 pub mod ser;
 
+#[cfg(test)]
+mod roundtrip;
+
 pub use de::*;
+pub use function::*;
+pub use human_readable::*;
 pub use ser::*;
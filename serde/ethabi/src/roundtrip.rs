@@ -0,0 +1,378 @@
+//! Property based round-trip tests of [`to_abi`]/[`from_abi`], cross-checked against the `ethabi` crate.
+//!
+//! Values are generated as dynamic [`Token`] trees for random [`ParamType`] shapes,
+//! and (de)serialized through the same newtype names as `ethers_primitives` types.
+
+use ethabi::{ParamType, Token, Uint};
+use proptest::prelude::*;
+use serde::{
+    de::{self, DeserializeSeed},
+    ser::{SerializeSeq, SerializeTuple},
+    Deserialize, Serialize,
+};
+
+use crate::{to_abi, AbiDeserializer};
+
+/// Serialize [`Token`] like the matching `ethers_primitives` type.
+struct AbiToken<'a>(&'a Token);
+
+impl<'a> Serialize for AbiToken<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.0 {
+            Token::Address(address) => {
+                let mut buff = [0u8; 32];
+
+                buff[12..].copy_from_slice(address.as_bytes());
+
+                serializer.serialize_newtype_struct("address", &buff)
+            }
+            Token::FixedBytes(bytes) => {
+                let mut buff = [0u8; 32];
+
+                buff[..bytes.len()].copy_from_slice(bytes);
+
+                serializer.serialize_newtype_struct("bytes32", &buff)
+            }
+            Token::Bytes(bytes) => serializer.serialize_bytes(bytes),
+            Token::Int(value) => serializer.serialize_newtype_struct("int256", &to_bytes32(value)),
+            Token::Uint(value) => {
+                serializer.serialize_newtype_struct("uint256", &to_bytes32(value))
+            }
+            Token::Bool(value) => serializer.serialize_bool(*value),
+            Token::String(value) => serializer.serialize_str(value),
+            Token::Array(tokens) => {
+                let mut seq = serializer.serialize_seq(Some(tokens.len()))?;
+
+                for token in tokens {
+                    seq.serialize_element(&AbiToken(token))?;
+                }
+
+                seq.end()
+            }
+            Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+                let mut tuple = serializer.serialize_tuple(tokens.len())?;
+
+                for token in tokens {
+                    tuple.serialize_element(&AbiToken(token))?;
+                }
+
+                tuple.end()
+            }
+        }
+    }
+}
+
+fn to_bytes32(value: &Uint) -> [u8; 32] {
+    let mut buff = [0u8; 32];
+
+    value.to_big_endian(&mut buff);
+
+    buff
+}
+
+/// Deserialize [`Token`] of [`ParamType`] like the matching `ethers_primitives` type.
+struct TokenSeed<'a>(&'a ParamType);
+
+impl<'de, 'a> DeserializeSeed<'de> for TokenSeed<'a> {
+    type Value = Token;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match self.0 {
+            ParamType::Address => {
+                let buff = deserializer.deserialize_newtype_struct("address", BytesVisitor)?;
+
+                Ok(Token::Address(ethabi::Address::from_slice(&buff[12..])))
+            }
+            ParamType::FixedBytes(len) => {
+                let buff = deserializer.deserialize_newtype_struct("bytes32", BytesVisitor)?;
+
+                Ok(Token::FixedBytes(buff[..*len].to_vec()))
+            }
+            ParamType::Bytes => Ok(Token::Bytes(
+                deserializer.deserialize_newtype_struct("bytes", BytesVisitor)?,
+            )),
+            ParamType::Int(_) => {
+                let buff = deserializer.deserialize_newtype_struct("int256", BytesVisitor)?;
+
+                Ok(Token::Int(Uint::from_big_endian(&buff)))
+            }
+            ParamType::Uint(_) => {
+                let buff = deserializer.deserialize_newtype_struct("uint256", BytesVisitor)?;
+
+                Ok(Token::Uint(Uint::from_big_endian(&buff)))
+            }
+            ParamType::Bool => Ok(Token::Bool(bool::deserialize(deserializer)?)),
+            ParamType::String => Ok(Token::String(String::deserialize(deserializer)?)),
+            ParamType::Array(kind) => Ok(Token::Array(
+                deserializer.deserialize_seq(TokensVisitor::Array(kind.as_ref().clone()))?,
+            )),
+            ParamType::FixedArray(kind, len) => {
+                Ok(Token::FixedArray(deserializer.deserialize_tuple(
+                    *len,
+                    TokensVisitor::Tuple(vec![kind.as_ref().clone(); *len]),
+                )?))
+            }
+            ParamType::Tuple(kinds) => Ok(Token::Tuple(
+                deserializer.deserialize_tuple(kinds.len(), TokensVisitor::Tuple(kinds.clone()))?,
+            )),
+        }
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> de::Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "expect bytes")
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v.to_vec())
+    }
+}
+
+/// Visit elements of dynamic array or tuple (fixed array).
+enum TokensVisitor {
+    Array(ParamType),
+    Tuple(Vec<ParamType>),
+}
+
+impl<'de> de::Visitor<'de> for TokensVisitor {
+    type Value = Vec<Token>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "expect abi tuple or array")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut tokens = vec![];
+
+        match &self {
+            Self::Array(kind) => {
+                while let Some(token) = seq.next_element_seed(TokenSeed(kind))? {
+                    tokens.push(token);
+                }
+            }
+            Self::Tuple(kinds) => {
+                // Read exactly `kinds.len()` elements like derived rust types.
+                for kind in kinds {
+                    let token = seq
+                        .next_element_seed(TokenSeed(kind))?
+                        .ok_or_else(|| de::Error::invalid_length(tokens.len(), &self))?;
+
+                    tokens.push(token);
+                }
+            }
+        }
+
+        Ok(tokens)
+    }
+}
+
+/// Returns true if `kind` is encoded the same as the abi spec when nested in a tuple or array.
+///
+/// Known bug: nested tuples and fixed arrays are always encoded as dynamic types (head offset +
+/// tail), while the abi spec encodes static ones inline. The deserializer can not tell static
+/// composite types apart before reading them, so these cases are checked by the ignored
+/// `test_static_*` known failing tests instead.
+fn is_spec_compatible(kind: &ParamType) -> bool {
+    match kind {
+        ParamType::Array(kind) => is_spec_compatible(kind),
+        ParamType::FixedArray(kind, _) => kind.is_dynamic() && is_spec_compatible(kind),
+        ParamType::Tuple(kinds) => kind.is_dynamic() && kinds.iter().all(is_spec_compatible),
+        _ => true,
+    }
+}
+
+fn param_type() -> impl Strategy<Value = ParamType> {
+    let leaf = prop_oneof![
+        Just(ParamType::Address),
+        Just(ParamType::Bool),
+        Just(ParamType::String),
+        Just(ParamType::Bytes),
+        (1..=32usize).prop_map(ParamType::FixedBytes),
+        (1..=32usize).prop_map(|len| ParamType::Uint(len * 8)),
+        (1..=32usize).prop_map(|len| ParamType::Int(len * 8)),
+    ];
+
+    leaf.prop_recursive(3, 16, 4, |inner| {
+        prop_oneof![
+            inner
+                .clone()
+                .prop_map(|kind| ParamType::Array(Box::new(kind))),
+            (inner.clone(), 1..=3usize)
+                .prop_map(|(kind, len)| ParamType::FixedArray(Box::new(kind), len)),
+            prop::collection::vec(inner, 1..=4).prop_map(ParamType::Tuple),
+        ]
+    })
+}
+
+fn token(kind: &ParamType) -> BoxedStrategy<Token> {
+    match kind {
+        ParamType::Address => any::<[u8; 20]>()
+            .prop_map(|buff| Token::Address(buff.into()))
+            .boxed(),
+        ParamType::FixedBytes(len) => prop::collection::vec(any::<u8>(), *len)
+            .prop_map(Token::FixedBytes)
+            .boxed(),
+        ParamType::Bytes => prop::collection::vec(any::<u8>(), 0..=96)
+            .prop_map(Token::Bytes)
+            .boxed(),
+        ParamType::Int(bits) => {
+            let bits = *bits;
+
+            any::<[u8; 32]>()
+                .prop_map(move |mut buff| {
+                    // Sign extend the lowest `bits` bits.
+                    let start = 32 - bits / 8;
+
+                    let fill = if buff[start] & 0x80 != 0 { 0xff } else { 0x00 };
+
+                    buff[..start].fill(fill);
+
+                    Token::Int(Uint::from_big_endian(&buff))
+                })
+                .boxed()
+        }
+        ParamType::Uint(bits) => {
+            let bits = *bits;
+
+            any::<[u8; 32]>()
+                .prop_map(move |mut buff| {
+                    buff[..(32 - bits / 8)].fill(0);
+
+                    Token::Uint(Uint::from_big_endian(&buff))
+                })
+                .boxed()
+        }
+        ParamType::Bool => any::<bool>().prop_map(Token::Bool).boxed(),
+        ParamType::String => any::<String>().prop_map(Token::String).boxed(),
+        ParamType::Array(kind) => prop::collection::vec(token(kind), 0..=3)
+            .prop_map(Token::Array)
+            .boxed(),
+        ParamType::FixedArray(kind, len) => prop::collection::vec(token(kind), *len)
+            .prop_map(Token::FixedArray)
+            .boxed(),
+        ParamType::Tuple(kinds) => kinds
+            .iter()
+            .map(token)
+            .collect::<Vec<_>>()
+            .prop_map(Token::Tuple)
+            .boxed(),
+    }
+}
+
+/// Function parameter list and its values.
+fn params() -> impl Strategy<Value = (Vec<ParamType>, Vec<Token>)> {
+    prop::collection::vec(param_type(), 1..=4).prop_flat_map(|kinds| {
+        let tokens = kinds.iter().map(token).collect::<Vec<_>>();
+
+        (Just(kinds), tokens)
+    })
+}
+
+fn decode(kinds: &[ParamType], data: Vec<u8>) -> Result<Vec<Token>, crate::AbiDeError> {
+    let kind = ParamType::Tuple(kinds.to_vec());
+
+    match TokenSeed(&kind).deserialize(&mut AbiDeserializer::new(data))? {
+        Token::Tuple(tokens) => Ok(tokens),
+        token => panic!("Decode params as tuple, got {:?}", token),
+    }
+}
+
+/// Tuple of `uint256` and static nested tuple `(uint256,bool)`.
+fn static_nested_tuple() -> Vec<Token> {
+    vec![
+        Token::Uint(1u64.into()),
+        Token::Tuple(vec![Token::Uint(2u64.into()), Token::Bool(true)]),
+    ]
+}
+
+/// Tuple of static fixed array `uint256[2]` and `uint256`.
+fn static_fixed_array() -> Vec<Token> {
+    vec![
+        Token::FixedArray(vec![Token::Uint(1u64.into()), Token::Uint(2u64.into())]),
+        Token::Uint(3u64.into()),
+    ]
+}
+
+#[test]
+#[ignore = "known bug: static nested tuples are encoded with head offset instead of inline"]
+fn test_static_nested_tuple_spec() {
+    let tokens = static_nested_tuple();
+
+    let data = to_abi(&AbiToken(&Token::Tuple(tokens.clone()))).expect("Encode params");
+
+    assert_eq!(data, ethabi::encode(&tokens));
+}
+
+#[test]
+#[ignore = "known bug: static fixed arrays are encoded with head offset instead of inline"]
+fn test_static_fixed_array_spec() {
+    let tokens = static_fixed_array();
+
+    let data = to_abi(&AbiToken(&Token::Tuple(tokens.clone()))).expect("Encode params");
+
+    assert_eq!(data, ethabi::encode(&tokens));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn test_roundtrip((kinds, tokens) in params()) {
+        let data = to_abi(&AbiToken(&Token::Tuple(tokens.clone()))).expect("Encode params");
+
+        prop_assert_eq!(decode(&kinds, data).expect("Decode params"), tokens);
+    }
+
+    #[test]
+    fn test_ethabi_compatible(
+        (kinds, tokens) in params().prop_filter("Static nested tuple", |(kinds, _)| {
+            kinds.iter().all(is_spec_compatible)
+        })
+    ) {
+        let data = to_abi(&AbiToken(&Token::Tuple(tokens.clone()))).expect("Encode params");
+
+        prop_assert_eq!(&data, &ethabi::encode(&tokens));
+
+        prop_assert_eq!(ethabi::decode(&kinds, &data).expect("ethabi decode"), tokens.clone());
+
+        prop_assert_eq!(decode(&kinds, ethabi::encode(&tokens)).expect("Decode ethabi data"), tokens);
+    }
+
+    #[test]
+    #[ignore = "known bug: static nested tuples and fixed arrays are encoded with head offset instead of inline"]
+    fn test_static_composite_ethabi_compatible(
+        (kinds, tokens) in params().prop_filter("Static nested tuple", |(kinds, _)| {
+            !kinds.iter().all(is_spec_compatible)
+        })
+    ) {
+        let data = to_abi(&AbiToken(&Token::Tuple(tokens.clone()))).expect("Encode params");
+
+        prop_assert_eq!(&data, &ethabi::encode(&tokens));
+
+        prop_assert_eq!(decode(&kinds, ethabi::encode(&tokens)).expect("Decode ethabi data"), tokens);
+    }
+}
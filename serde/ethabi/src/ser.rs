@@ -1,8 +1,17 @@
+use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{ser, Serialize};
 
 use thiserror::Error;
 
+/// Matches `bytes<M>` newtype names, compiled once rather than on every newtype call.
+pub(crate) static BYTES_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^bytes(\d{1,2})$").expect("Compile bytes regex"));
+
+/// Matches `int<M>` and `uint<M>` newtype names.
+pub(crate) static INT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(u)?int(\d{1,3})$").expect("Compile int regex"));
+
 /// followed by the minimum number of zero-bytes such that `len(bytes)` is a multiple of 32
 fn padding_right(mut bytes: Vec<u8>) -> Vec<u8> {
     let padding_zeros = 32 - bytes.len() % 32;
@@ -282,7 +291,7 @@ impl<'a> ser::Serializer for &'a mut AbiSerializer {
     }
 
     fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("Contract abi don't support rust char")
+        Err(AbiSerError::UnsupportType("char".to_owned()))
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
@@ -333,9 +342,8 @@ impl<'a> ser::Serializer for &'a mut AbiSerializer {
     {
         match name {
             "bytes" => {
-                let bytes = unsafe { (value as *const T).cast::<Vec<u8>>().as_ref().unwrap() };
-
-                self.encode_bytes(bytes.to_owned())
+                // Payload is written by `serialize_bytes`.
+                value.serialize(self)
             }
             "address" => {
                 let bytes = unsafe { (value as *const T).cast::<[u8; 32]>().as_ref().unwrap() };
@@ -343,10 +351,7 @@ impl<'a> ser::Serializer for &'a mut AbiSerializer {
                 return self.encode_bytes32(bytes);
             }
             _ => {
-                let bytes_regex = Regex::new(r"^bytes(\d{1,2})$").unwrap();
-                let int_regex = Regex::new(r"^(u)?int(\d{1,3})$").unwrap();
-
-                if let Some(caps) = bytes_regex.captures(name) {
+                if let Some(caps) = BYTES_REGEX.captures(name) {
                     let len: usize = caps[1].parse().unwrap();
                     if len <= 32 {
                         let bytes =
@@ -356,7 +361,7 @@ impl<'a> ser::Serializer for &'a mut AbiSerializer {
                     }
                 }
 
-                if let Some(caps) = int_regex.captures(name) {
+                if let Some(caps) = INT_REGEX.captures(name) {
                     let len: usize = caps[2].parse().unwrap();
                     if len <= 256 {
                         let bytes =
@@ -381,11 +386,11 @@ impl<'a> ser::Serializer for &'a mut AbiSerializer {
     where
         T: serde::Serialize,
     {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(AbiSerError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("Contract abi don't support rust Option")
+        Err(AbiSerError::UnsupportType("option".to_owned()))
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
@@ -398,7 +403,7 @@ impl<'a> ser::Serializer for &'a mut AbiSerializer {
     where
         T: serde::Serialize,
     {
-        unimplemented!("Contract abi don't support rust Option")
+        Err(AbiSerError::UnsupportType("option".to_owned()))
     }
 
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
@@ -410,6 +415,7 @@ impl<'a> ser::Serializer for &'a mut AbiSerializer {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.start_tuple(false)?;
         Ok(self)
     }
 
@@ -420,7 +426,7 @@ impl<'a> ser::Serializer for &'a mut AbiSerializer {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(AbiSerError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
@@ -444,7 +450,7 @@ impl<'a> ser::Serializer for &'a mut AbiSerializer {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(AbiSerError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
@@ -487,7 +493,7 @@ impl<'a> ser::Serializer for &'a mut AbiSerializer {
         _variant_index: u32,
         _variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(AbiSerError::UnsupportType("enum".to_owned()))
     }
 }
 
@@ -496,21 +502,21 @@ impl<'a> ser::SerializeMap for &'a mut AbiSerializer {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(AbiSerError::UnsupportType("map".to_owned()))
     }
 
     fn serialize_key<T: ?Sized>(&mut self, _key: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(AbiSerError::UnsupportType("map".to_owned()))
     }
 
     fn serialize_value<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(AbiSerError::UnsupportType("map".to_owned()))
     }
 }
 
@@ -646,7 +652,38 @@ impl<'a> ser::SerializeTupleStruct for &'a mut AbiSerializer {
 pub fn to_abi<S: Serialize>(value: &S) -> anyhow::Result<Vec<u8>> {
     let mut serializer = AbiSerializer::default();
 
-    value.serialize(&mut serializer).expect("");
+    value.serialize(&mut serializer)?;
 
     Ok(serializer.finalize()?)
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{from_abi, AbiDeError};
+
+    #[derive(Serialize)]
+    enum Side {
+        Sell(u8),
+    }
+
+    #[test]
+    fn test_unsupported_types() {
+        let err = to_abi(&(1u8, 'a')).unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<AbiSerError>(),
+            Some(AbiSerError::UnsupportType(name)) if name == "char"
+        ));
+
+        assert!(to_abi(&(1u8, Some(2u8))).is_err());
+        assert!(to_abi(&(Side::Sell(1),)).is_err());
+
+        assert!(matches!(
+            from_abi::<Option<u8>, _>(vec![0u8; 32]),
+            Err(AbiDeError::UnsupportType(_))
+        ));
+    }
+}
@@ -0,0 +1,104 @@
+use bytes::BytesMut;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use serde::{Serialize, Serializer};
+use serde_ethrlp::{rlp_encode, rlp_encode_into};
+
+/// Rlp string of fixed length, e.g. address or storage key.
+struct Bytes<const N: usize>([u8; N]);
+
+impl<const N: usize> Serialize for Bytes<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+/// Rlp unsigned integer serialized as newtype, like `ethers_primitives::U256`.
+struct U256([u8; 32]);
+
+impl Serialize for U256 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct("uint256", &self.0)
+    }
+}
+
+#[derive(Serialize)]
+struct AccessListItem {
+    address: Bytes<20>,
+    storage_keys: Vec<Bytes<32>>,
+}
+
+#[derive(Serialize)]
+struct Tx {
+    chain_id: u64,
+    nonce: u64,
+    max_priority_fee_per_gas: u128,
+    max_fee_per_gas: u128,
+    gas: u64,
+    to: Bytes<20>,
+    value: u128,
+    data: String,
+    access_list: Vec<AccessListItem>,
+}
+
+/// Transaction with `items` access list items of 16 storage keys.
+fn access_list_tx(items: usize) -> Tx {
+    Tx {
+        chain_id: 1,
+        nonce: 0x1234,
+        max_priority_fee_per_gas: 1_000_000_000,
+        max_fee_per_gas: 30_000_000_000,
+        gas: 1_000_000,
+        to: Bytes([0x11; 20]),
+        value: 1_000_000_000_000_000_000,
+        data: "0".repeat(1024),
+        access_list: (0..items)
+            .map(|i| AccessListItem {
+                address: Bytes([i as u8; 20]),
+                storage_keys: (0..16).map(|k| Bytes([k as u8; 32])).collect(),
+            })
+            .collect(),
+    }
+}
+
+fn bench_encode(c: &mut Criterion) {
+    let tx = access_list_tx(100);
+
+    c.bench_function("rlp_encode access list tx", |b| {
+        b.iter(|| rlp_encode(black_box(&tx)).unwrap())
+    });
+
+    let mut buff = BytesMut::new();
+
+    c.bench_function("rlp_encode_into reused buffer", |b| {
+        b.iter(|| {
+            buff.clear();
+
+            rlp_encode_into(black_box(&tx), &mut buff).unwrap();
+        })
+    });
+}
+
+fn bench_newtype(c: &mut Criterion) {
+    let values = (0..1000u32)
+        .map(|i| {
+            let mut buff = [0u8; 32];
+
+            buff[28..].copy_from_slice(&i.to_be_bytes());
+
+            U256(buff)
+        })
+        .collect::<Vec<_>>();
+
+    c.bench_function("rlp_encode uint256[1000]", |b| {
+        b.iter(|| rlp_encode(black_box(&values)).unwrap())
+    });
+}
+
+criterion_group!(benches, bench_encode, bench_newtype);
+criterion_main!(benches);
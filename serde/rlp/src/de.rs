@@ -0,0 +1,691 @@
+use serde::{de, Deserialize};
+use thiserror::Error;
+
+use crate::unsigned_to_buff;
+
+/// Rlp deserializer error variant
+#[derive(Debug, Error)]
+pub enum RlpDeError {
+    #[error("Unknown error,{0}")]
+    Unknown(String),
+
+    #[error("Try read next item failed,{0}")]
+    InsufficentInputs(String),
+
+    #[error("Trailing {0} bytes after decoding item")]
+    TrailingBytes(usize),
+
+    #[error("Non-canonical integer encoding, leading zeros,{0}")]
+    NonCanonicalInteger(String),
+
+    #[error("Non-canonical length prefix,{0}")]
+    NonCanonicalSize(String),
+
+    #[error("Expect rlp string, got list")]
+    ExpectString,
+
+    #[error("Expect rlp list, got string")]
+    ExpectList,
+
+    #[error("Rlp string length out of range,{0}")]
+    OutOfRange(String),
+
+    #[error("Unsupport deserialize type, {0}")]
+    UnsupportType(String),
+}
+
+impl de::Error for RlpDeError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        Self::Unknown(msg.to_string())
+    }
+}
+
+/// Rlp decoding mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RlpMode {
+    /// Reject trailing bytes, integers with leading zeros and non-canonical length prefixes,
+    /// matching consensus rules. Use it to validate data from untrusted peers.
+    #[default]
+    Strict,
+    /// Accept and ignore all the above.
+    Lenient,
+}
+
+/// Decoded rlp item, payload without length prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RlpItem<'de> {
+    String(&'de [u8]),
+    List(&'de [u8]),
+}
+
+/// Rlp(RECURSIVE-LENGTH PREFIX) format decoder.
+#[derive(Debug, Clone)]
+pub struct RlpDecoder<'de> {
+    buff: &'de [u8],
+    mode: RlpMode,
+}
+
+impl<'de> RlpDecoder<'de> {
+    /// Create decoder of `buff` with decoding `mode`.
+    pub fn new(buff: &'de [u8], mode: RlpMode) -> Self {
+        Self { buff, mode }
+    }
+
+    fn is_strict(&self) -> bool {
+        self.mode == RlpMode::Strict
+    }
+
+    /// Read length of `len_of_len` bytes, used by long string and long list.
+    fn read_len(&mut self, len_of_len: usize) -> Result<usize, RlpDeError> {
+        if self.buff.len() < len_of_len {
+            return Err(RlpDeError::InsufficentInputs("Read length".to_owned()));
+        }
+
+        let (buff, remaining) = self.buff.split_at(len_of_len);
+
+        self.buff = remaining;
+
+        if self.is_strict() && buff[0] == 0 {
+            return Err(RlpDeError::NonCanonicalSize(
+                "length with leading zeros".to_owned(),
+            ));
+        }
+
+        let buff = unsigned_to_buff(buff);
+
+        if buff.len() > usize::BITS as usize / 8 {
+            return Err(RlpDeError::OutOfRange(format!(
+                "length of {} bytes",
+                buff.len()
+            )));
+        }
+
+        let len = buff.iter().fold(0usize, |len, b| (len << 8) | *b as usize);
+
+        if self.is_strict() && len <= 55 {
+            return Err(RlpDeError::NonCanonicalSize(format!(
+                "long form length {} <= 55",
+                len
+            )));
+        }
+
+        Ok(len)
+    }
+
+    fn read_payload(&mut self, len: usize) -> Result<&'de [u8], RlpDeError> {
+        if self.buff.len() < len {
+            return Err(RlpDeError::InsufficentInputs(format!(
+                "Read {} bytes payload, remaining {}",
+                len,
+                self.buff.len()
+            )));
+        }
+
+        let (payload, remaining) = self.buff.split_at(len);
+
+        self.buff = remaining;
+
+        Ok(payload)
+    }
+
+    fn read_item(&mut self) -> Result<RlpItem<'de>, RlpDeError> {
+        let prefix = *self
+            .buff
+            .first()
+            .ok_or_else(|| RlpDeError::InsufficentInputs("Read item prefix".to_owned()))?;
+
+        match prefix {
+            0x00..=0x7f => Ok(RlpItem::String(self.read_payload(1)?)),
+            0x80..=0xb7 => {
+                self.buff = &self.buff[1..];
+
+                let payload = self.read_payload((prefix - 0x80) as usize)?;
+
+                if self.is_strict() && payload.len() == 1 && payload[0] < 0x80 {
+                    return Err(RlpDeError::NonCanonicalSize(format!(
+                        "single byte {:#04x} with length prefix",
+                        payload[0]
+                    )));
+                }
+
+                Ok(RlpItem::String(payload))
+            }
+            0xb8..=0xbf => {
+                self.buff = &self.buff[1..];
+
+                let len = self.read_len((prefix - 0xb7) as usize)?;
+
+                Ok(RlpItem::String(self.read_payload(len)?))
+            }
+            0xc0..=0xf7 => {
+                self.buff = &self.buff[1..];
+
+                Ok(RlpItem::List(self.read_payload((prefix - 0xc0) as usize)?))
+            }
+            0xf8..=0xff => {
+                self.buff = &self.buff[1..];
+
+                let len = self.read_len((prefix - 0xf7) as usize)?;
+
+                Ok(RlpItem::List(self.read_payload(len)?))
+            }
+        }
+    }
+
+    /// Read rlp string item.
+    pub fn read_string(&mut self) -> Result<&'de [u8], RlpDeError> {
+        match self.read_item()? {
+            RlpItem::String(buff) => Ok(buff),
+            RlpItem::List(_) => Err(RlpDeError::ExpectString),
+        }
+    }
+
+    /// Read rlp list item, returns decoder of list payload.
+    pub fn read_list(&mut self) -> Result<RlpDecoder<'de>, RlpDeError> {
+        match self.read_item()? {
+            RlpItem::List(buff) => Ok(RlpDecoder::new(buff, self.mode)),
+            RlpItem::String(_) => Err(RlpDeError::ExpectList),
+        }
+    }
+
+    /// Read rlp string item as big endian unsigned integer, returns integer bytes without leading zeros.
+    pub fn read_unsigned(&mut self, max_len: usize) -> Result<&'de [u8], RlpDeError> {
+        let buff = self.read_string()?;
+
+        if self.is_strict() && buff.first() == Some(&0) {
+            return Err(RlpDeError::NonCanonicalInteger(format!("{:x?}", buff)));
+        }
+
+        let buff = unsigned_to_buff(buff);
+
+        if buff.len() > max_len {
+            return Err(RlpDeError::OutOfRange(format!(
+                "integer of {} bytes, max {}",
+                buff.len(),
+                max_len
+            )));
+        }
+
+        Ok(buff)
+    }
+
+    fn read_u128(&mut self, max_len: usize) -> Result<u128, RlpDeError> {
+        let buff = self.read_unsigned(max_len)?;
+
+        Ok(buff
+            .iter()
+            .fold(0u128, |value, b| (value << 8) | *b as u128))
+    }
+
+    /// Returns true if all data is consumed.
+    pub fn is_empty(&self) -> bool {
+        self.buff.is_empty()
+    }
+
+    /// Close decoder, returns [`TrailingBytes`](RlpDeError::TrailingBytes) in strict mode if data remains.
+    pub fn finalize(self) -> Result<(), RlpDeError> {
+        if self.is_strict() && !self.buff.is_empty() {
+            return Err(RlpDeError::TrailingBytes(self.buff.len()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse `bytes<M>` and `uint<M>` newtype names, returns `(is_uint, M)`.
+fn parse_newtype_name(name: &str) -> Option<(bool, usize)> {
+    let (is_uint, len) = if let Some(len) = name.strip_prefix("bytes") {
+        (false, len)
+    } else if let Some(len) = name.strip_prefix("uint") {
+        (true, len)
+    } else {
+        return None;
+    };
+
+    if len.is_empty() || len.starts_with('0') || !len.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let len: usize = len.parse().ok()?;
+
+    match (is_uint, len) {
+        (false, 1..=32) => Some((false, len)),
+        (true, 8..=256) if len.is_multiple_of(8) => Some((true, len)),
+        _ => None,
+    }
+}
+
+macro_rules! deserialize_unsigned {
+    ($name: ident, $visit: ident, $t: ty) => {
+        fn $name<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            let value = self.read_u128(std::mem::size_of::<$t>())?;
+
+            visitor.$visit(value as $t)
+        }
+    };
+}
+
+macro_rules! deserialize_unsupport {
+    ($name: ident, $t: expr) => {
+        fn $name<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            Err(RlpDeError::UnsupportType($t.to_owned()))
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &mut RlpDecoder<'de> {
+    type Error = RlpDeError;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.read_item()? {
+            RlpItem::String(buff) => visitor.visit_borrowed_bytes(buff),
+            RlpItem::List(buff) => visitor.visit_seq(ListAccess {
+                de: RlpDecoder::new(buff, self.mode),
+                len: None,
+            }),
+        }
+    }
+
+    deserialize_unsigned!(deserialize_u8, visit_u8, u8);
+    deserialize_unsigned!(deserialize_u16, visit_u16, u16);
+    deserialize_unsigned!(deserialize_u32, visit_u32, u32);
+    deserialize_unsigned!(deserialize_u64, visit_u64, u64);
+    deserialize_unsigned!(deserialize_u128, visit_u128, u128);
+
+    // Rlp has no canonical signed integer encoding.
+    deserialize_unsupport!(deserialize_i8, "i8");
+    deserialize_unsupport!(deserialize_i16, "i16");
+    deserialize_unsupport!(deserialize_i32, "i32");
+    deserialize_unsupport!(deserialize_i64, "i64");
+    deserialize_unsupport!(deserialize_i128, "i128");
+    deserialize_unsupport!(deserialize_bool, "bool");
+    deserialize_unsupport!(deserialize_f32, "f32");
+    deserialize_unsupport!(deserialize_f64, "f64");
+    deserialize_unsupport!(deserialize_char, "char");
+    deserialize_unsupport!(deserialize_map, "map");
+    deserialize_unsupport!(deserialize_identifier, "identifier");
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_borrowed_bytes(self.read_string()?)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_byte_buf(self.read_string()?.to_vec())
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let buff = self.read_string()?;
+
+        visitor.visit_borrowed_str(std::str::from_utf8(buff).map_err(de::Error::custom)?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        // `None` is encoded as empty string.
+        if self.buff.first() == Some(&0x80) {
+            self.buff = &self.buff[1..];
+
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match name {
+            "bytes" => visitor.visit_byte_buf(self.read_string()?.to_vec()),
+            "address" => {
+                let buff = self.read_string()?;
+
+                if buff.len() != 20 {
+                    return Err(RlpDeError::OutOfRange(format!(
+                        "address of {} bytes",
+                        buff.len()
+                    )));
+                }
+
+                // Same as abi format, address is left padded to 32 bytes.
+                let mut bytes32 = vec![0u8; 12];
+
+                bytes32.extend_from_slice(buff);
+
+                visitor.visit_byte_buf(bytes32)
+            }
+            _ => match parse_newtype_name(name) {
+                Some((false, len)) => {
+                    let buff = self.read_string()?;
+
+                    if buff.len() > len || (self.is_strict() && buff.len() != len) {
+                        return Err(RlpDeError::OutOfRange(format!(
+                            "{} of {} bytes",
+                            name,
+                            buff.len()
+                        )));
+                    }
+
+                    let mut bytes32 = buff.to_vec();
+
+                    bytes32.resize(32, 0);
+
+                    visitor.visit_byte_buf(bytes32)
+                }
+                Some((true, bits)) => {
+                    let buff = self.read_unsigned(bits / 8)?;
+
+                    let mut bytes32 = vec![0u8; 32 - buff.len()];
+
+                    bytes32.extend_from_slice(buff);
+
+                    visitor.visit_byte_buf(bytes32)
+                }
+                // Other newtype structs are encoded as list of one item.
+                None => {
+                    let mut de = self.read_list()?;
+
+                    let value = visitor.visit_newtype_struct(&mut de)?;
+
+                    de.finalize()?;
+
+                    Ok(value)
+                }
+            },
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let de = self.read_list()?;
+
+        visitor.visit_seq(ListAccess { de, len: None })
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let de = self.read_list()?;
+
+        let mut access = ListAccess { de, len: Some(len) };
+
+        let value = visitor.visit_seq(&mut access)?;
+
+        access.de.finalize()?;
+
+        Ok(value)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(RlpDeError::UnsupportType("enum".to_owned()))
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.read_item()?;
+
+        visitor.visit_unit()
+    }
+}
+
+/// Access items of rlp list, `len` is [`None`] for variable length sequence.
+struct ListAccess<'de> {
+    de: RlpDecoder<'de>,
+    len: Option<usize>,
+}
+
+impl<'de> de::SeqAccess<'de> for ListAccess<'de> {
+    type Error = RlpDeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match &mut self.len {
+            Some(0) => Ok(None),
+            Some(len) => {
+                *len -= 1;
+
+                seed.deserialize(&mut self.de).map(Some)
+            }
+            None if self.de.is_empty() => Ok(None),
+            None => seed.deserialize(&mut self.de).map(Some),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.len
+    }
+}
+
+/// Deserialize rust value from rlp format in [`Strict`](RlpMode::Strict) mode.
+pub fn rlp_decode<'de, D: Deserialize<'de>>(data: &'de [u8]) -> Result<D, RlpDeError> {
+    rlp_decode_with_mode(data, RlpMode::Strict)
+}
+
+/// Deserialize rust value from rlp format with decoding `mode`.
+pub fn rlp_decode_with_mode<'de, D: Deserialize<'de>>(
+    data: &'de [u8],
+    mode: RlpMode,
+) -> Result<D, RlpDeError> {
+    let mut decoder = RlpDecoder::new(data, mode);
+
+    let value = D::deserialize(&mut decoder)?;
+
+    decoder.finalize()?;
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use crate::rlp_encode;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Tx {
+        nonce: u64,
+        to: Option<String>,
+        data: Vec<String>,
+    }
+
+    #[test]
+    fn test_rlp_decode() {
+        assert_eq!(
+            rlp_decode::<String>(&[0x83, b'd', b'o', b'g']).unwrap(),
+            "dog"
+        );
+
+        assert_eq!(
+            rlp_decode::<Vec<String>>(&[0xc8, 0x83u8, b'c', b'a', b't', 0x83, b'd', b'o', b'g'])
+                .unwrap(),
+            ["cat", "dog"]
+        );
+
+        assert_eq!(rlp_decode::<u64>(&[0x80]).unwrap(), 0);
+        assert_eq!(rlp_decode::<u64>(&[0x0f]).unwrap(), 15);
+        assert_eq!(rlp_decode::<u64>(&[0x82, 0x04, 0x00]).unwrap(), 1024);
+
+        let long = "Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+
+        assert_eq!(
+            rlp_decode::<String>(&rlp_encode(long).unwrap()).unwrap(),
+            long
+        );
+
+        let tx = Tx {
+            nonce: 0x100,
+            to: None,
+            data: vec![long.to_owned(), "".to_owned()],
+        };
+
+        assert_eq!(rlp_decode::<Tx>(&rlp_encode(&tx).unwrap()).unwrap(), tx);
+
+        let tx = Tx {
+            to: Some("dog".to_owned()),
+            ..tx
+        };
+
+        assert_eq!(rlp_decode::<Tx>(&rlp_encode(&tx).unwrap()).unwrap(), tx);
+    }
+
+    #[test]
+    fn test_strict_mode() {
+        let lenient = |data: &[u8]| rlp_decode_with_mode::<u64>(data, RlpMode::Lenient);
+
+        // Trailing bytes
+        assert!(matches!(
+            rlp_decode::<u64>(&[0x0f, 0x01]),
+            Err(RlpDeError::TrailingBytes(1))
+        ));
+        assert_eq!(lenient(&[0x0f, 0x01]).unwrap(), 15);
+
+        // Trailing list items
+        let data = rlp_encode(&(1u64, 2u64, 3u64)).unwrap();
+
+        assert!(matches!(
+            rlp_decode::<(u64, u64)>(&data),
+            Err(RlpDeError::TrailingBytes(1))
+        ));
+        assert_eq!(
+            rlp_decode_with_mode::<(u64, u64)>(&data, RlpMode::Lenient).unwrap(),
+            (1, 2)
+        );
+
+        // Integer with leading zeros
+        assert!(matches!(
+            rlp_decode::<u64>(&[0x82, 0x00, 0x0f]),
+            Err(RlpDeError::NonCanonicalInteger(_))
+        ));
+        assert!(matches!(
+            rlp_decode::<u64>(&[0x00]),
+            Err(RlpDeError::NonCanonicalInteger(_))
+        ));
+        assert_eq!(lenient(&[0x82, 0x00, 0x0f]).unwrap(), 15);
+
+        // Single byte with length prefix
+        assert!(matches!(
+            rlp_decode::<u64>(&[0x81, 0x0f]),
+            Err(RlpDeError::NonCanonicalSize(_))
+        ));
+        assert_eq!(lenient(&[0x81, 0x0f]).unwrap(), 15);
+
+        // Long form length of short string
+        assert!(matches!(
+            rlp_decode::<String>(&[0xb8, 0x03, b'd', b'o', b'g']),
+            Err(RlpDeError::NonCanonicalSize(_))
+        ));
+        assert_eq!(
+            rlp_decode_with_mode::<String>(&[0xb8, 0x03, b'd', b'o', b'g'], RlpMode::Lenient)
+                .unwrap(),
+            "dog"
+        );
+
+        // Out of range
+        assert!(matches!(
+            rlp_decode::<u8>(&[0x82, 0x04, 0x00]),
+            Err(RlpDeError::OutOfRange(_))
+        ));
+
+        assert!(matches!(
+            rlp_decode::<u64>(&[0x83, 0x04, 0x00]),
+            Err(RlpDeError::InsufficentInputs(_))
+        ));
+    }
+}
@@ -1,8 +1,21 @@
+mod de;
+pub use de::*;
+
+use bytes::{BufMut, BytesMut};
+use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{ser, Serialize};
 
 use thiserror::Error;
 
+/// Matches `bytes<M>` newtype names, compiled once rather than on every newtype call.
+pub(crate) static BYTES_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^bytes(\d{1,2})$").expect("Compile bytes regex"));
+
+/// Matches `int<M>` and `uint<M>` newtype names.
+pub(crate) static INT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(u)?int(\d{1,3})$").expect("Compile int regex"));
+
 /// Abi serializer error variant
 #[derive(Debug, Error)]
 pub enum RlpError {
@@ -31,79 +44,56 @@ impl ser::Error for RlpError {
     }
 }
 
-#[derive(Debug, Default)]
-struct RlpList {
-    buff: Vec<u8>,
-}
-
-impl RlpList {
-    fn finalize(mut self) -> Result<Vec<u8>, RlpError> {
-        let len = self.buff.len();
-        if len <= 55 {
-            let mut buff = vec![0xc0u8 + len as u8];
+/// Returns rlp length prefix buffer and prefix length of item with payload length `len`,
+/// `offset` is `0x80` for string or `0xc0` for list.
+fn length_prefix(offset: u8, len: usize) -> ([u8; 9], usize) {
+    let mut prefix = [0u8; 9];
 
-            buff.append(&mut self.buff);
+    if len <= 55 {
+        prefix[0] = offset + len as u8;
 
-            Ok(buff)
-        } else {
-            let mut len_buff = unsigned_to_buff(&len.to_be_bytes()).to_vec();
-
-            let mut buff = vec![0xf7 + len_buff.len() as u8];
-
-            buff.append(&mut len_buff);
-            buff.append(&mut self.buff);
-
-            Ok(buff)
-        }
-    }
-
-    pub fn rlp_append_string(&mut self, bytes: &[u8]) -> Result<(), RlpError> {
-        self.buff.append(&mut bytes.to_owned());
-
-        Ok(())
-    }
-}
-
-fn rlp_encode_string(bytes: &[u8]) -> Result<Vec<u8>, RlpError> {
-    let len = bytes.len();
-
-    match len {
-        0 => Ok(vec![0x80u8]),
-        len @ 1..=55 => {
-            if len == 1 && bytes[0] < 0x80 {
-                Ok(bytes.to_owned())
-            } else {
-                let mut buff = vec![0x80u8 + len as u8];
-
-                buff.append(&mut bytes.to_owned());
-
-                Ok(buff)
-            }
-        }
-        len => {
-            let mut len_buff = unsigned_to_buff(&len.to_be_bytes()).to_vec();
+        (prefix, 1)
+    } else {
+        let len_buff = len.to_be_bytes();
 
-            let mut buff = vec![0xb7 + len_buff.len() as u8];
+        let len_buff = unsigned_to_buff(&len_buff);
 
-            buff.append(&mut len_buff);
-            buff.append(&mut bytes.to_owned());
+        prefix[0] = offset + 55 + len_buff.len() as u8;
+        prefix[1..=len_buff.len()].copy_from_slice(len_buff);
 
-            Ok(buff)
-        }
+        (prefix, len_buff.len() + 1)
     }
 }
 
 /// Rlp(RECURSIVE-LENGTH PREFIX) format stream like encoder.
+///
+/// Items are written into one buffer, list length prefixes are backpatched by [`end_list`](RlpEncoder::end_list),
+/// so no intermediate buffer is allocated for nested lists.
 #[derive(Debug, Default)]
 pub struct RlpEncoder {
-    buff: Vec<u8>,
-    list_stack: Vec<RlpList>,
+    buff: BytesMut,
+    /// Length of caller data in buff before encoding.
+    root: usize,
+    /// Payload start offsets of openned lists.
+    list_stack: Vec<usize>,
 }
 
 impl RlpEncoder {
+    /// Create encoder appending encoding data to `buff`, e.g. a reused buffer or a buffer with tx type prefix.
+    pub fn with_buffer(buff: BytesMut) -> Self {
+        Self {
+            root: buff.len(),
+            buff,
+            list_stack: Default::default(),
+        }
+    }
+
     /// Start a new encoding round for list item
     pub fn begin_list(&mut self) -> Result<(), RlpError> {
-        self.list_stack.push(Default::default());
+        // Reserve one byte for short list prefix, extended by end_list if payload is longer than 55 bytes.
+        self.buff.put_u8(0xc0);
+
+        self.list_stack.push(self.buff.len());
 
         Ok(())
     }
@@ -112,18 +102,21 @@ impl RlpEncoder {
     /// `string` means **"a certain number of bytes of binary data"**; no special encodings are used,
     /// and no knowledge about the content of the strings is implied.
     pub fn append_string(&mut self, bytes: &[u8]) -> Result<(), RlpError> {
-        if !self.list_stack.is_empty() {
-            self.list_stack
-                .last_mut()
-                .unwrap()
-                .rlp_append_string(&rlp_encode_string(bytes)?)?;
+        if self.list_stack.is_empty() && self.buff.len() != self.root {
+            return Err(RlpError::List);
+        }
+
+        if bytes.len() == 1 && bytes[0] < 0x80 {
+            self.buff.put_u8(bytes[0]);
         } else {
-            if self.buff.len() != 0 {
-                return Err(RlpError::List.into());
-            } else {
-                self.buff = rlp_encode_string(bytes)?;
-            }
+            let (prefix, prefix_len) = length_prefix(0x80, bytes.len());
+
+            self.buff.reserve(prefix_len + bytes.len());
+
+            self.buff.put_slice(&prefix[..prefix_len]);
+            self.buff.put_slice(bytes);
         }
+
         Ok(())
     }
 
@@ -131,29 +124,35 @@ impl RlpEncoder {
     ///
     /// Call [`begin_list`](RlpEncoder::begin_list) first before calling this fn
     pub fn end_list(&mut self) -> Result<(), RlpError> {
-        if let Some(list) = self.list_stack.pop() {
-            let mut buff = list.finalize()?;
-
-            // check if in a list
-            if !self.list_stack.is_empty() {
-                self.list_stack
-                    .last_mut()
-                    .unwrap()
-                    .rlp_append_string(&buff)?;
-            } else {
-                self.buff.append(&mut buff);
-            }
+        let start = self.list_stack.pop().ok_or(RlpError::UnopenList)?;
 
-            return Ok(());
-        } else {
-            return Err(RlpError::UnopenList.into());
+        let len = self.buff.len() - start;
+
+        let (prefix, prefix_len) = length_prefix(0xc0, len);
+
+        if prefix_len > 1 {
+            // Shift payload to make room for long list prefix.
+            let extra = prefix_len - 1;
+
+            self.buff.resize(self.buff.len() + extra, 0);
+
+            self.buff.copy_within(start..start + len, start + extra);
         }
+
+        self.buff[(start - 1)..(start - 1 + prefix_len)].copy_from_slice(&prefix[..prefix_len]);
+
+        Ok(())
     }
 
     /// Close encoder and return result bytes.
     pub fn finalize(self) -> Result<Vec<u8>, RlpError> {
+        Ok(self.finalize_bytes()?.into())
+    }
+
+    /// Close encoder and return result buffer, including data of buffer passed to [`with_buffer`](RlpEncoder::with_buffer).
+    pub fn finalize_bytes(self) -> Result<BytesMut, RlpError> {
         if !self.list_stack.is_empty() {
-            return Err(RlpError::UnclosedList.into());
+            return Err(RlpError::UnclosedList);
         }
 
         Ok(self.buff)
@@ -201,7 +200,7 @@ impl<'a> ser::Serializer for &'a mut RlpEncoder {
     }
 
     fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("Contract abi don't support rust char")
+        Err(RlpError::UnsupportType("char".to_owned()))
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
@@ -256,9 +255,8 @@ impl<'a> ser::Serializer for &'a mut RlpEncoder {
     {
         match name {
             "bytes" => {
-                let bytes = unsafe { (value as *const T).cast::<Vec<u8>>().as_ref().unwrap() };
-
-                self.append_string(bytes)
+                // Payload is written by `serialize_bytes`.
+                value.serialize(self)
             }
             "address" => {
                 let bytes = unsafe { (value as *const T).cast::<[u8; 32]>().as_ref().unwrap() };
@@ -266,10 +264,7 @@ impl<'a> ser::Serializer for &'a mut RlpEncoder {
                 return self.append_string(&bytes[12..]);
             }
             _ => {
-                let bytes_regex = Regex::new(r"^bytes(\d{1,2})$").unwrap();
-                let int_regex = Regex::new(r"^(u)?int(\d{1,3})$").unwrap();
-
-                if let Some(caps) = bytes_regex.captures(name) {
+                if let Some(caps) = BYTES_REGEX.captures(name) {
                     let len: usize = caps[1].parse().unwrap();
                     if len <= 32 {
                         let bytes =
@@ -279,7 +274,7 @@ impl<'a> ser::Serializer for &'a mut RlpEncoder {
                     }
                 }
 
-                if let Some(caps) = int_regex.captures(name) {
+                if let Some(caps) = INT_REGEX.captures(name) {
                     let len: usize = caps[2].parse().unwrap();
                     if len <= 256 {
                         let bytes =
@@ -321,7 +316,7 @@ impl<'a> ser::Serializer for &'a mut RlpEncoder {
     where
         T: serde::Serialize,
     {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(RlpError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
@@ -363,7 +358,7 @@ impl<'a> ser::Serializer for &'a mut RlpEncoder {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(RlpError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
@@ -390,7 +385,7 @@ impl<'a> ser::Serializer for &'a mut RlpEncoder {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(RlpError::UnsupportType("enum".to_owned()))
     }
 
     fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
@@ -447,7 +442,7 @@ impl<'a> ser::Serializer for &'a mut RlpEncoder {
         _variant_index: u32,
         _variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("Contract abi don't support rust enum")
+        Err(RlpError::UnsupportType("enum".to_owned()))
     }
 }
 
@@ -456,21 +451,21 @@ impl<'a> ser::SerializeMap for &'a mut RlpEncoder {
 
     type Ok = ();
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        Err(RlpError::UnsupportType("map".to_owned()))
     }
 
     fn serialize_key<T: ?Sized>(&mut self, _key: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(RlpError::UnsupportType("map".to_owned()))
     }
 
     fn serialize_value<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        unimplemented!()
+        Err(RlpError::UnsupportType("map".to_owned()))
     }
 }
 
@@ -614,6 +609,33 @@ pub fn rlp_encode<S: Serialize + ?Sized>(value: &S) -> anyhow::Result<Vec<u8>> {
     Ok(serializer.finalize()?)
 }
 
+/// Serialize rust value to rlp format, appending to `buff`.
+///
+/// Reuse `buff` across calls to avoid allocations, e.g. encoding many transactions.
+/// `buff` is left unchanged if encoding failed.
+pub fn rlp_encode_into<S: Serialize + ?Sized>(
+    value: &S,
+    buff: &mut BytesMut,
+) -> anyhow::Result<()> {
+    let root = buff.len();
+
+    let mut serializer = RlpEncoder::with_buffer(std::mem::take(buff));
+
+    let mut result = value.serialize(&mut serializer);
+
+    if result.is_ok() && !serializer.list_stack.is_empty() {
+        result = Err(RlpError::UnclosedList);
+    }
+
+    *buff = serializer.buff;
+
+    if result.is_err() {
+        buff.truncate(root);
+    }
+
+    Ok(result?)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -699,6 +721,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_long_list() {
+        let long = "Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+
+        // Payload of 58 bytes, list prefix is backpatched to long form.
+        let data = rlp_encode(&vec![long]).unwrap();
+
+        assert_eq!(data[..3], [0xf8, 0x3a, 0xb8]);
+        assert_eq!(data.len(), 60);
+
+        // Nested long lists, inner payload of 0x03a0 bytes, outer payload of 0x3a30 bytes.
+        let value = vec![vec![long; 16]; 16];
+
+        let data = rlp_encode(&value).unwrap();
+
+        assert_eq!(data[..3], [0xf9, 0x3a, 0x30]);
+        assert_eq!(data[3..6], [0xf9, 0x03, 0xa0]);
+
+        assert_eq!(rlp_decode::<Vec<Vec<String>>>(&data).unwrap(), value);
+    }
+
+    #[test]
+    fn test_encode_into() {
+        let mut buff = BytesMut::from(&[0x02u8][..]);
+
+        rlp_encode_into(&vec!["cat", "dog"], &mut buff).unwrap();
+
+        assert_eq!(
+            buff[..],
+            [0x02, 0xc8, 0x83u8, b'c', b'a', b't', 0x83, b'd', b'o', b'g']
+        );
+
+        buff.clear();
+
+        rlp_encode_into("dog", &mut buff).unwrap();
+
+        assert_eq!(buff[..], [0x83u8, b'd', b'o', b'g']);
+
+        // Buffer is unchanged if encoding failed.
+        assert!(rlp_encode_into(&true, &mut buff).is_err());
+
+        assert_eq!(buff[..], [0x83u8, b'd', b'o', b'g']);
+    }
+
+    #[test]
+    fn test_unsupported_types() {
+        #[derive(Serialize)]
+        enum Side {
+            Buy,
+        }
+
+        assert!(matches!(
+            rlp_encode(&'a').unwrap_err().downcast_ref::<RlpError>(),
+            Some(RlpError::UnsupportType(name)) if name == "char"
+        ));
+
+        assert!(rlp_encode(&vec![Side::Buy]).is_err());
+    }
+
     #[test]
     fn test_option() {
         _ = pretty_env_logger::try_init();
@@ -2,3 +2,5 @@ pub mod error;
 pub mod signer;
 
 pub mod wallet;
+
+pub mod policy;
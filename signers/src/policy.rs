@@ -0,0 +1,431 @@
+//! Signing permission policy wrapping another [`Signer`].
+
+use ethers_core::{
+    Address, Bytes, Eip1559Signature, SignedTransaction, TypedData, TypedTransactionRequest, U256,
+};
+use futures::{channel::mpsc, StreamExt};
+use jsonrpc_rs::channel::TransportChannel;
+use jsonrpc_rs::{ErrorCode, RPCError, RPCResult, Server};
+use serde::{Deserialize, Serialize};
+
+use crate::{signer::Signer, wallet::LocalWalletChannel};
+
+/// Signing request rejected by [`SignerPolicy`].
+///
+/// Returned as `data` field of signer rpc error, see [`PolicyViolation::from_rpc_error`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum PolicyViolation {
+    /// Chain id is not allowed
+    #[error("ChainNotAllowed: chain id {chain_id}")]
+    ChainNotAllowed { chain_id: u64 },
+    /// Transferring value exceeds the max value
+    #[error("ValueExceeded: value {value}, max {max}")]
+    ValueExceeded { value: U256, max: U256 },
+    /// Target contract is not in allowlist
+    #[error("ContractNotAllowed: {address}")]
+    ContractNotAllowed { address: Address },
+    /// Contract creation is not allowed if contract allowlist is set
+    #[error("ContractCreation: contract creation is not allowed")]
+    ContractCreation,
+}
+
+impl PolicyViolation {
+    /// Extract policy violation from signer rpc error, returns [`None`] if `err` is not caused by policy.
+    pub fn from_rpc_error(err: &RPCError) -> Option<Self> {
+        err.data
+            .as_ref()
+            .and_then(|data| serde_json::from_value(data.clone()).ok())
+    }
+}
+
+impl From<PolicyViolation> for RPCError {
+    fn from(value: PolicyViolation) -> Self {
+        RPCError {
+            code: ErrorCode::InternalError,
+            message: value.to_string(),
+            data: serde_json::to_value(&value).ok(),
+        }
+    }
+}
+
+/// Signing permissions, all checks are disabled by default.
+///
+/// ```no_run
+/// # use ethers_signer::policy::SignerPolicy;
+/// # fn example(signer: ethers_signer::signer::Signer) {
+/// let signer = SignerPolicy::new()
+///     .allow_chain(1)
+///     .allow_contract("0x70997970C51812dc3A010C7d01b50e0d17dc79C8".try_into().unwrap())
+///     .wrap(signer);
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SignerPolicy {
+    allowed_chains: Option<Vec<u64>>,
+    max_value: Option<U256>,
+    allowed_contracts: Option<Vec<Address>>,
+}
+
+impl SignerPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow signing for `chain_id`, other chains are rejected once any chain is allowed.
+    pub fn allow_chain(mut self, chain_id: u64) -> Self {
+        self.allowed_chains
+            .get_or_insert_with(Vec::new)
+            .push(chain_id);
+        self
+    }
+
+    /// Reject transactions transferring more than `max` wei.
+    pub fn max_value(mut self, max: U256) -> Self {
+        self.max_value = Some(max);
+        self
+    }
+
+    /// Allow transactions to `address`, other targets and contract creation are rejected once any contract is allowed.
+    ///
+    /// Also applies to eip712 `verifyingContract`.
+    pub fn allow_contract(mut self, address: Address) -> Self {
+        self.allowed_contracts
+            .get_or_insert_with(Vec::new)
+            .push(address);
+        self
+    }
+
+    /// Check transaction request against this policy.
+    pub fn check_transaction(&self, tx: &TypedTransactionRequest) -> Result<(), PolicyViolation> {
+        let (chain_id, to, value) = match tx {
+            TypedTransactionRequest::Legacy(tx) => (
+                // Legacy transactions are signed with chain id 1 if not set.
+                tx.chain_id.map(Option::<u64>::from).unwrap_or(Some(1)),
+                tx.to,
+                tx.value,
+            ),
+            TypedTransactionRequest::Eip2930(tx) => (
+                tx.tx.chain_id.map(Option::<u64>::from).unwrap_or(Some(1)),
+                tx.tx.to,
+                tx.tx.value,
+            ),
+            TypedTransactionRequest::Eip1559(tx) => (tx.chain_id.into(), tx.to, tx.value),
+        };
+
+        self.check_chain(chain_id)?;
+
+        if let (Some(max), Some(value)) = (self.max_value, value) {
+            if value > max {
+                return Err(PolicyViolation::ValueExceeded { value, max });
+            }
+        }
+
+        if self.allowed_contracts.is_some() {
+            match to {
+                Some(address) => self.check_contract(address)?,
+                None => return Err(PolicyViolation::ContractCreation),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check eip712 typed data domain against this policy.
+    pub fn check_typed_data<V>(&self, typed_data: &TypedData<V>) -> Result<(), PolicyViolation>
+    where
+        V: Serialize,
+    {
+        self.check_chain(typed_data.domain.chain_id.into())?;
+
+        self.check_contract(typed_data.domain.verifying_contract)
+    }
+
+    fn check_chain(&self, chain_id: Option<u64>) -> Result<(), PolicyViolation> {
+        if let Some(allowed) = &self.allowed_chains {
+            // Chain id overflows u64 is never allowed.
+            let chain_id = chain_id.unwrap_or(u64::MAX);
+
+            if !allowed.contains(&chain_id) {
+                return Err(PolicyViolation::ChainNotAllowed { chain_id });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_contract(&self, address: Address) -> Result<(), PolicyViolation> {
+        if let Some(allowed) = &self.allowed_contracts {
+            if !allowed.contains(&address) {
+                return Err(PolicyViolation::ContractNotAllowed { address });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wrap `signer`, signing requests violating this policy are rejected before reaching `signer`.
+    pub fn wrap(self, signer: Signer) -> Signer {
+        let (client_output, dispatcher_input) = mpsc::channel(20);
+        let (dispatcher_output, client_input) = mpsc::channel(20);
+
+        let client_transport = LocalWalletChannel {
+            receiver: client_input.map(|c| Ok(c)).boxed(),
+            sender: client_output,
+        };
+
+        let policy = self;
+
+        LocalWalletChannel::spawn(async move {
+            let server_transport = LocalWalletChannel {
+                receiver: dispatcher_input.map(|c| Ok(c)).boxed(),
+                sender: dispatcher_output,
+            };
+
+            let mut server = Server::default();
+
+            let (tx_policy, inner) = (policy.clone(), signer.clone());
+
+            server.async_handle("signer_ethTransaction", move |tx| {
+                sign_transaction(tx_policy.clone(), inner.clone(), tx)
+            });
+
+            let (envelope_policy, inner) = (policy.clone(), signer.clone());
+
+            server.async_handle("signer_signTransaction", move |tx| {
+                sign_transaction_envelope(envelope_policy.clone(), inner.clone(), tx)
+            });
+
+            let (batch_policy, inner) = (policy.clone(), signer.clone());
+
+            server.async_handle("signer_ethTransactionBatch", move |txs| {
+                sign_transactions(batch_policy.clone(), inner.clone(), txs)
+            });
+
+            let (validator_policy, inner) = (policy.clone(), signer.clone());
+
+            server.async_handle("signer_intendedValidator", move |request| {
+                sign_intended_validator(validator_policy.clone(), inner.clone(), request)
+            });
+
+            let inner = signer.clone();
+
+            server.async_handle("signer_typedData", move |typed_data| {
+                sign_typed_data(policy.clone(), inner.clone(), typed_data)
+            });
+
+            let inner = signer.clone();
+
+            server.async_handle("signer_decrypt", move |data| decrypt(inner.clone(), data));
+
+            let inner = signer.clone();
+
+            server.async_handle("signer_accounts", move |()| accounts(inner.clone()));
+
+            let inner = signer;
+
+            server.async_handle("signer_address", move |()| address_of(inner.clone()));
+
+            server.accept(server_transport);
+
+            Ok(())
+        });
+
+        Signer::new(jsonrpc_rs::Client::new(
+            "signer_policy".to_owned(),
+            client_transport,
+        ))
+    }
+}
+
+async fn sign_transaction(
+    policy: SignerPolicy,
+    mut signer: Signer,
+    tx: TypedTransactionRequest,
+) -> RPCResult<Option<Bytes>> {
+    if let Err(violation) = policy.check_transaction(&tx) {
+        log::warn!("signer policy reject tx, {}", violation);
+
+        return Err(violation.into());
+    }
+
+    Ok(Some(signer.sign_eth_transaction(tx).await?))
+}
+
+async fn sign_transaction_envelope(
+    policy: SignerPolicy,
+    mut signer: Signer,
+    tx: TypedTransactionRequest,
+) -> RPCResult<Option<SignedTransaction>> {
+    if let Err(violation) = policy.check_transaction(&tx) {
+        log::warn!("signer policy reject tx, {}", violation);
+
+        return Err(violation.into());
+    }
+
+    Ok(Some(signer.sign_transaction(tx).await?))
+}
+
+async fn sign_transactions(
+    policy: SignerPolicy,
+    mut signer: Signer,
+    txs: Vec<TypedTransactionRequest>,
+) -> RPCResult<Option<Vec<Bytes>>> {
+    // The whole batch is rejected if any transaction violates policy.
+    for tx in &txs {
+        if let Err(violation) = policy.check_transaction(tx) {
+            log::warn!("signer policy reject tx batch, {}", violation);
+
+            return Err(violation.into());
+        }
+    }
+
+    Ok(Some(signer.sign_eth_transactions(txs).await?))
+}
+
+async fn sign_typed_data(
+    policy: SignerPolicy,
+    mut signer: Signer,
+    typed_data: TypedData<serde_json::Value>,
+) -> RPCResult<Option<Eip1559Signature>> {
+    if let Err(violation) = policy.check_typed_data(&typed_data) {
+        log::warn!("signer policy reject typed data, {}", violation);
+
+        return Err(violation.into());
+    }
+
+    Ok(Some(signer.sign_typed_data(typed_data).await?))
+}
+
+async fn sign_intended_validator(
+    policy: SignerPolicy,
+    mut signer: Signer,
+    (validator, data): (Address, Bytes),
+) -> RPCResult<Option<Eip1559Signature>> {
+    if let Err(violation) = policy.check_contract(validator) {
+        log::warn!(
+            "signer policy reject intended validator data, {}",
+            violation
+        );
+
+        return Err(violation.into());
+    }
+
+    Ok(Some(
+        signer
+            .sign_data_with_intended_validator(validator, data)
+            .await?,
+    ))
+}
+
+async fn decrypt(mut signer: Signer, data: Bytes) -> RPCResult<Option<Bytes>> {
+    Ok(Some(signer.decrypt(data).await?))
+}
+
+async fn accounts(mut signer: Signer) -> RPCResult<Option<Vec<Address>>> {
+    Ok(Some(signer.accounts().await?))
+}
+
+async fn address_of(mut signer: Signer) -> RPCResult<Option<Address>> {
+    Ok(Some(signer.address().await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers_core::LegacyTransactionRequest;
+    use ethers_wallet::wallet::Wallet;
+    use serde_json::json;
+
+    use crate::wallet::WalletSigner;
+
+    use super::*;
+
+    fn tx(to: &str, value: &str) -> LegacyTransactionRequest {
+        json!({
+            "chainId": "0x1",
+            "nonce": "0x1",
+            "to": to,
+            "value": value,
+            "data": "0x",
+            "gas": "0x60000",
+            "gasPrice": "0x60000111"
+        })
+        .try_into()
+        .expect("Create tx")
+    }
+
+    #[test]
+    fn test_check_transaction() {
+        let allowed: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        let policy = SignerPolicy::new()
+            .allow_chain(1)
+            .max_value(U256::from(100u64))
+            .allow_contract(allowed);
+
+        let check = |tx: LegacyTransactionRequest| policy.check_transaction(&tx.into());
+
+        assert_eq!(
+            check(tx("0x70997970C51812dc3A010C7d01b50e0d17dc79C8", "0x1")),
+            Ok(())
+        );
+
+        assert_eq!(
+            check(tx("0x70997970C51812dc3A010C7d01b50e0d17dc79C8", "0x65")),
+            Err(PolicyViolation::ValueExceeded {
+                value: U256::from(101u64),
+                max: U256::from(100u64)
+            })
+        );
+
+        assert!(matches!(
+            check(tx("0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC", "0x1")),
+            Err(PolicyViolation::ContractNotAllowed { .. })
+        ));
+
+        let mut other_chain = tx("0x70997970C51812dc3A010C7d01b50e0d17dc79C8", "0x1");
+
+        other_chain.chain_id = Some(56u64.into());
+
+        assert_eq!(
+            check(other_chain),
+            Err(PolicyViolation::ChainNotAllowed { chain_id: 56 })
+        );
+
+        let mut creation = tx("0x70997970C51812dc3A010C7d01b50e0d17dc79C8", "0x1");
+
+        creation.to = None;
+
+        assert_eq!(check(creation), Err(PolicyViolation::ContractCreation));
+    }
+
+    #[async_std::test]
+    async fn test_wrap_signer() {
+        let wallet =
+            Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .expect("Create hardhat account 0 wallet");
+
+        let signer = wallet
+            .try_into_signer()
+            .expect("Try convert wallet into signer");
+
+        let mut signer = SignerPolicy::new().max_value(U256::from(1u64)).wrap(signer);
+
+        signer
+            .sign_eth_transaction(tx("0x70997970C51812dc3A010C7d01b50e0d17dc79C8", "0x1"))
+            .await
+            .expect("Sign tx");
+
+        let err = signer
+            .sign_eth_transaction(tx("0x70997970C51812dc3A010C7d01b50e0d17dc79C8", "0x2"))
+            .await
+            .expect_err("Policy reject tx");
+
+        assert!(matches!(
+            PolicyViolation::from_rpc_error(&err),
+            Some(PolicyViolation::ValueExceeded { .. })
+        ));
+    }
+}
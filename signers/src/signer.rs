@@ -1,8 +1,6 @@
 use std::fmt::{Debug, Display};
 
-use ethers_eip2718::*;
-use ethers_eip712::TypedData;
-use ethers_primitives::*;
+use ethers_core::*;
 use jsonrpc_rs::RPCResult;
 use serde::Serialize;
 
@@ -18,6 +16,10 @@ impl Signer {
     }
 
     /// Returns the signed transaction of the parameter `transaction_request`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "signer", skip_all, fields(method = "signer_ethTransaction"))
+    )]
     pub async fn sign_eth_transaction<T>(&mut self, transaction_request: T) -> RPCResult<Bytes>
     where
         T: TryInto<TypedTransactionRequest>,
@@ -32,7 +34,62 @@ impl Signer {
             .await
     }
 
+    /// Returns [`SignedTransaction`] of `transaction_request`, which retains the signature and tx hash.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "signer", skip_all, fields(method = "signer_signTransaction"))
+    )]
+    pub async fn sign_transaction<T>(
+        &mut self,
+        transaction_request: T,
+    ) -> RPCResult<SignedTransaction>
+    where
+        T: TryInto<TypedTransactionRequest>,
+        T::Error: Display + Debug,
+    {
+        let transaction_request = transaction_request
+            .try_into()
+            .map_err(jsonrpc_rs::map_error)?;
+
+        self.rpc_client
+            .call("signer_signTransaction", vec![transaction_request])
+            .await
+    }
+
+    /// Returns the signed transactions of `transaction_requests`, in the same order.
+    ///
+    /// Signs the whole batch in one request, local wallet signers sign on a worker pool.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "signer",
+            skip_all,
+            fields(method = "signer_ethTransactionBatch")
+        )
+    )]
+    pub async fn sign_eth_transactions<T>(
+        &mut self,
+        transaction_requests: Vec<T>,
+    ) -> RPCResult<Vec<Bytes>>
+    where
+        T: TryInto<TypedTransactionRequest>,
+        T::Error: Display + Debug,
+    {
+        let transaction_requests = transaction_requests
+            .into_iter()
+            .map(|tx| tx.try_into().map_err(jsonrpc_rs::map_error))
+            .collect::<RPCResult<Vec<_>>>()?;
+
+        self.rpc_client
+            .call("signer_ethTransactionBatch", vec![transaction_requests])
+            .await
+    }
+
     /// Returns the signed typed data, using [`eip-712`](https://eips.ethereum.org/EIPS/eip-712) algorithm
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "signer", skip_all, fields(method = "signer_typedData"))
+    )]
     pub async fn sign_typed_data<T, V>(&mut self, typed_data: T) -> RPCResult<Eip1559Signature>
     where
         T: TryInto<TypedData<V>>,
@@ -46,6 +103,27 @@ impl Signer {
             .await
     }
 
+    /// Returns signature of `data` with intended `validator`, using
+    /// [`eip-191`](https://eips.ethereum.org/EIPS/eip-191) version `0x00` framing.
+    pub async fn sign_data_with_intended_validator<A, B>(
+        &mut self,
+        validator: A,
+        data: B,
+    ) -> RPCResult<Eip1559Signature>
+    where
+        A: TryInto<Address>,
+        A::Error: Display + Debug,
+        B: TryInto<Bytes>,
+        B::Error: Display + Debug,
+    {
+        let validator = validator.try_into().map_err(jsonrpc_rs::map_error)?;
+        let data = data.try_into().map_err(jsonrpc_rs::map_error)?;
+
+        self.rpc_client
+            .call("signer_intendedValidator", vec![(validator, data)])
+            .await
+    }
+
     /// Decript data using signer private key.
     pub async fn decrypt<B>(&mut self, encrypt_data: B) -> RPCResult<Bytes>
     where
@@ -64,7 +142,14 @@ impl Signer {
         self.rpc_client.call("signer_accounts", ()).await
     }
 
-    /// Get associating signer account addresses
+    /// Returns the default signer account, aka the first one of [`accounts`](Self::accounts).
+    ///
+    /// Returns [`None`] if signer manages no account.
+    pub async fn default_account(&mut self) -> RPCResult<Option<Address>> {
+        Ok(self.accounts().await?.into_iter().next())
+    }
+
+    /// Get associating signer account address
     pub async fn address(&mut self) -> RPCResult<Address> {
         self.rpc_client.call("signer_address", ()).await
     }
@@ -1,23 +1,23 @@
 //! Create signer from local wallet.
 
-use ethers_eip2718::TypedTransactionRequest;
-use ethers_eip712::TypedData;
-use ethers_primitives::{Address, Bytes, Eip1559Signature, Eip55};
+use ethers_core::{
+    Address, Bytes, Eip1559Signature, Eip55, SignedTransaction, TypedData, TypedTransactionRequest,
+};
 use futures::{
     channel::mpsc::{self, SendError, Sender},
-    executor::ThreadPool,
     stream::BoxStream,
-    task::SpawnExt,
     StreamExt,
 };
 use jsonrpc_rs::{channel::TransportChannel, map_error, RPCData, RPCResult, Server};
-use once_cell::sync::OnceCell;
 
 use crate::signer::Signer;
 
-use ethers_wallet::wallet::Wallet;
+use ethers_wallet::{
+    message::MessageSigner,
+    wallet::{Wallet, WalletBackend},
+};
 
-/// Ethererum network provider tokio io event driver channel.
+/// Local wallet signer io event driver channel.
 ///
 pub(crate) struct LocalWalletChannel {
     pub(crate) receiver: BoxStream<'static, RPCResult<RPCData>>,
@@ -37,11 +37,7 @@ impl TransportChannel for LocalWalletChannel {
     where
         Fut: futures::Future<Output = RPCResult<()>> + Send + 'static,
     {
-        static INSTANCE: OnceCell<ThreadPool> = OnceCell::new();
-
-        let executor = INSTANCE.get_or_init(|| ThreadPool::new().unwrap());
-
-        _ = executor.spawn(async move {
+        ethers_runtime::spawn(async move {
             let result = future.await;
 
             match result {
@@ -65,7 +61,7 @@ pub trait WalletSigner {
 
 impl WalletSigner for Wallet {
     fn try_into_signer(self) -> anyhow::Result<Signer> {
-        let address = Address::from(self.public_key()?);
+        let address = self.address()?;
 
         let (client_output, dispatcher_input) = mpsc::channel(20);
         let (dispatcher_output, client_input) = mpsc::channel(20);
@@ -93,6 +89,20 @@ impl WalletSigner for Wallet {
 
             let wallet = self.clone();
 
+            #[allow(unused_parens)]
+            server.async_handle("signer_signTransaction", move |tx| {
+                sign_transaction_envelope(wallet.clone(), tx)
+            });
+
+            let wallet = self.clone();
+
+            #[allow(unused_parens)]
+            server.async_handle("signer_ethTransactionBatch", move |txs| {
+                sign_transactions(wallet.clone(), txs)
+            });
+
+            let wallet = self.clone();
+
             #[allow(unused_parens)]
             server.async_handle("signer_typedData", move |typed_data| {
                 sign_typed_data(wallet.clone(), typed_data)
@@ -101,17 +111,18 @@ impl WalletSigner for Wallet {
             let wallet = self.clone();
 
             #[allow(unused_parens)]
-            server.async_handle("signer_decrypt", move |data| decrypt(wallet.clone(), data));
+            server.async_handle("signer_intendedValidator", move |request| {
+                sign_intended_validator(wallet.clone(), request)
+            });
 
-            let public_key = self.public_key().map_err(map_error)?;
+            let wallet = self.clone();
 
-            let address = Address::from(public_key);
+            #[allow(unused_parens)]
+            server.async_handle("signer_decrypt", move |data| decrypt(wallet.clone(), data));
 
             #[allow(unused_parens)]
             server.async_handle("signer_accounts", move |()| accounts(address.clone()));
 
-            let address = Address::from(public_key);
-
             #[allow(unused_parens)]
             server.async_handle("signer_address", move |()| address_of(address.clone()));
 
@@ -138,6 +149,38 @@ async fn sign_transaction(wallet: Wallet, t: TypedTransactionRequest) -> RPCResu
     Ok(Some(bytes))
 }
 
+#[allow(unused)]
+async fn sign_transaction_envelope(
+    wallet: Wallet,
+    t: TypedTransactionRequest,
+) -> RPCResult<Option<SignedTransaction>> {
+    let hashed = t.sign_hash().map_err(map_error)?;
+
+    let signature = wallet.sign(hashed).map_err(map_error)?;
+
+    let signed = SignedTransaction::new(t, signature).map_err(map_error)?;
+
+    Ok(Some(signed))
+}
+
+#[allow(unused)]
+async fn sign_transactions(
+    wallet: Wallet,
+    txs: Vec<TypedTransactionRequest>,
+) -> RPCResult<Option<Vec<Bytes>>> {
+    let hashes = txs
+        .iter()
+        .map(|tx| tx.sign_hash())
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(map_error)?;
+
+    let signatures = wallet.sign_batch(&hashes).map_err(map_error)?;
+
+    let signed = TypedTransactionRequest::rlp_signed_batch(&txs, signatures).map_err(map_error)?;
+
+    Ok(Some(signed))
+}
+
 #[allow(unused)]
 async fn sign_typed_data(
     wallet: Wallet,
@@ -152,6 +195,18 @@ async fn sign_typed_data(
     Ok(Some(signature))
 }
 
+#[allow(unused)]
+async fn sign_intended_validator(
+    wallet: Wallet,
+    (validator, data): (Address, Bytes),
+) -> RPCResult<Option<Eip1559Signature>> {
+    let signature = wallet
+        .sign_data_with_intended_validator(&validator, &data)
+        .map_err(map_error)?;
+
+    Ok(Some(signature))
+}
+
 #[allow(unused)]
 async fn accounts(address: Address) -> RPCResult<Option<Vec<Address>>> {
     Ok(Some(vec![address]))
@@ -170,10 +225,10 @@ async fn decrypt(wallet: Wallet, data: Bytes) -> RPCResult<Option<Bytes>> {
 #[cfg(test)]
 mod tests {
 
-    use ethers_eip2718::{Eip2930TransactionRequest, LegacyTransactionRequest};
-    use ethers_eip712::TypedData;
-    use ethers_primitives::Address;
-    use ethers_wallet::wallet::Wallet;
+    use ethers_core::{
+        keccak256, Address, Eip2930TransactionRequest, LegacyTransactionRequest, TypedData, H256,
+    };
+    use ethers_wallet::{message::intended_validator_hash, wallet::Wallet};
     use serde::{Deserialize, Serialize};
     use serde_json::json;
 
@@ -237,6 +292,50 @@ mod tests {
             "0x01f86601018460000111830600009470997970c51812dc3a010c7d01b50e0d17dc79c80180c080a0fd6402ef803609fce890c09304abd681fe29c25616e960c1f44db1a026f5d03ba00867e433e4ddd6e8b0c8f283c08a7f81d2cc41cc29aaa8631d7b979a8ec9e8ec",
             signed_tx.to_string()
         );
+
+        let envelope = signer.sign_transaction(tx).await.expect("Sign tx");
+
+        assert_eq!(envelope.raw(), &signed_tx);
+        assert_eq!(envelope.hash(), &H256::from(keccak256(&signed_tx)));
+    }
+
+    #[async_std::test]
+    async fn test_sign_tx_batch() {
+        let _ = pretty_env_logger::try_init();
+
+        let wallet =
+            Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .expect("Create hardhat account 0 wallet");
+
+        let mut signer = wallet
+            .try_into_signer()
+            .expect("Try convert wallet into signer");
+
+        let txs = (1..=10u64)
+            .map(|nonce| {
+                json!({
+                    "nonce": format!("0x{:x}", nonce),
+                    "to": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+                    "value":"0x1",
+                    "gas":"0x60000",
+                    "gasPrice": "0x60000111"
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let signed_txs = signer
+            .sign_eth_transactions(txs.clone())
+            .await
+            .expect("Sign tx batch");
+
+        assert_eq!(signed_txs.len(), txs.len());
+
+        let signed_tx = signer
+            .sign_eth_transaction(txs[0].clone())
+            .await
+            .expect("Sign tx");
+
+        assert_eq!(signed_txs[0], signed_tx);
     }
 
     #[async_std::test]
@@ -273,4 +372,34 @@ mod tests {
 
         assert_eq!(signature.to_string(),"0x006ea8bb309a3401225701f3565e32519f94a0ea91a5910ce9229fe488e773584c0390416a2190d9560219dab757ecca2029e63fa9d1c2aebf676cc25b9f03126a");
     }
+
+    #[async_std::test]
+    async fn test_sign_intended_validator() {
+        _ = pretty_env_logger::try_init();
+
+        let wallet =
+            Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .expect("Create hardhat account 0 wallet");
+
+        let mut signer = wallet
+            .clone()
+            .try_into_signer()
+            .expect("Try convert wallet into signer");
+
+        let validator: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        let signature = signer
+            .sign_data_with_intended_validator(validator, "0x68656c6c6f")
+            .await
+            .expect("Sign data with intended validator");
+
+        let hashed = intended_validator_hash(&validator, b"hello");
+
+        assert_eq!(
+            wallet.recover(hashed, signature).unwrap(),
+            wallet.address().unwrap()
+        );
+    }
 }
@@ -0,0 +1,731 @@
+use std::fmt::{Debug, Display};
+
+use jsonrpc_rs::RPCError;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_ethabi::{ConstructorFragment, EventFragment, Fragment, FunctionFragment};
+use serde_json::Value;
+
+use crate::{
+    decode_revert_data, events::param_type, link_bytecode_with, revert_data, revert_reason,
+    Address, BlockNumberOrTag, Bytes, CallOptions, Client, DefaultTransactionReceipter, Deployed,
+    FromEtherHex, Log, NameOrAddress, SendOptions, StorageLayout, TransactionReceipt, TxRejection,
+    TxReverted, H256,
+};
+
+/// Contract errors, returned by dynamic contracts and contract bindings.
+///
+/// Errors of contract bindings are boxed into [`anyhow::Error`], use
+/// [`ContractResultExt::contract_error`] to match on failure modes.
+#[derive(Debug, thiserror::Error)]
+pub enum ContractError {
+    /// Function not found in contract abi
+    #[error("FunctionNotFound: {0}")]
+    FunctionNotFound(String),
+    /// Bare function name matches several overloads, call it by full signature instead
+    #[error("AmbiguousFunction: {0} matches overloads {1:?}, use full signature")]
+    AmbiguousFunction(String, Vec<String>),
+    /// Event not found in contract abi
+    #[error("EventNotFound: {0}")]
+    EventNotFound(String),
+    /// Bare event name matches several overloads, use full signature instead
+    #[error("AmbiguousEvent: {0} matches overloads {1:?}, use full signature")]
+    AmbiguousEvent(String, Vec<String>),
+    /// Log topic0 mismatch with event signature
+    #[error("EventMismatch: log topic0 is not event {0}")]
+    EventMismatch(String),
+    /// Log topics index out of range
+    #[error("TopicNotFound: log topics[{0}] not found")]
+    TopicNotFound(usize),
+    /// Invalid contract abi json
+    #[error("AbiJson: {0}")]
+    AbiJson(String),
+    /// Call or transaction reverted
+    #[error("Revert: reason: {}", decoded.as_deref().unwrap_or("unknown"))]
+    Revert {
+        /// Selector of revert data, e.g. `Error(string)` or custom error.
+        selector: Option<[u8; 4]>,
+        /// Raw revert data, empty if not returned by node.
+        data: Bytes,
+        /// Revert reason decoded by [`decode_revert_data`].
+        decoded: Option<String>,
+        tx_hash: Option<H256>,
+        receipt: Option<Box<TransactionReceipt>>,
+    },
+    /// Transaction ran out of gas or gas limit is lower than required
+    #[error("OutOfGas: {message}")]
+    OutOfGas {
+        message: String,
+        tx_hash: Option<H256>,
+        receipt: Option<Box<TransactionReceipt>>,
+    },
+    /// Rpc node errors except reverts and out of gas
+    #[error("ProviderError: {0}")]
+    ProviderError(anyhow::Error),
+    /// Signing transaction failed
+    #[error("SignerError: {0}")]
+    SignerError(anyhow::Error),
+    /// Rpc request or receipt waiting timeout
+    #[error("Timeout: {message}")]
+    Timeout {
+        message: String,
+        tx_hash: Option<H256>,
+    },
+    /// Transaction rejected by node, sending the same transaction again fails too
+    #[error("Rejected: {kind:?}, {message}")]
+    Rejected {
+        kind: TxRejection,
+        message: String,
+        tx_hash: Option<H256>,
+    },
+}
+
+impl ContractError {
+    /// Classify `err` of contract call or transaction, errors which are not recognized
+    /// are returned as [`ContractError::ProviderError`].
+    pub fn classify(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<ContractError>() {
+            Ok(err) => return err,
+            Err(err) => err,
+        };
+
+        let err = match err.downcast::<TxReverted>() {
+            Ok(reverted) => {
+                return Self::Revert {
+                    selector: None,
+                    data: Bytes::default(),
+                    decoded: reverted.reason,
+                    tx_hash: Some(reverted.tx_hash),
+                    receipt: Some(Box::new(reverted.receipt)),
+                }
+            }
+            Err(err) => err,
+        };
+
+        let rpc_error = match err.downcast_ref::<RPCError>() {
+            Some(rpc_error) => rpc_error,
+            None => return Self::ProviderError(err),
+        };
+
+        if let Some(data) = revert_data(rpc_error) {
+            return Self::Revert {
+                selector: data.get(..4).map(|selector| selector.try_into().unwrap()),
+                decoded: decode_revert_data(&data),
+                data: data.into(),
+                tx_hash: None,
+                receipt: None,
+            };
+        }
+
+        let message = rpc_error.message.to_lowercase();
+
+        if message.contains("out of gas")
+            || message.contains("gas required exceeds")
+            || message.contains("intrinsic gas too low")
+        {
+            return Self::OutOfGas {
+                message: rpc_error.message.clone(),
+                tx_hash: None,
+                receipt: None,
+            };
+        }
+
+        if let Some(kind) = TxRejection::classify(rpc_error) {
+            return Self::Rejected {
+                kind,
+                message: rpc_error.message.clone(),
+                tx_hash: None,
+            };
+        }
+
+        if message.starts_with("timeout") {
+            return Self::Timeout {
+                message: rpc_error.message.clone(),
+                tx_hash: None,
+            };
+        }
+
+        if let Some(reason) = revert_reason(rpc_error) {
+            return Self::Revert {
+                selector: None,
+                data: Bytes::default(),
+                decoded: Some(reason),
+                tx_hash: None,
+                receipt: None,
+            };
+        }
+
+        Self::ProviderError(err)
+    }
+
+    /// Returns hash of failed transaction, [`None`] if failed before broadcasting.
+    pub fn tx_hash(&self) -> Option<&H256> {
+        match self {
+            Self::Revert { tx_hash, .. }
+            | Self::OutOfGas { tx_hash, .. }
+            | Self::Timeout { tx_hash, .. }
+            | Self::Rejected { tx_hash, .. } => tx_hash.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns receipt of failed transaction if mined.
+    pub fn receipt(&self) -> Option<&TransactionReceipt> {
+        match self {
+            Self::Revert { receipt, .. } | Self::OutOfGas { receipt, .. } => receipt.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Set hash of failed transaction if not set.
+    pub(crate) fn with_tx_hash(mut self, hash: H256) -> Self {
+        match &mut self {
+            Self::Revert { tx_hash, .. }
+            | Self::OutOfGas { tx_hash, .. }
+            | Self::Timeout { tx_hash, .. }
+            | Self::Rejected { tx_hash, .. } => {
+                tx_hash.get_or_insert(hash);
+            }
+            _ => {}
+        }
+
+        self
+    }
+}
+
+/// Convert errors of contract bindings to [`ContractError`].
+///
+/// ```ignore
+/// match token.transfer(to, amount, ops).await.contract_error() {
+///     Err(ContractError::Revert { decoded, .. }) => log::error!("reverted, {:?}", decoded),
+///     Err(ContractError::OutOfGas { .. }) => retry_with_more_gas(),
+///     result => result?,
+/// }
+/// ```
+pub trait ContractResultExt<T> {
+    fn contract_error(self) -> Result<T, ContractError>;
+}
+
+impl<T> ContractResultExt<T> for anyhow::Result<T> {
+    fn contract_error(self) -> Result<T, ContractError> {
+        self.map_err(ContractError::classify)
+    }
+}
+
+/// Contract instance created at runtime from human-readable abi fragments.
+#[derive(Clone)]
+pub struct Contract {
+    /// Deployed contract address.
+    pub address: Address,
+    /// Client to communicate with contract.
+    pub client: Client,
+    /// Block of [`call`](Self::call) if [`CallOptions::block`] is not set, see [`at_block`](Self::at_block).
+    pub block: Option<BlockNumberOrTag>,
+    pub(crate) storage_layout: Option<StorageLayout>,
+    functions: Vec<FunctionFragment>,
+    events: Vec<EventFragment>,
+}
+
+impl Contract {
+    /// Create new dynamic contract instance with human-readable abi `fragments`.
+    ///
+    /// `constructor` fragments are ignored.
+    ///
+    /// ```ignore
+    /// let erc20 = Contract::new_dyn(
+    ///     address,
+    ///     &[
+    ///         "function balanceOf(address) view returns (uint256)",
+    ///         "event Transfer(address indexed from, address indexed to, uint256 value)",
+    ///     ],
+    ///     client,
+    /// )?;
+    /// ```
+    pub fn new_dyn<A, S>(address: A, fragments: &[S], client: Client) -> anyhow::Result<Self>
+    where
+        A: TryInto<Address>,
+        A::Error: std::error::Error + Sync + Send + 'static,
+        S: AsRef<str>,
+    {
+        let mut functions = vec![];
+        let mut events = vec![];
+
+        for fragment in fragments {
+            // Constructor is only used by deployment, see [`encode_constructor`].
+            if fragment.as_ref().trim().starts_with("constructor") {
+                continue;
+            }
+
+            match fragment.as_ref().parse::<Fragment>()? {
+                Fragment::Function(function) => functions.push(function),
+                Fragment::Event(event) => events.push(event),
+            }
+        }
+
+        Ok(Self {
+            address: address.try_into()?,
+            client,
+            block: None,
+            storage_layout: None,
+            functions,
+            events,
+        })
+    }
+
+    /// Create new dynamic contract instance like [`new_dyn`](Self::new_dyn), `address` is
+    /// either contract address or ENS name resolved by client provider.
+    pub async fn resolve_dyn<A, S>(
+        address: A,
+        fragments: &[S],
+        client: Client,
+    ) -> anyhow::Result<Self>
+    where
+        A: TryInto<NameOrAddress>,
+        A::Error: Debug + Display,
+        S: AsRef<str>,
+    {
+        let address = client.provider.clone().resolve(address).await?;
+
+        Self::new_dyn(address, fragments, client)
+    }
+
+    /// Deploy contract `name` with hex `bytecode` and constructor `args`, returns dynamic contract
+    /// instance with human-readable abi `fragments` and deployment tx receipt.
+    ///
+    /// ```ignore
+    /// let deployed = Contract::deploy_dyn(
+    ///     client,
+    ///     "Token",
+    ///     bytecode,
+    ///     &["constructor(string)", "function name() view returns (string)"],
+    ///     &("Token",),
+    ///     SendOptions::default(),
+    /// )
+    /// .await?;
+    ///
+    /// let token = deployed.contract;
+    /// ```
+    pub async fn deploy_dyn<S, A>(
+        client: Client,
+        name: &str,
+        bytecode: &str,
+        fragments: &[S],
+        args: &A,
+        ops: SendOptions,
+    ) -> anyhow::Result<Deployed<Self>>
+    where
+        S: AsRef<str>,
+        A: Serialize,
+    {
+        let bytecode = link_bytecode_with(bytecode, &client.link_references, &client.libraries)?;
+
+        let deploy_data = encode_constructor(&bytecode, fragments, args)?;
+
+        client
+            .deploy_with_receipt(name, deploy_data, ops)
+            .await?
+            .try_map(|address| Self::new_dyn(address, fragments, client.clone()))
+    }
+
+    /// Pin reads of this contract to `block`, e.g. history block of archive node,
+    /// for consistent snapshot across calls.
+    pub fn at_block<B: Into<BlockNumberOrTag>>(mut self, block: B) -> Self {
+        self.block = Some(block.into());
+        self
+    }
+
+    /// Get function fragment by name or signature, e.g. `balanceOf` or `balanceOf(address)`.
+    ///
+    /// Overloaded functions must be named by full signature.
+    pub fn function(&self, name: &str) -> anyhow::Result<&FunctionFragment> {
+        if let Some(function) = self.functions.iter().find(|f| f.signature() == name) {
+            return Ok(function);
+        }
+
+        let matched = self
+            .functions
+            .iter()
+            .filter(|f| f.name == name)
+            .collect::<Vec<_>>();
+
+        match matched.as_slice() {
+            [] => Err(ContractError::FunctionNotFound(name.to_owned()).into()),
+            [function] => Ok(*function),
+            overloads => Err(ContractError::AmbiguousFunction(
+                name.to_owned(),
+                overloads.iter().map(|f| f.signature()).collect(),
+            )
+            .into()),
+        }
+    }
+
+    /// Get event fragment by name or signature, e.g. `Transfer` or `Transfer(address,address,uint256)`.
+    ///
+    /// Overloaded events must be named by full signature.
+    pub fn event(&self, name: &str) -> anyhow::Result<&EventFragment> {
+        if let Some(event) = self.events.iter().find(|e| e.signature() == name) {
+            return Ok(event);
+        }
+
+        let matched = self
+            .events
+            .iter()
+            .filter(|e| e.name == name)
+            .collect::<Vec<_>>();
+
+        match matched.as_slice() {
+            [] => Err(ContractError::EventNotFound(name.to_owned()).into()),
+            [event] => Ok(*event),
+            overloads => Err(ContractError::AmbiguousEvent(
+                name.to_owned(),
+                overloads.iter().map(|e| e.signature()).collect(),
+            )
+            .into()),
+        }
+    }
+
+    /// Invoke contract method via `eth_call`, `args` is the tuple of function parameters.
+    pub async fn call<S, T>(&self, name: &str, args: &S) -> anyhow::Result<T>
+    where
+        S: Serialize,
+        T: DeserializeOwned,
+    {
+        self.call_with(name, args, CallOptions::default()).await
+    }
+
+    /// Invoke contract method via `eth_call` with [`CallOptions`], e.g. on a history block.
+    pub async fn call_with<S, T>(
+        &self,
+        name: &str,
+        args: &S,
+        mut ops: CallOptions,
+    ) -> anyhow::Result<T>
+    where
+        S: Serialize,
+        T: DeserializeOwned,
+    {
+        if ops.block.is_none() {
+            ops.block = self.block.clone();
+        }
+
+        let signature = self.function(name)?.signature();
+
+        let result = self
+            .client
+            .eth_call_with(&signature, &self.address, crate::to_abi(args)?, ops)
+            .await?;
+
+        Ok(crate::from_abi(result)?)
+    }
+
+    /// Send transaction to invoke contract method, `args` is the tuple of function parameters.
+    pub async fn send<S>(
+        &self,
+        name: &str,
+        args: &S,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter>
+    where
+        S: Serialize,
+    {
+        let signature = self.function(name)?.signature();
+
+        self.client
+            .send_raw_transaction(&signature, &self.address, crate::to_abi(args)?, ops)
+            .await
+    }
+
+    /// Decode event `log` data.
+    ///
+    /// `T` is the tuple of non-indexed event parameters,
+    /// indexed parameters can be read from `log.topics[1..]`.
+    pub fn decode_event<T>(&self, name: &str, log: &Log) -> anyhow::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.check_event(name, log)?;
+
+        Ok(crate::from_abi(log.data.0.clone())?)
+    }
+
+    /// Check if `log` is emitted by event `name`, aka `log.topics[0]` is the event signature hash.
+    pub fn check_event(&self, name: &str, log: &Log) -> anyhow::Result<&EventFragment> {
+        let event = self.event(name)?;
+
+        if !event.anonymous {
+            let topic: H256 = event.topic().into();
+
+            if log.topics.first() != Some(&topic) {
+                return Err(ContractError::EventMismatch(event.signature()).into());
+            }
+        }
+
+        Ok(event)
+    }
+}
+
+/// Decode indexed event parameter from `log.topics[index]`, `topics[0]` is the event signature hash.
+pub fn decode_topic<T>(log: &Log, index: usize) -> anyhow::Result<T>
+where
+    T: DeserializeOwned,
+{
+    let topic = log
+        .topics
+        .get(index)
+        .ok_or(ContractError::TopicNotFound(index))?;
+
+    Ok(crate::from_abi(topic.0.to_vec())?)
+}
+
+/// Encode contract deploy data from hex `bytecode`, human-readable `abi` fragments and constructor `args`.
+///
+/// `args` is the tuple of constructor parameters, contract without `constructor` fragment
+/// in `abi` is treated as having no constructor parameters.
+///
+/// ```ignore
+/// let deploy_data = encode_constructor(
+///     bytecode,
+///     &["constructor(string name, uint8 decimals)"],
+///     &("USDT".to_owned(), 6u8),
+/// )?;
+///
+/// let address = client.deploy("USDT", deploy_data, SendOptions::default()).await?;
+/// ```
+pub fn encode_constructor<S, A>(bytecode: &str, abi: &[S], args: &A) -> anyhow::Result<Vec<u8>>
+where
+    S: AsRef<str>,
+    A: Serialize,
+{
+    let constructor = match abi
+        .iter()
+        .find(|fragment| fragment.as_ref().trim().starts_with("constructor"))
+    {
+        Some(fragment) => fragment.as_ref().parse()?,
+        None => ConstructorFragment::default(),
+    };
+
+    let bytecode = Vec::<u8>::from_eth_hex(bytecode)?;
+
+    serde_ethabi::encode_constructor(&bytecode, &constructor, args)
+}
+
+/// Encode contract deploy data from hex `bytecode`, abi json array and constructor `args`,
+/// see [`encode_constructor`].
+pub fn encode_constructor_json<A>(
+    bytecode: &str,
+    abi_json: &str,
+    args: &A,
+) -> anyhow::Result<Vec<u8>>
+where
+    A: Serialize,
+{
+    let abi: Value = serde_json::from_str(abi_json)?;
+
+    let items = abi
+        .as_array()
+        .ok_or_else(|| ContractError::AbiJson(abi.to_string()))?;
+
+    let mut fragments = vec![];
+
+    if let Some(constructor) = items
+        .iter()
+        .find(|c| c.get("type").and_then(|c| c.as_str()) == Some("constructor"))
+    {
+        let mut inputs = vec![];
+
+        if let Some(params) = constructor.get("inputs").and_then(|c| c.as_array()) {
+            for param in params {
+                inputs.push(param_type(param)?);
+            }
+        }
+
+        fragments.push(format!("constructor({})", inputs.join(",")));
+    }
+
+    encode_constructor(bytecode, &fragments, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpc_rs::ErrorCode;
+    use serde_json::json;
+
+    use super::*;
+
+    fn rpc_error(message: &str, data: Option<Value>) -> anyhow::Error {
+        RPCError {
+            code: ErrorCode::InternalError,
+            message: message.to_owned(),
+            data,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_classify() {
+        let reason = "0x08c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000001a4e6f7420656e6f7567682045746865722070726f76696465642e000000000000";
+
+        let cases = vec![
+            // hardhat, `require(msg.value >= price, "Not enough Ether provided.")`
+            (
+                rpc_error(
+                    "Error: VM Exception while processing transaction: reverted with reason string 'Not enough Ether provided.'",
+                    Some(json!({
+                        "message": "Error: VM Exception while processing transaction: reverted with reason string 'Not enough Ether provided.'",
+                        "data": reason,
+                    })),
+                ),
+                "Revert",
+            ),
+            // geth, custom error `ERC20InsufficientBalance(address,uint256,uint256)`
+            (
+                rpc_error(
+                    "execution reverted",
+                    Some(json!("0xe450d38c000000000000000000000000cd2a3d9f938e13cd947ec05abc7fe734df8dd82600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001")),
+                ),
+                "Revert",
+            ),
+            // hardhat, `revert()` without data
+            (
+                rpc_error("Error: Transaction reverted without a reason string", None),
+                "Revert",
+            ),
+            // geth, eth_estimateGas
+            (
+                rpc_error("gas required exceeds allowance (30000000)", None),
+                "OutOfGas",
+            ),
+            // geth, eth_sendRawTransaction
+            (rpc_error("intrinsic gas too low", None), "OutOfGas"),
+            // hardhat, gas limit lower than execution cost
+            (rpc_error("Transaction ran out of gas", None), "OutOfGas"),
+            // provider request timeout
+            (
+                rpc_error("Timeout: future not completed within 30s", None),
+                "Timeout",
+            ),
+            (
+                rpc_error("nonce too low: next nonce 3, tx nonce 2", None),
+                "Rejected",
+            ),
+            (
+                rpc_error("insufficient funds for gas * price + value", None),
+                "Rejected",
+            ),
+            (
+                anyhow::anyhow!("error sending request: connection refused"),
+                "ProviderError",
+            ),
+            (
+                ContractError::FunctionNotFound("transfer".to_owned()).into(),
+                "FunctionNotFound",
+            ),
+        ];
+
+        for (err, expect) in cases {
+            let message = err.to_string();
+
+            let variant = match ContractError::classify(err) {
+                ContractError::Revert { .. } => "Revert",
+                ContractError::OutOfGas { .. } => "OutOfGas",
+                ContractError::Timeout { .. } => "Timeout",
+                ContractError::Rejected { .. } => "Rejected",
+                ContractError::ProviderError(_) => "ProviderError",
+                ContractError::FunctionNotFound(_) => "FunctionNotFound",
+                err => panic!("unexpected {}", err),
+            };
+
+            assert_eq!(variant, expect, "{}", message);
+        }
+
+        match ContractError::classify(rpc_error("execution reverted", Some(json!(reason)))) {
+            ContractError::Revert {
+                selector, decoded, ..
+            } => {
+                assert_eq!(selector, Some([0x08, 0xc3, 0x79, 0xa0]));
+                assert_eq!(decoded.as_deref(), Some("Not enough Ether provided."));
+            }
+            err => panic!("unexpected {}", err),
+        }
+
+        let hash = H256::default();
+
+        let err = ContractError::classify(rpc_error("intrinsic gas too low", None))
+            .with_tx_hash(hash.clone());
+
+        assert_eq!(err.tx_hash(), Some(&hash));
+    }
+
+    #[test]
+    fn test_encode_constructor() {
+        // bytecode, offset of `name`, `decimals`, length of `name` and padded `name`
+        let mut expected = vec![0x60, 0x80];
+
+        for word in [0x40u8, 6, 4] {
+            expected.extend([0u8; 31]);
+            expected.push(word);
+        }
+
+        expected.extend(b"USDT");
+        expected.extend([0u8; 28]);
+
+        let args = ("USDT".to_owned(), 6u8);
+
+        let deploy_data = encode_constructor(
+            "0x6080",
+            &[
+                "function decimals() view returns (uint8)",
+                "constructor(string memory name, uint8 decimals)",
+            ],
+            &args,
+        )
+        .unwrap();
+
+        assert_eq!(deploy_data, expected);
+
+        let abi_json = json!([
+            { "type": "function", "name": "decimals", "inputs": [], "outputs": [{ "name": "", "type": "uint8" }], "stateMutability": "view" },
+            { "type": "constructor", "inputs": [{ "name": "name", "type": "string" }, { "name": "decimals", "type": "uint8" }], "stateMutability": "nonpayable" }
+        ])
+        .to_string();
+
+        assert_eq!(
+            encode_constructor_json("0x6080", &abi_json, &args).unwrap(),
+            expected
+        );
+
+        // missing `decimals`
+        let err = encode_constructor_json("0x6080", &abi_json, &("USDT".to_owned(),))
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("constructor(string,uint8)"), "{}", err);
+
+        // contract without constructor
+        let abi_json = json!([
+            { "type": "function", "name": "decimals", "inputs": [], "outputs": [{ "name": "", "type": "uint8" }], "stateMutability": "view" }
+        ])
+        .to_string();
+
+        assert_eq!(
+            encode_constructor_json("0x6080", &abi_json, &()).unwrap(),
+            vec![0x60, 0x80]
+        );
+        assert_eq!(
+            encode_constructor::<&str, _>("0x6080", &[], &()).unwrap(),
+            vec![0x60, 0x80]
+        );
+
+        let err = encode_constructor_json("0x6080", &abi_json, &(1u8,))
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("constructor()"), "{}", err);
+
+        assert!(matches!(
+            encode_constructor_json("0x6080", "{}", &())
+                .unwrap_err()
+                .downcast_ref::<ContractError>(),
+            Some(ContractError::AbiJson(_))
+        ));
+    }
+}
@@ -0,0 +1,52 @@
+//! Prebuilt contract bindings for common token standards.
+
+mod erc20;
+pub use erc20::*;
+mod erc721;
+pub use erc721::*;
+mod erc1155;
+pub use erc1155::*;
+
+#[cfg(test)]
+mod tests {
+    use ethers_signer::wallet::WalletSigner;
+    use ethers_wallet::wallet::Wallet;
+    use jsonrpc_rs::{ErrorCode, RPCError};
+
+    use crate::{providers::providers::mock::MockProvider, Client};
+
+    /// Returns client of `mock` signing with hardhat account #0 on chain 1.
+    pub(super) fn mock_client(mock: &MockProvider) -> Client {
+        let signer =
+            Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap()
+                .try_into_signer()
+                .unwrap();
+
+        Client::from((mock.provider(), signer)).with_chain_id(1u64)
+    }
+
+    /// Fail `eth_estimateGas` to stop sending the next transaction after its calldata is built.
+    pub(super) fn fail_send(mock: &MockProvider) {
+        mock.push_error(
+            "eth_estimateGas",
+            RPCError {
+                code: ErrorCode::InternalError,
+                message: "execution reverted".to_owned(),
+                data: None,
+            },
+        );
+    }
+
+    /// Returns tx `data` of the last `method` request received by `mock`.
+    pub(super) fn calldata(mock: &MockProvider, method: &str) -> String {
+        let (_, params) = mock
+            .requests()
+            .into_iter()
+            .rev()
+            .find(|(name, _)| name == method)
+            .expect("request");
+
+        params[0]["data"].as_str().expect("tx data").to_owned()
+    }
+}
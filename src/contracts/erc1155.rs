@@ -0,0 +1,265 @@
+use crate::{
+    decode_topic, Address, Bytes, Client, Contract, DefaultTransactionReceipter, Log, SendOptions,
+    U256,
+};
+
+/// ERC-1155 multi token human-readable abi, including metadata uri extension.
+pub const ERC1155_ABI: &[&str] = &[
+    "function uri(uint256 id) view returns (string)",
+    "function balanceOf(address account, uint256 id) view returns (uint256)",
+    "function balanceOfBatch(address[] accounts, uint256[] ids) view returns (uint256[])",
+    "function isApprovedForAll(address account, address operator) view returns (bool)",
+    "function setApprovalForAll(address operator, bool approved)",
+    "function safeTransferFrom(address from, address to, uint256 id, uint256 amount, bytes data)",
+    "function safeBatchTransferFrom(address from, address to, uint256[] ids, uint256[] amounts, bytes data)",
+    "event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value)",
+    "event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values)",
+    "event ApprovalForAll(address indexed account, address indexed operator, bool approved)",
+    "event URI(string value, uint256 indexed id)",
+];
+
+/// ERC-1155 `TransferSingle` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ERC1155TransferSingle {
+    pub operator: Address,
+    pub from: Address,
+    pub to: Address,
+    pub id: U256,
+    pub value: U256,
+}
+
+/// ERC-1155 `TransferBatch` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ERC1155TransferBatch {
+    pub operator: Address,
+    pub from: Address,
+    pub to: Address,
+    pub ids: Vec<U256>,
+    pub values: Vec<U256>,
+}
+
+/// ERC-1155 `ApprovalForAll` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ERC1155ApprovalForAll {
+    pub account: Address,
+    pub operator: Address,
+    pub approved: bool,
+}
+
+/// ERC-1155 `URI` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ERC1155URI {
+    pub value: String,
+    pub id: U256,
+}
+
+/// ERC-1155 multi token contract binding.
+#[derive(Clone)]
+pub struct ERC1155(pub Contract);
+
+impl ERC1155 {
+    /// Create ERC-1155 binding for token deployed at `address`.
+    pub fn new<A>(address: A, client: Client) -> anyhow::Result<Self>
+    where
+        A: TryInto<Address>,
+        A::Error: std::error::Error + Sync + Send + 'static,
+    {
+        Ok(Self(Contract::new_dyn(address, ERC1155_ABI, client)?))
+    }
+
+    /// Token contract address.
+    pub fn address(&self) -> &Address {
+        &self.0.address
+    }
+
+    pub async fn uri(&self, id: U256) -> anyhow::Result<String> {
+        let (uri,) = self.0.call("uri", &(id,)).await?;
+
+        Ok(uri)
+    }
+
+    pub async fn balance_of(&self, account: Address, id: U256) -> anyhow::Result<U256> {
+        let (balance,) = self.0.call("balanceOf", &(account, id)).await?;
+
+        Ok(balance)
+    }
+
+    pub async fn balance_of_batch(
+        &self,
+        accounts: Vec<Address>,
+        ids: Vec<U256>,
+    ) -> anyhow::Result<Vec<U256>> {
+        let (balances,) = self.0.call("balanceOfBatch", &(accounts, ids)).await?;
+
+        Ok(balances)
+    }
+
+    pub async fn is_approved_for_all(
+        &self,
+        account: Address,
+        operator: Address,
+    ) -> anyhow::Result<bool> {
+        let (approved,) = self
+            .0
+            .call("isApprovedForAll", &(account, operator))
+            .await?;
+
+        Ok(approved)
+    }
+
+    pub async fn set_approval_for_all(
+        &self,
+        operator: Address,
+        approved: bool,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        self.0
+            .send("setApprovalForAll", &(operator, approved), ops)
+            .await
+    }
+
+    pub async fn safe_transfer_from(
+        &self,
+        from: Address,
+        to: Address,
+        id: U256,
+        amount: U256,
+        data: Bytes,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        self.0
+            .send("safeTransferFrom", &(from, to, id, amount, data), ops)
+            .await
+    }
+
+    pub async fn safe_batch_transfer_from(
+        &self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        amounts: Vec<U256>,
+        data: Bytes,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        self.0
+            .send(
+                "safeBatchTransferFrom",
+                &(from, to, ids, amounts, data),
+                ops,
+            )
+            .await
+    }
+
+    /// Decode `TransferSingle` event from `log`.
+    pub fn decode_transfer_single(&self, log: &Log) -> anyhow::Result<ERC1155TransferSingle> {
+        let (id, value) = self.0.decode_event("TransferSingle", log)?;
+
+        Ok(ERC1155TransferSingle {
+            operator: decode_topic(log, 1)?,
+            from: decode_topic(log, 2)?,
+            to: decode_topic(log, 3)?,
+            id,
+            value,
+        })
+    }
+
+    /// Decode `TransferBatch` event from `log`.
+    pub fn decode_transfer_batch(&self, log: &Log) -> anyhow::Result<ERC1155TransferBatch> {
+        let (ids, values) = self.0.decode_event("TransferBatch", log)?;
+
+        Ok(ERC1155TransferBatch {
+            operator: decode_topic(log, 1)?,
+            from: decode_topic(log, 2)?,
+            to: decode_topic(log, 3)?,
+            ids,
+            values,
+        })
+    }
+
+    /// Decode `ApprovalForAll` event from `log`.
+    pub fn decode_approval_for_all(&self, log: &Log) -> anyhow::Result<ERC1155ApprovalForAll> {
+        let (approved,) = self.0.decode_event("ApprovalForAll", log)?;
+
+        Ok(ERC1155ApprovalForAll {
+            account: decode_topic(log, 1)?,
+            operator: decode_topic(log, 2)?,
+            approved,
+        })
+    }
+
+    /// Decode `URI` event from `log`.
+    pub fn decode_uri(&self, log: &Log) -> anyhow::Result<ERC1155URI> {
+        let (value,) = self.0.decode_event("URI", log)?;
+
+        Ok(ERC1155URI {
+            value,
+            id: decode_topic(log, 1)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        contracts::tests::*, providers::providers::mock::MockProvider, to_abi, ToEtherHex,
+    };
+
+    use super::*;
+
+    #[async_std::test]
+    async fn test_balance_of_batch() {
+        let mock = MockProvider::new();
+
+        let token = ERC1155::new(
+            "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+            mock_client(&mock),
+        )
+        .unwrap();
+
+        assert_eq!(
+            token.0.function("balanceOfBatch").unwrap().selector(),
+            [0x4e, 0x12, 0x73, 0xf4]
+        );
+
+        let accounts: Vec<Address> = vec![
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+                .try_into()
+                .unwrap(),
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+                .try_into()
+                .unwrap(),
+        ];
+
+        let balances = vec![U256::from(10u64), U256::from(20u64)];
+
+        mock.push(
+            "eth_call",
+            to_abi(&(balances.clone(),)).unwrap().to_eth_hex(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            token
+                .balance_of_batch(accounts, vec![U256::from(1u64), U256::from(2u64)])
+                .await
+                .unwrap(),
+            balances
+        );
+
+        assert_eq!(
+            calldata(&mock, "eth_call"),
+            concat!(
+                "0x4e1273f4",
+                "0000000000000000000000000000000000000000000000000000000000000040",
+                "00000000000000000000000000000000000000000000000000000000000000a0",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "00000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c8",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+            )
+        );
+        assert!(mock.pending().is_empty());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{
+    decode_topic, Address, Client, Contract, DefaultTransactionReceipter, Log, SendOptions, U256,
+};
+
+/// ERC-20 token human-readable abi, including metadata extension.
+pub const ERC20_ABI: &[&str] = &[
+    "function name() view returns (string)",
+    "function symbol() view returns (string)",
+    "function decimals() view returns (uint8)",
+    "function totalSupply() view returns (uint256)",
+    "function balanceOf(address owner) view returns (uint256)",
+    "function allowance(address owner, address spender) view returns (uint256)",
+    "function transfer(address to, uint256 value) returns (bool)",
+    "function approve(address spender, uint256 value) returns (bool)",
+    "function transferFrom(address from, address to, uint256 value) returns (bool)",
+    "event Transfer(address indexed from, address indexed to, uint256 value)",
+    "event Approval(address indexed owner, address indexed spender, uint256 value)",
+];
+
+/// ERC-20 `Transfer` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ERC20Transfer {
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+}
+
+/// ERC-20 `Approval` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ERC20Approval {
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+}
+
+/// ERC-20 token contract binding.
+#[derive(Clone)]
+pub struct ERC20(pub Contract);
+
+impl ERC20 {
+    /// Create ERC-20 binding for token deployed at `address`.
+    pub fn new<A>(address: A, client: Client) -> anyhow::Result<Self>
+    where
+        A: TryInto<Address>,
+        A::Error: std::error::Error + Sync + Send + 'static,
+    {
+        Ok(Self(Contract::new_dyn(address, ERC20_ABI, client)?))
+    }
+
+    /// Token contract address.
+    pub fn address(&self) -> &Address {
+        &self.0.address
+    }
+
+    pub async fn name(&self) -> anyhow::Result<String> {
+        let (name,) = self.0.call("name", &()).await?;
+
+        Ok(name)
+    }
+
+    pub async fn symbol(&self) -> anyhow::Result<String> {
+        let (symbol,) = self.0.call("symbol", &()).await?;
+
+        Ok(symbol)
+    }
+
+    pub async fn decimals(&self) -> anyhow::Result<u8> {
+        let (decimals,) = self.0.call("decimals", &()).await?;
+
+        Ok(decimals)
+    }
+
+    pub async fn total_supply(&self) -> anyhow::Result<U256> {
+        let (total_supply,) = self.0.call("totalSupply", &()).await?;
+
+        Ok(total_supply)
+    }
+
+    pub async fn balance_of(&self, owner: Address) -> anyhow::Result<U256> {
+        let (balance,) = self.0.call("balanceOf", &(owner,)).await?;
+
+        Ok(balance)
+    }
+
+    pub async fn allowance(&self, owner: Address, spender: Address) -> anyhow::Result<U256> {
+        let (allowance,) = self.0.call("allowance", &(owner, spender)).await?;
+
+        Ok(allowance)
+    }
+
+    pub async fn transfer(
+        &self,
+        to: Address,
+        value: U256,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        self.0.send("transfer", &(to, value), ops).await
+    }
+
+    pub async fn approve(
+        &self,
+        spender: Address,
+        value: U256,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        self.0.send("approve", &(spender, value), ops).await
+    }
+
+    pub async fn transfer_from(
+        &self,
+        from: Address,
+        to: Address,
+        value: U256,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        self.0.send("transferFrom", &(from, to, value), ops).await
+    }
+
+    /// Decode `Transfer` event from `log`.
+    pub fn decode_transfer(&self, log: &Log) -> anyhow::Result<ERC20Transfer> {
+        let (value,) = self.0.decode_event("Transfer", log)?;
+
+        Ok(ERC20Transfer {
+            from: decode_topic(log, 1)?,
+            to: decode_topic(log, 2)?,
+            value,
+        })
+    }
+
+    /// Decode `Approval` event from `log`.
+    pub fn decode_approval(&self, log: &Log) -> anyhow::Result<ERC20Approval> {
+        let (value,) = self.0.decode_event("Approval", log)?;
+
+        Ok(ERC20Approval {
+            owner: decode_topic(log, 1)?,
+            spender: decode_topic(log, 2)?,
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{contracts::tests::*, providers::providers::mock::MockProvider};
+
+    use super::*;
+
+    #[async_std::test]
+    async fn test_transfer() {
+        let mock = MockProvider::new();
+
+        let token = ERC20::new(
+            "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+            mock_client(&mock),
+        )
+        .unwrap();
+
+        assert_eq!(
+            token.0.function("transfer").unwrap().selector(),
+            [0xa9, 0x05, 0x9c, 0xbb]
+        );
+
+        let to: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        fail_send(&mock);
+
+        let result = token
+            .transfer(to, U256::from(1000u64), SendOptions::new().nonce(0u64))
+            .await;
+
+        assert!(result.is_err());
+
+        assert_eq!(
+            calldata(&mock, "eth_estimateGas"),
+            concat!(
+                "0xa9059cbb",
+                "00000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c8",
+                "00000000000000000000000000000000000000000000000000000000000003e8",
+            )
+        );
+        assert!(mock.pending().is_empty());
+    }
+}
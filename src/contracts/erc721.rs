@@ -0,0 +1,324 @@
+use crate::{
+    decode_topic, Address, Bytes, Client, Contract, DefaultTransactionReceipter, Log, SendOptions,
+    U256,
+};
+
+/// ERC-721 token human-readable abi, including metadata extension.
+pub const ERC721_ABI: &[&str] = &[
+    "function name() view returns (string)",
+    "function symbol() view returns (string)",
+    "function tokenURI(uint256 tokenId) view returns (string)",
+    "function balanceOf(address owner) view returns (uint256)",
+    "function ownerOf(uint256 tokenId) view returns (address)",
+    "function getApproved(uint256 tokenId) view returns (address)",
+    "function isApprovedForAll(address owner, address operator) view returns (bool)",
+    "function approve(address to, uint256 tokenId)",
+    "function setApprovalForAll(address operator, bool approved)",
+    "function transferFrom(address from, address to, uint256 tokenId)",
+    "function safeTransferFrom(address from, address to, uint256 tokenId)",
+    "function safeTransferFrom(address from, address to, uint256 tokenId, bytes data)",
+    "event Transfer(address indexed from, address indexed to, uint256 indexed tokenId)",
+    "event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId)",
+    "event ApprovalForAll(address indexed owner, address indexed operator, bool approved)",
+];
+
+/// ERC-721 `Transfer` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ERC721Transfer {
+    pub from: Address,
+    pub to: Address,
+    pub token_id: U256,
+}
+
+/// ERC-721 `Approval` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ERC721Approval {
+    pub owner: Address,
+    pub approved: Address,
+    pub token_id: U256,
+}
+
+/// ERC-721 `ApprovalForAll` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ERC721ApprovalForAll {
+    pub owner: Address,
+    pub operator: Address,
+    pub approved: bool,
+}
+
+/// ERC-721 non-fungible token contract binding.
+#[derive(Clone)]
+pub struct ERC721(pub Contract);
+
+impl ERC721 {
+    /// Create ERC-721 binding for token deployed at `address`.
+    pub fn new<A>(address: A, client: Client) -> anyhow::Result<Self>
+    where
+        A: TryInto<Address>,
+        A::Error: std::error::Error + Sync + Send + 'static,
+    {
+        Ok(Self(Contract::new_dyn(address, ERC721_ABI, client)?))
+    }
+
+    /// Token contract address.
+    pub fn address(&self) -> &Address {
+        &self.0.address
+    }
+
+    pub async fn name(&self) -> anyhow::Result<String> {
+        let (name,) = self.0.call("name", &()).await?;
+
+        Ok(name)
+    }
+
+    pub async fn symbol(&self) -> anyhow::Result<String> {
+        let (symbol,) = self.0.call("symbol", &()).await?;
+
+        Ok(symbol)
+    }
+
+    pub async fn token_uri(&self, token_id: U256) -> anyhow::Result<String> {
+        let (uri,) = self.0.call("tokenURI", &(token_id,)).await?;
+
+        Ok(uri)
+    }
+
+    pub async fn balance_of(&self, owner: Address) -> anyhow::Result<U256> {
+        let (balance,) = self.0.call("balanceOf", &(owner,)).await?;
+
+        Ok(balance)
+    }
+
+    pub async fn owner_of(&self, token_id: U256) -> anyhow::Result<Address> {
+        let (owner,) = self.0.call("ownerOf", &(token_id,)).await?;
+
+        Ok(owner)
+    }
+
+    pub async fn get_approved(&self, token_id: U256) -> anyhow::Result<Address> {
+        let (approved,) = self.0.call("getApproved", &(token_id,)).await?;
+
+        Ok(approved)
+    }
+
+    pub async fn is_approved_for_all(
+        &self,
+        owner: Address,
+        operator: Address,
+    ) -> anyhow::Result<bool> {
+        let (approved,) = self.0.call("isApprovedForAll", &(owner, operator)).await?;
+
+        Ok(approved)
+    }
+
+    pub async fn approve(
+        &self,
+        to: Address,
+        token_id: U256,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        self.0.send("approve", &(to, token_id), ops).await
+    }
+
+    pub async fn set_approval_for_all(
+        &self,
+        operator: Address,
+        approved: bool,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        self.0
+            .send("setApprovalForAll", &(operator, approved), ops)
+            .await
+    }
+
+    pub async fn transfer_from(
+        &self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        self.0
+            .send("transferFrom", &(from, to, token_id), ops)
+            .await
+    }
+
+    pub async fn safe_transfer_from(
+        &self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        self.0
+            .send(
+                "safeTransferFrom(address,address,uint256)",
+                &(from, to, token_id),
+                ops,
+            )
+            .await
+    }
+
+    pub async fn safe_transfer_from_with_data(
+        &self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        self.0
+            .send(
+                "safeTransferFrom(address,address,uint256,bytes)",
+                &(from, to, token_id, data),
+                ops,
+            )
+            .await
+    }
+
+    /// Decode `Transfer` event from `log`.
+    pub fn decode_transfer(&self, log: &Log) -> anyhow::Result<ERC721Transfer> {
+        self.0.check_event("Transfer", log)?;
+
+        Ok(ERC721Transfer {
+            from: decode_topic(log, 1)?,
+            to: decode_topic(log, 2)?,
+            token_id: decode_topic(log, 3)?,
+        })
+    }
+
+    /// Decode `Approval` event from `log`.
+    pub fn decode_approval(&self, log: &Log) -> anyhow::Result<ERC721Approval> {
+        self.0.check_event("Approval", log)?;
+
+        Ok(ERC721Approval {
+            owner: decode_topic(log, 1)?,
+            approved: decode_topic(log, 2)?,
+            token_id: decode_topic(log, 3)?,
+        })
+    }
+
+    /// Decode `ApprovalForAll` event from `log`.
+    pub fn decode_approval_for_all(&self, log: &Log) -> anyhow::Result<ERC721ApprovalForAll> {
+        let (approved,) = self.0.decode_event("ApprovalForAll", log)?;
+
+        Ok(ERC721ApprovalForAll {
+            owner: decode_topic(log, 1)?,
+            operator: decode_topic(log, 2)?,
+            approved,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{contracts::tests::*, providers::providers::mock::MockProvider, ContractError};
+
+    use super::*;
+
+    #[async_std::test]
+    async fn test_safe_transfer_from() {
+        let mock = MockProvider::new();
+
+        let token = ERC721::new(
+            "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+            mock_client(&mock),
+        )
+        .unwrap();
+
+        assert_eq!(
+            token
+                .0
+                .function("safeTransferFrom(address,address,uint256)")
+                .unwrap()
+                .selector(),
+            [0x42, 0x84, 0x2e, 0x0e]
+        );
+        assert_eq!(
+            token
+                .0
+                .function("safeTransferFrom(address,address,uint256,bytes)")
+                .unwrap()
+                .selector(),
+            [0xb8, 0x8d, 0x4f, 0xde]
+        );
+
+        // Bare name of overloaded function is ambiguous.
+        match token
+            .0
+            .function("safeTransferFrom")
+            .unwrap_err()
+            .downcast_ref::<ContractError>()
+        {
+            Some(ContractError::AmbiguousFunction(name, overloads)) => {
+                assert_eq!(name, "safeTransferFrom");
+                assert_eq!(
+                    overloads,
+                    &[
+                        "safeTransferFrom(address,address,uint256)",
+                        "safeTransferFrom(address,address,uint256,bytes)"
+                    ]
+                );
+            }
+            err => panic!("unexpected {:?}", err),
+        }
+
+        let from: Address = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+            .try_into()
+            .unwrap();
+        let to: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        fail_send(&mock);
+
+        let result = token
+            .safe_transfer_from(
+                from.clone(),
+                to.clone(),
+                U256::from(1u64),
+                SendOptions::new().nonce(0u64),
+            )
+            .await;
+
+        assert!(result.is_err());
+
+        assert_eq!(
+            calldata(&mock, "eth_estimateGas"),
+            concat!(
+                "0x42842e0e",
+                "000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "00000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c8",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+            )
+        );
+
+        fail_send(&mock);
+
+        let result = token
+            .safe_transfer_from_with_data(
+                from,
+                to,
+                U256::from(1u64),
+                Bytes::from(vec![0xbe, 0xef]),
+                SendOptions::new().nonce(0u64),
+            )
+            .await;
+
+        assert!(result.is_err());
+
+        assert_eq!(
+            calldata(&mock, "eth_estimateGas"),
+            concat!(
+                "0xb88d4fde",
+                "000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "00000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c8",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "0000000000000000000000000000000000000000000000000000000000000080",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "beef000000000000000000000000000000000000000000000000000000000000",
+            )
+        );
+        assert!(mock.pending().is_empty());
+    }
+}
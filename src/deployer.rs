@@ -0,0 +1,187 @@
+use crate::{
+    keccak256, Address, BlockNumberOrTag, Client, ClientError, Contract, FromEtherHex, SendOptions,
+    Status, H256,
+};
+
+/// Address of the deterministic deployment proxy, deployed at the same address on most evm chains,
+/// see <https://github.com/Arachnid/deterministic-deployment-proxy>.
+pub const DEFAULT_CREATE2_FACTORY: &str = "0x4e59b44847b379578588920ca78fbf26c0b4956c";
+
+/// Deterministic deployment errors
+#[derive(Debug, thiserror::Error)]
+pub enum DeployerError {
+    /// No code at create2 factory address
+    #[error("FactoryNotDeployed: no code at create2 factory {0}")]
+    FactoryNotDeployed(Address),
+    /// Deployment tx succeeded but no code at predicted address
+    #[error("DeployFailed: no code at predicted address {0} after tx {1}")]
+    DeployFailed(Address, H256),
+}
+
+/// Result of [`Deployer::deploy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deployment {
+    /// Deterministic contract address.
+    pub address: Address,
+    /// Deployment tx hash, [`None`] if contract was already deployed.
+    pub tx_hash: Option<H256>,
+}
+
+impl Deployment {
+    /// Returns true if contract was deployed by this call.
+    pub fn deployed(&self) -> bool {
+        self.tx_hash.is_some()
+    }
+}
+
+/// Deploy contracts through a `CREATE2` factory, contract addresses only depend on factory address,
+/// salt and contract init code, and are the same across chains.
+///
+/// The factory is called with `salt ++ init_code` as tx data, see [`DEFAULT_CREATE2_FACTORY`].
+///
+/// ```ignore
+/// let deployer = Deployer::new(client);
+///
+/// let salt = Deployer::salt("my-token-v1");
+///
+/// let deployment = deployer
+///     .deploy("MyToken", &salt, init_code, SendOptions::default())
+///     .await?;
+/// ```
+#[derive(Clone)]
+pub struct Deployer {
+    /// Client to send deployment transactions.
+    pub client: Client,
+    /// Create2 factory address.
+    pub factory: Address,
+}
+
+impl Deployer {
+    /// Create deployer using [`DEFAULT_CREATE2_FACTORY`].
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            factory: DEFAULT_CREATE2_FACTORY
+                .try_into()
+                .expect("Parse default create2 factory address"),
+        }
+    }
+
+    /// Use create2 factory deployed at `factory` instead of [`DEFAULT_CREATE2_FACTORY`].
+    pub fn factory<A>(mut self, factory: A) -> anyhow::Result<Self>
+    where
+        A: TryInto<Address>,
+        A::Error: std::error::Error + Sync + Send + 'static,
+    {
+        self.factory = factory.try_into()?;
+
+        Ok(self)
+    }
+
+    /// Derive salt from human-readable `label`, aka keccak256 hash of `label`.
+    pub fn salt(label: &str) -> H256 {
+        keccak256(label).into()
+    }
+
+    /// Build contract init code from hex `deploy_data` and abi encoded constructor `call_data`.
+    pub fn init_code(deploy_data: &str, mut call_data: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        let mut buff = Vec::<u8>::from_eth_hex(deploy_data)?;
+
+        buff.append(&mut call_data);
+
+        Ok(buff)
+    }
+
+    /// Returns address of contract deployed with `salt` and `init_code`.
+    pub fn predict_address(&self, salt: &H256, init_code: &[u8]) -> Address {
+        self.factory.create2(salt, init_code)
+    }
+
+    /// Returns true if code exists at `address`.
+    pub async fn is_deployed(&self, address: Address) -> anyhow::Result<bool> {
+        let code = self
+            .client
+            .provider
+            .clone()
+            .eth_get_code(address, None::<BlockNumberOrTag>)
+            .await?;
+
+        Ok(!code.0.is_empty())
+    }
+
+    /// Deploy contract `name` with `salt` and `init_code`.
+    ///
+    /// Deployment is skipped if code already exists at the predicted address.
+    pub async fn deploy(
+        &self,
+        name: &str,
+        salt: &H256,
+        init_code: Vec<u8>,
+        ops: SendOptions,
+    ) -> anyhow::Result<Deployment> {
+        let address = self.predict_address(salt, &init_code);
+
+        if self.is_deployed(address).await? {
+            log::debug!(target: name, "Skip deployment, code exists at {}", address);
+
+            return Ok(Deployment {
+                address,
+                tx_hash: None,
+            });
+        }
+
+        if !self.is_deployed(self.factory).await? {
+            return Err(DeployerError::FactoryNotDeployed(self.factory).into());
+        }
+
+        let mut call_data = salt.0.to_vec();
+
+        call_data.extend_from_slice(&init_code);
+
+        // Factory call data has no function selector.
+        let tx_hash = self
+            .client
+            ._send_raw_transaction(name, Some(&self.factory), call_data, ops, false)
+            .await?;
+
+        let receipt = self
+            .client
+            .provider
+            .register_transaction_listener(tx_hash.clone())?
+            .wait()
+            .await?;
+
+        if !matches!(receipt.status, Some(Status::Success)) {
+            return Err(ClientError::TxFailure(tx_hash).into());
+        }
+
+        if !self.is_deployed(address).await? {
+            return Err(DeployerError::DeployFailed(address, tx_hash).into());
+        }
+
+        log::debug!(target: name, "Deployed at {}, tx {}", address, tx_hash);
+
+        Ok(Deployment {
+            address,
+            tx_hash: Some(tx_hash),
+        })
+    }
+
+    /// Deploy contract `name` and returns dynamic contract instance with human-readable abi `fragments`,
+    /// see [`deploy`](Self::deploy) and [`Contract::new_dyn`].
+    pub async fn deploy_dyn<S>(
+        &self,
+        name: &str,
+        salt: &H256,
+        init_code: Vec<u8>,
+        fragments: &[S],
+        ops: SendOptions,
+    ) -> anyhow::Result<Contract>
+    where
+        S: AsRef<str>,
+    {
+        let deployment = self.deploy(name, salt, init_code, ops).await?;
+
+        Contract::new_dyn(deployment.address, fragments, self.client.clone())
+    }
+}
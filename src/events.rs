@@ -0,0 +1,332 @@
+use serde::de::DeserializeOwned;
+use serde_ethabi::{EventFragment, Fragment};
+use serde_json::Value;
+
+use crate::{Address, Log, TransactionReceipt};
+
+/// Contract event errors
+#[derive(Debug, thiserror::Error)]
+pub enum EventError {
+    /// Invalid event abi json
+    #[error("AbiJson: {0}")]
+    AbiJson(String),
+}
+
+/// Contract event type which can be decoded from log.
+///
+/// Contract bindings generate `{Contract}Events` enum of all contract events, use
+/// [`contract_event!`](crate::contract_event) to implement it for single macro generated event types.
+pub trait ContractEvent: Sized {
+    /// Try decode event from `log`, returns [`None`] if `log` is not emitted by this event.
+    fn decode_log(log: &Log) -> anyhow::Result<Option<Self>>;
+}
+
+/// Convert abi json param type to canonical type, expanding `tuple` with `components`.
+pub(crate) fn param_type(param: &Value) -> anyhow::Result<String> {
+    let kind = param
+        .get("type")
+        .and_then(|c| c.as_str())
+        .ok_or_else(|| EventError::AbiJson(param.to_string()))?;
+
+    if let Some(suffix) = kind.strip_prefix("tuple") {
+        let components = param
+            .get("components")
+            .and_then(|c| c.as_array())
+            .ok_or_else(|| EventError::AbiJson(param.to_string()))?
+            .iter()
+            .map(param_type)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(format!("({}){}", components.join(","), suffix))
+    } else {
+        Ok(kind.to_owned())
+    }
+}
+
+/// Parse event abi json, e.g. `{"type":"event","name":"Withdrawal","inputs":[...],"anonymous":false}`.
+pub fn event_fragment_from_json<S: AsRef<str>>(json: S) -> anyhow::Result<EventFragment> {
+    let value: Value = serde_json::from_str(json.as_ref())?;
+
+    let name = value
+        .get("name")
+        .and_then(|c| c.as_str())
+        .ok_or_else(|| EventError::AbiJson(value.to_string()))?;
+
+    let mut inputs = vec![];
+
+    if let Some(params) = value.get("inputs").and_then(|c| c.as_array()) {
+        for param in params {
+            let mut input = param_type(param)?;
+
+            if param.get("indexed").and_then(|c| c.as_bool()) == Some(true) {
+                input.push_str(" indexed");
+            }
+
+            if let Some(name) = param.get("name").and_then(|c| c.as_str()) {
+                if !name.is_empty() {
+                    input.push(' ');
+                    input.push_str(name);
+                }
+            }
+
+            inputs.push(input);
+        }
+    }
+
+    let anonymous = if value.get("anonymous").and_then(|c| c.as_bool()) == Some(true) {
+        " anonymous"
+    } else {
+        ""
+    };
+
+    match format!("event {}({}){}", name, inputs.join(", "), anonymous).parse::<Fragment>()? {
+        Fragment::Event(event) => Ok(event),
+        _ => Err(EventError::AbiJson(value.to_string()).into()),
+    }
+}
+
+/// Decode `log` with `event` abi, returns [`None`] if `log` is not emitted by `event`.
+///
+/// Anonymous events have no topic0, so any log with the indexed parameters number of topics which
+/// decodes successfully is matched, callers should filter logs by the emitting contract address.
+pub fn decode_log_with<T>(event: &EventFragment, log: &Log) -> anyhow::Result<Option<T>>
+where
+    T: DeserializeOwned,
+{
+    let topics = log.topics.iter().map(|c| c.0).collect::<Vec<_>>();
+
+    if event.anonymous {
+        let indexed = event.inputs.iter().filter(|p| p.indexed).count();
+
+        if topics.len() != indexed {
+            return Ok(None);
+        }
+
+        return Ok(event.decode_log(&topics, &log.data.0).ok());
+    }
+
+    if topics.first() != Some(&event.topic()) {
+        return Ok(None);
+    }
+
+    Ok(Some(event.decode_log(&topics, &log.data.0)?))
+}
+
+/// Implement [`ContractEvent`] for macro generated event types.
+///
+/// ```ignore
+/// hardhat!(Example);
+///
+/// contract_event!(ExampleWithdrawal);
+/// ```
+#[macro_export]
+macro_rules! contract_event {
+    ($($ty: ty),+) => {
+        $(
+            impl $crate::ContractEvent for $ty {
+                fn decode_log(log: &$crate::Log) -> Result<Option<Self>, $crate::Error> {
+                    let event = $crate::event_fragment_from_json(<$ty>::abi_json())?;
+
+                    $crate::decode_log_with(&event, log)
+                }
+            }
+        )+
+    };
+}
+
+/// Decode events from transaction receipt logs.
+///
+/// Returns one result per log emitted by this event type, so a malformed log does not hide the others.
+pub trait DecodedLogs {
+    /// Decode events `E` emitted by the contract which this tx is sent to.
+    fn decoded_logs<E: ContractEvent>(&self) -> Vec<anyhow::Result<E>>;
+
+    /// Decode events `E` emitted by contract `address`.
+    fn decoded_logs_from<E: ContractEvent>(&self, address: &Address) -> Vec<anyhow::Result<E>>;
+}
+
+impl DecodedLogs for TransactionReceipt {
+    fn decoded_logs<E: ContractEvent>(&self) -> Vec<anyhow::Result<E>> {
+        if let Some(address) = self.to.or(self.contract_address) {
+            self.decoded_logs_from(&address)
+        } else {
+            vec![]
+        }
+    }
+
+    fn decoded_logs_from<E: ContractEvent>(&self, address: &Address) -> Vec<anyhow::Result<E>> {
+        self.logs
+            .iter()
+            .filter(|log| log.address == *address)
+            .filter_map(|log| E::decode_log(log).transpose())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::U256;
+
+    const TRANSFER_TOPIC: &str =
+        "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+    const TOKEN: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+
+    fn word(value: u64) -> String {
+        format!("{:064x}", value)
+    }
+
+    fn log_json(address: &str, topics: &[String], data: String) -> serde_json::Value {
+        json!({
+            "removed": false,
+            "logIndex": "0x0",
+            "transactionIndex": "0x0",
+            "transactionHash": "0x8b1b6d2e7a5f5f3a4e0c8d1e4a8c1b5f3e6d8a2c4b6e8f0a1c3e5b7d9f1a3c5e",
+            "blockHash": "0x8b1b6d2e7a5f5f3a4e0c8d1e4a8c1b5f3e6d8a2c4b6e8f0a1c3e5b7d9f1a3c5e",
+            "blockNumber": "0x10",
+            "address": address,
+            "data": format!("0x{}", data),
+            "topics": topics,
+        })
+    }
+
+    /// `Transfer(0x..01, 0x..02, value)` log emitted by `address`.
+    fn transfer_log(address: &str, value: u64) -> serde_json::Value {
+        log_json(
+            address,
+            &[
+                TRANSFER_TOPIC.to_owned(),
+                format!("0x{}", word(1)),
+                format!("0x{}", word(2)),
+            ],
+            word(value),
+        )
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Transfer {
+        from: Address,
+        to: Address,
+        value: U256,
+    }
+
+    struct TransferEvent(Transfer);
+
+    impl ContractEvent for TransferEvent {
+        fn decode_log(log: &Log) -> anyhow::Result<Option<Self>> {
+            let event = event_fragment_from_json(
+                r#"{"type":"event","name":"Transfer","anonymous":false,"inputs":[
+                    {"name":"from","type":"address","indexed":true},
+                    {"name":"to","type":"address","indexed":true},
+                    {"name":"value","type":"uint256","indexed":false}]}"#,
+            )?;
+
+            Ok(decode_log_with(&event, log)?.map(TransferEvent))
+        }
+    }
+
+    #[test]
+    fn test_decode_log_with() {
+        let log: Log = serde_json::from_value(transfer_log(TOKEN, 1000)).unwrap();
+
+        let transfer = TransferEvent::decode_log(&log).unwrap().unwrap().0;
+
+        assert_eq!(
+            transfer.from,
+            Address::try_from(format!("0x{:040x}", 1).as_str()).unwrap()
+        );
+        assert_eq!(
+            transfer.to,
+            Address::try_from(format!("0x{:040x}", 2).as_str()).unwrap()
+        );
+        assert_eq!(transfer.value, U256::from(1000u64));
+
+        // Other topic0
+        let log: Log =
+            serde_json::from_value(log_json(TOKEN, &[format!("0x{}", word(3))], word(1000)))
+                .unwrap();
+
+        assert!(TransferEvent::decode_log(&log).unwrap().is_none());
+
+        // Matched topic0 with truncated data
+        let log: Log = serde_json::from_value(log_json(
+            TOKEN,
+            &[TRANSFER_TOPIC.to_owned(), format!("0x{}", word(1))],
+            word(1000),
+        ))
+        .unwrap();
+
+        assert!(TransferEvent::decode_log(&log).is_err());
+    }
+
+    #[test]
+    fn test_decode_anonymous() {
+        let event = event_fragment_from_json(
+            r#"{"type":"event","name":"Anon","anonymous":true,"inputs":[
+                {"name":"id","type":"uint256","indexed":true},
+                {"name":"value","type":"uint256","indexed":false}]}"#,
+        )
+        .unwrap();
+
+        let log: Log =
+            serde_json::from_value(log_json(TOKEN, &[format!("0x{}", word(7))], word(1000)))
+                .unwrap();
+
+        assert_eq!(
+            decode_log_with::<(U256, U256)>(&event, &log).unwrap(),
+            Some((U256::from(7u64), U256::from(1000u64)))
+        );
+
+        // Logs of other events are not matched by topics number or decode failure.
+        let log: Log = serde_json::from_value(transfer_log(TOKEN, 1000)).unwrap();
+
+        assert_eq!(decode_log_with::<(U256, U256)>(&event, &log).unwrap(), None);
+
+        let log: Log =
+            serde_json::from_value(log_json(TOKEN, &[format!("0x{}", word(7))], String::new()))
+                .unwrap();
+
+        assert_eq!(decode_log_with::<(U256, U256)>(&event, &log).unwrap(), None);
+    }
+
+    #[test]
+    fn test_decoded_logs() {
+        let malformed = log_json(
+            TOKEN,
+            &[TRANSFER_TOPIC.to_owned(), format!("0x{}", word(1))],
+            word(1000),
+        );
+
+        let receipt: TransactionReceipt = serde_json::from_value(json!({
+            "from": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "to": TOKEN,
+            "contractAddress": null,
+            "gasUsed": "0x5208",
+            "cumulativeGasUsed": "0x5208",
+            "effectiveGasPrice": "0x1",
+            "transactionIndex": "0x0",
+            "blockHash": "0x8b1b6d2e7a5f5f3a4e0c8d1e4a8c1b5f3e6d8a2c4b6e8f0a1c3e5b7d9f1a3c5e",
+            "blockNumber": "0x10",
+            "status": "0x1",
+            "logs": [
+                transfer_log(TOKEN, 1),
+                transfer_log("0x70997970C51812dc3A010C7d01b50e0d17dc79C8", 2),
+                malformed,
+                transfer_log(TOKEN, 3),
+            ],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+        }))
+        .unwrap();
+
+        let events = receipt.decoded_logs::<TransferEvent>();
+
+        // Logs of other contracts are skipped, the malformed log fails alone.
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].as_ref().unwrap().0.value, U256::from(1u64));
+        assert!(events[1].is_err());
+        assert_eq!(events[2].as_ref().unwrap().0.value, U256::from(3u64));
+    }
+}
@@ -1,4 +1,5 @@
 pub use ethers_eip2718 as eip2718;
+pub use ethers_eip712 as eip712;
 pub use ethers_hardhat as hardhat;
 pub use ethers_macros as macros;
 pub use ethers_primitives as primitives;
@@ -8,3 +9,26 @@ pub use ethers_wallet as wallet;
 
 mod runtime;
 pub use runtime::*;
+
+mod contract;
+pub use contract::*;
+
+mod proxy;
+pub use proxy::*;
+
+mod storage;
+pub use storage::*;
+
+mod events;
+pub use events::*;
+
+mod deployer;
+pub use deployer::*;
+
+mod linker;
+pub use linker::*;
+
+mod siwe;
+pub use siwe::*;
+
+pub mod contracts;
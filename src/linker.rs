@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{keccak256, Address, Eip55};
+
+/// Library linking errors
+#[derive(Debug, thiserror::Error)]
+pub enum LinkError {
+    /// Bytecode still contains library placeholders after linking
+    #[error("UnlinkedLibraries: unresolved library placeholders {0:?}")]
+    Unlinked(Vec<String>),
+}
+
+/// Library placeholder length in hex bytecode, aka length of a hex address.
+const PLACEHOLDER_LEN: usize = 40;
+
+/// Placeholder position of artifact `linkReferences`, in bytes of bytecode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkOffset {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// Artifact `linkReferences`, source name -> library name -> placeholder positions.
+///
+/// ```ignore
+/// let references: LinkReferences = serde_json::from_value(artifact["linkReferences"].clone())?;
+/// ```
+pub type LinkReferences = HashMap<String, HashMap<String, Vec<LinkOffset>>>;
+
+/// Returns solc (>=0.5) library placeholder of `fully_qualified_name`, e.g. `contracts/Math.sol:Math`.
+///
+/// The placeholder is `__$` + first 34 hex chars of keccak256 hash of the name + `$__`.
+pub fn library_placeholder(fully_qualified_name: &str) -> String {
+    let hash = keccak256(fully_qualified_name)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+
+    format!("__${}$__", &hash[..34])
+}
+
+/// Returns legacy solc (<0.5) library placeholder of `name`, the name truncated to 36 chars and padded with `_`.
+fn legacy_library_placeholder(name: &str) -> String {
+    let name = name.chars().take(PLACEHOLDER_LEN - 4).collect::<String>();
+
+    format!("__{:_<width$}", name, width = PLACEHOLDER_LEN - 2)
+}
+
+/// Replace library placeholders of hex `bytecode` with addresses of `libraries`.
+///
+/// Library names must be fully qualified, e.g. `contracts/Math.sol:Math`, use
+/// [`link_bytecode_with`] to link bare names, e.g. `Math`.
+///
+/// Returns [`LinkError::Unlinked`] if any placeholder remains after linking.
+pub fn link_bytecode(
+    bytecode: &str,
+    libraries: &HashMap<String, Address>,
+) -> anyhow::Result<String> {
+    link_bytecode_with(bytecode, &LinkReferences::default(), libraries)
+}
+
+/// Replace library placeholders of hex `bytecode` with addresses of `libraries`, bare library
+/// names are resolved to fully qualified names by artifact `link_references`.
+///
+/// Returns [`LinkError::Unlinked`] if any placeholder remains after linking.
+pub fn link_bytecode_with(
+    bytecode: &str,
+    link_references: &LinkReferences,
+    libraries: &HashMap<String, Address>,
+) -> anyhow::Result<String> {
+    let mut bytecode = bytecode.to_owned();
+
+    let mut replace = |name: &str, address: &Address| {
+        let address = address.to_checksum_string();
+
+        let address = address.trim_start_matches("0x");
+
+        bytecode = bytecode
+            .replace(&library_placeholder(name), address)
+            .replace(&legacy_library_placeholder(name), address);
+    };
+
+    for (name, address) in libraries {
+        replace(name, address);
+    }
+
+    for (source_name, references) in link_references {
+        for name in references.keys() {
+            if let Some(address) = libraries.get(name) {
+                replace(&format!("{}:{}", source_name, name), address);
+            }
+        }
+    }
+
+    let unlinked = unlinked_placeholders(&bytecode);
+
+    if !unlinked.is_empty() {
+        return Err(LinkError::Unlinked(unlinked).into());
+    }
+
+    Ok(bytecode)
+}
+
+/// Returns distinct library placeholders remaining in hex `bytecode`.
+pub fn unlinked_placeholders(bytecode: &str) -> Vec<String> {
+    let mut placeholders: Vec<String> = vec![];
+
+    let mut remaining = bytecode;
+
+    while let Some(index) = remaining.find("__") {
+        let end = (index + PLACEHOLDER_LEN).min(remaining.len());
+
+        let placeholder = &remaining[index..end];
+
+        if !placeholders.iter().any(|c| c == placeholder) {
+            placeholders.push(placeholder.to_owned());
+        }
+
+        remaining = &remaining[end..];
+    }
+
+    placeholders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn references() -> LinkReferences {
+        serde_json::from_str(
+            r#"{ "contracts/lib/Math.sol": { "Math": [{ "length": 20, "start": 5 }] } }"#,
+        )
+        .unwrap()
+    }
+
+    fn bytecode() -> String {
+        format!(
+            "0x6080604052{}6000",
+            library_placeholder("contracts/lib/Math.sol:Math")
+        )
+    }
+
+    #[test]
+    fn test_library_placeholder() {
+        // Example of solc docs, library `BigInt` in `libraries/bigint.sol`.
+        assert_eq!(
+            library_placeholder("libraries/bigint.sol:BigInt"),
+            "__$30bbc0abd4d6364515865950d3e0d10953$__"
+        );
+
+        assert_eq!(
+            legacy_library_placeholder("Math"),
+            format!("__Math{}", "_".repeat(34))
+        );
+
+        // Truncated by chars instead of bytes.
+        assert_eq!(
+            legacy_library_placeholder(&"数".repeat(40)).chars().count(),
+            PLACEHOLDER_LEN
+        );
+    }
+
+    #[test]
+    fn test_link_bytecode() {
+        let address = Address([0x11; 20]);
+
+        let libraries = HashMap::from([("Math".to_owned(), address.clone())]);
+
+        let linked = link_bytecode_with(&bytecode(), &references(), &libraries).unwrap();
+
+        assert_eq!(linked, format!("0x6080604052{}6000", "11".repeat(20)));
+
+        let libraries =
+            HashMap::from([("contracts/lib/Math.sol:Math".to_owned(), address.clone())]);
+
+        assert_eq!(link_bytecode(&bytecode(), &libraries).unwrap(), linked);
+
+        let libraries = HashMap::from([("Math".to_owned(), address)]);
+
+        let err = link_bytecode(&bytecode(), &libraries).unwrap_err();
+
+        match err.downcast_ref::<LinkError>() {
+            Some(LinkError::Unlinked(placeholders)) => assert_eq!(
+                placeholders,
+                &vec![library_placeholder("contracts/lib/Math.sol:Math")]
+            ),
+            _ => panic!("unexpected {}", err),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{from_abi, Address, BlockNumberOrTag, Client, Contract, H256, U256};
+
+/// EIP-1967 implementation slot, `bytes32(uint256(keccak256('eip1967.proxy.implementation')) - 1)`
+pub const EIP1967_IMPLEMENTATION_SLOT: &str =
+    "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
+
+/// EIP-1967 admin slot, `bytes32(uint256(keccak256('eip1967.proxy.admin')) - 1)`
+pub const EIP1967_ADMIN_SLOT: &str =
+    "0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103";
+
+/// EIP-1967 beacon slot, `bytes32(uint256(keccak256('eip1967.proxy.beacon')) - 1)`
+pub const EIP1967_BEACON_SLOT: &str =
+    "0xa3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50";
+
+/// Proxy errors
+#[derive(Debug, thiserror::Error)]
+pub enum ProxyError {
+    /// Address is not a EIP-1967 proxy
+    #[error("NotProxy: {0} is not a EIP-1967 proxy")]
+    NotProxy(Address),
+}
+
+/// Proxy pattern detected from EIP-1967 storage slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyKind {
+    /// Transparent proxy, implementation and admin slots are both set.
+    Transparent,
+    /// UUPS proxy, only implementation slot is set, upgrade logic lives in implementation.
+    Uups,
+    /// Beacon proxy, implementation is provided by the beacon contract.
+    Beacon,
+}
+
+/// EIP-1967 proxy details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyInfo {
+    pub kind: ProxyKind,
+    pub implementation: Address,
+    pub admin: Option<Address>,
+    pub beacon: Option<Address>,
+}
+
+/// Returns address of the lower 20 bytes of slot `word`, [`None`] if it is zero.
+fn slot_address(word: &H256) -> Option<Address> {
+    let mut buff = [0u8; 20];
+
+    buff.copy_from_slice(&word.0[12..]);
+
+    let address = Address(buff);
+
+    if address.is_zero() {
+        None
+    } else {
+        Some(address)
+    }
+}
+
+/// EIP-1967 proxy helper.
+#[derive(Clone)]
+pub struct Proxy {
+    /// Proxy contract address.
+    pub address: Address,
+    /// Client to communicate with proxy contract.
+    pub client: Client,
+}
+
+impl Proxy {
+    pub fn new<A>(address: A, client: Client) -> anyhow::Result<Self>
+    where
+        A: TryInto<Address>,
+        A::Error: std::error::Error + Sync + Send + 'static,
+    {
+        Ok(Self {
+            address: address.try_into()?,
+            client,
+        })
+    }
+
+    /// Read address from storage `slot`, returns [`None`] if slot is empty.
+    async fn read_slot(&self, slot: &str) -> anyhow::Result<Option<Address>> {
+        let slot: U256 = slot.try_into()?;
+
+        let value: H256 = self
+            .client
+            .provider
+            .clone()
+            .eth_get_storage_at(self.address, slot, None::<BlockNumberOrTag>)
+            .await?;
+
+        Ok(slot_address(&value))
+    }
+
+    /// Returns address stored in EIP-1967 implementation slot.
+    pub async fn implementation_slot(&self) -> anyhow::Result<Option<Address>> {
+        self.read_slot(EIP1967_IMPLEMENTATION_SLOT).await
+    }
+
+    /// Returns address stored in EIP-1967 admin slot.
+    pub async fn admin(&self) -> anyhow::Result<Option<Address>> {
+        self.read_slot(EIP1967_ADMIN_SLOT).await
+    }
+
+    /// Returns address stored in EIP-1967 beacon slot.
+    pub async fn beacon(&self) -> anyhow::Result<Option<Address>> {
+        self.read_slot(EIP1967_BEACON_SLOT).await
+    }
+
+    /// Detect proxy pattern, returns [`None`] if address is not a EIP-1967 proxy.
+    pub async fn detect(&self) -> anyhow::Result<Option<ProxyInfo>> {
+        if let Some(beacon) = self.beacon().await? {
+            let (implementation,): (Address,) = from_abi(
+                self.client
+                    .eth_call("implementation()", &beacon, vec![])
+                    .await?,
+            )?;
+
+            return Ok(Some(ProxyInfo {
+                kind: ProxyKind::Beacon,
+                implementation,
+                admin: self.admin().await?,
+                beacon: Some(beacon),
+            }));
+        }
+
+        if let Some(implementation) = self.implementation_slot().await? {
+            let admin = self.admin().await?;
+
+            let kind = if admin.is_some() {
+                ProxyKind::Transparent
+            } else {
+                ProxyKind::Uups
+            };
+
+            return Ok(Some(ProxyInfo {
+                kind,
+                implementation,
+                admin,
+                beacon: None,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    /// Returns current implementation address, resolving beacon if needed.
+    pub async fn implementation(&self) -> anyhow::Result<Address> {
+        self.detect()
+            .await?
+            .map(|info| info.implementation)
+            .ok_or_else(|| ProxyError::NotProxy(self.address).into())
+    }
+
+    /// Create dynamic contract instance targeting proxy address with implementation abi `fragments`.
+    pub fn contract<S>(&self, fragments: &[S]) -> anyhow::Result<Contract>
+    where
+        S: AsRef<str>,
+    {
+        Contract::new_dyn(self.address, fragments, self.client.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{keccak256, providers::providers::mock::MockProvider, to_abi, ToEtherHex, Uint};
+
+    use super::*;
+
+    fn word(address: &Address) -> String {
+        format!("0x{}{}", "00".repeat(12), &address.0.to_eth_hex()[2..])
+    }
+
+    fn zero() -> String {
+        format!("0x{}", "00".repeat(32))
+    }
+
+    #[test]
+    fn test_slots() {
+        for (slot, name) in [
+            (EIP1967_IMPLEMENTATION_SLOT, "eip1967.proxy.implementation"),
+            (EIP1967_ADMIN_SLOT, "eip1967.proxy.admin"),
+            (EIP1967_BEACON_SLOT, "eip1967.proxy.beacon"),
+        ] {
+            let expect = Uint(keccak256(name)).checked_sub(U256::from(1u64)).unwrap();
+
+            assert_eq!(U256::try_from(slot).unwrap(), expect, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_slot_address() {
+        let word: H256 = "0x000000000000000000000000cd2a3d9f938e13cd947ec05abc7fe734df8dd826"
+            .try_into()
+            .unwrap();
+
+        assert_eq!(
+            slot_address(&word),
+            Some(
+                "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+                    .try_into()
+                    .unwrap()
+            )
+        );
+
+        assert_eq!(slot_address(&H256::default()), None);
+    }
+
+    #[async_std::test]
+    async fn test_detect() {
+        let mock = MockProvider::new();
+
+        let proxy = Proxy::new(
+            "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+            Client::new(mock.provider()),
+        )
+        .unwrap();
+
+        let implementation = Address([0x11; 20]);
+        let admin = Address([0x22; 20]);
+        let beacon = Address([0x33; 20]);
+
+        // Beacon slot is read first, then implementation and admin slots.
+        mock.push("eth_getStorageAt", zero()).unwrap();
+        mock.push("eth_getStorageAt", word(&implementation))
+            .unwrap();
+        mock.push("eth_getStorageAt", word(&admin)).unwrap();
+
+        let info = proxy.detect().await.unwrap().unwrap();
+
+        assert_eq!(info.kind, ProxyKind::Transparent);
+        assert_eq!(info.implementation, implementation);
+        assert_eq!(info.admin, Some(admin));
+
+        let slots = mock
+            .requests()
+            .into_iter()
+            .map(|(_, params)| U256::try_from(params[1].as_str().unwrap()).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            slots,
+            [
+                EIP1967_BEACON_SLOT,
+                EIP1967_IMPLEMENTATION_SLOT,
+                EIP1967_ADMIN_SLOT
+            ]
+            .map(|slot| U256::try_from(slot).unwrap())
+        );
+
+        mock.push("eth_getStorageAt", zero()).unwrap();
+        mock.push("eth_getStorageAt", word(&implementation))
+            .unwrap();
+        mock.push("eth_getStorageAt", zero()).unwrap();
+
+        assert_eq!(proxy.detect().await.unwrap().unwrap().kind, ProxyKind::Uups);
+
+        mock.push("eth_getStorageAt", word(&beacon)).unwrap();
+        mock.push("eth_call", to_abi(&(implementation,)).unwrap().to_eth_hex())
+            .unwrap();
+        mock.push("eth_getStorageAt", zero()).unwrap();
+
+        let info = proxy.detect().await.unwrap().unwrap();
+
+        assert_eq!(info.kind, ProxyKind::Beacon);
+        assert_eq!(info.implementation, implementation);
+        assert_eq!(info.beacon, Some(beacon));
+
+        mock.push("eth_getStorageAt", zero()).unwrap();
+        mock.push("eth_getStorageAt", zero()).unwrap();
+
+        assert!(proxy.detect().await.unwrap().is_none());
+        assert!(mock.pending().is_empty());
+    }
+}
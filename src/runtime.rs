@@ -1,9 +1,10 @@
-use std::fmt::Display;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
-pub use ethers_eip2718::*;
+pub use ethers_core::*;
 pub use ethers_macros::*;
-pub use ethers_primitives::*;
 pub use ethers_provider::*;
 pub use ethers_signer::signer::Signer;
 pub use serde::{Deserialize, Serialize};
@@ -11,7 +12,8 @@ pub use serde_ethabi::from_abi;
 pub use serde_ethabi::to_abi;
 
 pub use anyhow::Error;
-use serde_json::json;
+
+use crate::{link_bytecode_with, ContractError, LinkReferences};
 
 /// Contract client errors
 #[derive(Debug, thiserror::Error)]
@@ -29,84 +31,557 @@ pub enum ClientError {
     /// Expect signer to execute send_raw_transaction
     #[error("Accounts: signer return empty accounts list")]
     Accounts,
+    /// Sender account of [`SendOptions`] is not managed by signer
+    #[error("AccountNotFound: signer does not manage account {0}")]
+    AccountNotFound(Address),
+    /// Chain id of [`SendOptions`] mismatches with connected network
+    #[error("ChainIdMismatch: expect chain_id {expect}, network chain_id is {got}")]
+    ChainIdMismatch { expect: U64, got: U64 },
+    /// Sender balance is lower than `gas * max fee + value` of the transaction
+    #[error("InsufficientFunds: account {address} balance {balance}, required {required}")]
+    InsufficientFunds {
+        address: Address,
+        balance: U256,
+        required: U256,
+    },
+    /// [`TxPolicy::Legacy`] transaction can't carry the access list of [`SendOptions`]
+    #[error("LegacyAccessList: legacy transaction policy can't send access list")]
+    LegacyAccessList,
 }
 
+/// Options of sending contract transactions, unset fields are filled by [`Client`].
+///
+/// ```
+/// # use ethers_rs::*;
+/// let ops = SendOptions::new()
+///     .value("1.1".parse::<Ether>().unwrap())
+///     .gas(100_000u64);
+/// ```
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
-pub struct TxOptions {
-    /// Mannul set gas price.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub gas_price: Option<U256>,
+#[serde(rename_all = "camelCase")]
+pub struct SendOptions {
     /// Transferring ether values.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<U256>,
+    /// Gas limit, estimated by `eth_estimateGas` if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<U256>,
+    /// Percent added to `eth_estimateGas` result, ignored if [`gas`](Self::gas) is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_buffer: Option<u64>,
+    /// Mannul set gas price, also used as eip1559 fees if those are not set.
+    #[serde(alias = "gas_price", skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<U256>,
+    /// EIP-1559 max fee per gas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    /// EIP-1559 max priority fee per gas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// Sender account, must be one of signer accounts, default is the first one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<Address>,
+    /// Transaction nonce, fetched by `eth_getTransactionCount` if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U256>,
+    /// Expected chain id, sending is refused if connected network chain id differs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<U64>,
+    /// EIP-2930 access list, sent as [`Eip2930TransactionRequest`] if [`TxPolicy::Auto`] falls back to
+    /// legacy transaction. Rejected with [`ClientError::LegacyAccessList`] by [`TxPolicy::Legacy`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<AccessList>,
+    /// Check sender balance covers the max cost of transaction before signing.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub check_balance: bool,
+}
+
+impl SendOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set transferring ether value, e.g. `"1.1".parse::<Ether>()?`.
+    pub fn value<V: Into<U256>>(mut self, value: V) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Set gas limit.
+    pub fn gas<V: Into<U256>>(mut self, gas: V) -> Self {
+        self.gas = Some(gas.into());
+        self
+    }
+
+    /// Set gas limit, skips `eth_estimateGas`, same as [`gas`](Self::gas).
+    pub fn gas_limit<V: Into<U256>>(self, gas: V) -> Self {
+        self.gas(gas)
+    }
+
+    /// Add `percent` to estimated gas limit, e.g. `20` sends `estimated * 1.2` gas limit.
+    ///
+    /// Useful if gas usage depends on state changed by earlier transactions of the same block.
+    pub fn gas_buffer(mut self, percent: u64) -> Self {
+        self.gas_buffer = Some(percent);
+        self
+    }
+
+    /// Set gas price.
+    pub fn gas_price<V: Into<U256>>(mut self, gas_price: V) -> Self {
+        self.gas_price = Some(gas_price.into());
+        self
+    }
+
+    /// Set EIP-1559 max fee per gas.
+    pub fn max_fee_per_gas<V: Into<U256>>(mut self, fee: V) -> Self {
+        self.max_fee_per_gas = Some(fee.into());
+        self
+    }
+
+    /// Set EIP-1559 max priority fee per gas.
+    pub fn max_priority_fee_per_gas<V: Into<U256>>(mut self, fee: V) -> Self {
+        self.max_priority_fee_per_gas = Some(fee.into());
+        self
+    }
+
+    /// Set sender account.
+    pub fn from(mut self, from: Address) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    /// Set transaction nonce.
+    pub fn nonce<V: Into<U256>>(mut self, nonce: V) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Set expected chain id, see [`Client::chain_id`].
+    pub fn chain_id<V: Into<U64>>(mut self, chain_id: V) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Set EIP-2930 access list.
+    pub fn access_list(mut self, access_list: AccessList) -> Self {
+        self.access_list = Some(access_list);
+        self
+    }
+
+    /// Refuse sending with [`ClientError::InsufficientFunds`] if sender balance is lower than
+    /// `gas * max fee + value`, costs one more `eth_getBalance` call.
+    pub fn check_balance(mut self, check_balance: bool) -> Self {
+        self.check_balance = check_balance;
+        self
+    }
 }
 
-impl<'a> TryFrom<&'a str> for TxOptions {
+impl<'a> TryFrom<&'a str> for SendOptions {
     type Error = anyhow::Error;
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
         Ok(serde_json::from_str(value)?)
     }
 }
 
-impl TryFrom<String> for TxOptions {
+impl TryFrom<String> for SendOptions {
     type Error = anyhow::Error;
     fn try_from(value: String) -> Result<Self, Self::Error> {
         Ok(serde_json::from_str(&value)?)
     }
 }
 
-impl TryFrom<serde_json::Value> for TxOptions {
+impl TryFrom<serde_json::Value> for SendOptions {
     type Error = anyhow::Error;
     fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
         Ok(serde_json::from_value(value)?)
     }
 }
 
+#[deprecated(note = "use `SendOptions` instead")]
+pub type TxOptions = SendOptions;
+
+#[deprecated(note = "use `SendOptions::new().value(..)` instead")]
 pub trait ToTxOptions {
-    fn to_tx_options(self) -> TxOptions;
+    fn to_tx_options(self) -> SendOptions;
 }
 
+#[allow(deprecated)]
 impl<T: EthereumUnit> ToTxOptions for T {
-    fn to_tx_options(self) -> TxOptions {
-        TxOptions {
-            gas_price: None,
-            value: Some(self.to_u256()),
+    fn to_tx_options(self) -> SendOptions {
+        SendOptions::new().value(self.to_u256())
+    }
+}
+
+/// Options of contract `eth_call`.
+#[derive(Clone, Debug, Default)]
+pub struct CallOptions {
+    /// Caller address, `msg.sender` of the call.
+    pub from: Option<Address>,
+    /// Transferring ether values.
+    pub value: Option<U256>,
+    /// Gas limit of the call.
+    pub gas: Option<U256>,
+    /// Block to execute call on, default is latest block.
+    pub block: Option<BlockNumberOrTag>,
+}
+
+impl CallOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set caller address.
+    pub fn from(mut self, from: Address) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    /// Set transferring ether value.
+    pub fn value<V: Into<U256>>(mut self, value: V) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Set gas limit.
+    pub fn gas<V: Into<U256>>(mut self, gas: V) -> Self {
+        self.gas = Some(gas.into());
+        self
+    }
+
+    /// Set block number or tag to execute call on.
+    pub fn block<B: Into<BlockNumberOrTag>>(mut self, block: B) -> Self {
+        self.block = Some(block.into());
+        self
+    }
+}
+
+/// Deployed contract `C` with deployment tx receipt, see [`Client::deploy_with_receipt`].
+#[derive(Debug)]
+pub struct Deployed<C> {
+    /// Contract binding or instance.
+    pub contract: C,
+    /// Deployed contract address, aka `contract_address` of receipt.
+    pub address: Address,
+    /// Deployment tx receipt.
+    pub receipt: TransactionReceipt,
+}
+
+impl<C> Deployed<C> {
+    /// Convert contract instance with `f`, e.g. construct binding from deployed address.
+    pub fn map<T, F>(self, f: F) -> Deployed<T>
+    where
+        F: FnOnce(C) -> T,
+    {
+        Deployed {
+            contract: f(self.contract),
+            address: self.address,
+            receipt: self.receipt,
         }
     }
+
+    /// Same as [`map`](Self::map) with fallible `f`.
+    pub fn try_map<T, F>(self, f: F) -> anyhow::Result<Deployed<T>>
+    where
+        F: FnOnce(C) -> anyhow::Result<T>,
+    {
+        Ok(Deployed {
+            contract: f(self.contract)?,
+            address: self.address,
+            receipt: self.receipt,
+        })
+    }
+
+    /// Drop receipt and returns contract instance.
+    pub fn into_contract(self) -> C {
+        self.contract
+    }
+}
+
+/// Transaction type used by [`Client`] to send transactions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TxPolicy {
+    /// Always send [`LegacyTransactionRequest`] with EIP-155 signature,
+    /// required by chains rejecting typed transactions. Access lists are rejected.
+    #[default]
+    Legacy,
+    /// Always send [`Eip1559TransactionRequest`].
+    Eip1559,
+    /// Send [`Eip1559TransactionRequest`] if latest block has base fee, aka chain supports EIP-1559,
+    /// otherwise fallback to [`LegacyTransactionRequest`].
+    Auto,
+}
+
+/// Transaction type resolved from [`TxPolicy`].
+enum TxKind {
+    Legacy,
+    /// EIP-1559 transaction, with latest block base fee if already fetched.
+    Eip1559(Option<U256>),
+}
+
+/// Receiver of gas used by deployments and method calls sent by [`Client`], see
+/// [`with_gas_recorder`](Client::with_gas_recorder).
+pub trait GasRecorder: Send + Sync {
+    /// Record contract `name` deployment gas used.
+    fn record_deployment(&self, name: &str, address: Address, gas_used: U256);
+
+    /// Record gas used of calling `method` of contract deployed at `to`.
+    fn record_method(&self, to: &Address, method: &str, gas_used: U256);
+}
+
+impl GasRecorder for crate::hardhat::gas_reporter::GasReporter {
+    fn record_deployment(&self, name: &str, address: Address, gas_used: U256) {
+        self.record_deployment(name, address, gas_used)
+    }
+
+    fn record_method(&self, to: &Address, method: &str, gas_used: U256) {
+        self.record_method(to, method, gas_used)
+    }
+}
+
+impl<R: GasRecorder + ?Sized> GasRecorder for &R {
+    fn record_deployment(&self, name: &str, address: Address, gas_used: U256) {
+        (**self).record_deployment(name, address, gas_used)
+    }
+
+    fn record_method(&self, to: &Address, method: &str, gas_used: U256) {
+        (**self).record_method(to, method, gas_used)
+    }
 }
 
 /// Client to communicate with ethereum contract.
+///
+/// Create with [`Client::new`] or `From<(Provider, Signer)>`, struct literals are not
+/// supported since the cached chain id is private.
 #[derive(Clone)]
 pub struct Client {
     /// rpc client provider for ethereum rpc node.
     pub provider: Provider,
     /// tx signer.
     pub signer: Option<Signer>,
+    /// Library addresses linked into deployment bytecode, see [`link_bytecode_with`].
+    pub libraries: HashMap<String, Address>,
+    /// Artifact `linkReferences` resolving bare names of [`libraries`](Self::libraries).
+    pub link_references: LinkReferences,
+    /// Transaction type policy, default is [`TxPolicy::Legacy`].
+    pub tx_policy: TxPolicy,
+    /// Account impersonated on dev network fork, transactions are sent by `eth_sendTransaction`
+    /// as this account instead of signing locally, see [`impersonate`](Self::impersonate).
+    pub impersonating: Option<Address>,
+    /// Gas used recorder of sent transactions, see [`with_gas_recorder`](Self::with_gas_recorder).
+    pub gas_recorder: Option<Arc<dyn GasRecorder>>,
+    /// Cached `eth_chainId` result, shared by clones.
+    chain_id: Arc<Mutex<Option<U64>>>,
 }
 
 impl From<(Provider, Signer)> for Client {
     fn from((provider, signer): (Provider, Signer)) -> Self {
+        let mut client = Self::new(provider);
+
+        client.signer = Some(signer);
+
+        client
+    }
+}
+
+impl Client {
+    /// Create client without signer, transactions are sent by [`signer`](Self::signer) or
+    /// [`impersonating`](Self::impersonating) account if set.
+    pub fn new(provider: Provider) -> Self {
         Self {
             provider,
-            signer: Some(signer),
+            signer: None,
+            libraries: Default::default(),
+            link_references: Default::default(),
+            tx_policy: Default::default(),
+            impersonating: None,
+            gas_recorder: None,
+            chain_id: Default::default(),
         }
     }
-}
 
-impl Client {
+    /// Link library `name` deployed at `address` when deploying contracts.
+    ///
+    /// `name` is either fully qualified, e.g. `contracts/Math.sol:Math`, or bare name `Math`
+    /// resolved by [`with_link_references`](Self::with_link_references).
+    pub fn with_library<A>(mut self, name: &str, address: A) -> anyhow::Result<Self>
+    where
+        A: TryInto<Address>,
+        A::Error: std::error::Error + Sync + Send + 'static,
+    {
+        self.libraries.insert(name.to_owned(), address.try_into()?);
+
+        Ok(self)
+    }
+
+    /// Add `linkReferences` of deploying contract artifact, used to resolve bare library names.
+    pub fn with_link_references(mut self, references: LinkReferences) -> Self {
+        for (source_name, libraries) in references {
+            self.link_references
+                .entry(source_name)
+                .or_default()
+                .extend(libraries);
+        }
+
+        self
+    }
+
+    /// Set transaction type policy, see [`TxPolicy`].
+    pub fn with_tx_policy(mut self, policy: TxPolicy) -> Self {
+        self.tx_policy = policy;
+        self
+    }
+
+    /// Record gas used of deployments and method calls with `recorder`, e.g. hardhat gas reporter.
+    ///
+    /// ```ignore
+    /// let client = client.with_gas_recorder(hardhat::gas_reporter::enable_gas_reporter());
+    /// ```
+    pub fn with_gas_recorder<R: GasRecorder + 'static>(mut self, recorder: R) -> Self {
+        self.gas_recorder = Some(Arc::new(recorder));
+        self
+    }
+
+    /// Pin chain id of client instead of fetching it by `eth_chainId`.
+    pub fn with_chain_id<V: Into<U64>>(self, chain_id: V) -> Self {
+        *self.chain_id.lock().unwrap() = Some(chain_id.into());
+        self
+    }
+
+    /// Returns client sending transactions as `address` on hardhat/anvil network or fork,
+    /// without its private key, e.g. acting as a whale or governance contract in tests.
+    ///
+    /// Calls `hardhat_impersonateAccount`, transactions are sent by `eth_sendTransaction`
+    /// and signed by the node.
+    ///
+    /// ```ignore
+    /// let whale = client.impersonate(whale_address).await?;
+    ///
+    /// let usdc = USDC::new(usdc_address, whale)?;
+    ///
+    /// usdc.transfer(receiver, amount, SendOptions::default()).await?;
+    /// ```
+    pub async fn impersonate<A>(&self, address: A) -> anyhow::Result<Client>
+    where
+        A: TryInto<Address>,
+        A::Error: std::error::Error + Sync + Send + 'static,
+    {
+        let address = address.try_into()?;
+
+        self.provider
+            .clone()
+            .hardhat_impersonate_account(address)
+            .await?;
+
+        let mut client = self.clone();
+
+        client.impersonating = Some(address);
+
+        Ok(client)
+    }
+
+    /// Stop impersonating account of [`impersonate`](Self::impersonate), calls `hardhat_stopImpersonatingAccount`.
+    pub async fn stop_impersonating(&mut self) -> anyhow::Result<()> {
+        if let Some(address) = self.impersonating.take() {
+            self.provider
+                .clone()
+                .hardhat_stop_impersonating_account(address)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns chain id of connected network, fetched by `eth_chainId` once and cached,
+    /// signed transactions are bound to it as EIP-155 replay protection.
+    pub async fn chain_id(&self) -> anyhow::Result<U64> {
+        let cached = *self.chain_id.lock().unwrap();
+
+        if let Some(chain_id) = cached {
+            return Ok(chain_id);
+        }
+
+        let chain_id = self.provider.clone().eth_chain_id().await?;
+
+        *self.chain_id.lock().unwrap() = Some(chain_id);
+
+        Ok(chain_id)
+    }
+
+    /// Returns transaction type to send, see [`TxPolicy`].
+    async fn tx_kind(&self, provider: &mut Provider) -> anyhow::Result<TxKind> {
+        match self.tx_policy {
+            TxPolicy::Legacy => Ok(TxKind::Legacy),
+            TxPolicy::Eip1559 => Ok(TxKind::Eip1559(None)),
+            TxPolicy::Auto => match self.base_fee(provider).await? {
+                Some(base_fee) => Ok(TxKind::Eip1559(Some(base_fee))),
+                None => Ok(TxKind::Legacy),
+            },
+        }
+    }
+
+    /// Returns base fee of latest block, [`None`] if chain does not support EIP-1559.
+    async fn base_fee(&self, provider: &mut Provider) -> anyhow::Result<Option<U256>> {
+        let block = provider.eth_get_block_by_number("latest", false).await?;
+
+        Ok(block.and_then(|block| block.base_fee_per_gas))
+    }
+
     pub async fn deploy_contract(
         &self,
         constract_name: &str,
-        mut call_data: Vec<u8>,
+        call_data: Vec<u8>,
         deploy_data: &str,
-        ops: TxOptions,
+        ops: SendOptions,
+    ) -> anyhow::Result<Address> {
+        Ok(self
+            .deploy_contract_with_receipt(constract_name, call_data, deploy_data, ops)
+            .await?
+            .address)
+    }
+
+    /// Deploy contract with `deploy_data`, aka contract bytecode followed by abi encoded constructor args.
+    ///
+    /// See [`encode_constructor`](crate::encode_constructor) to build `deploy_data` from bytecode and abi.
+    pub async fn deploy(
+        &self,
+        constract_name: &str,
+        deploy_data: Vec<u8>,
+        ops: SendOptions,
     ) -> anyhow::Result<Address> {
+        Ok(self
+            .deploy_with_receipt(constract_name, deploy_data, ops)
+            .await?
+            .address)
+    }
+
+    /// Same as [`deploy_contract`](Self::deploy_contract), also returns deployment tx receipt.
+    pub async fn deploy_contract_with_receipt(
+        &self,
+        constract_name: &str,
+        mut call_data: Vec<u8>,
+        deploy_data: &str,
+        ops: SendOptions,
+    ) -> anyhow::Result<Deployed<Address>> {
+        let deploy_data = link_bytecode_with(deploy_data, &self.link_references, &self.libraries)?;
+
         let mut buff = Vec::<u8>::from_eth_hex(deploy_data)?;
 
         buff.append(&mut call_data);
 
+        self.deploy_with_receipt(constract_name, buff, ops).await
+    }
+
+    /// Same as [`deploy`](Self::deploy), also returns deployment tx receipt.
+    ///
+    /// Returns [`ClientError::DeployContract`] if receipt `contract_address` is null.
+    pub async fn deploy_with_receipt(
+        &self,
+        constract_name: &str,
+        deploy_data: Vec<u8>,
+        ops: SendOptions,
+    ) -> anyhow::Result<Deployed<Address>> {
         let tx_hash = self
-            ._send_raw_transaction(constract_name, None, buff, ops, false)
+            ._send_raw_transaction(constract_name, None, deploy_data, ops, false)
             .await?;
 
         let receipt = self
@@ -117,14 +592,27 @@ impl Client {
 
         let status = receipt
             .status
+            .clone()
             .ok_or(ClientError::TxFailure(tx_hash.clone()))?;
 
         match status {
             Status::Success => {
                 if let Some(contract_address) = receipt.contract_address {
-                    return Ok(contract_address);
+                    if let Some(recorder) = &self.gas_recorder {
+                        recorder.record_deployment(
+                            constract_name,
+                            contract_address,
+                            receipt.gas_used,
+                        );
+                    }
+
+                    return Ok(Deployed {
+                        address: contract_address,
+                        contract: contract_address,
+                        receipt,
+                    });
                 } else {
-                    return Err(ClientError::TxFailure(tx_hash).into());
+                    return Err(ClientError::DeployContract(tx_hash).into());
                 }
             }
             Status::Failure => return Err(ClientError::TxFailure(tx_hash).into()),
@@ -136,27 +624,86 @@ impl Client {
         &self,
         method_name: &str,
         to: &Address,
-        mut call_data: Vec<u8>,
+        call_data: Vec<u8>,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.eth_call_with(method_name, to, call_data, CallOptions::default())
+            .await
+    }
+
+    /// Invoke contract pure/view method with [`CallOptions`], without send transaction.
+    pub async fn eth_call_with(
+        &self,
+        method_name: &str,
+        to: &Address,
+        call_data: Vec<u8>,
+        ops: CallOptions,
     ) -> anyhow::Result<Vec<u8>> {
         log::debug!("eth_call {}", method_name);
 
         let mut provider = self.provider.clone();
 
-        let mut selector_name = keccak256(method_name.as_bytes())[0..4].to_vec();
+        let tx = Self::call_request(method_name, to, call_data, &ops);
 
-        selector_name.append(&mut call_data);
+        let result = provider
+            .eth_call(tx, ops.block)
+            .await
+            .map_err(|err| ContractError::classify(err.into()))?;
 
-        let call_data: Bytes = selector_name.into();
+        Ok(result.into())
+    }
 
-        let tx: LegacyTransactionRequest = json!({
-            "to": to,
-            "data":call_data,
-        })
-        .try_into()?;
+    /// Run state-changing method through `eth_call` to get the would-be return value, like ethers.js `callStatic`.
+    ///
+    /// The call is executed on the pending block and sent from client default account,
+    /// unless [`CallOptions`] sets them. Reverts are returned as [`ContractError::Revert`].
+    pub async fn call_static(
+        &self,
+        method_name: &str,
+        to: &Address,
+        call_data: Vec<u8>,
+        mut ops: CallOptions,
+    ) -> anyhow::Result<Vec<u8>> {
+        log::debug!("call static {}", method_name);
+
+        if ops.from.is_none() && (self.signer.is_some() || self.impersonating.is_some()) {
+            ops.from = Some(self.default_account().await?);
+        }
+
+        let block = ops
+            .block
+            .clone()
+            .unwrap_or_else(|| BlockTag::Pending.into());
+
+        let tx = Self::call_request(method_name, to, call_data, &ops);
+
+        let result = self
+            .provider
+            .clone()
+            .eth_call(tx, Some(block))
+            .await
+            .map_err(|err| ContractError::classify(err.into()))?;
+
+        Ok(result.into())
+    }
+
+    fn call_request(
+        method_name: &str,
+        to: &Address,
+        mut call_data: Vec<u8>,
+        ops: &CallOptions,
+    ) -> LegacyTransactionRequest {
+        let mut selector_name = serde_ethabi::function_selector(method_name).to_vec();
 
-        let result = provider.eth_call(tx, None::<BlockNumberOrTag>).await?;
+        selector_name.append(&mut call_data);
 
-        Ok(result.0)
+        LegacyTransactionRequest {
+            from: ops.from.clone(),
+            to: Some(to.clone()),
+            data: Some(selector_name.into()),
+            value: ops.value.clone(),
+            gas: ops.gas.clone(),
+            ..Default::default()
+        }
     }
 
     /// Send raw transaction contract `to`.
@@ -170,57 +717,131 @@ impl Client {
         method_name: &str,
         to: &Address,
         call_data: Vec<u8>,
-        ops: TxOptions,
+        ops: SendOptions,
     ) -> anyhow::Result<DefaultTransactionReceipter> {
         let tx_hash = self
             ._send_raw_transaction(method_name, Some(to), call_data, ops, true)
             .await?;
 
+        // Hardhat network mines tx immediately in automine mode. The tx is already sent,
+        // so receipt fetching errors are only logged.
+        if let Some(recorder) = &self.gas_recorder {
+            match self
+                .provider
+                .clone()
+                .eth_get_transaction_receipt(tx_hash.clone())
+                .await
+            {
+                Ok(Some(receipt)) => recorder.record_method(to, method_name, receipt.gas_used),
+                Ok(None) => {
+                    log::debug!(target: method_name, "Gas recorder skip pending tx {}", tx_hash)
+                }
+                Err(err) => {
+                    log::warn!(target: method_name, "Gas recorder fetch receipt of {} error, {}", tx_hash, err)
+                }
+            }
+        }
+
         self.provider.register_transaction_listener(tx_hash.clone())
     }
 
-    async fn _send_raw_transaction(
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "contract_send",
+            skip_all,
+            fields(
+                method = method_name,
+                to = ?to,
+                chain_id = tracing::field::Empty,
+                nonce = tracing::field::Empty,
+                tx_hash = tracing::field::Empty
+            )
+        )
+    )]
+    pub(crate) async fn _send_raw_transaction(
         &self,
         method_name: &str,
         to: Option<&Address>,
         mut call_data: Vec<u8>,
-        ops: TxOptions,
+        ops: SendOptions,
         selector: bool,
     ) -> anyhow::Result<H256> {
+        if self.tx_policy == TxPolicy::Legacy && ops.access_list.is_some() {
+            return Err(ClientError::LegacyAccessList.into());
+        }
+
         let mut provider = self.provider.clone();
 
-        let mut signer = self
-            .signer
-            .clone()
-            .ok_or(ClientError::SignerExpect(method_name.to_owned()))?;
+        let address = match (self.impersonating, ops.from) {
+            (Some(impersonating), Some(from)) if impersonating != from => {
+                return Err(ClientError::AccountNotFound(from).into());
+            }
+            (Some(impersonating), _) => impersonating,
+            (None, Some(from)) => {
+                let mut signer = self
+                    .signer
+                    .clone()
+                    .ok_or(ClientError::SignerExpect(method_name.to_owned()))?;
 
-        let address = {
-            let mut accounts = signer.accounts().await?;
+                let accounts = signer.accounts().await?;
 
-            if accounts.is_empty() {
-                return Err(ClientError::Accounts.into());
-            }
+                if accounts.is_empty() {
+                    return Err(ClientError::Accounts.into());
+                }
+
+                if !accounts.contains(&from) {
+                    return Err(ClientError::AccountNotFound(from).into());
+                }
 
-            accounts.remove(0)
+                from
+            }
+            (None, None) => self
+                .signer
+                .clone()
+                .ok_or(ClientError::SignerExpect(method_name.to_owned()))?
+                .default_account()
+                .await?
+                .ok_or(ClientError::Accounts)?,
         };
 
         // Get nonce first.
-        let nonce = provider.eth_get_transaction_count(address).await?;
+        let nonce = if let Some(nonce) = ops.nonce {
+            nonce
+        } else {
+            provider
+                .eth_get_transaction_count(address, None::<BlockNumberOrTag>)
+                .await?
+        };
 
         log::debug!(
             target: method_name,
-            "Fetch account {} nonce, {}",
+            "Use account {} nonce, {}",
             address.to_checksum_string(),
             nonce
         );
 
-        // Get chain id
-        let chain_id = provider.eth_chain_id().await?;
+        let chain_id = self.chain_id().await?;
+
+        if let Some(expect) = ops.chain_id {
+            if expect != chain_id {
+                return Err(ClientError::ChainIdMismatch {
+                    expect,
+                    got: chain_id,
+                }
+                .into());
+            }
+        }
+
+        log::debug!(target: method_name, "Use chain_id, {}", chain_id);
 
-        log::debug!(target: method_name, "Fetch chain_id, {}", chain_id);
+        #[cfg(feature = "tracing")]
+        tracing::Span::current()
+            .record("nonce", tracing::field::display(&nonce))
+            .record("chain_id", tracing::field::display(&chain_id));
 
         let call_data = if selector {
-            let mut selector_name = keccak256(method_name.as_bytes())[0..4].to_vec();
+            let mut selector_name = serde_ethabi::function_selector(method_name).to_vec();
 
             selector_name.append(&mut call_data);
 
@@ -230,6 +851,7 @@ impl Client {
         };
 
         let mut tx = LegacyTransactionRequest {
+            from: Some(address),
             chain_id: Some(chain_id),
             nonce: Some(nonce),
             to: to.map(|c| c.clone()),
@@ -239,59 +861,279 @@ impl Client {
         };
 
         // estimate gas
-        let gas = provider
-            .eth_estimate_gas(tx.clone(), None::<BlockNumberOrTag>)
-            .await?;
+        let gas = if let Some(gas) = ops.gas {
+            gas
+        } else {
+            let gas = provider
+                .eth_estimate_gas(tx.clone(), None::<BlockNumberOrTag>)
+                .await
+                .map_err(|err| ContractError::classify(err.into()))?;
+
+            match ops.gas_buffer {
+                Some(percent) => {
+                    log::debug!(target: method_name, "Estimated gas {}, add {}%", gas, percent);
 
-        log::debug!(target: method_name, "Fetch estimate gas, {}", gas);
+                    gas * (100 + percent) / 100u64
+                }
+                None => gas,
+            }
+        };
+
+        log::debug!(target: method_name, "Use gas limit, {}", gas);
 
         tx.gas = Some(gas);
 
-        // Get gas price
+        let request: TypedTransactionRequest = match self.tx_kind(&mut provider).await? {
+            TxKind::Legacy => {
+                let gas_price = if let Some(gas_price) = ops.gas_price {
+                    gas_price
+                } else {
+                    provider.eth_gas_price().await?
+                };
 
-        let gas_price = if let Some(gas_price) = ops.gas_price {
-            gas_price
-        } else {
-            provider.eth_gas_price().await?
+                log::debug!(target: method_name, "Fetch gas price, {}", gas_price);
+
+                tx.gas_price = Some(gas_price);
+
+                log::debug!(
+                    target: method_name,
+                    "Try sign transaction, {}",
+                    serde_json::to_string(&tx)?,
+                );
+
+                if let Some(access_list) = ops.access_list {
+                    Eip2930TransactionRequest { tx, access_list }.into()
+                } else {
+                    tx.into()
+                }
+            }
+            TxKind::Eip1559(base_fee) => {
+                let (max_fee_per_gas, max_priority_fee_per_gas) = match (
+                    ops.max_fee_per_gas,
+                    ops.max_priority_fee_per_gas,
+                    ops.gas_price,
+                ) {
+                    (Some(max_fee), Some(priority_fee), _) => (max_fee, priority_fee),
+                    (None, None, Some(gas_price)) => (gas_price, gas_price),
+                    (max_fee, priority_fee, _) => {
+                        let priority_fee = if let Some(priority_fee) = priority_fee {
+                            priority_fee
+                        } else {
+                            provider.eth_max_priority_fee_per_gas().await?
+                        };
+
+                        let max_fee = if let Some(max_fee) = max_fee {
+                            max_fee
+                        } else {
+                            // Reuse base fee of `TxPolicy::Auto` check.
+                            let base_fee = match base_fee {
+                                Some(base_fee) => base_fee,
+                                None => self.base_fee(&mut provider).await?.unwrap_or_default(),
+                            };
+
+                            base_fee * 2usize + priority_fee
+                        };
+
+                        (max_fee, priority_fee)
+                    }
+                };
+
+                log::debug!(
+                    target: method_name,
+                    "Fetch max fee per gas {}, max priority fee per gas {}",
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas
+                );
+
+                let tx = Eip1559TransactionRequest {
+                    from: tx.from,
+                    chain_id: Uint(chain_id.0),
+                    nonce,
+                    max_priority_fee_per_gas,
+                    max_fee_per_gas,
+                    gas,
+                    to: tx.to,
+                    value: tx.value,
+                    data: tx.data,
+                    access_list: ops.access_list.unwrap_or_default(),
+                };
+
+                log::debug!(
+                    target: method_name,
+                    "Try sign transaction, {}",
+                    serde_json::to_string(&tx)?,
+                );
+
+                tx.into()
+            }
         };
 
-        log::debug!(target: method_name, "Fetch gas price, {}", gas_price);
+        if ops.check_balance {
+            let required = request.max_cost().unwrap_or_else(U256::max_value);
 
-        tx.gas_price = Some(gas_price);
+            let balance = provider
+                .eth_get_balance(address, None::<BlockNumberOrTag>)
+                .await?;
 
-        log::debug!(
-            target: method_name,
-            "Try sign transaction, {}",
-            serde_json::to_string(&tx)?,
-        );
+            if balance < required {
+                return Err(ClientError::InsufficientFunds {
+                    address,
+                    balance,
+                    required,
+                }
+                .into());
+            }
+        }
 
-        let signed_tx = signer.sign_eth_transaction(tx).await?;
+        let hash = if self.impersonating.is_some() {
+            provider
+                .eth_send_transaction(request)
+                .await
+                .map_err(|err| ContractError::classify(err.into()))?
+        } else {
+            let mut signer = self
+                .signer
+                .clone()
+                .ok_or(ClientError::SignerExpect(method_name.to_owned()))?;
 
-        log::debug!(
-            target: method_name,
-            "Signed transaction, {}",
-            signed_tx.to_string()
-        );
+            let signed_tx = signer
+                .sign_transaction(request)
+                .await
+                .map_err(|err| ContractError::SignerError(err.into()))?;
+
+            log::debug!(
+                target: method_name,
+                "Signed transaction {}, {}",
+                signed_tx.hash(),
+                signed_tx.raw()
+            );
 
-        let hash = provider.eth_send_raw_transaction(signed_tx).await?;
+            let tx_hash = signed_tx.hash().clone();
+
+            provider
+                .broadcast_raw_transaction(signed_tx.into_raw())
+                .await
+                .map_err(|err| ContractError::classify(err.into()).with_tx_hash(tx_hash))?
+        };
 
         log::debug!(target: method_name, "Send transaction success, {}", hash);
 
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("tx_hash", tracing::field::display(&hash));
+
         Ok(hash)
     }
 
-    /// Get balance of client bound signer.
+    /// Get accounts managed by client bound signer.
     ///
     /// If client signer is [`None`], returns error [`ClientError::SignerExpect`].
-    pub async fn balance(&self) -> anyhow::Result<U256> {
+    pub async fn accounts(&self) -> anyhow::Result<Vec<Address>> {
+        let mut signer = self
+            .signer
+            .clone()
+            .ok_or(ClientError::SignerExpect("accounts".to_owned()))?;
+
+        Ok(signer.accounts().await?)
+    }
+
+    /// Get default account of client bound signer, used as sender if [`SendOptions::from`] is not set.
+    ///
+    /// Returns the impersonated account instead, see [`impersonate`](Self::impersonate).
+    ///
+    /// If client signer is [`None`], returns error [`ClientError::SignerExpect`].
+    pub async fn default_account(&self) -> anyhow::Result<Address> {
+        if let Some(address) = self.impersonating {
+            return Ok(address);
+        }
+
         let mut signer = self
             .signer
             .clone()
-            .ok_or(ClientError::SignerExpect("balance".to_owned()))?;
+            .ok_or(ClientError::SignerExpect("default_account".to_owned()))?;
+
+        Ok(signer
+            .default_account()
+            .await?
+            .ok_or(ClientError::Accounts)?)
+    }
+
+    /// Get balance of client bound signer default account.
+    ///
+    /// If client signer is [`None`], returns error [`ClientError::SignerExpect`].
+    pub async fn balance(&self) -> anyhow::Result<U256> {
+        let address = self.default_account().await?;
+
+        Ok(self
+            .provider
+            .clone()
+            .eth_get_balance(address, None::<BlockNumberOrTag>)
+            .await?)
+    }
 
-        let address = signer.address().await?;
+    /// Get balance of client bound signer default account at `block`.
+    ///
+    /// If client signer is [`None`], returns error [`ClientError::SignerExpect`].
+    pub async fn balance_at<BT>(&self, block: BT) -> anyhow::Result<U256>
+    where
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
+    {
+        let address = self.default_account().await?;
 
-        Ok(self.provider.clone().eth_get_balance(address).await?)
+        Ok(self
+            .provider
+            .clone()
+            .eth_get_balance(address, Some(block))
+            .await?)
+    }
+
+    /// Get nonce of client bound signer default account at `block`.
+    ///
+    /// If client signer is [`None`], returns error [`ClientError::SignerExpect`].
+    pub async fn nonce_at<BT>(&self, block: BT) -> anyhow::Result<U256>
+    where
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
+    {
+        let address = self.default_account().await?;
+
+        Ok(self
+            .provider
+            .clone()
+            .eth_get_transaction_count(address, Some(block))
+            .await?)
+    }
+
+    /// Get contract code of `address` at `block`.
+    pub async fn code_at<A, BT>(&self, address: A, block: BT) -> anyhow::Result<Bytes>
+    where
+        A: TryInto<NameOrAddress>,
+        A::Error: Debug + Display,
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
+    {
+        Ok(self
+            .provider
+            .clone()
+            .eth_get_code(address, Some(block))
+            .await?)
+    }
+
+    /// Get storage `slot` value of `address` at `block`.
+    pub async fn storage_at<A, P, BT>(&self, address: A, slot: P, block: BT) -> anyhow::Result<H256>
+    where
+        A: TryInto<NameOrAddress>,
+        A::Error: Debug + Display,
+        P: TryInto<U256>,
+        P::Error: Debug + Display,
+        BT: TryInto<BlockNumberOrTag>,
+        BT::Error: Debug + Display,
+    {
+        Ok(self
+            .provider
+            .clone()
+            .eth_get_storage_at(address, slot, Some(block))
+            .await?)
     }
 }
 
@@ -310,3 +1152,290 @@ impl Display for AbiEvent {
         write!(f, "{}", serde_json::to_string(&self.0).unwrap())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ethers_signer::wallet::WalletSigner;
+    use ethers_wallet::wallet::Wallet;
+
+    use crate::providers::providers::mock::MockProvider;
+
+    use super::*;
+
+    #[async_std::test]
+    async fn test_chain_id_mismatch() {
+        let mock = MockProvider::new();
+
+        let signer =
+            Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap()
+                .try_into_signer()
+                .unwrap();
+
+        let client = Client::from((mock.provider(), signer)).with_chain_id(1u64);
+
+        let to: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        let result = client
+            .send_raw_transaction(
+                "transfer(address,uint256)",
+                &to,
+                vec![],
+                SendOptions::new().nonce(0u64).chain_id(5u64),
+            )
+            .await;
+
+        match result {
+            Err(err) => assert!(matches!(
+                err.downcast_ref::<ClientError>(),
+                Some(ClientError::ChainIdMismatch { .. })
+            )),
+            Ok(_) => panic!("expect ChainIdMismatch"),
+        }
+
+        // Neither `eth_chainId` nor gas estimation and broadcasting are requested.
+        assert!(mock.requests().is_empty());
+    }
+
+    #[async_std::test]
+    async fn test_gas_recorder_receipt_error() {
+        let mock = MockProvider::new();
+
+        let signer =
+            Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap()
+                .try_into_signer()
+                .unwrap();
+
+        let client = Client::from((mock.provider(), signer))
+            .with_chain_id(1u64)
+            .with_gas_recorder(crate::hardhat::gas_reporter::GasReporter::default());
+
+        let to: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        mock.push("eth_sendRawTransaction", H256::default())
+            .unwrap();
+        mock.push_error(
+            "eth_getTransactionReceipt",
+            jsonrpc_rs::RPCError {
+                code: jsonrpc_rs::ErrorCode::InternalError,
+                message: "connection reset".to_owned(),
+                data: None,
+            },
+        );
+
+        // The tx is broadcast, receipt fetching error of gas recorder is not returned.
+        client
+            .send_raw_transaction(
+                "withdraw()",
+                &to,
+                vec![],
+                SendOptions::new().nonce(0u64).gas(21000u64).gas_price(1u64),
+            )
+            .await
+            .unwrap();
+
+        assert!(mock.pending().is_empty());
+    }
+
+    fn policy_client(mock: &MockProvider, policy: TxPolicy) -> Client {
+        let signer =
+            Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap()
+                .try_into_signer()
+                .unwrap();
+
+        Client::from((mock.provider(), signer))
+            .with_chain_id(1u64)
+            .with_tx_policy(policy)
+    }
+
+    fn latest_block(base_fee: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "hash": "0x0101010101010101010101010101010101010101010101010101010101010101",
+            "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "miner": "0x0000000000000000000000000000000000000000",
+            "stateRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "transactionsRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "receiptsRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "number": "0x10",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "timestamp": "0x0",
+            "extraData": "0x",
+            "mixHash": null,
+            "nonce": null,
+            "size": "0x0",
+            "transactions": [],
+            "uncles": [],
+            "baseFeePerGas": base_fee,
+        })
+    }
+
+    async fn send_with_policy(
+        mock: &MockProvider,
+        policy: TxPolicy,
+        ops: SendOptions,
+    ) -> anyhow::Result<DefaultTransactionReceipter> {
+        let to: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        policy_client(mock, policy)
+            .send_raw_transaction("withdraw()", &to, vec![], ops.nonce(0u64).gas(21000u64))
+            .await
+    }
+
+    /// Returns requested methods and envelope type of broadcast tx, [`None`] for legacy tx.
+    fn sent(mock: &MockProvider) -> (Vec<String>, Option<u8>) {
+        let requests = mock.requests();
+
+        let (_, params) = requests
+            .iter()
+            .find(|(method, _)| method == "eth_sendRawTransaction")
+            .expect("broadcast raw tx");
+
+        let raw = Vec::<u8>::from_eth_hex(params[0].as_str().unwrap()).unwrap();
+
+        let methods = requests.into_iter().map(|(method, _)| method).collect();
+
+        (methods, if raw[0] < 0xc0 { Some(raw[0]) } else { None })
+    }
+
+    #[async_std::test]
+    async fn test_tx_policy_legacy() {
+        let mock = MockProvider::new();
+
+        mock.push("eth_gasPrice", "0x3b9aca00").unwrap();
+        mock.push("eth_sendRawTransaction", H256::default())
+            .unwrap();
+
+        send_with_policy(&mock, TxPolicy::Legacy, SendOptions::new())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            sent(&mock),
+            (
+                vec![
+                    "eth_gasPrice".to_owned(),
+                    "eth_sendRawTransaction".to_owned()
+                ],
+                None
+            )
+        );
+
+        // Access list can't be sent by legacy tx.
+        let mock = MockProvider::new();
+
+        let result = send_with_policy(
+            &mock,
+            TxPolicy::Legacy,
+            SendOptions::new().access_list(AccessList::default()),
+        )
+        .await;
+
+        match result {
+            Err(err) => assert!(matches!(
+                err.downcast_ref::<ClientError>(),
+                Some(ClientError::LegacyAccessList)
+            )),
+            Ok(_) => panic!("expect LegacyAccessList"),
+        }
+
+        assert!(mock.requests().is_empty());
+    }
+
+    #[async_std::test]
+    async fn test_tx_policy_eip1559() {
+        let mock = MockProvider::new();
+
+        mock.push("eth_maxPriorityFeePerGas", "0x3b9aca00").unwrap();
+        mock.push("eth_getBlockByNumber", latest_block(Some("0x7")))
+            .unwrap();
+        mock.push("eth_sendRawTransaction", H256::default())
+            .unwrap();
+
+        send_with_policy(&mock, TxPolicy::Eip1559, SendOptions::new())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            sent(&mock),
+            (
+                vec![
+                    "eth_maxPriorityFeePerGas".to_owned(),
+                    "eth_getBlockByNumber".to_owned(),
+                    "eth_sendRawTransaction".to_owned()
+                ],
+                Some(2)
+            )
+        );
+    }
+
+    #[async_std::test]
+    async fn test_tx_policy_auto() {
+        // London chain, the latest block is fetched once for both policy check and max fee.
+        let mock = MockProvider::new();
+
+        mock.push("eth_getBlockByNumber", latest_block(Some("0x7")))
+            .unwrap();
+        mock.push("eth_maxPriorityFeePerGas", "0x3b9aca00").unwrap();
+        mock.push("eth_sendRawTransaction", H256::default())
+            .unwrap();
+
+        send_with_policy(&mock, TxPolicy::Auto, SendOptions::new())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            sent(&mock),
+            (
+                vec![
+                    "eth_getBlockByNumber".to_owned(),
+                    "eth_maxPriorityFeePerGas".to_owned(),
+                    "eth_sendRawTransaction".to_owned()
+                ],
+                Some(2)
+            )
+        );
+
+        // Pre-London chain falls back to legacy tx.
+        let mock = MockProvider::new();
+
+        mock.push("eth_getBlockByNumber", latest_block(None))
+            .unwrap();
+        mock.push("eth_gasPrice", "0x3b9aca00").unwrap();
+        mock.push("eth_sendRawTransaction", H256::default())
+            .unwrap();
+
+        send_with_policy(&mock, TxPolicy::Auto, SendOptions::new())
+            .await
+            .unwrap();
+
+        assert_eq!(sent(&mock).1, None);
+
+        // Pre-London chain with access list sends EIP-2930 tx.
+        let mock = MockProvider::new();
+
+        mock.push("eth_getBlockByNumber", latest_block(None))
+            .unwrap();
+        mock.push("eth_gasPrice", "0x3b9aca00").unwrap();
+        mock.push("eth_sendRawTransaction", H256::default())
+            .unwrap();
+
+        send_with_policy(
+            &mock,
+            TxPolicy::Auto,
+            SendOptions::new().access_list(AccessList::default()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(sent(&mock).1, Some(1));
+    }
+}
@@ -0,0 +1,175 @@
+use ethers_wallet::siwe::SiweError;
+pub use ethers_wallet::siwe::SiweMessage;
+
+use crate::{to_abi, BlockNumberOrTag, Bytes, Client, ContractError, Signature, H256};
+
+/// EIP-1271 `isValidSignature(bytes32,bytes)` magic return value.
+const EIP1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+impl Client {
+    /// Verify siwe `message` signed by `signature` at current system time, requested by
+    /// relying party `domain` with issued `nonce`.
+    ///
+    /// EOA signatures are verified by recovery, contract accounts are verified via EIP-1271 `isValidSignature`.
+    /// Reverted `isValidSignature` calls are reported as [`SiweError::InvalidSignature`], other rpc
+    /// errors are returned as is.
+    pub async fn verify_siwe(
+        &self,
+        message: &SiweMessage,
+        signature: &[u8],
+        domain: &str,
+        nonce: &str,
+    ) -> anyhow::Result<()> {
+        message.validate_request(domain, nonce)?;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_secs();
+
+        message.validate_at(now)?;
+
+        let recovered = Signature::try_from(signature)
+            .ok()
+            .and_then(|signature| message.recover(&signature).ok());
+
+        if recovered == Some(message.address) {
+            return Ok(());
+        }
+
+        let code = self
+            .provider
+            .clone()
+            .eth_get_code(message.address, None::<BlockNumberOrTag>)
+            .await?;
+
+        if code.is_empty() {
+            return Err(match recovered {
+                Some(recovered) => SiweError::SignerMismatch {
+                    expected: message.address,
+                    recovered,
+                },
+                None => SiweError::InvalidSignature(message.address),
+            }
+            .into());
+        }
+
+        let hash: H256 = message.hash().into();
+
+        let call_data = to_abi(&(hash, Bytes::from(signature.to_vec())))?;
+
+        let result = match self
+            .eth_call(
+                "isValidSignature(bytes32,bytes)",
+                &message.address,
+                call_data,
+            )
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => match ContractError::classify(err) {
+                ContractError::Revert { .. } => vec![],
+                err => return Err(err.into()),
+            },
+        };
+
+        if result.len() < 4 || result[..4] != EIP1271_MAGIC_VALUE {
+            return Err(SiweError::InvalidSignature(message.address).into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpc_rs::{ErrorCode, RPCError};
+
+    use crate::{providers::providers::mock::MockProvider, Address};
+
+    use super::*;
+
+    fn rpc_error(message: &str) -> RPCError {
+        RPCError {
+            code: ErrorCode::InternalError,
+            message: message.to_owned(),
+            data: None,
+        }
+    }
+
+    #[async_std::test]
+    async fn test_verify_contract_account() {
+        let wallet: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        let message = SiweMessage::new("example.com", wallet, "https://example.com/login", 1)
+            .nonce("32891756");
+
+        // Signatures of contract accounts are opaque to the client.
+        let signature = vec![0x01; 65];
+
+        let magic = format!("0x1626ba7e{}", "0".repeat(56));
+
+        let mock = MockProvider::new();
+
+        let client = Client::new(mock.provider());
+
+        mock.push("eth_getCode", "0x6080").unwrap();
+        mock.push("eth_call", &magic).unwrap();
+
+        client
+            .verify_siwe(&message, &signature, "example.com", "32891756")
+            .await
+            .unwrap();
+
+        let (method, params) = mock.requests().pop().unwrap();
+
+        assert_eq!(method, "eth_call");
+        assert!(params[0]["data"]
+            .as_str()
+            .unwrap()
+            .starts_with("0x1626ba7e"));
+
+        // `isValidSignature` reverted.
+        mock.push("eth_getCode", "0x6080").unwrap();
+        mock.push_error("eth_call", rpc_error("execution reverted"));
+
+        let err = client
+            .verify_siwe(&message, &signature, "example.com", "32891756")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<SiweError>(),
+            Some(SiweError::InvalidSignature(_))
+        ));
+
+        // Transport errors are not invalid signatures.
+        mock.push("eth_getCode", "0x6080").unwrap();
+        mock.push_error("eth_call", rpc_error("connection refused"));
+
+        let err = client
+            .verify_siwe(&message, &signature, "example.com", "32891756")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ContractError>(),
+            Some(ContractError::ProviderError(_))
+        ));
+
+        // Message of other site is refused before any request.
+        let requests = mock.requests().len();
+
+        let err = client
+            .verify_siwe(&message, &signature, "evil.com", "32891756")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<SiweError>(),
+            Some(SiweError::DomainMismatch { .. })
+        ));
+        assert_eq!(mock.requests().len(), requests);
+    }
+}
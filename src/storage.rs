@@ -0,0 +1,672 @@
+use std::{
+    collections::HashMap,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{from_abi, keccak256, to_abi, BlockNumberOrTag, Bytes, Contract, Uint, H256, U256};
+
+/// Storage layout errors
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    /// Build info has no `storageLayout` output of contract
+    #[error("LayoutNotFound: storageLayout of {0} not found, add `storageLayout` to solc outputSelection")]
+    LayoutNotFound(String),
+    /// State variable not found in storage layout
+    #[error("VariableNotFound: {0}")]
+    VariableNotFound(String),
+    /// Type not found in storage layout types
+    #[error("TypeNotFound: {0}")]
+    TypeNotFound(String),
+    /// Variable is neither mapping nor dynamic array
+    #[error("NotIndexable: {0} of type {1} is neither mapping nor dynamic array")]
+    NotIndexable(String, String),
+    /// Value spans multiple slots, e.g. string, struct or static array
+    #[error("UnsupportedEncoding: {0} of type {1} is not a single slot value")]
+    UnsupportedEncoding(String, String),
+    /// Mapping key or array index is not encoded as expected
+    #[error("InvalidKey: {0}")]
+    InvalidKey(String),
+    /// Contract has no storage layout, see [`Contract::with_storage_layout`]
+    #[error("StorageLayoutRequired: contract {0} has no storage layout")]
+    StorageLayoutRequired(String),
+    /// Slot calculation overflows uint256
+    #[error("SlotOverflow: {0}")]
+    SlotOverflow(String),
+}
+
+/// State variable or struct member of solc storage layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageEntry {
+    pub ast_id: u64,
+    pub contract: String,
+    pub label: String,
+    /// Byte offset in slot, counting from the right.
+    pub offset: usize,
+    /// Slot number as decimal string.
+    pub slot: String,
+    /// Type id in [`StorageLayout::types`], e.g. `t_mapping(t_address,t_uint256)`.
+    pub r#type: String,
+}
+
+/// Type of solc storage layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageType {
+    /// One of `inplace`, `mapping`, `dynamic_array` or `bytes`.
+    pub encoding: String,
+    pub label: String,
+    pub number_of_bytes: String,
+    /// Key type of mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Value type of mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Element type of array.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// Members of struct.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<StorageEntry>>,
+}
+
+/// Solc `storageLayout` output of contract.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageLayout {
+    pub storage: Vec<StorageEntry>,
+    #[serde(default)]
+    pub types: HashMap<String, StorageType>,
+}
+
+/// Resolved storage location of value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageSlot {
+    pub slot: U256,
+    /// Byte offset in slot, counting from the right.
+    pub offset: usize,
+    /// Type id of value in [`StorageLayout::types`].
+    pub r#type: String,
+}
+
+impl StorageLayout {
+    /// Load storage layout of hardhat `artifact`, e.g. `sol/artifacts/contracts/Token.sol/Token.json`.
+    ///
+    /// The layout is read from build info referenced by `{Name}.dbg.json` next to the artifact,
+    /// which requires `storageLayout` in solc `outputSelection` of hardhat config.
+    pub fn from_artifact<P: AsRef<Path>>(artifact: P) -> anyhow::Result<Self> {
+        let artifact = artifact.as_ref();
+
+        let json: Value = serde_json::from_str(&read_to_string(artifact)?)?;
+
+        let name = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_owned())
+                .ok_or_else(|| StorageError::LayoutNotFound(artifact.display().to_string()))
+        };
+
+        let source_name = name("sourceName")?;
+        let contract_name = name("contractName")?;
+
+        let dbg_path = artifact.with_file_name(format!("{}.dbg.json", contract_name));
+
+        let dbg: Value = serde_json::from_str(&read_to_string(&dbg_path)?)?;
+
+        let build_info = dbg
+            .get("buildInfo")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| StorageError::LayoutNotFound(dbg_path.display().to_string()))?;
+
+        let build_info: PathBuf = dbg_path
+            .parent()
+            .map(|dir| dir.join(build_info))
+            .unwrap_or_else(|| build_info.into());
+
+        Self::from_build_info(&read_to_string(build_info)?, &source_name, &contract_name)
+    }
+
+    /// Parse storage layout of `source_name:contract_name` from hardhat build info json.
+    pub fn from_build_info(
+        build_info: &str,
+        source_name: &str,
+        contract_name: &str,
+    ) -> anyhow::Result<Self> {
+        let build_info: Value = serde_json::from_str(build_info)?;
+
+        let layout = build_info
+            .pointer("/output/contracts")
+            .and_then(|contracts| contracts.get(source_name))
+            .and_then(|contracts| contracts.get(contract_name))
+            .and_then(|contract| contract.get("storageLayout"))
+            .filter(|layout| !layout.is_null())
+            .ok_or_else(|| {
+                StorageError::LayoutNotFound(format!("{}:{}", source_name, contract_name))
+            })?;
+
+        Ok(serde_json::from_value(layout.clone())?)
+    }
+
+    /// Returns type of `type_id`.
+    pub fn storage_type(&self, type_id: &str) -> Result<&StorageType, StorageError> {
+        self.types
+            .get(type_id)
+            .ok_or_else(|| StorageError::TypeNotFound(type_id.to_owned()))
+    }
+
+    /// Returns storage location of state variable `label`.
+    pub fn slot_of(&self, label: &str) -> Result<StorageSlot, StorageError> {
+        let entry = self
+            .storage
+            .iter()
+            .find(|entry| entry.label == label)
+            .ok_or_else(|| StorageError::VariableNotFound(label.to_owned()))?;
+
+        let slot = entry
+            .slot
+            .parse::<u64>()
+            .map_err(|_| StorageError::SlotOverflow(entry.slot.clone()))?;
+
+        Ok(StorageSlot {
+            slot: slot.into(),
+            offset: entry.offset,
+            r#type: entry.r#type.clone(),
+        })
+    }
+
+    /// Returns storage location of `key` of mapping `label`, or element `key` of dynamic array `label`.
+    ///
+    /// Mapping slot is `keccak256(key ‖ slot)`, where `key` is padded to 32 bytes except `string`
+    /// and `bytes` keys. Array elements start at `keccak256(slot)`, elements of at most 16
+    /// bytes are packed.
+    pub fn slot_of_key<K: Serialize>(&self, label: &str, key: &K) -> anyhow::Result<StorageSlot> {
+        let location = self.slot_of(label)?;
+
+        let storage_type = self.storage_type(&location.r#type)?;
+
+        let key = to_abi(key)?;
+
+        match storage_type.encoding.as_str() {
+            "mapping" => {
+                let key_type = storage_type.key.as_deref().unwrap_or_default();
+                let value_type = storage_type.value.clone().unwrap_or_default();
+
+                let key = if self.storage_type(key_type)?.encoding == "bytes" {
+                    // `to_abi` encodes standalone string/bytes as `len ‖ data`.
+                    let len: U256 = from_abi(key.get(..32).unwrap_or_default().to_vec())?;
+
+                    key.get(32..)
+                        .and_then(|data| data.get(..len_of(&len)?))
+                        .ok_or_else(|| StorageError::InvalidKey(label.to_owned()))?
+                        .to_vec()
+                } else if key.len() == 32 {
+                    key
+                } else {
+                    return Err(StorageError::InvalidKey(label.to_owned()).into());
+                };
+
+                Ok(StorageSlot {
+                    slot: Uint(keccak256([key.as_slice(), &location.slot.0].concat())),
+                    offset: 0,
+                    r#type: value_type,
+                })
+            }
+            "dynamic_array" => {
+                let base = storage_type.base.clone().unwrap_or_default();
+
+                let index: U256 =
+                    from_abi(key).map_err(|_| StorageError::InvalidKey(label.to_owned()))?;
+
+                let size = self
+                    .storage_type(&base)?
+                    .number_of_bytes
+                    .parse::<u64>()
+                    .unwrap_or(32);
+
+                let overflow = || StorageError::SlotOverflow(label.to_owned());
+
+                let (slot, offset) = if size <= 16 {
+                    let per_slot = U256::from(32 / size);
+
+                    let offset = index.checked_rem(per_slot).ok_or_else(overflow)?;
+
+                    (
+                        index.checked_div(per_slot).ok_or_else(overflow)?,
+                        len_of(&offset).ok_or_else(overflow)? * size as usize,
+                    )
+                } else {
+                    let slots = U256::from(size.div_ceil(32));
+
+                    (index.checked_mul(slots).ok_or_else(overflow)?, 0)
+                };
+
+                let slot = Uint(keccak256(location.slot.0))
+                    .checked_add(slot)
+                    .ok_or_else(overflow)?;
+
+                Ok(StorageSlot {
+                    slot,
+                    offset,
+                    r#type: base,
+                })
+            }
+            _ => {
+                Err(StorageError::NotIndexable(label.to_owned(), storage_type.label.clone()).into())
+            }
+        }
+    }
+
+    /// Returns byte size and type label of single slot value of `location`.
+    fn inplace_size(&self, location: &StorageSlot) -> Result<(usize, &str), StorageError> {
+        let storage_type = self.storage_type(&location.r#type)?;
+
+        let size = storage_type
+            .number_of_bytes
+            .parse::<usize>()
+            .ok()
+            .filter(|size| storage_type.encoding == "inplace" && location.offset + size <= 32)
+            .ok_or_else(|| {
+                StorageError::UnsupportedEncoding(
+                    location.r#type.clone(),
+                    storage_type.label.clone(),
+                )
+            })?;
+
+        Ok((size, storage_type.label.as_str()))
+    }
+
+    /// Decode single slot value of `location` from storage `word`.
+    pub fn decode<T: DeserializeOwned>(
+        &self,
+        location: &StorageSlot,
+        word: &H256,
+    ) -> anyhow::Result<T> {
+        let (size, label) = self.inplace_size(location)?;
+
+        let end = 32 - location.offset;
+
+        let value = &word.0[end - size..end];
+
+        let mut buff = [0u8; 32];
+
+        // Fixed bytes are left aligned in abi, the other value types are right aligned,
+        // negative signed integers are sign extended.
+        if label.starts_with("bytes") {
+            buff[..size].copy_from_slice(value);
+        } else {
+            if label.starts_with("int") && value[0] & 0x80 != 0 {
+                buff = [0xff; 32];
+            }
+
+            buff[32 - size..].copy_from_slice(value);
+        }
+
+        Ok(from_abi(buff.to_vec())?)
+    }
+
+    /// Encode single slot `value` of `location` into storage `word`, the other values
+    /// packed in the same slot are kept.
+    pub fn encode<T: Serialize>(
+        &self,
+        location: &StorageSlot,
+        word: &H256,
+        value: &T,
+    ) -> anyhow::Result<H256> {
+        let (size, label) = self.inplace_size(location)?;
+
+        let buff = to_abi(value)?;
+
+        if buff.len() != 32 {
+            return Err(StorageError::UnsupportedEncoding(
+                location.r#type.clone(),
+                format!("abi encoded value of {} bytes", buff.len()),
+            )
+            .into());
+        }
+
+        let value = if label.starts_with("bytes") {
+            &buff[..size]
+        } else {
+            &buff[32 - size..]
+        };
+
+        let end = 32 - location.offset;
+
+        let mut word = word.0;
+
+        word[end - size..end].copy_from_slice(value);
+
+        Ok(word.into())
+    }
+}
+
+/// Convert small `value` to usize, returns [`None`] if it does not fit.
+fn len_of(value: &U256) -> Option<usize> {
+    if value.0[..24].iter().any(|b| *b != 0) {
+        return None;
+    }
+
+    usize::try_from(u64::from_be_bytes(value.0[24..].try_into().ok()?)).ok()
+}
+
+impl Contract {
+    /// Attach storage layout for [`storage_slot_of`](Self::storage_slot_of) and
+    /// [`read_storage`](Self::read_storage), see [`StorageLayout::from_artifact`].
+    pub fn with_storage_layout(mut self, layout: StorageLayout) -> Self {
+        self.storage_layout = Some(layout);
+        self
+    }
+
+    /// Returns storage layout of contract or [`StorageError::StorageLayoutRequired`].
+    pub fn storage_layout(&self) -> Result<&StorageLayout, StorageError> {
+        self.storage_layout
+            .as_ref()
+            .ok_or_else(|| StorageError::StorageLayoutRequired(self.address.to_string()))
+    }
+
+    /// Returns storage slot of state variable `label`.
+    pub fn storage_slot_of(&self, label: &str) -> anyhow::Result<U256> {
+        Ok(self.storage_layout()?.slot_of(label)?.slot)
+    }
+
+    async fn read_slot<T: DeserializeOwned>(&self, location: StorageSlot) -> anyhow::Result<T> {
+        let word: H256 = self
+            .client
+            .provider
+            .clone()
+            .eth_get_storage_at(self.address, location.slot, self.block.clone())
+            .await?;
+
+        self.storage_layout()?.decode(&location, &word)
+    }
+
+    /// Read single slot state variable `label` via `eth_getStorageAt`, e.g. `owner`.
+    pub async fn read_storage_var<T: DeserializeOwned>(&self, label: &str) -> anyhow::Result<T> {
+        let location = self.storage_layout()?.slot_of(label)?;
+
+        self.read_slot(location).await
+    }
+
+    /// Read value of `key` of mapping `label`, or element `key` of dynamic array `label`,
+    /// via `eth_getStorageAt`.
+    ///
+    /// ```ignore
+    /// let balance = token.read_storage::<U256>("balances", owner).await?;
+    /// ```
+    pub async fn read_storage<T: DeserializeOwned>(
+        &self,
+        label: &str,
+        key: impl Serialize,
+    ) -> anyhow::Result<T> {
+        let location = self.storage_layout()?.slot_of_key(label, &key)?;
+
+        self.read_slot(location).await
+    }
+
+    async fn write_slot<T: Serialize>(
+        &self,
+        location: StorageSlot,
+        value: &T,
+    ) -> anyhow::Result<()> {
+        let layout = self.storage_layout()?;
+
+        let mut provider = self.client.provider.clone();
+
+        // Values of less than 32 bytes may share the slot with other variables.
+        let word: H256 = if layout.inplace_size(&location)?.0 < 32 {
+            provider
+                .eth_get_storage_at(self.address, location.slot, None::<BlockNumberOrTag>)
+                .await?
+        } else {
+            H256::default()
+        };
+
+        let word = layout.encode(&location, &word, value)?;
+
+        provider
+            .hardhat_set_storage_at(self.address, location.slot, word)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Overwrite single slot state variable `label` via `hardhat_setStorageAt`, dev networks only.
+    pub async fn write_storage_var<T: Serialize>(
+        &self,
+        label: &str,
+        value: T,
+    ) -> anyhow::Result<()> {
+        let location = self.storage_layout()?.slot_of(label)?;
+
+        self.write_slot(location, &value).await
+    }
+
+    /// Overwrite value of `key` of mapping `label`, or element `key` of dynamic array `label`,
+    /// via `hardhat_setStorageAt`, dev networks only.
+    ///
+    /// Array length is not changed, writing elements beyond the length has no visible effect.
+    ///
+    /// ```ignore
+    /// token
+    ///     .write_storage("balances", holder, U256::from(1_000_000u64))
+    ///     .await?;
+    /// ```
+    pub async fn write_storage<T: Serialize>(
+        &self,
+        label: &str,
+        key: impl Serialize,
+        value: T,
+    ) -> anyhow::Result<()> {
+        let location = self.storage_layout()?.slot_of_key(label, &key)?;
+
+        self.write_slot(location, &value).await
+    }
+
+    /// Replace runtime code of contract via `hardhat_setCode`, dev networks only.
+    pub async fn set_code<B: Into<Bytes>>(&self, code: B) -> anyhow::Result<()> {
+        self.client
+            .provider
+            .clone()
+            .hardhat_set_code(self.address, code.into())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Address, Bytes4};
+
+    use super::*;
+
+    /// Build info of
+    ///
+    /// ```solidity
+    /// contract Layout {
+    ///     address owner;
+    ///     uint8 decimals;
+    ///     int8 delta;
+    ///     bytes4 tag;
+    ///     mapping(address => uint256) balances;
+    ///     uint64[] items;
+    ///     mapping(string => bytes4) names;
+    /// }
+    /// ```
+    fn layout() -> StorageLayout {
+        StorageLayout::from_build_info(
+            include_str!("./storage_layout.json"),
+            "contracts/Layout.sol",
+            "Layout",
+        )
+        .unwrap()
+    }
+
+    fn owner() -> Address {
+        "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+            .try_into()
+            .unwrap()
+    }
+
+    /// Slot 0 with `owner`, `decimals = 18`, `delta = -1` and `tag = 0xdeadbeef`.
+    fn packed_word() -> H256 {
+        "0x000000000000deadbeefff12cd2a3d9f938e13cd947ec05abc7fe734df8dd826"
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_slot_of() {
+        let layout = layout();
+
+        let packed = [("owner", 0), ("decimals", 20), ("delta", 21), ("tag", 22)];
+
+        for (label, offset) in packed {
+            let location = layout.slot_of(label).unwrap();
+
+            assert_eq!(location.slot, U256::from(0u64));
+            assert_eq!(location.offset, offset);
+        }
+
+        assert_eq!(layout.slot_of("balances").unwrap().slot, U256::from(1u64));
+
+        assert!(matches!(
+            layout.slot_of("supply"),
+            Err(StorageError::VariableNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_slot_of_key() {
+        let layout = layout();
+
+        // keccak256(pad32(owner) ‖ uint256(1))
+        let mut preimage = [0u8; 64];
+        preimage[12..32].copy_from_slice(&owner().0);
+        preimage[63] = 1;
+
+        let location = layout.slot_of_key("balances", &owner()).unwrap();
+
+        assert_eq!(location.slot.0, keccak256(preimage));
+        assert_eq!(location.r#type, "t_uint256");
+
+        // keccak256("abc" ‖ uint256(3)), string keys are not padded.
+        let mut preimage = b"abc".to_vec();
+        preimage.extend_from_slice(&[0u8; 31]);
+        preimage.push(3);
+
+        let location = layout.slot_of_key("names", &"abc".to_owned()).unwrap();
+
+        assert_eq!(location.slot.0, keccak256(preimage));
+        assert_eq!(location.r#type, "t_bytes4");
+
+        // keccak256(uint256(2)), 4 uint64 elements per slot.
+        let items: U256 = "0x405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5ace"
+            .try_into()
+            .unwrap();
+
+        let location = layout.slot_of_key("items", &U256::from(3u64)).unwrap();
+
+        assert_eq!(location.slot, items);
+        assert_eq!(location.offset, 24);
+
+        let location = layout.slot_of_key("items", &U256::from(5u64)).unwrap();
+
+        assert_eq!(location.slot, items.checked_add(U256::from(1u64)).unwrap());
+        assert_eq!(location.offset, 8);
+
+        assert!(layout
+            .slot_of_key("owner", &U256::from(0u64))
+            .unwrap_err()
+            .downcast_ref::<StorageError>()
+            .is_some_and(|err| matches!(err, StorageError::NotIndexable(..))));
+    }
+
+    #[test]
+    fn test_decode_packed() {
+        let layout = layout();
+
+        let word = packed_word();
+
+        let address: Address = layout
+            .decode(&layout.slot_of("owner").unwrap(), &word)
+            .unwrap();
+
+        assert_eq!(address, owner());
+
+        let decimals: u8 = layout
+            .decode(&layout.slot_of("decimals").unwrap(), &word)
+            .unwrap();
+
+        assert_eq!(decimals, 18);
+
+        let delta: i8 = layout
+            .decode(&layout.slot_of("delta").unwrap(), &word)
+            .unwrap();
+
+        assert_eq!(delta, -1);
+
+        let tag: Bytes4 = layout
+            .decode(&layout.slot_of("tag").unwrap(), &word)
+            .unwrap();
+
+        assert_eq!(tag.as_bytes(), &[0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn test_encode_packed() {
+        let layout = layout();
+
+        let word = packed_word();
+
+        let decimals = layout.slot_of("decimals").unwrap();
+
+        let updated = layout.encode(&decimals, &word, &6u8).unwrap();
+
+        assert_eq!(
+            updated,
+            "0x000000000000deadbeefff06cd2a3d9f938e13cd947ec05abc7fe734df8dd826"
+                .try_into()
+                .unwrap()
+        );
+
+        let delta = layout.slot_of("delta").unwrap();
+
+        for value in [i8::MIN, -1, 0, i8::MAX] {
+            let updated = layout.encode(&delta, &word, &value).unwrap();
+
+            assert_eq!(layout.decode::<i8>(&delta, &updated).unwrap(), value);
+            assert_eq!(
+                layout.decode::<u8>(&decimals, &updated).unwrap(),
+                18,
+                "neighbour of delta is kept"
+            );
+        }
+
+        let tag = layout.slot_of("tag").unwrap();
+
+        let value: Bytes4 = "0x01020304".try_into().unwrap();
+
+        let updated = layout.encode(&tag, &word, &value).unwrap();
+
+        assert_eq!(layout.decode::<Bytes4>(&tag, &updated).unwrap(), value);
+        assert_eq!(layout.decode::<i8>(&delta, &updated).unwrap(), -1);
+
+        let owner = layout.slot_of("owner").unwrap();
+
+        let value = Address::ZERO;
+
+        let updated = layout.encode(&owner, &word, &value).unwrap();
+
+        assert_eq!(layout.decode::<Address>(&owner, &updated).unwrap(), value);
+        assert_eq!(layout.decode::<u8>(&decimals, &updated).unwrap(), 18);
+        assert_eq!(
+            layout.decode::<Bytes4>(&tag, &updated).unwrap().as_bytes(),
+            &[0xde, 0xad, 0xbe, 0xef]
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Encrypted HD wallet keystore, stores one encrypted mnemonic instead of one keystore per derived key.
+
+use ethers_primitives::Address;
+use rand::{CryptoRng, Rng};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::keystore::{CryptoJson, KeyStoreError};
+
+use super::{
+    bip32::DriveKey,
+    bip39::{languages, Bip39Generator},
+};
+
+/// Default ethereum derivation path prefix, account `index` is derived at `m/44'/60'/0'/0/{index}`.
+pub const DEFAULT_HD_PATH: &str = "m/44'/60'/0'/0";
+
+/// Derived account metadata, stored in plain text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HdAccount {
+    /// Address index of derivation path.
+    pub index: u32,
+    pub address: Address,
+}
+
+/// Secrets encrypted in [`HdKeyStore::crypto`].
+#[derive(Serialize, Deserialize)]
+struct HdSecret {
+    mnemonic: String,
+    passphrase: String,
+}
+
+/// Encrypted HD wallet keystore, like MetaMask vaults.
+///
+/// Mnemonic and bip39 passphrase are encrypted in web3 secret storage `crypto` format,
+/// derivation path and derived accounts are stored in plain text, so accounts can be listed
+/// without unlocking.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HdKeyStore {
+    pub id: Uuid,
+    pub version: u8,
+    pub crypto: CryptoJson,
+    /// Derivation path prefix, see [`DEFAULT_HD_PATH`].
+    pub hd_path: String,
+    /// Derived accounts, in ascending index order.
+    pub accounts: Vec<HdAccount>,
+}
+
+impl HdKeyStore {
+    /// Encrypt `mnemonic` and bip39 `passphrase` with `password`, the first account is derived at [`DEFAULT_HD_PATH`].
+    pub fn encrypt<M, S, P>(mnemonic: M, passphrase: S, password: P) -> Result<Self, KeyStoreError>
+    where
+        M: AsRef<str>,
+        S: AsRef<str>,
+        P: AsRef<[u8]>,
+    {
+        Self::encrypt_with(
+            &mut rand::rngs::OsRng,
+            mnemonic,
+            passphrase,
+            DEFAULT_HD_PATH,
+            password,
+        )
+    }
+
+    /// Encrypt `mnemonic` with `rng`, accounts are derived at `{hd_path}/{index}`.
+    pub fn encrypt_with<R, M, S, H, P>(
+        rng: &mut R,
+        mnemonic: M,
+        passphrase: S,
+        hd_path: H,
+        password: P,
+    ) -> Result<Self, KeyStoreError>
+    where
+        R: Rng + CryptoRng,
+        M: AsRef<str>,
+        S: AsRef<str>,
+        H: AsRef<str>,
+        P: AsRef<[u8]>,
+    {
+        Bip39Generator::new(languages::en_us()).mnemonic_check(mnemonic.as_ref())?;
+
+        let secret = HdSecret {
+            mnemonic: mnemonic.as_ref().to_owned(),
+            passphrase: passphrase.as_ref().to_owned(),
+        };
+
+        let crypto = CryptoJson::encrypt_with(rng, &serde_json::to_vec(&secret)?, password)?;
+
+        let mut keystore = Self {
+            id: Uuid::new_v4(),
+            version: 1,
+            crypto,
+            hd_path: hd_path.as_ref().trim_end_matches('/').to_owned(),
+            accounts: vec![],
+        };
+
+        let unlocked = UnlockedHdKeyStore::new(secret, keystore.hd_path.clone());
+
+        keystore.accounts.push(unlocked.account(0)?);
+
+        Ok(keystore)
+    }
+
+    /// Returns derived accounts, without unlocking.
+    pub fn accounts(&self) -> &[HdAccount] {
+        &self.accounts
+    }
+
+    /// Decrypt mnemonic with `password`.
+    pub fn unlock<P>(&self, password: P) -> Result<UnlockedHdKeyStore, KeyStoreError>
+    where
+        P: AsRef<[u8]>,
+    {
+        let secret: HdSecret = serde_json::from_slice(&self.crypto.decrypt(password)?)?;
+
+        Ok(UnlockedHdKeyStore::new(secret, self.hd_path.clone()))
+    }
+
+    /// Derive the account next to the last one, returns the new account.
+    pub fn add_account<P>(&mut self, password: P) -> Result<HdAccount, KeyStoreError>
+    where
+        P: AsRef<[u8]>,
+    {
+        let index = self
+            .accounts
+            .last()
+            .map(|account| account.index + 1)
+            .unwrap_or(0);
+
+        let account = self.unlock(password)?.account(index)?;
+
+        self.accounts.push(account.clone());
+
+        Ok(account)
+    }
+}
+
+impl TryInto<String> for HdKeyStore {
+    type Error = serde_json::Error;
+    fn try_into(self) -> Result<String, Self::Error> {
+        serde_json::to_string_pretty(&self)
+    }
+}
+
+impl std::str::FromStr for HdKeyStore {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Decrypted [`HdKeyStore`], derives account private keys.
+pub struct UnlockedHdKeyStore {
+    master: DriveKey,
+    mnemonic: String,
+    hd_path: String,
+}
+
+impl UnlockedHdKeyStore {
+    fn new(secret: HdSecret, hd_path: String) -> Self {
+        Self {
+            master: DriveKey::new(&secret.mnemonic, &secret.passphrase),
+            mnemonic: secret.mnemonic,
+            hd_path,
+        }
+    }
+
+    /// Returns the decrypted mnemonic.
+    pub fn mnemonic(&self) -> &str {
+        &self.mnemonic
+    }
+
+    /// Derive key of account `index`.
+    pub fn drive(&self, index: u32) -> Result<DriveKey, KeyStoreError> {
+        Ok(self.master.drive(format!("{}/{}", self.hd_path, index))?)
+    }
+
+    /// Returns private key of account `index`, usable as [`KeyProvider`](crate::wallet::KeyProvider).
+    pub fn private_key(&self, index: u32) -> Result<Vec<u8>, KeyStoreError> {
+        Ok(self.drive(index)?.private_key.to_be_bytes().to_vec())
+    }
+
+    /// Returns account metadata of `index`.
+    pub fn account(&self, index: u32) -> Result<HdAccount, KeyStoreError> {
+        Ok(HdAccount {
+            index,
+            address: Address::from(self.drive(index)?.public_key),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers_primitives::*;
+
+    use super::*;
+
+    #[test]
+    fn test_hd_keystore() {
+        let mut keystore = HdKeyStore::encrypt(
+            "test test test test test test test test test test test junk",
+            "",
+            "thebestrandompassword",
+        )
+        .expect("Encrypt mnemonic");
+
+        assert_eq!(
+            keystore.accounts()[0].address.to_checksum_string(),
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+        );
+
+        let account = keystore
+            .add_account("thebestrandompassword")
+            .expect("Add account");
+
+        assert_eq!(account.index, 1);
+        assert_eq!(
+            account.address.to_checksum_string(),
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+        );
+
+        assert!(matches!(
+            keystore.add_account("wrong"),
+            Err(KeyStoreError::MacMismatch)
+        ));
+
+        let json: String = keystore.try_into().unwrap();
+
+        assert!(!json.contains("junk"));
+
+        let keystore: HdKeyStore = json.parse().expect("Load hd keystore");
+
+        assert_eq!(keystore.accounts().len(), 2);
+
+        let unlocked = keystore.unlock("thebestrandompassword").expect("Unlock");
+
+        assert_eq!(
+            unlocked.private_key(0).unwrap().to_eth_hex(),
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        );
+
+        assert!(HdKeyStore::encrypt("test test junk", "", "password").is_err());
+    }
+}
@@ -12,10 +12,14 @@ use rand::{CryptoRng, Rng};
 use scrypt::{scrypt, Params as ScryptParams};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
-use sha3::{Digest, Keccak256};
 use uuid::Uuid;
 
-use crate::{hash::pbkdf2::pbkdf2_hmac, wallet::KeyProvider, WalletError};
+use crate::{
+    hash::pbkdf2::pbkdf2_hmac,
+    hd_wallet::{bip32::Bip32Error, bip39::Bip39Error},
+    wallet::KeyProvider,
+    WalletError,
+};
 
 pub type IV = Bytes;
 pub type CipherText = Bytes;
@@ -39,6 +43,15 @@ pub enum KeyStoreError {
 
     #[error("Load key error,{0}")]
     KeyProvider(WalletError),
+
+    #[error("Invalid mnemonic,{0}")]
+    Mnemonic(#[from] Bip39Error),
+
+    #[error("Derive key error,{0}")]
+    Derive(#[from] Bip32Error),
+
+    #[error("Invalid encrypted payload,{0}")]
+    Payload(#[from] serde_json::Error),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -121,6 +134,33 @@ pub trait KeyStoreEncrypt {
         R: Rng + CryptoRng,
         P: AsRef<[u8]>,
         B: KeyProvider,
+    {
+        let pk = pk.load()?;
+
+        let sec_key = SecretKey::from_be_bytes(&pk)?;
+
+        let address = Address::try_from(sec_key).ok();
+
+        let crypto = CryptoJson::encrypt_with(rng, &pk, password)?;
+
+        // If a file name is not specified for the keystore, simply use the strigified uuid.
+        let id = Uuid::new_v4();
+
+        Ok(KeyStore {
+            id,
+            version: 3,
+            crypto,
+            address,
+        })
+    }
+}
+
+impl CryptoJson {
+    /// Encrypt `data` with `password`, using scrypt kdf and AES-128-CTR cipher.
+    pub fn encrypt_with<R, P>(rng: &mut R, data: &[u8], password: P) -> Result<Self, KeyStoreError>
+    where
+        R: Rng + CryptoRng,
+        P: AsRef<[u8]>,
     {
         // Generate a random salt.
         let mut salt = vec![0u8; DEFAULT_KEY_SIZE];
@@ -138,89 +178,50 @@ pub trait KeyStoreEncrypt {
         scrypt(password.as_ref(), &salt, &scrypt_params, key.as_mut_slice())
             .map_err(|err| KeyStoreError::Scrypt(err.to_string()))?;
 
-        // Encrypt the private key using AES-128-CTR.
+        // Encrypt the data using AES-128-CTR.
         let mut iv = vec![0u8; DEFAULT_IV_SIZE];
         rng.fill_bytes(iv.as_mut_slice());
 
         let encryptor = Aes128Ctr::new(&key[..16], &iv[..16]).expect("invalid length");
 
-        let mut ciphertext = pk.load()?;
-
-        let sec_key = SecretKey::from_be_bytes(&ciphertext)?;
-
-        let address = Address::try_from(sec_key).ok();
+        let mut ciphertext = data.to_vec();
 
         encryptor.apply_keystream(&mut ciphertext);
 
         // Calculate the MAC.
-        let mut mac = Keccak256::new();
-
-        mac.update(&key[16..32]);
-        mac.update(&ciphertext);
-
-        let mac = mac.finalize();
-
-        // If a file name is not specified for the keystore, simply use the strigified uuid.
-        let id = Uuid::new_v4();
-
-        // Construct and serialize the encrypted JSON keystore.
-        Ok(KeyStore {
-            id,
-            version: 3,
-            crypto: CryptoJson {
-                cipher: String::from(DEFAULT_CIPHER),
-                cipherparams: CipherparamsJson { iv: iv.into() },
-                ciphertext: ciphertext.to_vec().into(),
-                kdf: KdfType::Scrypt,
-                kdfparams: KdfparamsType::Scrypt {
-                    dklen: DEFAULT_KDF_PARAMS_DKLEN,
-                    n: 2u32.pow(DEFAULT_KDF_PARAMS_LOG_N as u32),
-                    p: DEFAULT_KDF_PARAMS_P,
-                    r: DEFAULT_KDF_PARAMS_R,
-                    salt: salt.into(),
-                },
-                mac: mac.to_vec().into(),
+        let mac = keccak256_concat(&[&key[16..32], &ciphertext]);
+
+        Ok(CryptoJson {
+            cipher: String::from(DEFAULT_CIPHER),
+            cipherparams: CipherparamsJson { iv: iv.into() },
+            ciphertext: ciphertext.into(),
+            kdf: KdfType::Scrypt,
+            kdfparams: KdfparamsType::Scrypt {
+                dklen: DEFAULT_KDF_PARAMS_DKLEN,
+                n: 2u32.pow(DEFAULT_KDF_PARAMS_LOG_N as u32),
+                p: DEFAULT_KDF_PARAMS_P,
+                r: DEFAULT_KDF_PARAMS_R,
+                salt: salt.into(),
             },
-            address,
+            mac: mac.to_vec().into(),
         })
     }
-}
 
-struct Aes128Ctr {
-    inner: ctr::CtrCore<Aes128, ctr::flavors::Ctr128BE>,
-}
-
-impl Aes128Ctr {
-    fn new(key: &[u8], iv: &[u8]) -> Result<Self, cipher::InvalidLength> {
-        let cipher = aes::Aes128::new_from_slice(key).unwrap();
-        let inner = ctr::CtrCore::inner_iv_slice_init(cipher, iv).unwrap();
-        Ok(Self { inner })
-    }
-
-    fn apply_keystream(self, buf: &mut [u8]) {
-        self.inner.apply_keystream_partial(buf.into());
-    }
-}
-
-impl KeyStoreEncrypt for KeyStore {}
-
-impl KeyStore {
-    pub fn decrypt_into<S>(self, password: S) -> Result<Vec<u8>, KeyStoreError>
+    /// Decrypt data with `password`, returns [`KeyStoreError::MacMismatch`] if `password` is wrong.
+    pub fn decrypt<S>(&self, password: S) -> Result<Vec<u8>, KeyStoreError>
     where
         S: AsRef<[u8]>,
     {
-        let keystore = self;
-
         // Derive the key.
-        let key = match keystore.crypto.kdfparams {
+        let key = match &self.kdfparams {
             KdfparamsType::Pbkdf2 {
                 c,
                 dklen,
                 prf: _,
                 salt,
             } => {
-                let mut key = vec![0u8; dklen as usize];
-                pbkdf2_hmac::<Sha256>(password.as_ref(), &salt.0, c, key.as_mut_slice());
+                let mut key = vec![0u8; *dklen as usize];
+                pbkdf2_hmac::<Sha256>(password.as_ref(), salt, *c, key.as_mut_slice());
                 key
             }
             KdfparamsType::Scrypt {
@@ -230,42 +231,61 @@ impl KeyStore {
                 r,
                 salt,
             } => {
-                let mut key = vec![0u8; dklen as usize];
-                let log_n = (n as f32).log2() as u8;
-                let scrypt_params = ScryptParams::new(log_n, r, p)
+                let mut key = vec![0u8; *dklen as usize];
+                let log_n = (*n as f32).log2() as u8;
+                let scrypt_params = ScryptParams::new(log_n, *r, *p)
                     .map_err(|err| KeyStoreError::Scrypt(err.to_string()))?;
 
-                scrypt(
-                    password.as_ref(),
-                    &salt.0,
-                    &scrypt_params,
-                    key.as_mut_slice(),
-                )
-                .map_err(|err| KeyStoreError::Scrypt(err.to_string()))?;
+                scrypt(password.as_ref(), salt, &scrypt_params, key.as_mut_slice())
+                    .map_err(|err| KeyStoreError::Scrypt(err.to_string()))?;
                 key
             }
         };
 
         // Calculate the MAC.
-        let mut derived_mac = Keccak256::new();
-
-        derived_mac.update(&key[16..32]);
-        derived_mac.update(&keystore.crypto.ciphertext.0);
-        let derived_mac = derived_mac.finalize();
+        let derived_mac = keccak256_concat(&[&key[16..32], &self.ciphertext]);
 
-        if derived_mac.as_slice() != keystore.crypto.mac.0.as_slice() {
+        // Compare in constant time, avoid leaking mac prefix matches.
+        if !bool::from(derived_mac.as_slice().ct_eq(self.mac.as_bytes())) {
             return Err(KeyStoreError::MacMismatch);
         }
 
-        // Decrypt the private key bytes using AES-128-CTR
-        let decryptor = Aes128Ctr::new(&key[..16], &keystore.crypto.cipherparams.iv.0[..16])
-            .expect("invalid length");
+        // Decrypt the data using AES-128-CTR
+        let decryptor =
+            Aes128Ctr::new(&key[..16], &self.cipherparams.iv[..16]).expect("invalid length");
 
-        let mut pk = keystore.crypto.ciphertext.0;
+        let mut data = self.ciphertext.to_vec();
 
-        decryptor.apply_keystream(&mut pk);
+        decryptor.apply_keystream(&mut data);
 
-        Ok(pk)
+        Ok(data)
+    }
+}
+
+struct Aes128Ctr {
+    inner: ctr::CtrCore<Aes128, ctr::flavors::Ctr128BE>,
+}
+
+impl Aes128Ctr {
+    fn new(key: &[u8], iv: &[u8]) -> Result<Self, cipher::InvalidLength> {
+        let cipher = aes::Aes128::new_from_slice(key).unwrap();
+        let inner = ctr::CtrCore::inner_iv_slice_init(cipher, iv).unwrap();
+        Ok(Self { inner })
+    }
+
+    fn apply_keystream(self, buf: &mut [u8]) {
+        self.inner.apply_keystream_partial(buf.into());
+    }
+}
+
+impl KeyStoreEncrypt for KeyStore {}
+
+impl KeyStore {
+    pub fn decrypt_into<S>(self, password: S) -> Result<Vec<u8>, KeyStoreError>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.crypto.decrypt(password)
     }
 }
 
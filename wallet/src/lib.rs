@@ -10,4 +10,10 @@ pub mod hd_wallet;
 
 pub mod keystore;
 
+pub mod typed_data;
+
+pub mod message;
+
+pub mod siwe;
+
 mod hash;
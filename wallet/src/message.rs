@@ -0,0 +1,81 @@
+//! [`EIP-191`](https://eips.ethereum.org/EIPS/eip-191) signed data helpers.
+
+use ethers_primitives::{keccak256_concat, Address, Eip1559Signature};
+
+use crate::wallet::WalletBackend;
+
+/// Returns EIP-191 version `0x45` hash of `data`, aka `personal_sign` message hash.
+pub fn personal_message_hash<D: AsRef<[u8]>>(data: D) -> [u8; 32] {
+    let data = data.as_ref();
+
+    keccak256_concat(&[
+        format!("\x19Ethereum Signed Message:\n{}", data.len()).as_bytes(),
+        data,
+    ])
+}
+
+/// Returns EIP-191 version `0x00` hash of `data`, aka `keccak256(0x19 || 0x00 || validator || data)`.
+pub fn intended_validator_hash<D: AsRef<[u8]>>(validator: &Address, data: D) -> [u8; 32] {
+    keccak256_concat(&[&[0x19, 0x00], &validator.0, data.as_ref()])
+}
+
+/// EIP-191 signing helpers of [`WalletBackend`].
+pub trait MessageSigner: WalletBackend {
+    /// Sign `data` with `personal_sign` framing.
+    fn sign_personal_message<D: AsRef<[u8]>>(&self, data: D) -> anyhow::Result<Eip1559Signature> {
+        self.sign(personal_message_hash(data))
+    }
+
+    /// Sign `data` with intended `validator` framing, EIP-191 version `0x00`.
+    fn sign_data_with_intended_validator<D: AsRef<[u8]>>(
+        &self,
+        validator: &Address,
+        data: D,
+    ) -> anyhow::Result<Eip1559Signature> {
+        self.sign(intended_validator_hash(validator, data))
+    }
+}
+
+impl<W: WalletBackend> MessageSigner for W {}
+
+#[cfg(test)]
+mod tests {
+    use ethers_primitives::*;
+
+    use super::*;
+    use crate::wallet::Wallet;
+
+    #[test]
+    fn test_intended_validator() {
+        let wallet =
+            Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap();
+
+        let validator: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            .try_into()
+            .unwrap();
+
+        let mut message = vec![0x19, 0x00];
+
+        message.extend_from_slice(&validator.0);
+        message.extend_from_slice(b"hello");
+
+        let hashed = intended_validator_hash(&validator, b"hello");
+
+        assert_eq!(hashed, keccak256(&message));
+
+        let signature = wallet
+            .sign_data_with_intended_validator(&validator, b"hello")
+            .unwrap();
+
+        assert_eq!(
+            wallet.recover(hashed, signature).unwrap(),
+            wallet.address().unwrap()
+        );
+
+        assert_eq!(
+            wallet.sign_personal_message("hello").unwrap().to_string(),
+            "0x01f16ea9a3478698f695fd1401bfe27e9e4a7e8e3da94aa72b021125e31fa899cc573c48ea3fe1d4ab61a9db10c19032026e3ed2dbccba5a178235ac27f9450431"
+        );
+    }
+}
@@ -0,0 +1,679 @@
+//! [`EIP-4361`](https://eips.ethereum.org/EIPS/eip-4361) Sign-In with Ethereum messages.
+//!
+//! ```
+//! use ethers_wallet::{siwe::SiweMessage, wallet::Wallet};
+//! use ethers_wallet::message::MessageSigner;
+//!
+//! let wallet =
+//!     Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+//!
+//! let message = SiweMessage::new(
+//!     "example.com",
+//!     wallet.address().unwrap(),
+//!     "https://example.com/login",
+//!     1,
+//! )
+//! .statement("Sign in to example.com");
+//!
+//! let signature = wallet.sign_personal_message(message.to_string()).unwrap();
+//!
+//! let message: SiweMessage = message.to_string().parse().unwrap();
+//!
+//! message
+//!     .verify_signature(&signature.try_into().unwrap())
+//!     .unwrap();
+//! ```
+
+use std::{fmt::Display, str::FromStr};
+
+use ethers_primitives::{Address, Eip1559Signature, Eip55, Signature, SignatureError};
+use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
+use thiserror::Error;
+
+use crate::{message::personal_message_hash, wallet::recover_address};
+
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+
+/// Length of nonces generated by [`generate_nonce`].
+const NONCE_LENGTH: usize = 17;
+
+#[derive(Debug, Error)]
+pub enum SiweError {
+    #[error("Invalid siwe message, {0}")]
+    Parse(String),
+    #[error("Invalid siwe timestamp {0}")]
+    Timestamp(String),
+    #[error("Siwe message expired at {0}")]
+    Expired(String),
+    #[error("Siwe message is not valid before {0}")]
+    NotYetValid(String),
+    #[error("Siwe signer mismatch, expect {expected}, recovered {recovered}")]
+    SignerMismatch {
+        expected: Address,
+        recovered: Address,
+    },
+    #[error("Invalid siwe signature of account {0}")]
+    InvalidSignature(Address),
+    #[error("Siwe domain mismatch, expect {expected}, got {got}")]
+    DomainMismatch { expected: String, got: String },
+    #[error("Siwe nonce mismatch, expect {expected}, got {got}")]
+    NonceMismatch { expected: String, got: String },
+    #[error("{0}")]
+    Signature(#[from] SignatureError),
+    #[error("Recover siwe signer error, {0}")]
+    Recover(String),
+}
+
+/// Generate random alphanumeric nonce of siwe message.
+pub fn generate_nonce() -> String {
+    OsRng
+        .sample_iter(&Alphanumeric)
+        .take(NONCE_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Sign-In with Ethereum message, formatted and parsed as the EIP-4361 plain text message.
+///
+/// Timestamps are kept as RFC 3339 strings, so parsed messages format back to the signed text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiweMessage {
+    /// Optional uri scheme of `domain`.
+    pub scheme: Option<String>,
+    /// RFC 3986 authority requesting the signing.
+    pub domain: String,
+    /// Account performing the signing.
+    pub address: Address,
+    /// Human readable assertion, must not contain `\n`.
+    pub statement: Option<String>,
+    /// RFC 3986 uri referring to the subject of signing.
+    pub uri: String,
+    /// Message version, must be `1`.
+    pub version: String,
+    /// EIP-155 chain id of `address`.
+    pub chain_id: u64,
+    /// Random alphanumeric string of at least 8 chars, prevents replay attacks.
+    pub nonce: String,
+    pub issued_at: String,
+    pub expiration_time: Option<String>,
+    pub not_before: Option<String>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Create message issued now, with random nonce.
+    pub fn new<D: Into<String>, U: Into<String>>(
+        domain: D,
+        address: Address,
+        uri: U,
+        chain_id: u64,
+    ) -> Self {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            scheme: None,
+            domain: domain.into(),
+            address,
+            statement: None,
+            uri: uri.into(),
+            version: "1".to_owned(),
+            chain_id,
+            nonce: generate_nonce(),
+            issued_at: format_rfc3339(now),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: vec![],
+        }
+    }
+
+    pub fn scheme<S: Into<String>>(mut self, scheme: S) -> Self {
+        self.scheme = Some(scheme.into());
+        self
+    }
+
+    pub fn statement<S: Into<String>>(mut self, statement: S) -> Self {
+        self.statement = Some(statement.into());
+        self
+    }
+
+    pub fn nonce<S: Into<String>>(mut self, nonce: S) -> Self {
+        self.nonce = nonce.into();
+        self
+    }
+
+    /// Set issued time to unix `timestamp` seconds.
+    pub fn issued_at(mut self, timestamp: u64) -> Self {
+        self.issued_at = format_rfc3339(timestamp);
+        self
+    }
+
+    /// Set expiration time to unix `timestamp` seconds.
+    pub fn expiration_time(mut self, timestamp: u64) -> Self {
+        self.expiration_time = Some(format_rfc3339(timestamp));
+        self
+    }
+
+    /// Set not before time to unix `timestamp` seconds.
+    pub fn not_before(mut self, timestamp: u64) -> Self {
+        self.not_before = Some(format_rfc3339(timestamp));
+        self
+    }
+
+    pub fn request_id<S: Into<String>>(mut self, request_id: S) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Append resource uri.
+    pub fn resource<S: Into<String>>(mut self, resource: S) -> Self {
+        self.resources.push(resource.into());
+        self
+    }
+
+    /// Returns EIP-191 `personal_sign` hash of formatted message.
+    pub fn hash(&self) -> [u8; 32] {
+        personal_message_hash(self.to_string())
+    }
+
+    /// Check message is valid at unix `timestamp` seconds, aka not expired and not before `not_before`.
+    pub fn validate_at(&self, timestamp: u64) -> Result<(), SiweError> {
+        let timestamp = timestamp as i64;
+
+        if let Some(expiration_time) = &self.expiration_time {
+            if timestamp >= parse_timestamp(expiration_time)? {
+                return Err(SiweError::Expired(expiration_time.clone()));
+            }
+        }
+
+        if let Some(not_before) = &self.not_before {
+            if timestamp < parse_timestamp(not_before)? {
+                return Err(SiweError::NotYetValid(not_before.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check message is requested by `domain` with `nonce` issued by the relying party,
+    /// prevents replaying messages signed for other sites or sessions.
+    pub fn validate_request(&self, domain: &str, nonce: &str) -> Result<(), SiweError> {
+        if self.domain != domain {
+            return Err(SiweError::DomainMismatch {
+                expected: domain.to_owned(),
+                got: self.domain.clone(),
+            });
+        }
+
+        if self.nonce != nonce {
+            return Err(SiweError::NonceMismatch {
+                expected: nonce.to_owned(),
+                got: self.nonce.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Recover signer of message `signature`.
+    pub fn recover(&self, signature: &Signature) -> Result<Address, SiweError> {
+        recover_address(self.hash(), Eip1559Signature::from(*signature))
+            .map_err(|err| SiweError::Recover(err.to_string()))
+    }
+
+    /// Verify `signature` is signed by EOA [`address`](Self::address).
+    ///
+    /// Contract accounts sign with EIP-1271 and must be verified on chain.
+    pub fn verify_signature(&self, signature: &Signature) -> Result<(), SiweError> {
+        let recovered = self.recover(signature)?;
+
+        if recovered != self.address {
+            return Err(SiweError::SignerMismatch {
+                expected: self.address,
+                recovered,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for SiweMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{}://", scheme)?;
+        }
+
+        write!(f, "{}{}\n{}\n\n", self.domain, PREAMBLE, self.address)?;
+
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{}", statement)?;
+        }
+
+        write!(
+            f,
+            "\nURI: {}\nVersion: {}\nChain ID: {}\nNonce: {}\nIssued At: {}",
+            self.uri, self.version, self.chain_id, self.nonce, self.issued_at
+        )?;
+
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {}", expiration_time)?;
+        }
+
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\nNot Before: {}", not_before)?;
+        }
+
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\nRequest ID: {}", request_id)?;
+        }
+
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+
+            for resource in &self.resources {
+                write!(f, "\n- {}", resource)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = SiweError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |message: &str| SiweError::Parse(message.to_owned());
+
+        let mut lines = s.split('\n').peekable();
+
+        let header = lines
+            .next()
+            .and_then(|line| line.strip_suffix(PREAMBLE))
+            .ok_or_else(|| err("expect preamble"))?;
+
+        let (scheme, domain) = match header.split_once("://") {
+            Some((scheme, domain)) => (Some(scheme.to_owned()), domain),
+            None => (None, header),
+        };
+
+        if domain.is_empty() {
+            return Err(err("expect domain"));
+        }
+
+        let line = lines.next().ok_or_else(|| err("expect address"))?;
+
+        // Address must be EIP-55 checksum encoded.
+        let address = Address::try_from(line)
+            .ok()
+            .filter(|address| address.to_checksum_string() == line)
+            .ok_or_else(|| SiweError::Parse(format!("invalid address {}", line)))?;
+
+        if lines.next() != Some("") {
+            return Err(err("expect empty line after address"));
+        }
+
+        let statement = match lines.next() {
+            Some("") => None,
+            Some(line) if !line.starts_with("URI: ") => {
+                if lines.next() != Some("") {
+                    return Err(err("expect empty line after statement"));
+                }
+
+                Some(line.to_owned())
+            }
+            _ => return Err(err("expect statement or empty line")),
+        };
+
+        let mut tag = |name: &str, required: bool| -> Result<Option<String>, SiweError> {
+            match lines.peek().and_then(|line| line.strip_prefix(name)) {
+                Some(value) => {
+                    let value = value.to_owned();
+                    lines.next();
+                    Ok(Some(value))
+                }
+                None if required => Err(SiweError::Parse(format!("expect {}", name.trim()))),
+                None => Ok(None),
+            }
+        };
+
+        let uri = tag("URI: ", true)?.unwrap();
+        let version = tag("Version: ", true)?.unwrap();
+        let chain_id = tag("Chain ID: ", true)?.unwrap();
+        let nonce = tag("Nonce: ", true)?.unwrap();
+        let issued_at = tag("Issued At: ", true)?.unwrap();
+        let expiration_time = tag("Expiration Time: ", false)?;
+        let not_before = tag("Not Before: ", false)?;
+        let request_id = tag("Request ID: ", false)?;
+
+        let mut resources = vec![];
+
+        if tag("Resources:", false)?.is_some() {
+            while let Some(resource) = tag("- ", false)? {
+                resources.push(resource);
+            }
+        }
+
+        if lines.next().is_some() {
+            return Err(err("unexpected trailing lines"));
+        }
+
+        let message = Self {
+            scheme,
+            domain: domain.to_owned(),
+            address,
+            statement,
+            uri,
+            version,
+            chain_id: chain_id
+                .parse()
+                .map_err(|_| SiweError::Parse(format!("invalid chain id {}", chain_id)))?,
+            nonce,
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        };
+
+        message.check()?;
+
+        Ok(message)
+    }
+}
+
+impl SiweMessage {
+    /// Check field formats, formatted message must be the signed text.
+    fn check(&self) -> Result<(), SiweError> {
+        if self.version != "1" {
+            return Err(SiweError::Parse(format!(
+                "unsupported version {}",
+                self.version
+            )));
+        }
+
+        if self.nonce.len() < 8 || !self.nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(SiweError::Parse(format!("invalid nonce {}", self.nonce)));
+        }
+
+        parse_timestamp(&self.issued_at)?;
+
+        for timestamp in [&self.expiration_time, &self.not_before]
+            .into_iter()
+            .flatten()
+        {
+            parse_timestamp(timestamp)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_timestamp(value: &str) -> Result<i64, SiweError> {
+    parse_rfc3339(value).ok_or_else(|| SiweError::Timestamp(value.to_owned()))
+}
+
+/// Parse RFC 3339 date time into unix timestamp seconds, fractional seconds are truncated.
+fn parse_rfc3339(value: &str) -> Option<i64> {
+    let bytes = value.as_bytes();
+
+    if bytes.len() < 20
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return None;
+    }
+
+    let number = |range: std::ops::Range<usize>| -> Option<i64> {
+        let digits = value.get(range)?;
+
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        digits.parse().ok()
+    };
+
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+
+    let mut rest = &value[19..];
+
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+
+        if digits == 0 {
+            return None;
+        }
+
+        rest = &fraction[digits..];
+    }
+
+    let offset = match rest.as_bytes() {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), _, _, b':', _, _] => {
+            let (hours, minutes) = (
+                number(value.len() - 5..value.len() - 3)?,
+                number(value.len() - 2..value.len())?,
+            );
+
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+
+            let offset = hours * 3600 + minutes * 60;
+
+            if *sign == b'+' {
+                offset
+            } else {
+                -offset
+            }
+        }
+        _ => return None,
+    };
+
+    Some(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+/// Format unix `timestamp` seconds as RFC 3339 UTC date time.
+fn format_rfc3339(timestamp: u64) -> String {
+    let timestamp = timestamp as i64;
+
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+
+    let seconds = timestamp.rem_euclid(86400);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since unix epoch of proleptic gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+/// Proleptic gregorian date of days since unix epoch.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let doe = days - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+
+    (yoe + era * 400 + (month <= 2) as i64, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{message::MessageSigner, wallet::Wallet};
+
+    const MESSAGE: &str = "https://example.com wants you to sign in with your Ethereum account:
+0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
+
+I accept the ExampleOrg Terms of Service: https://example.com/tos
+
+URI: https://example.com/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Expiration Time: 2021-10-01T16:25:24.000+02:00
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    #[test]
+    fn test_parse() {
+        let message: SiweMessage = MESSAGE.parse().unwrap();
+
+        assert_eq!(message.scheme.as_deref(), Some("https"));
+        assert_eq!(message.domain, "example.com");
+        assert_eq!(message.chain_id, 1);
+        assert_eq!(message.resources.len(), 2);
+        assert_eq!(message.to_string(), MESSAGE);
+
+        let no_statement = MESSAGE.replace(
+            "I accept the ExampleOrg Terms of Service: https://example.com/tos\n\n",
+            "\n",
+        );
+
+        let message: SiweMessage = no_statement.parse().unwrap();
+
+        assert_eq!(message.statement, None);
+        assert_eq!(message.to_string(), no_statement);
+
+        assert!(MESSAGE
+            .replace("Nonce: 32891756", "Nonce: 123")
+            .parse::<SiweMessage>()
+            .is_err());
+        assert!(MESSAGE
+            .replace("Version: 1", "Version: 2")
+            .parse::<SiweMessage>()
+            .is_err());
+        assert!(MESSAGE
+            .replace(
+                "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+                "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+            )
+            .parse::<SiweMessage>()
+            .is_err());
+        assert!(MESSAGE
+            .replace("2021-09-30T16:25:24Z", "2021-09-31T16:25:24Z")
+            .parse::<SiweMessage>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate_at() {
+        let message: SiweMessage = MESSAGE.parse().unwrap();
+
+        // 2021-10-01T14:25:24Z
+        assert_eq!(
+            parse_rfc3339("2021-10-01T16:25:24.000+02:00"),
+            Some(1633098324)
+        );
+        assert_eq!(format_rfc3339(1633098324), "2021-10-01T14:25:24Z");
+
+        assert!(message.validate_at(1633098323).is_ok());
+        assert!(matches!(
+            message.validate_at(1633098324),
+            Err(SiweError::Expired(_))
+        ));
+
+        assert!(message.validate_request("example.com", "32891756").is_ok());
+        assert!(matches!(
+            message.validate_request("evil.com", "32891756"),
+            Err(SiweError::DomainMismatch { .. })
+        ));
+        assert!(matches!(
+            message.validate_request("example.com", "12345678"),
+            Err(SiweError::NonceMismatch { .. })
+        ));
+
+        let message = message.not_before(1633098000);
+
+        assert!(matches!(
+            message.validate_at(1633097999),
+            Err(SiweError::NotYetValid(_))
+        ));
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let wallet =
+            Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap();
+
+        let message = SiweMessage::new(
+            "example.com",
+            wallet.address().unwrap(),
+            "https://example.com/login",
+            1,
+        )
+        .expiration_time(4102444800);
+
+        assert_eq!(message.nonce.len(), NONCE_LENGTH);
+
+        let signature: Signature = wallet
+            .sign_personal_message(message.to_string())
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let parsed: SiweMessage = message.to_string().parse().unwrap();
+
+        assert_eq!(parsed, message);
+        assert!(parsed.verify_signature(&signature).is_ok());
+
+        let other = parsed.clone().nonce("0123456789");
+
+        assert!(matches!(
+            other.verify_signature(&signature),
+            Err(SiweError::SignerMismatch { .. })
+        ));
+    }
+}
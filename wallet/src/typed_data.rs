@@ -0,0 +1,139 @@
+//! EIP712 typed data signing with precomputed domain separator.
+
+use std::marker::PhantomData;
+
+use ethers_eip712::{DomainSeparator, EIP712Domain, MessageType};
+use ethers_primitives::{Address, Eip1559Signature, H256};
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+
+use crate::wallet::WalletBackend;
+
+/// Typed data signer binds a wallet with signing domain, for signing many messages of type `M`.
+///
+/// Domain separator is calculated once at creation, message type definitions are calculated on first signing,
+/// so `M` must have a fixed type structure, e.g. not [`serde_json::Value`].
+pub struct TypedDataSigner<W, M> {
+    wallet: W,
+    domain: DomainSeparator,
+    message_type: OnceCell<MessageType>,
+    _marker: PhantomData<fn(&M)>,
+}
+
+impl<W, M> TypedDataSigner<W, M>
+where
+    W: WalletBackend,
+    M: Serialize,
+{
+    /// Create new signer with `domain`.
+    pub fn new(wallet: W, domain: EIP712Domain) -> anyhow::Result<Self> {
+        Ok(Self::with_domain_separator(
+            wallet,
+            DomainSeparator::new(domain)?,
+        ))
+    }
+
+    /// Create new signer with precomputed `domain` separator.
+    pub fn with_domain_separator(wallet: W, domain: DomainSeparator) -> Self {
+        Self {
+            wallet,
+            domain,
+            message_type: OnceCell::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn domain(&self) -> &DomainSeparator {
+        &self.domain
+    }
+
+    /// Signer address.
+    pub fn address(&self) -> anyhow::Result<Address> {
+        self.wallet.address()
+    }
+
+    /// Returns EIP712 signing hash of `message`.
+    pub fn sign_hash(&self, message: &M) -> anyhow::Result<H256> {
+        let message_type = self
+            .message_type
+            .get_or_try_init(|| MessageType::of(message))?;
+
+        self.domain.sign_hash(message_type, message)
+    }
+
+    /// Sign `message`.
+    pub fn sign(&self, message: &M) -> anyhow::Result<Eip1559Signature> {
+        let hashed = self.sign_hash(message)?;
+
+        self.wallet.sign(hashed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use crate::wallet::Wallet;
+
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Person {
+        pub name: String,
+        pub wallet: Address,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Mail {
+        pub from: Person,
+        pub to: Person,
+        pub contents: String,
+    }
+
+    #[test]
+    fn test_sign_mail() {
+        let _ = pretty_env_logger::try_init();
+
+        let domain = json!({
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+        });
+
+        let wallet =
+            Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .expect("Create wallet from private key");
+
+        let signer =
+            TypedDataSigner::new(wallet.clone(), serde_json::from_value(domain).unwrap()).unwrap();
+
+        let mail: Mail = serde_json::from_value(json!({
+          "from": {
+            "name": "Cow",
+            "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+          },
+          "to": {
+            "name": "Bob",
+            "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+          },
+          "contents": "Hello, Bob!"
+        }))
+        .unwrap();
+
+        let hashed = signer.sign_hash(&mail).unwrap();
+
+        assert_eq!(
+            hashed.to_string(),
+            "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+
+        let signature = signer.sign(&mail).unwrap();
+
+        assert_eq!(
+            wallet.recover(&hashed, signature).unwrap(),
+            signer.address().unwrap()
+        );
+    }
+}
@@ -1,18 +1,104 @@
 //! ethers-rs wallet facade
 //!
 
-use ethers_primitives::FromEtherHex;
+use ethers_primitives::{Address, Eip1559Signature, FromEtherHex, U256};
 
-use crate::{hd_wallet::bip32::DriveKey, Result};
+use crate::{hd_wallet::bip32::DriveKey, Result, WalletError};
 
 #[cfg(feature = "rust_crypto")]
 mod rust_crypto;
 #[cfg(feature = "rust_crypto")]
-pub type Wallet = rust_crypto::LocalWalletRustCrypto;
+pub use rust_crypto::LocalWalletRustCrypto;
+
+#[cfg(feature = "secp256k1")]
+mod libsecp;
+#[cfg(feature = "secp256k1")]
+pub use libsecp::LocalWalletSecp256k1;
+
+/// Local wallet, signing with `secp256k1` C library if feature `secp256k1` is enabled,
+/// otherwise with the pure-Rust `k256`.
+#[cfg(feature = "secp256k1")]
+pub type Wallet = LocalWalletSecp256k1;
+#[cfg(all(feature = "rust_crypto", not(feature = "secp256k1")))]
+pub type Wallet = LocalWalletRustCrypto;
+
+/// Recover signer address from hashed data and signature, with the same backend as [`Wallet`].
+#[cfg(feature = "secp256k1")]
+pub use libsecp::recover_address;
+#[cfg(all(feature = "rust_crypto", not(feature = "secp256k1")))]
+pub use rust_crypto::recover_address;
 
 #[cfg(feature = "openssl")]
 mod openssl;
 
+/// Common ECDSA signing trait of local wallet backends.
+pub trait WalletBackend: Sized + Clone {
+    /// Create new local wallet from key provider
+    fn new<P: KeyProvider>(provider: P) -> Result<Self>;
+
+    /// Sign hashed data and returns signature
+    fn sign<S: AsRef<[u8]>>(&self, hashed: S) -> anyhow::Result<Eip1559Signature>;
+
+    /// Recover signer address from hashed data and signature.
+    fn recover<H: AsRef<[u8]>>(
+        &self,
+        hashed: H,
+        signature: Eip1559Signature,
+    ) -> anyhow::Result<Address>;
+
+    /// Verify signature `(r, s)` of hashed data signed by this wallet.
+    fn verify(&self, hashed: &[u8], r: U256, s: U256) -> anyhow::Result<bool>;
+
+    /// Wallet address.
+    fn address(&self) -> anyhow::Result<Address>;
+
+    /// Sign many hashed data on a worker pool sized by available parallelism.
+    ///
+    /// Signatures are returned in the order of `hashes`, the first signing error aborts the batch.
+    fn sign_batch<S>(&self, hashes: &[S]) -> anyhow::Result<Vec<Eip1559Signature>>
+    where
+        S: AsRef<[u8]> + Sync,
+        Self: Sync,
+    {
+        let workers = std::thread::available_parallelism()
+            .map(|workers| workers.get())
+            .unwrap_or(1)
+            .min(hashes.len());
+
+        if workers <= 1 {
+            return hashes.iter().map(|hashed| self.sign(hashed)).collect();
+        }
+
+        let chunk_size = hashes.len().div_ceil(workers);
+
+        std::thread::scope(|scope| {
+            let handles = hashes
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|hashed| self.sign(hashed))
+                            .collect::<anyhow::Result<Vec<_>>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let mut signatures = Vec::with_capacity(hashes.len());
+
+            for handle in handles {
+                let mut chunk = handle
+                    .join()
+                    .map_err(|_| WalletError::ECDSA("signing worker panicked".to_owned()))??;
+
+                signatures.append(&mut chunk);
+            }
+
+            Ok(signatures)
+        })
+    }
+}
+
 /// Private key provider trait
 pub trait KeyProvider {
     /// Load private key to memory
@@ -54,21 +140,7 @@ mod tests {
 
     use ethers_primitives::*;
 
-    use super::Wallet;
-
-    use sha3::{Digest, Keccak256};
-
-    /// Compute the Keccak-256 hash of input bytes.
-    pub fn keccak256<S>(bytes: S) -> [u8; 32]
-    where
-        S: AsRef<[u8]>,
-    {
-        let mut hasher = Keccak256::new();
-
-        hasher.update(bytes.as_ref());
-
-        hasher.finalize().into()
-    }
+    use super::{Wallet, WalletBackend};
 
     #[test]
     fn test_public_key() {
@@ -78,7 +150,7 @@ mod tests {
             Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
                 .expect("Create wallet from private key");
 
-        let address = Address::from(wallet.public_key().unwrap());
+        let address = wallet.address().unwrap();
 
         assert_eq!(
             address.to_checksum_string(),
@@ -110,7 +182,7 @@ mod tests {
             .verify(&hashed, signature.r, signature.s)
             .expect("Verify signature"));
 
-        let address = Address::from(wallet.public_key().unwrap());
+        let address = wallet.address().unwrap();
 
         assert_eq!(
             address.to_checksum_string(),
@@ -126,4 +198,63 @@ mod tests {
             "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
         );
     }
+
+    #[test]
+    fn test_sign_batch() {
+        let wallet =
+            Wallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .expect("Create wallet from private key");
+
+        let hashes = (0..100u32)
+            .map(|i| keccak256(i.to_be_bytes()))
+            .collect::<Vec<_>>();
+
+        let signatures = WalletBackend::sign_batch(&wallet, &hashes).expect("Sign batch");
+
+        assert_eq!(signatures.len(), hashes.len());
+
+        for (hashed, signature) in hashes.iter().zip(signatures) {
+            assert_eq!(
+                signature.to_string(),
+                wallet.sign(hashed).unwrap().to_string()
+            );
+        }
+
+        assert!(WalletBackend::sign_batch(&wallet, &Vec::<[u8; 32]>::new())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[cfg(all(feature = "rust_crypto", feature = "secp256k1"))]
+    #[test]
+    fn test_backends_verify() {
+        use super::{LocalWalletRustCrypto, LocalWalletSecp256k1};
+
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+        let k256 = LocalWalletRustCrypto::new(key).unwrap();
+        let libsecp = LocalWalletSecp256k1::new(key).unwrap();
+
+        let hashed = keccak256("hello");
+
+        let signature = k256.sign(hashed).unwrap();
+
+        assert_eq!(
+            signature.to_string(),
+            libsecp.sign(hashed).unwrap().to_string()
+        );
+
+        // secp256k1 curve order n, the malleable signature is (r, n - s).
+        let n: U256 = "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+            .try_into()
+            .unwrap();
+
+        let high_s = n - signature.s;
+
+        assert!(k256.verify(&hashed, signature.r, signature.s).unwrap());
+        assert!(libsecp.verify(&hashed, signature.r, signature.s).unwrap());
+
+        assert!(!k256.verify(&hashed, signature.r, high_s).unwrap());
+        assert!(!libsecp.verify(&hashed, signature.r, high_s).unwrap());
+    }
 }
@@ -0,0 +1,164 @@
+use ethers_primitives::{keccak256, Address, Eip1559Signature, Uint, U256};
+use once_cell::sync::Lazy;
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId, Signature},
+    All, Message, PublicKey, Secp256k1, SecretKey,
+};
+
+use crate::{Result, WalletError};
+
+use super::{KeyProvider, WalletBackend};
+
+static CONTEXT: Lazy<Secp256k1<All>> = Lazy::new(Secp256k1::new);
+
+/// Local wallet backed by the `secp256k1` C library.
+#[derive(Clone)]
+pub struct LocalWalletSecp256k1 {
+    secret_key: SecretKey,
+}
+
+impl LocalWalletSecp256k1 {
+    /// Create new local wallet from key provider
+    pub fn new<P: KeyProvider>(provider: P) -> Result<Self> {
+        let key_data = provider.load()?;
+
+        let secret_key =
+            SecretKey::from_slice(&key_data).map_err(|e| WalletError::ECDSA(format!("{}", e)))?;
+
+        Ok(Self { secret_key })
+    }
+}
+
+fn to_message(hashed: &[u8]) -> anyhow::Result<Message> {
+    Ok(Message::from_digest_slice(hashed)
+        .map_err(|err| WalletError::ECDSA(format!("Invalid hashed message, {}", err)))?)
+}
+
+fn to_address(public_key: &PublicKey) -> Address {
+    let buff = public_key.serialize_uncompressed();
+
+    let digest = keccak256(&buff[1..]);
+
+    Address(digest[12..].try_into().unwrap())
+}
+
+/// Recover signer address from hashed data and signature.
+pub fn recover_address<H>(hashed: H, signature: Eip1559Signature) -> anyhow::Result<Address>
+where
+    H: AsRef<[u8]>,
+{
+    let message = to_message(hashed.as_ref())?;
+
+    let recover_id = RecoveryId::from_i32(signature.v as i32)
+        .map_err(|_| WalletError::RecoverId(signature.v))?;
+
+    let mut buff = [0u8; 64];
+
+    buff[..32].copy_from_slice(&signature.r.0);
+    buff[32..].copy_from_slice(&signature.s.0);
+
+    let sig = RecoverableSignature::from_compact(&buff, recover_id)
+        .map_err(|err| WalletError::ECDSA(format!("Convert signature error, {}", err)))?;
+
+    let public_key = CONTEXT
+        .recover_ecdsa(&message, &sig)
+        .map_err(|err| WalletError::ECDSA(format!("Recover public key error, {}", err)))?;
+
+    Ok(to_address(&public_key))
+}
+
+impl LocalWalletSecp256k1 {
+    pub fn recover<H>(&self, hashed: H, signature: Eip1559Signature) -> anyhow::Result<Address>
+    where
+        H: AsRef<[u8]>,
+    {
+        recover_address(hashed, signature)
+    }
+
+    /// Sign hashed data and returns signature
+    pub fn sign<S>(&self, hashed: S) -> anyhow::Result<Eip1559Signature>
+    where
+        S: AsRef<[u8]>,
+    {
+        let message = to_message(hashed.as_ref())?;
+
+        let (recover_id, buff) = CONTEXT
+            .sign_ecdsa_recoverable(&message, &self.secret_key)
+            .serialize_compact();
+
+        Ok(Eip1559Signature {
+            v: recover_id.to_i32() as u8,
+            r: Uint(buff[..32].try_into().unwrap()),
+            s: Uint(buff[32..].try_into().unwrap()),
+        })
+    }
+
+    pub fn verify<R, S>(&self, hashed: &[u8], r: R, s: S) -> anyhow::Result<bool>
+    where
+        R: TryInto<U256>,
+        S: TryInto<U256>,
+        R::Error: std::error::Error + Sync + Send + 'static,
+        S::Error: std::error::Error + Sync + Send + 'static,
+    {
+        let mut buff = [0; 64];
+
+        let r: U256 = r.try_into()?;
+        let s: U256 = s.try_into()?;
+
+        buff[..32].copy_from_slice(&r.0);
+        buff[32..].copy_from_slice(&s.0);
+
+        let message = to_message(hashed)?;
+
+        let sig = Signature::from_compact(&buff)
+            .map_err(|err| WalletError::ECDSA(format!("Convert signature error, {}", err)))?;
+
+        // Reject high-s signatures(EIP-2) like `k256`, instead of normalizing them.
+        let mut low_s = sig;
+
+        low_s.normalize_s();
+
+        if low_s != sig {
+            return Ok(false);
+        }
+
+        Ok(CONTEXT
+            .verify_ecdsa(&message, &sig, &self.public_key())
+            .is_ok())
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.secret_key.public_key(&CONTEXT)
+    }
+
+    /// Wallet address.
+    pub fn address(&self) -> anyhow::Result<Address> {
+        Ok(to_address(&self.public_key()))
+    }
+}
+
+impl WalletBackend for LocalWalletSecp256k1 {
+    fn new<P: KeyProvider>(provider: P) -> Result<Self> {
+        Self::new(provider)
+    }
+
+    fn sign<S: AsRef<[u8]>>(&self, hashed: S) -> anyhow::Result<Eip1559Signature> {
+        self.sign(hashed)
+    }
+
+    fn recover<H: AsRef<[u8]>>(
+        &self,
+        hashed: H,
+        signature: Eip1559Signature,
+    ) -> anyhow::Result<Address> {
+        self.recover(hashed, signature)
+    }
+
+    fn verify(&self, hashed: &[u8], r: U256, s: U256) -> anyhow::Result<bool> {
+        self.verify(hashed, r, s)
+    }
+
+    fn address(&self) -> anyhow::Result<Address> {
+        self.address()
+    }
+}
@@ -1,4 +1,4 @@
-use ethers_primitives::{Address, U256};
+use ethers_primitives::{Address, Eip1559Signature, U256};
 use k256::{
     ecdsa::{
         self,
@@ -12,7 +12,7 @@ use sha2::Sha256;
 
 use crate::{Result, WalletError};
 
-use super::KeyProvider;
+use super::{KeyProvider, WalletBackend};
 
 #[derive(Clone)]
 pub struct LocalWalletRustCrypto {
@@ -31,27 +31,31 @@ impl LocalWalletRustCrypto {
     }
 }
 
+/// Recover signer address from hashed data and signature.
+pub fn recover_address<H>(hashed: H, signature: Eip1559Signature) -> anyhow::Result<Address>
+where
+    H: AsRef<[u8]>,
+{
+    let (sig, recover_id) = signature.try_into()?;
+
+    let key = VerifyingKey::recover_from_prehash(hashed.as_ref(), &sig, recover_id)
+        .map_err(|err| WalletError::ECDSA(format!("Recover public key error, {}", err)))?;
+
+    let pubkey: PublicKey = key.into();
+
+    Ok(pubkey.into())
+}
+
 impl LocalWalletRustCrypto {
-    pub fn recover<H>(
-        &self,
-        hashed: H,
-        signature: ethers_primitives::Eip1559Signature,
-    ) -> anyhow::Result<Address>
+    pub fn recover<H>(&self, hashed: H, signature: Eip1559Signature) -> anyhow::Result<Address>
     where
         H: AsRef<[u8]>,
     {
-        let (sig, recover_id) = signature.try_into()?;
-
-        let key = VerifyingKey::recover_from_prehash(hashed.as_ref(), &sig, recover_id)
-            .map_err(|err| WalletError::ECDSA(format!("Recover public key error, {}", err)))?;
-
-        let pubkey: PublicKey = key.into();
-
-        Ok(pubkey.into())
+        recover_address(hashed, signature)
     }
 
     /// Sign hashed data and returns signature
-    pub fn sign<S>(&self, hashed: S) -> anyhow::Result<ethers_primitives::Eip1559Signature>
+    pub fn sign<S>(&self, hashed: S) -> anyhow::Result<Eip1559Signature>
     where
         S: AsRef<[u8]>,
     {
@@ -97,4 +101,35 @@ impl LocalWalletRustCrypto {
     pub fn public_key(&self) -> anyhow::Result<PublicKey> {
         Ok(self.sign_key.verifying_key().into())
     }
+
+    /// Wallet address.
+    pub fn address(&self) -> anyhow::Result<Address> {
+        Ok(self.public_key()?.into())
+    }
+}
+
+impl WalletBackend for LocalWalletRustCrypto {
+    fn new<P: KeyProvider>(provider: P) -> Result<Self> {
+        Self::new(provider)
+    }
+
+    fn sign<S: AsRef<[u8]>>(&self, hashed: S) -> anyhow::Result<Eip1559Signature> {
+        self.sign(hashed)
+    }
+
+    fn recover<H: AsRef<[u8]>>(
+        &self,
+        hashed: H,
+        signature: Eip1559Signature,
+    ) -> anyhow::Result<Address> {
+        self.recover(hashed, signature)
+    }
+
+    fn verify(&self, hashed: &[u8], r: U256, s: U256) -> anyhow::Result<bool> {
+        self.verify(hashed, r, s)
+    }
+
+    fn address(&self) -> anyhow::Result<Address> {
+        self.address()
+    }
 }